@@ -0,0 +1,167 @@
+// Compare the substring search algorithms selectable through `SearcherBuilder`.
+//
+// KMP only keeps up with Two-Way on haystacks dense with partial matches of a
+// short needle (`near_matches_short`, ~18000 ns/iter for both). Everywhere else
+// Two-Way's byteset lets it skip most of the haystack and it is 3–10x faster
+// on bytes, so `Algorithm::Auto` never picks KMP.
+//
+// BNDM loses to Two-Way by about 2x on short needles of mostly distinct bytes
+// (`letters_8`, `wide_8`), which the byteset skips well, and ties from 32
+// bytes on. Once the needle repeats its bytes the byteset rarely skips and
+// BNDM is 3–15x faster (`dna_8` to `dna_64`), so `Algorithm::Auto` picks it
+// for such needles.
+//
+// SIMD is 2–30x faster than Two-Way, and faster than Boyer–Moore–Horspool on
+// long needles, so `Algorithm::Auto` picks it wherever the CPU supports it.
+// It only loses to Two-Way by about 1.4x on haystacks dense with partial
+// matches (`near_matches_short`), and to BNDM by 1.2–1.8x on needles of 32
+// or more bytes from a small alphabet (`dna_32`, `dna_64`), which keep BNDM.
+
+#![feature(test)]
+
+extern crate pattern_3;
+extern crate test;
+
+use pattern_3::{Algorithm, SearcherBuilder};
+use pattern_3::ext;
+use test::{black_box, Bencher};
+
+fn lcg_bytes(seed: u32, len: usize, alphabet: u8) -> Vec<u8> {
+    let mut state = seed;
+    (0..len).map(|_| {
+        state = state.wrapping_mul(1103515245).wrapping_add(12345);
+        b'a' + ((state >> 16) as u8 % alphabet)
+    }).collect()
+}
+
+macro_rules! make_bench {
+    ($hay:expr, $needle:expr; $($name:ident: $algorithm:ident),+) => {
+        $(
+            #[bench]
+            fn $name(b: &mut Bencher) {
+                let hay = $hay;
+                let needle = $needle;
+                let needle = SearcherBuilder::new().algorithm(Algorithm::$algorithm).needle(&needle[..]);
+                b.iter(|| black_box(ext::matches(&hay[..], needle).count()));
+            }
+        )+
+    }
+}
+
+// Short needle, haystack full of near-matches.
+make_bench!(
+    b"aaab".repeat(2500), b"aab";
+    near_matches_short_two_way: TwoWay, near_matches_short_kmp: Kmp,
+    near_matches_short_bndm: Bndm, near_matches_short_simd: Simd
+);
+make_bench!(
+    b"aaaaaaaaaaaaaaab".repeat(600), b"aaaaaaaaaaaaaab";
+    near_matches_long_two_way: TwoWay, near_matches_long_kmp: Kmp, near_matches_long_bmh: Bmh,
+    near_matches_long_bndm: Bndm, near_matches_long_simd: Simd
+);
+
+// Small alphabet, e.g. DNA.
+make_bench!(
+    lcg_bytes(1, 10000, 4), b"acgt";
+    dna_short_two_way: TwoWay, dna_short_kmp: Kmp, dna_short_bndm: Bndm, dna_short_simd: Simd
+);
+make_bench!(
+    lcg_bytes(1, 10000, 4), b"acgtacgtaacgtacgttgca";
+    dna_long_two_way: TwoWay, dna_long_kmp: Kmp, dna_long_bmh: Bmh, dna_long_bndm: Bndm,
+    dna_long_simd: Simd
+);
+
+// Large alphabet, few partial matches.
+make_bench!(
+    lcg_bytes(2, 10000, 26), b"xyz";
+    random_short_two_way: TwoWay, random_short_kmp: Kmp, random_short_bmh: Bmh,
+    random_short_bndm: Bndm, random_short_simd: Simd
+);
+make_bench!(
+    lcg_bytes(2, 10000, 26), b"quickbrownfoxjumps";
+    random_long_two_way: TwoWay, random_long_kmp: Kmp, random_long_bmh: Bmh,
+    random_long_bndm: Bndm, random_long_simd: Simd
+);
+make_bench!(
+    lcg_bytes(2, 10000, 64), lcg_bytes(3, 8, 64);
+    wide_8_two_way: TwoWay, wide_8_bmh: Bmh, wide_8_bndm: Bndm, wide_8_simd: Simd
+);
+make_bench!(
+    lcg_bytes(2, 10000, 64), lcg_bytes(3, 16, 64);
+    wide_16_two_way: TwoWay, wide_16_bmh: Bmh, wide_16_bndm: Bndm, wide_16_simd: Simd
+);
+make_bench!(
+    lcg_bytes(2, 10000, 64), lcg_bytes(3, 32, 64);
+    wide_32_two_way: TwoWay, wide_32_bmh: Bmh, wide_32_bndm: Bndm, wide_32_simd: Simd
+);
+make_bench!(
+    lcg_bytes(2, 10000, 64), lcg_bytes(3, 64, 64);
+    wide_64_two_way: TwoWay, wide_64_bmh: Bmh, wide_64_bndm: Bndm, wide_64_simd: Simd
+);
+make_bench!(
+    lcg_bytes(2, 10000, 64), lcg_bytes(3, 128, 64);
+    wide_128_two_way: TwoWay, wide_128_bmh: Bmh, wide_128_simd: Simd
+);
+make_bench!(
+    lcg_bytes(2, 10000, 64), lcg_bytes(3, 256, 64);
+    wide_256_two_way: TwoWay, wide_256_bmh: Bmh, wide_256_simd: Simd
+);
+
+// Needles drawn from the haystack's small alphabet, so that every item is in
+// the Two-Way byteset.
+make_bench!(
+    lcg_bytes(2, 10000, 4), lcg_bytes(3, 8, 4);
+    dna_8_two_way: TwoWay, dna_8_bndm: Bndm, dna_8_simd: Simd
+);
+make_bench!(
+    lcg_bytes(2, 10000, 4), lcg_bytes(3, 16, 4);
+    dna_16_two_way: TwoWay, dna_16_bndm: Bndm, dna_16_simd: Simd
+);
+make_bench!(
+    lcg_bytes(2, 10000, 4), lcg_bytes(3, 32, 4);
+    dna_32_two_way: TwoWay, dna_32_bndm: Bndm, dna_32_simd: Simd
+);
+make_bench!(
+    lcg_bytes(2, 10000, 4), lcg_bytes(3, 64, 4);
+    dna_64_two_way: TwoWay, dna_64_bndm: Bndm, dna_64_simd: Simd
+);
+make_bench!(
+    lcg_bytes(2, 10000, 26), lcg_bytes(3, 8, 26);
+    letters_8_two_way: TwoWay, letters_8_bndm: Bndm, letters_8_simd: Simd
+);
+make_bench!(
+    lcg_bytes(2, 10000, 26), lcg_bytes(3, 32, 26);
+    letters_32_two_way: TwoWay, letters_32_bndm: Bndm, letters_32_simd: Simd
+);
+make_bench!(
+    lcg_bytes(2, 10000, 26), lcg_bytes(3, 64, 26);
+    letters_64_two_way: TwoWay, letters_64_bndm: Bndm, letters_64_simd: Simd
+);
+
+// Long needles over smaller alphabets.
+make_bench!(
+    lcg_bytes(2, 10000, 26), lcg_bytes(3, 256, 26);
+    letters_256_two_way: TwoWay, letters_256_bmh: Bmh, letters_256_simd: Simd
+);
+make_bench!(
+    lcg_bytes(2, 10000, 4), lcg_bytes(3, 256, 4);
+    dna_256_two_way: TwoWay, dna_256_bmh: Bmh, dna_256_simd: Simd
+);
+
+fn widen(bytes: &[u8]) -> Vec<u32> {
+    bytes.iter().map(|&b| b as u32).collect()
+}
+
+// Non-byte items, where Two-Way has no byteset to skip with.
+make_bench!(
+    widen(&b"aaab".repeat(2500)), widen(b"aab");
+    u32_near_matches_short_two_way: TwoWay, u32_near_matches_short_kmp: Kmp
+);
+make_bench!(
+    widen(&lcg_bytes(1, 10000, 4)), widen(b"acgt");
+    u32_dna_short_two_way: TwoWay, u32_dna_short_kmp: Kmp
+);
+make_bench!(
+    widen(&lcg_bytes(2, 10000, 26)), widen(b"quickbrownfoxjumps");
+    u32_random_long_two_way: TwoWay, u32_random_long_kmp: Kmp
+);
@@ -101,4 +101,24 @@ fn bench_trim_end_half(b: &mut Bencher) {
     });
 }
 
-fn main() {}
\ No newline at end of file
+// ~28000 ns/iter
+#[bench]
+fn bench_find_byte_with_closure(b: &mut Bencher) {
+    let mut sl = vec![b'x'; 50000];
+    sl.push(b'\n');
+    b.iter(|| {
+        black_box(ext::find(&*sl, |c: &u8| *c == b'\n'));
+    });
+}
+
+// ~650 ns/iter
+#[bench]
+fn bench_find_byte_with_memchr(b: &mut Bencher) {
+    let mut sl = vec![b'x'; 50000];
+    sl.push(b'\n');
+    b.iter(|| {
+        black_box(ext::find(&*sl, b'\n'));
+    });
+}
+
+fn main() {}
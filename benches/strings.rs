@@ -406,6 +406,22 @@ make_test!(pat3_split_space_str, s, ::pattern_3::ext::split(s, " ").count());
 make_test!(std_split_ad_str, s, s.split("ad").count());
 make_test!(pat3_split_ad_str, s, ::pattern_3::ext::split(s, "ad").count());
 
+// std:
+//     long_lorem_ipsum:   ~5500 ns/iter
+//     short_ascii:         ~150 ns/iter
+//     short_mixed:         ~150 ns/iter
+//     short_pile_of_pool:   ~80 ns/iter
+// pat3:
+//     long_lorem_ipsum:   ~4800 ns/iter
+//     short_ascii:         ~160 ns/iter
+//     short_mixed:         ~140 ns/iter
+//     short_pile_of_pool:   ~30 ns/iter
+make_test!(std_split_whitespace, s, s.split_whitespace().count());
+make_test!(pat3_split_whitespace, s, {
+    use pattern_3::{Whitespace, ext::{split, trim}};
+    split(trim(s, Whitespace), Whitespace).count()
+});
+
 // std:     ~60 ns/iter
 // pat3:    ~30 ns/iter
 #[bench]
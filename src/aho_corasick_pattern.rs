@@ -0,0 +1,92 @@
+//! Multi-needle automata from the `aho-corasick` crate as patterns.
+
+use pattern::{Pattern, Searcher};
+use haystack::{Haystack, Span};
+use byte_hay::{ByteHay, EmptyMatches, find_on_boundaries};
+use aho_corasick::{AhoCorasick, Anchored, Input};
+use std::ops::Range;
+
+/// Searcher of a `&AhoCorasick`, finding any of its needles at once.
+///
+/// Which match is reported among overlapping candidates follows the
+/// automaton's `MatchKind`.
+///
+/// Needles are arbitrary bytes, so matches splitting a character are skipped
+/// when searching a `str`. The automaton cannot search backwards, so this
+/// searcher is forward-only.
+///
+/// ```rust
+/// extern crate aho_corasick;
+/// extern crate pattern_3;
+/// use aho_corasick::{AhoCorasick, MatchKind};
+/// use pattern_3::ext::{match_ranges, replace_with};
+///
+/// let ac = AhoCorasick::builder()
+///     .match_kind(MatchKind::LeftmostLongest)
+///     .build(&["apple", "app", "banana"])
+///     .unwrap();
+/// let hay = "an apple and a banana app";
+/// assert_eq!(
+///     match_ranges(hay, &ac).map(|(r, s)| (r.start, s)).collect::<Vec<_>>(),
+///     vec![(3, "apple"), (15, "banana"), (22, "app")],
+/// );
+///
+/// let mut out = String::new();
+/// replace_with(hay, &ac, |_| "fruit", |s| out.push_str(s));
+/// assert_eq!(out, "an fruit and a fruit fruit");
+/// ```
+///
+/// # Panics
+///
+/// Searching panics if the automaton was built with `StartKind::Anchored`.
+/// Consuming, as in `starts_with` or `trim_start`, panics unless it was built
+/// with `StartKind::Anchored` or `StartKind::Both`.
+#[derive(Debug, Clone)]
+pub struct AhoCorasickSearcher<'p> {
+    automaton: &'p AhoCorasick,
+    empty: EmptyMatches,
+}
+
+fn find(automaton: &AhoCorasick, hay: &[u8], range: Range<usize>) -> Option<Range<usize>> {
+    let input = Input::new(hay).range(range);
+    let m = automaton.try_find(input).expect("Aho-Corasick search failed")?;
+    Some(m.range())
+}
+
+unsafe impl<'p, T: ByteHay + ?Sized> Searcher<T> for AhoCorasickSearcher<'p> {
+    fn search(&mut self, span: Span<&T>) -> Option<Range<usize>> {
+        let (hay, range) = span.into_parts();
+        let automaton = self.automaton;
+        self.empty.search(hay, range, |r| {
+            find_on_boundaries(hay, r, |r| find(automaton, hay.as_bytes(), r))
+        })
+    }
+
+    fn consume(&mut self, span: Span<&T>) -> Option<usize> {
+        let (hay, range) = span.into_parts();
+        let input = Input::new(hay.as_bytes()).range(range).anchored(Anchored::Yes);
+        let m = self.automaton
+            .try_find(input)
+            .expect("consuming needs an automaton built with StartKind::Anchored or StartKind::Both")?;
+        if hay.is_boundary(m.end()) {
+            Some(m.end())
+        } else {
+            None
+        }
+    }
+}
+
+impl<'p, H: Haystack> Pattern<H> for &'p AhoCorasick
+where
+    H::Hay: ByteHay,
+{
+    type Searcher = AhoCorasickSearcher<'p>;
+
+    #[inline]
+    fn into_searcher(self) -> Self::Searcher {
+        AhoCorasickSearcher {
+            automaton: self,
+            empty: EmptyMatches::new(),
+        }
+    }
+}
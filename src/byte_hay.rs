@@ -0,0 +1,146 @@
+//! Hays searchable by automata over bytes, and helpers for searchers built
+//! on such automata.
+
+use haystack::Hay;
+use std::ops::Range;
+
+/// A hay viewed as bytes, with the byte offsets which are valid indices.
+///
+/// Automata from other crates match bytes and may report matches splitting a
+/// character, so searchers must check both ends before reporting a match in a
+/// `str`.
+pub trait ByteHay: Hay<Index = usize> {
+    fn as_bytes(&self) -> &[u8];
+
+    fn is_boundary(&self, index: usize) -> bool;
+}
+
+impl ByteHay for [u8] {
+    #[inline]
+    fn as_bytes(&self) -> &[u8] {
+        self
+    }
+
+    #[inline]
+    fn is_boundary(&self, _: usize) -> bool {
+        true
+    }
+}
+
+impl ByteHay for str {
+    #[inline]
+    fn as_bytes(&self) -> &[u8] {
+        str::as_bytes(self)
+    }
+
+    #[inline]
+    fn is_boundary(&self, index: usize) -> bool {
+        self.is_char_boundary(index)
+    }
+}
+
+/// Finds the first match which `find` reports in `range` and which does not
+/// split a character of `hay`.
+///
+/// `find` takes the range to search, and may be asked again with a later
+/// start after reporting a match splitting a character.
+#[cfg(any(feature = "regex-automata", feature = "aho-corasick"))]
+pub(crate) fn find_on_boundaries<T, F>(hay: &T, range: Range<usize>, mut find: F) -> Option<Range<usize>>
+where
+    T: ByteHay + ?Sized,
+    F: FnMut(Range<usize>) -> Option<Range<usize>>,
+{
+    let mut start = range.start;
+    loop {
+        let m = find(start..range.end)?;
+        if hay.is_boundary(m.start) && hay.is_boundary(m.end) {
+            return Some(m);
+        }
+        start = (m.start + 1..range.end).find(|&i| hay.is_boundary(i))?;
+    }
+}
+
+/// Finds the last match which `rfind` reports in `range` and which does not
+/// split a character of `hay`.
+#[cfg(feature = "regex-automata")]
+pub(crate) fn rfind_on_boundaries<T, F>(hay: &T, range: Range<usize>, mut rfind: F) -> Option<Range<usize>>
+where
+    T: ByteHay + ?Sized,
+    F: FnMut(Range<usize>) -> Option<Range<usize>>,
+{
+    let mut end = range.end;
+    loop {
+        let m = rfind(range.start..end)?;
+        if hay.is_boundary(m.start) && hay.is_boundary(m.end) {
+            return Some(m);
+        }
+        end = (range.start..m.end).rev().find(|&i| hay.is_boundary(i))?;
+    }
+}
+
+/// Where a searcher built on a "find the next match" function may report
+/// empty matches.
+///
+/// Such a function happily reports an empty match right after a non-empty
+/// match ending (resp. starting) at the same position, which a searcher must
+/// skip to match the behavior of `str::split` and friends.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct EmptyMatches {
+    // Whether an empty match may be reported at the start (resp. end) of the
+    // next search.
+    front: bool,
+    back: bool,
+}
+
+impl EmptyMatches {
+    #[inline]
+    pub(crate) fn new() -> Self {
+        EmptyMatches { front: true, back: true }
+    }
+
+    /// Returns the first match which `find` reports in `range`, skipping an
+    /// empty match right after the previous one.
+    pub(crate) fn search<T, F>(&mut self, hay: &T, range: Range<T::Index>, mut find: F) -> Option<Range<T::Index>>
+    where
+        T: Hay + ?Sized,
+        F: FnMut(Range<T::Index>) -> Option<Range<T::Index>>,
+    {
+        let mut start = range.start;
+        loop {
+            let m = find(start..range.end)?;
+            if m.end == start && !self.front {
+                self.front = true;
+                if start == range.end {
+                    return None;
+                }
+                start = unsafe { hay.next_index(start) };
+                continue;
+            }
+            self.front = false;
+            return Some(m);
+        }
+    }
+
+    /// Returns the last match which `rfind` reports in `range`, skipping an
+    /// empty match right before the previous one.
+    pub(crate) fn rsearch<T, F>(&mut self, hay: &T, range: Range<T::Index>, mut rfind: F) -> Option<Range<T::Index>>
+    where
+        T: Hay + ?Sized,
+        F: FnMut(Range<T::Index>) -> Option<Range<T::Index>>,
+    {
+        let mut end = range.end;
+        loop {
+            let m = rfind(range.start..end)?;
+            if m.start == end && !self.back {
+                self.back = true;
+                if end == range.start {
+                    return None;
+                }
+                end = unsafe { hay.prev_index(end) };
+                continue;
+            }
+            self.back = false;
+            return Some(m);
+        }
+    }
+}
@@ -0,0 +1,215 @@
+use pattern::*;
+use haystack::{Haystack, Span};
+use slices::slice::{TwoWaySearcher, NaiveSearcher, SliceSearcher, SliceHay};
+use slices::lossy::{Lossy, LossyCharEq};
+use strings::func::MultiCharEq;
+use bstr::{BStr, ByteSlice};
+#[cfg(feature = "std")]
+use bstr::BString;
+use std::ops::Range;
+
+impl SliceHay<u8> for BStr {
+    #[inline]
+    fn as_slice(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
+
+unsafe impl<'p> Searcher<BStr> for TwoWaySearcher<'p, u8> {
+    #[inline]
+    fn search(&mut self, span: Span<&BStr>) -> Option<Range<usize>> {
+        let (hay, range) = span.into_parts();
+        self.next(hay.as_bytes(), range)
+    }
+
+    fn consume(&mut self, span: Span<&BStr>) -> Option<usize> {
+        self.consume(span.as_bytes())
+    }
+}
+
+unsafe impl<'p> ReverseSearcher<BStr> for TwoWaySearcher<'p, u8> {
+    #[inline]
+    fn rsearch(&mut self, span: Span<&BStr>) -> Option<Range<usize>> {
+        let (hay, range) = span.into_parts();
+        self.next_back(hay.as_bytes(), range)
+    }
+
+    fn rconsume(&mut self, span: Span<&BStr>) -> Option<usize> {
+        self.rconsume(span.as_bytes())
+    }
+}
+
+unsafe impl<'p> Searcher<BStr> for NaiveSearcher<'p, u8> {
+    #[inline]
+    fn search(&mut self, span: Span<&BStr>) -> Option<Range<usize>> {
+        self.search(span.as_bytes())
+    }
+
+    #[inline]
+    fn consume(&mut self, span: Span<&BStr>) -> Option<usize> {
+        self.consume(span.as_bytes())
+    }
+
+    #[inline]
+    fn trim_start(&mut self, hay: &BStr) -> usize {
+        self.trim_start(hay.as_bytes())
+    }
+}
+
+unsafe impl<'p> ReverseSearcher<BStr> for NaiveSearcher<'p, u8> {
+    #[inline]
+    fn rsearch(&mut self, span: Span<&BStr>) -> Option<Range<usize>> {
+        self.rsearch(span.as_bytes())
+    }
+
+    #[inline]
+    fn rconsume(&mut self, span: Span<&BStr>) -> Option<usize> {
+        self.rconsume(span.as_bytes())
+    }
+
+    #[inline]
+    fn trim_end(&mut self, hay: &BStr) -> usize {
+        self.trim_end(hay.as_bytes())
+    }
+}
+
+impl<'p, H: Haystack<Hay = BStr>> Pattern<H> for &'p BStr {
+    type Searcher = SliceSearcher<'p, u8>;
+
+    #[inline]
+    fn into_searcher(self) -> Self::Searcher {
+        SliceSearcher::new_searcher(self.as_bytes())
+    }
+
+    #[inline]
+    fn into_consumer(self) -> Self::Searcher {
+        SliceSearcher::new_consumer(self.as_bytes())
+    }
+}
+
+// FIXME cannot impl `Pattern<(_: Haystack<Hay = BStr>)>` due to RFC 1672 being postponed.
+// (need to wait for chalk)
+macro_rules! impl_bytes_pattern {
+    (<[$($gen:tt)*]> for $pat:ty) => {
+        impl_bytes_pattern!(@impl <[$($gen)*]> for $pat, &'h BStr);
+        impl_bytes_pattern!(@impl <[$($gen)*]> for $pat, &'h mut BStr);
+        #[cfg(feature = "std")]
+        impl_bytes_pattern!(@impl <[$($gen)*]> for $pat, Box<BStr>);
+    };
+    (@impl <[$($gen:tt)*]> for $pat:ty, $ty:ty) => {
+        impl<'h, $($gen)*> Pattern<$ty> for $pat {
+            type Searcher = SliceSearcher<'p, u8>;
+
+            #[inline]
+            fn into_searcher(self) -> Self::Searcher {
+                SliceSearcher::new_searcher(self.as_bytes())
+            }
+
+            #[inline]
+            fn into_consumer(self) -> Self::Searcher {
+                SliceSearcher::new_consumer(self.as_bytes())
+            }
+        }
+    }
+}
+
+impl_bytes_pattern!(<['p]> for &'p [u8]);
+#[cfg(feature = "std")]
+impl_bytes_pattern!(<['p]> for &'p BString);
+
+//------------------------------------------------------------------------------
+// Lossy char searcher
+//------------------------------------------------------------------------------
+
+/// Searcher of `char` predicates over byte strings.
+///
+/// The byte string is decoded lossily: every invalid UTF-8 sequence (maximal
+/// subpart) is treated as U+FFFD REPLACEMENT CHARACTER, following `bstr`.
+#[derive(Clone, Debug)]
+pub struct LossyCharSearcher<F> {
+    predicate: F,
+}
+
+unsafe impl<F: FnMut(char) -> bool> Searcher<BStr> for LossyCharSearcher<F> {
+    #[inline]
+    fn search(&mut self, span: Span<&BStr>) -> Option<Range<usize>> {
+        let (hay, range) = span.into_parts();
+        let st = range.start;
+        let predicate = &mut self.predicate;
+        let (start, end, _) = hay[range].char_indices().find(|&(_, _, c)| predicate(c))?;
+        Some((st + start)..(st + end))
+    }
+
+    #[inline]
+    fn consume(&mut self, span: Span<&BStr>) -> Option<usize> {
+        let (hay, range) = span.into_parts();
+        let st = range.start;
+        let (_, end, c) = hay[range].char_indices().next()?;
+        if (self.predicate)(c) {
+            Some(st + end)
+        } else {
+            None
+        }
+    }
+}
+
+unsafe impl<F: FnMut(char) -> bool> ReverseSearcher<BStr> for LossyCharSearcher<F> {
+    #[inline]
+    fn rsearch(&mut self, span: Span<&BStr>) -> Option<Range<usize>> {
+        let (hay, range) = span.into_parts();
+        let st = range.start;
+        let predicate = &mut self.predicate;
+        let (start, end, _) = hay[range].char_indices().rev().find(|&(_, _, c)| predicate(c))?;
+        Some((st + start)..(st + end))
+    }
+
+    #[inline]
+    fn rconsume(&mut self, span: Span<&BStr>) -> Option<usize> {
+        let (hay, range) = span.into_parts();
+        let st = range.start;
+        let (start, _, c) = hay[range].char_indices().next_back()?;
+        if (self.predicate)(c) {
+            Some(st + start)
+        } else {
+            None
+        }
+    }
+}
+
+unsafe impl<F: FnMut(char) -> bool> DoubleEndedSearcher<BStr> for LossyCharSearcher<F> {}
+
+macro_rules! impl_char_pattern {
+    ($ty:ty) => {
+        impl<'h> Pattern<$ty> for Lossy<char> {
+            type Searcher = LossyCharSearcher<LossyCharEq>;
+
+            #[inline]
+            fn into_searcher(self) -> Self::Searcher {
+                LossyCharSearcher { predicate: LossyCharEq(self.0) }
+            }
+        }
+
+        impl<'h, F: FnMut(char) -> bool> Pattern<$ty> for Lossy<F> {
+            type Searcher = LossyCharSearcher<F>;
+
+            #[inline]
+            fn into_searcher(self) -> Self::Searcher {
+                LossyCharSearcher { predicate: self.0 }
+            }
+        }
+
+        impl<'h, 'p> Pattern<$ty> for Lossy<&'p [char]> {
+            type Searcher = LossyCharSearcher<MultiCharEq<'p>>;
+
+            #[inline]
+            fn into_searcher(self) -> Self::Searcher {
+                LossyCharSearcher { predicate: MultiCharEq(self.0) }
+            }
+        }
+    }
+}
+
+impl_char_pattern!(&'h BStr);
+impl_char_pattern!(&'h mut BStr);
+#[cfg(feature = "std")]
+impl_char_pattern!(Box<BStr>);
@@ -0,0 +1,116 @@
+use haystack::{Hay, Haystack, Span};
+use bstr::BStr;
+use std::ops::Range;
+
+// Note: `BString` cannot be a `Haystack` since it dereferences to `Vec<u8>`
+// instead of `BStr`. Use `Box<BStr>` for owned pieces, or `&mut BStr` for
+// unique access.
+
+impl Hay for BStr {
+    type Index = usize;
+
+    #[inline]
+    fn empty<'a>() -> &'a Self {
+        BStr::new(b"")
+    }
+
+    #[inline]
+    fn start_index(&self) -> usize {
+        0
+    }
+
+    #[inline]
+    fn end_index(&self) -> usize {
+        self.len()
+    }
+
+    #[inline]
+    unsafe fn slice_unchecked(&self, range: Range<usize>) -> &Self {
+        BStr::new(self.get_unchecked(range))
+    }
+
+    #[inline]
+    unsafe fn next_index(&self, index: usize) -> usize {
+        index + 1
+    }
+
+    #[inline]
+    unsafe fn prev_index(&self, index: usize) -> usize {
+        index - 1
+    }
+}
+
+impl<'h> Haystack for &'h mut BStr {
+    type Hay = BStr;
+
+    #[inline]
+    fn hay(&self) -> &BStr {
+        self
+    }
+
+    #[inline]
+    fn empty(&self) -> Self {
+        Self::default()
+    }
+
+    #[inline]
+    unsafe fn slice_unchecked(self, range: Range<usize>) -> Self {
+        self.get_unchecked_mut(range).as_mut()
+    }
+
+    #[inline]
+    unsafe fn split_around(self, range: Range<usize>) -> [Self; 3] {
+        let (haystack, right) = self.split_at_mut(range.end);
+        let (left, middle) = haystack.split_at_mut(range.start);
+        [left.as_mut(), middle.as_mut(), right.as_mut()]
+    }
+
+    #[inline]
+    fn restore_range(&self, range: Range<usize>, subrange: Range<usize>) -> Range<usize> {
+        (subrange.start + range.start)..(subrange.end + range.start)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Haystack for Box<BStr> {
+    type Hay = BStr;
+
+    #[inline]
+    fn hay(&self) -> &BStr {
+        self
+    }
+
+    #[inline]
+    fn empty(&self) -> Self {
+        Box::default()
+    }
+
+    #[inline]
+    unsafe fn slice_unchecked(self, range: Range<usize>) -> Self {
+        Haystack::slice_unchecked(Box::<[u8]>::from(self), range).into()
+    }
+
+    #[inline]
+    unsafe fn split_around(self, range: Range<usize>) -> [Self; 3] {
+        let [left, middle, right] = Box::<[u8]>::from(self).split_around(range);
+        [left.into(), middle.into(), right.into()]
+    }
+
+    #[inline]
+    fn restore_range(&self, range: Range<usize>, subrange: Range<usize>) -> Range<usize> {
+        (subrange.start + range.start)..(subrange.end + range.start)
+    }
+}
+
+impl<'h> Span<&'h BStr> {
+    /// Reinterprets the byte string span as a byte-array span.
+    #[inline]
+    pub fn as_bytes(self) -> Span<&'h [u8]> {
+        let (haystack, range) = self.into_parts();
+        unsafe {
+            Span::from_parts(&**haystack, range)
+        }
+    }
+}
+
+mod bstr_pat;
@@ -0,0 +1,127 @@
+use pattern::*;
+use haystack::{Hay, Haystack, Span};
+use super::{search_accepted, rsearch_accepted};
+use std::fmt;
+use std::ops::Range;
+
+/// A pattern whose matches are only accepted when a guard approves them.
+///
+/// The guard receives the whole hay and the range of a match of the inner
+/// pattern, so it can inspect the surroundings of the match. Rejected matches
+/// are skipped and the search continues right after the start of the rejected
+/// match, so occurrences overlapping a rejected one are still found.
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate pattern_3;
+/// use pattern_3::Filtered;
+/// use pattern_3::ext::match_indices;
+///
+/// // `TODO`, but not inside a word.
+/// let standalone = |hay: &str, range: std::ops::Range<usize>| {
+///     let is_word = |c: char| c.is_alphanumeric() || c == '_';
+///     !hay[..range.start].chars().next_back().map_or(false, is_word)
+///         && !hay[range.end..].chars().next().map_or(false, is_word)
+/// };
+/// assert_eq!(
+///     match_indices("TODOS TODO xTODO", Filtered("TODO", standalone)).collect::<Vec<_>>(),
+///     vec![(6, "TODO")],
+/// );
+/// ```
+#[derive(Clone, Copy)]
+pub struct Filtered<P, F>(pub P, pub F);
+
+impl<P: fmt::Debug, F> fmt::Debug for Filtered<P, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Filtered").field(&self.0).finish()
+    }
+}
+
+/// Searcher of a [`Filtered`] pattern.
+#[derive(Clone)]
+pub struct FilteredSearcher<S, F> {
+    searcher: S,
+    guard: F,
+}
+
+impl<S: fmt::Debug, F> fmt::Debug for FilteredSearcher<S, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("FilteredSearcher")
+            .field("searcher", &self.searcher)
+            .finish()
+    }
+}
+
+unsafe impl<A, S, F> Searcher<A> for FilteredSearcher<S, F>
+where
+    A: Hay + ?Sized,
+    S: Searcher<A>,
+    F: FnMut(&A, Range<A::Index>) -> bool,
+{
+    fn search(&mut self, span: Span<&A>) -> Option<Range<A::Index>> {
+        let FilteredSearcher { ref mut searcher, ref mut guard } = *self;
+        search_accepted(searcher, span, |hay, found| {
+            if guard(hay, found.clone()) { Some(found) } else { None }
+        })
+    }
+
+    fn consume(&mut self, span: Span<&A>) -> Option<A::Index> {
+        let (hay, range) = span.clone().into_parts();
+        let end = self.searcher.consume(span)?;
+        if (self.guard)(hay, range.start..end) {
+            Some(end)
+        } else {
+            None
+        }
+    }
+}
+
+unsafe impl<A, S, F> ReverseSearcher<A> for FilteredSearcher<S, F>
+where
+    A: Hay + ?Sized,
+    S: ReverseSearcher<A>,
+    F: FnMut(&A, Range<A::Index>) -> bool,
+{
+    fn rsearch(&mut self, span: Span<&A>) -> Option<Range<A::Index>> {
+        let FilteredSearcher { ref mut searcher, ref mut guard } = *self;
+        rsearch_accepted(searcher, span, |hay, found| {
+            if guard(hay, found.clone()) { Some(found) } else { None }
+        })
+    }
+
+    fn rconsume(&mut self, span: Span<&A>) -> Option<A::Index> {
+        let (hay, range) = span.clone().into_parts();
+        let start = self.searcher.rconsume(span)?;
+        if (self.guard)(hay, start..range.end) {
+            Some(start)
+        } else {
+            None
+        }
+    }
+}
+
+impl<H, P, F> Pattern<H> for Filtered<P, F>
+where
+    H: Haystack,
+    P: Pattern<H>,
+    F: FnMut(&H::Hay, Range<<H::Hay as Hay>::Index>) -> bool,
+{
+    type Searcher = FilteredSearcher<P::Searcher, F>;
+
+    #[inline]
+    fn into_searcher(self) -> Self::Searcher {
+        FilteredSearcher {
+            searcher: self.0.into_searcher(),
+            guard: self.1,
+        }
+    }
+
+    #[inline]
+    fn into_consumer(self) -> Self::Searcher {
+        FilteredSearcher {
+            searcher: self.0.into_consumer(),
+            guard: self.1,
+        }
+    }
+}
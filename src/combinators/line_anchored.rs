@@ -0,0 +1,232 @@
+use pattern::*;
+use haystack::{Hay, Haystack, Span};
+use memchr::{memchr, memrchr};
+use super::{search_accepted_with, rsearch_accepted_with};
+use std::ops::Range;
+
+/// Hays which can be split into lines.
+///
+/// The start of the hay and every position after a `'\n'` is the start of a
+/// line. The end of the hay and every position of a `'\n'` is the end of a
+/// line.
+pub trait LineHay: Hay<Index = usize> {
+    /// Returns whether a line starts at `index`.
+    fn is_line_start(&self, index: usize) -> bool;
+
+    /// Returns whether a line ends at `index`.
+    fn is_line_end(&self, index: usize) -> bool;
+
+    /// Returns the first start of a line after `index`.
+    fn next_line_start(&self, index: usize) -> Option<usize>;
+
+    /// Returns the last end of a line before `index`.
+    fn prev_line_end(&self, index: usize) -> Option<usize>;
+}
+
+impl LineHay for [u8] {
+    #[inline]
+    fn is_line_start(&self, index: usize) -> bool {
+        index == 0 || self[index - 1] == b'\n'
+    }
+
+    #[inline]
+    fn is_line_end(&self, index: usize) -> bool {
+        index == self.len() || self[index] == b'\n'
+    }
+
+    #[inline]
+    fn next_line_start(&self, index: usize) -> Option<usize> {
+        memchr(b'\n', &self[index..]).map(|i| index + i + 1)
+    }
+
+    #[inline]
+    fn prev_line_end(&self, index: usize) -> Option<usize> {
+        memrchr(b'\n', &self[..index])
+    }
+}
+
+// A '\n' byte is always a whole character, so the positions around it are
+// character boundaries.
+impl LineHay for str {
+    #[inline]
+    fn is_line_start(&self, index: usize) -> bool {
+        self.as_bytes().is_line_start(index)
+    }
+
+    #[inline]
+    fn is_line_end(&self, index: usize) -> bool {
+        self.as_bytes().is_line_end(index)
+    }
+
+    #[inline]
+    fn next_line_start(&self, index: usize) -> Option<usize> {
+        self.as_bytes().next_line_start(index)
+    }
+
+    #[inline]
+    fn prev_line_end(&self, index: usize) -> Option<usize> {
+        self.as_bytes().prev_line_end(index)
+    }
+}
+
+/// Which end of a line a [`LineAnchored`] pattern is anchored to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LineAnchor {
+    /// The match must begin at the start of a line.
+    Start,
+    /// The match must finish at the end of a line.
+    End,
+}
+
+/// A pattern which only matches at the start or the end of a line.
+///
+/// Occurrences of the inner pattern are only accepted when they begin at the
+/// start of a line ([`LineAnchor::Start`]), or finish at the end of a line
+/// ([`LineAnchor::End`]). Lines are delimited by `'\n'`, and the start and end
+/// of the hay are anchors as well. This is the `^` and `$` of a multi-line
+/// regex.
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate pattern_3;
+/// use pattern_3::LineAnchored;
+/// use pattern_3::ext::match_indices;
+///
+/// let log = "error: a\nnote: no error: here\nerror: b";
+/// assert_eq!(
+///     match_indices(log, LineAnchored::start("error:")).collect::<Vec<_>>(),
+///     vec![(0, "error:"), (30, "error:")],
+/// );
+///
+/// let code = "x;\nx; y\ny;";
+/// assert_eq!(
+///     match_indices(code, LineAnchored::end(';')).collect::<Vec<_>>(),
+///     vec![(1, ";"), (9, ";")],
+/// );
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct LineAnchored<P> {
+    /// The inner pattern.
+    pub pattern: P,
+    /// Which end of a line the occurrences must be anchored to.
+    pub anchor: LineAnchor,
+}
+
+impl<P> LineAnchored<P> {
+    /// Anchors the pattern to the start of a line.
+    #[inline]
+    pub fn start(pattern: P) -> Self {
+        LineAnchored { pattern, anchor: LineAnchor::Start }
+    }
+
+    /// Anchors the pattern to the end of a line.
+    #[inline]
+    pub fn end(pattern: P) -> Self {
+        LineAnchored { pattern, anchor: LineAnchor::End }
+    }
+}
+
+/// Searcher of a [`LineAnchored`] pattern.
+#[derive(Debug, Clone)]
+pub struct LineAnchoredSearcher<S> {
+    searcher: S,
+    anchor: LineAnchor,
+}
+
+#[inline]
+fn is_anchored<A: LineHay + ?Sized>(anchor: LineAnchor, hay: &A, range: &Range<usize>) -> bool {
+    match anchor {
+        LineAnchor::Start => hay.is_line_start(range.start),
+        LineAnchor::End => hay.is_line_end(range.end),
+    }
+}
+
+unsafe impl<A, S> Searcher<A> for LineAnchoredSearcher<S>
+where
+    A: LineHay + ?Sized,
+    S: Searcher<A>,
+{
+    fn search(&mut self, span: Span<&A>) -> Option<Range<usize>> {
+        let (anchor, end) = (self.anchor, span.original_range().end);
+        search_accepted_with(
+            &mut self.searcher,
+            span,
+            |hay, found| if is_anchored(anchor, hay, &found) { Some(found) } else { None },
+            // Retry from the next position where an accepted occurrence could
+            // begin, and stop once we run past the end of the span.
+            |hay, found| match anchor {
+                LineAnchor::Start => hay.next_line_start(found.start).filter(|&start| start <= end),
+                LineAnchor::End => Some(unsafe { hay.next_index(found.start) }),
+            },
+        )
+    }
+
+    fn consume(&mut self, span: Span<&A>) -> Option<usize> {
+        let (hay, range) = span.clone().into_parts();
+        if self.anchor == LineAnchor::Start && !hay.is_line_start(range.start) {
+            return None;
+        }
+        let end = self.searcher.consume(span)?;
+        if self.anchor == LineAnchor::End && !hay.is_line_end(end) {
+            return None;
+        }
+        Some(end)
+    }
+}
+
+unsafe impl<A, S> ReverseSearcher<A> for LineAnchoredSearcher<S>
+where
+    A: LineHay + ?Sized,
+    S: ReverseSearcher<A>,
+{
+    fn rsearch(&mut self, span: Span<&A>) -> Option<Range<usize>> {
+        let (anchor, start) = (self.anchor, span.original_range().start);
+        rsearch_accepted_with(
+            &mut self.searcher,
+            span,
+            |hay, found| if is_anchored(anchor, hay, &found) { Some(found) } else { None },
+            |hay, found| match anchor {
+                LineAnchor::End => hay.prev_line_end(found.end).filter(|&end| end >= start),
+                LineAnchor::Start => Some(unsafe { hay.prev_index(found.end) }),
+            },
+        )
+    }
+
+    fn rconsume(&mut self, span: Span<&A>) -> Option<usize> {
+        let (hay, range) = span.clone().into_parts();
+        if self.anchor == LineAnchor::End && !hay.is_line_end(range.end) {
+            return None;
+        }
+        let start = self.searcher.rconsume(span)?;
+        if self.anchor == LineAnchor::Start && !hay.is_line_start(start) {
+            return None;
+        }
+        Some(start)
+    }
+}
+
+impl<H, P> Pattern<H> for LineAnchored<P>
+where
+    H: Haystack,
+    H::Hay: LineHay,
+    P: Pattern<H>,
+{
+    type Searcher = LineAnchoredSearcher<P::Searcher>;
+
+    #[inline]
+    fn into_searcher(self) -> Self::Searcher {
+        LineAnchoredSearcher {
+            searcher: self.pattern.into_searcher(),
+            anchor: self.anchor,
+        }
+    }
+
+    #[inline]
+    fn into_consumer(self) -> Self::Searcher {
+        LineAnchoredSearcher {
+            searcher: self.pattern.into_consumer(),
+            anchor: self.anchor,
+        }
+    }
+}
@@ -0,0 +1,133 @@
+use pattern::*;
+use haystack::{Hay, Haystack, Span};
+use std::ops::Range;
+
+use super::filtered::{Filtered, FilteredSearcher};
+
+/// A pattern which only matches when followed by another pattern.
+///
+/// The assertion pattern `Q` is consumed right after every match of `P`, like
+/// a regex lookahead `P(?=Q)`. It does not contribute to the reported range.
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate pattern_3;
+/// use pattern_3::{FollowedBy, PrecededBy};
+/// use pattern_3::ext::match_indices;
+///
+/// let prices = "5 USD, 7 EUR, 9 USD";
+/// assert_eq!(
+///     match_indices(prices, FollowedBy(|c: char| c.is_ascii_digit(), " USD")).collect::<Vec<_>>(),
+///     vec![(0, "5"), (14, "9")],
+/// );
+/// assert_eq!(
+///     match_indices(prices, PrecededBy("USD", ", ")).count(),
+///     0,
+/// );
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct FollowedBy<P, Q>(pub P, pub Q);
+
+/// A pattern which only matches when preceded by another pattern.
+///
+/// The assertion pattern `Q` is consumed from the back right before every
+/// match of `P`, like a regex lookbehind `(?<=Q)P`. It does not contribute to
+/// the reported range.
+#[derive(Debug, Clone, Copy)]
+pub struct PrecededBy<P, Q>(pub P, pub Q);
+
+/// Guard of a [`FollowedBy`] pattern.
+#[derive(Debug, Clone)]
+pub struct Lookahead<S>(S);
+
+/// Guard of a [`PrecededBy`] pattern.
+#[derive(Debug, Clone)]
+pub struct Lookbehind<S>(S);
+
+impl<'a, A, S> FnOnce<(&'a A, Range<A::Index>)> for Lookahead<S>
+where
+    A: Hay + ?Sized,
+    S: Searcher<A>,
+{
+    type Output = bool;
+    #[inline]
+    extern "rust-call" fn call_once(mut self, args: (&'a A, Range<A::Index>)) -> bool {
+        self.call_mut(args)
+    }
+}
+
+impl<'a, A, S> FnMut<(&'a A, Range<A::Index>)> for Lookahead<S>
+where
+    A: Hay + ?Sized,
+    S: Searcher<A>,
+{
+    #[inline]
+    extern "rust-call" fn call_mut(&mut self, (hay, range): (&'a A, Range<A::Index>)) -> bool {
+        let span = unsafe { Span::from_parts(hay, range.end..hay.end_index()) };
+        self.0.consume(span).is_some()
+    }
+}
+
+impl<'a, A, S> FnOnce<(&'a A, Range<A::Index>)> for Lookbehind<S>
+where
+    A: Hay + ?Sized,
+    S: ReverseSearcher<A>,
+{
+    type Output = bool;
+    #[inline]
+    extern "rust-call" fn call_once(mut self, args: (&'a A, Range<A::Index>)) -> bool {
+        self.call_mut(args)
+    }
+}
+
+impl<'a, A, S> FnMut<(&'a A, Range<A::Index>)> for Lookbehind<S>
+where
+    A: Hay + ?Sized,
+    S: ReverseSearcher<A>,
+{
+    #[inline]
+    extern "rust-call" fn call_mut(&mut self, (hay, range): (&'a A, Range<A::Index>)) -> bool {
+        let span = unsafe { Span::from_parts(hay, hay.start_index()..range.start) };
+        self.0.rconsume(span).is_some()
+    }
+}
+
+impl<H, P, Q> Pattern<H> for FollowedBy<P, Q>
+where
+    H: Haystack,
+    P: Pattern<H>,
+    Q: Pattern<H>,
+{
+    type Searcher = FilteredSearcher<P::Searcher, Lookahead<Q::Searcher>>;
+
+    #[inline]
+    fn into_searcher(self) -> Self::Searcher {
+        Pattern::<H>::into_searcher(Filtered(self.0, Lookahead(self.1.into_consumer())))
+    }
+
+    #[inline]
+    fn into_consumer(self) -> Self::Searcher {
+        Pattern::<H>::into_consumer(Filtered(self.0, Lookahead(self.1.into_consumer())))
+    }
+}
+
+impl<H, P, Q> Pattern<H> for PrecededBy<P, Q>
+where
+    H: Haystack,
+    P: Pattern<H>,
+    Q: Pattern<H>,
+    Q::Searcher: ReverseSearcher<H::Hay>,
+{
+    type Searcher = FilteredSearcher<P::Searcher, Lookbehind<Q::Searcher>>;
+
+    #[inline]
+    fn into_searcher(self) -> Self::Searcher {
+        Pattern::<H>::into_searcher(Filtered(self.0, Lookbehind(self.1.into_consumer())))
+    }
+
+    #[inline]
+    fn into_consumer(self) -> Self::Searcher {
+        Pattern::<H>::into_consumer(Filtered(self.0, Lookbehind(self.1.into_consumer())))
+    }
+}
@@ -0,0 +1,111 @@
+//! Patterns built from other patterns.
+
+mod filtered;
+mod line_anchored;
+mod lookaround;
+pub(crate) mod progress;
+mod ranges;
+mod repeat;
+mod runs;
+mod then;
+mod with_index;
+
+pub use self::filtered::{Filtered, FilteredSearcher};
+pub use self::line_anchored::{LineAnchor, LineAnchored, LineAnchoredSearcher, LineHay};
+pub use self::lookaround::{FollowedBy, PrecededBy, Lookahead, Lookbehind};
+pub use self::progress::{Progress, ProgressSearcher};
+pub use self::ranges::RangesSearcher;
+pub use self::repeat::{repeat, Repeat, RepeatSearcher};
+pub use self::runs::{Runs, RunsSearcher};
+pub use self::then::{Then, ThenSearcher};
+pub use self::with_index::{with_index, WithIndex, WithIndexSearcher};
+
+use pattern::{Searcher, ReverseSearcher};
+use haystack::{Hay, Span};
+use std::ops::Range;
+
+/// Returns the first occurrence found by `searcher` in `span` which `accept`
+/// turns into a match.
+///
+/// The search is resumed at the index `skip` returns for a rejected
+/// occurrence, and stops if it returns `None`. Searchers of empty patterns
+/// already move past the position of their last match, so after an empty
+/// occurrence they are resumed right there instead.
+fn search_accepted_with<'a, A, S, F, K>(
+    searcher: &mut S,
+    span: Span<&'a A>,
+    mut accept: F,
+    mut skip: K,
+) -> Option<Range<A::Index>>
+where
+    A: Hay + ?Sized,
+    S: Searcher<A>,
+    F: FnMut(&'a A, Range<A::Index>) -> Option<Range<A::Index>>,
+    K: FnMut(&'a A, Range<A::Index>) -> Option<A::Index>,
+{
+    let (hay, range) = span.into_parts();
+    let mut start = range.start;
+    loop {
+        let found = searcher.search(unsafe { Span::from_parts(hay, start..range.end) })?;
+        if let Some(m) = accept(hay, found.clone()) {
+            return Some(m);
+        }
+        start = if found.start == found.end {
+            found.start
+        } else {
+            skip(hay, found)?
+        };
+    }
+}
+
+/// Returns the last occurrence found by `searcher` in `span` which `accept`
+/// turns into a match, resuming at the index `skip` returns for a rejected
+/// occurrence.
+fn rsearch_accepted_with<'a, A, S, F, K>(
+    searcher: &mut S,
+    span: Span<&'a A>,
+    mut accept: F,
+    mut skip: K,
+) -> Option<Range<A::Index>>
+where
+    A: Hay + ?Sized,
+    S: ReverseSearcher<A>,
+    F: FnMut(&'a A, Range<A::Index>) -> Option<Range<A::Index>>,
+    K: FnMut(&'a A, Range<A::Index>) -> Option<A::Index>,
+{
+    let (hay, range) = span.into_parts();
+    let mut end = range.end;
+    loop {
+        let found = searcher.rsearch(unsafe { Span::from_parts(hay, range.start..end) })?;
+        if let Some(m) = accept(hay, found.clone()) {
+            return Some(m);
+        }
+        end = if found.start == found.end {
+            found.end
+        } else {
+            skip(hay, found)?
+        };
+    }
+}
+
+/// Like [`search_accepted_with`], resuming right after the start of a
+/// rejected occurrence, so occurrences overlapping it are still found.
+fn search_accepted<'a, A, S, F>(searcher: &mut S, span: Span<&'a A>, accept: F) -> Option<Range<A::Index>>
+where
+    A: Hay + ?Sized,
+    S: Searcher<A>,
+    F: FnMut(&'a A, Range<A::Index>) -> Option<Range<A::Index>>,
+{
+    search_accepted_with(searcher, span, accept, |hay, found| Some(unsafe { hay.next_index(found.start) }))
+}
+
+/// Like [`rsearch_accepted_with`], resuming right before the end of a
+/// rejected occurrence.
+fn rsearch_accepted<'a, A, S, F>(searcher: &mut S, span: Span<&'a A>, accept: F) -> Option<Range<A::Index>>
+where
+    A: Hay + ?Sized,
+    S: ReverseSearcher<A>,
+    F: FnMut(&'a A, Range<A::Index>) -> Option<Range<A::Index>>,
+{
+    rsearch_accepted_with(searcher, span, accept, |hay, found| Some(unsafe { hay.prev_index(found.end) }))
+}
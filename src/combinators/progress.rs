@@ -0,0 +1,264 @@
+use pattern::*;
+use haystack::{Hay, Haystack, Span};
+use std::fmt;
+use std::ops::Range;
+
+/// A pattern reporting how far the scan of the haystack has progressed.
+///
+/// After every search, the callback is invoked with the position the scan
+/// has reached: the end of the match found, or the end of the searched span
+/// if there are no more matches (the start, when searching backwards). Since
+/// every bulk operation in [`ext`](::ext) is driven by a searcher, wrapping
+/// the pattern adds progress reporting to all of them.
+///
+/// Positions are indices into the hay given to the searcher. For shared
+/// haystacks like `&str` this is the whole haystack.
+///
+/// Long stretches without matches are reported along the way. A search
+/// probes windows of the span, each twice as long as the previous one,
+/// reporting the end of every window without a match. For patterns whose
+/// matches are no longer than a known bound, like `char`s, `&str`s and
+/// element predicates, each window resumes where the previous one ended
+/// (backing up just enough to find a match crossing the end), and the first
+/// match found in a window is the result. Other patterns may match
+/// differently once the span is cut short, so their windows all start at the
+/// same position, and the whole span is searched again after probing. This
+/// costs at most about three times a plain search. Probing needs a searcher
+/// which can be cloned; other searchers search the whole span at once.
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate pattern_3;
+/// use pattern_3::Progress;
+/// use pattern_3::ext::split;
+///
+/// let hay = "a,b,c";
+/// let mut reached = vec![];
+/// let pieces = split(hay, Progress(',', |i| reached.push(i))).collect::<Vec<_>>();
+/// assert_eq!(pieces, vec!["a", "b", "c"]);
+/// assert_eq!(reached, vec![2, 4, 5]);
+/// ```
+#[derive(Clone, Copy)]
+pub struct Progress<P, F>(pub P, pub F);
+
+impl<P: fmt::Debug, F> fmt::Debug for Progress<P, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("Progress").field(&self.0).finish()
+    }
+}
+
+/// Searcher of a [`Progress`] pattern.
+#[derive(Clone)]
+pub struct ProgressSearcher<S, F> {
+    searcher: S,
+    progress: F,
+}
+
+/// Searchers which may be cloned to probe a window of the span.
+trait TryClone: Sized {
+    fn try_clone(&self) -> Option<Self>;
+}
+
+impl<S> TryClone for S {
+    #[inline]
+    default fn try_clone(&self) -> Option<S> { None }
+}
+
+impl<S: Clone> TryClone for S {
+    #[inline]
+    fn try_clone(&self) -> Option<S> { Some(self.clone()) }
+}
+
+/// Searchers whose matches are bounded in length and ordered, so a search can
+/// resume after a window without a match.
+pub(crate) trait BoundedMatches {
+    /// Returns the maximum number of codewords a match covers, if known.
+    ///
+    /// A bound may only be given if matches are ordered the same by their
+    /// starts and by their ends, every match is found when searching a span
+    /// containing it, and no match is empty.
+    fn max_match_len(&self) -> Option<usize>;
+}
+
+impl<S> BoundedMatches for S {
+    #[inline]
+    default fn max_match_len(&self) -> Option<usize> { None }
+}
+
+/// The number of elements in the first window probed by a search.
+const FIRST_WINDOW: usize = 64 * 1024;
+
+/// Moves `index` forward by up to `n` elements, stopping at `end`.
+fn advance<A: Hay + ?Sized>(hay: &A, mut index: A::Index, end: A::Index, mut n: usize) -> A::Index {
+    while n > 0 && index != end {
+        index = unsafe { hay.next_index(index) };
+        n -= 1;
+    }
+    index
+}
+
+/// Moves `index` backward by up to `n` elements, stopping at `start`.
+fn retreat<A: Hay + ?Sized>(hay: &A, mut index: A::Index, start: A::Index, mut n: usize) -> A::Index {
+    while n > 0 && index != start {
+        index = unsafe { hay.prev_index(index) };
+        n -= 1;
+    }
+    index
+}
+
+impl<S: fmt::Debug, F> fmt::Debug for ProgressSearcher<S, F> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ProgressSearcher")
+            .field("searcher", &self.searcher)
+            .finish()
+    }
+}
+
+unsafe impl<A, S, F> Searcher<A> for ProgressSearcher<S, F>
+where
+    A: Hay + ?Sized,
+    S: Searcher<A>,
+    F: FnMut(A::Index),
+{
+    fn search(&mut self, span: Span<&A>) -> Option<Range<A::Index>> {
+        let (hay, range) = span.into_parts();
+        let max_len = self.searcher.max_match_len();
+        let mut window_start = range.start;
+        let mut window_end = range.start;
+        let mut len = FIRST_WINDOW;
+        loop {
+            window_end = advance(hay, window_end, range.end, len);
+            if window_end == range.end {
+                break;
+            }
+            let window = unsafe { Span::from_parts(hay, window_start..window_end) };
+            let mut probe = match self.searcher.try_clone() {
+                Some(probe) => probe,
+                None => break,
+            };
+            if let Some(found) = probe.search(window) {
+                if max_len.is_none() {
+                    break;
+                }
+                self.searcher = probe;
+                (self.progress)(found.end);
+                return Some(found);
+            }
+            (self.progress)(window_end);
+            if let Some(max_len) = max_len {
+                window_start = retreat(hay, window_end, window_start, max_len - 1);
+            }
+            len *= 2;
+        }
+        let found = self.searcher.search(unsafe { Span::from_parts(hay, window_start..range.end) });
+        (self.progress)(found.as_ref().map_or(range.end, |r| r.end));
+        found
+    }
+
+    #[inline]
+    fn consume(&mut self, span: Span<&A>) -> Option<A::Index> {
+        let found = self.searcher.consume(span);
+        if let Some(end) = found {
+            (self.progress)(end);
+        }
+        found
+    }
+
+    #[inline]
+    fn trim_start(&mut self, hay: &A) -> A::Index {
+        let start = self.searcher.trim_start(hay);
+        (self.progress)(start);
+        start
+    }
+}
+
+unsafe impl<A, S, F> ReverseSearcher<A> for ProgressSearcher<S, F>
+where
+    A: Hay + ?Sized,
+    S: ReverseSearcher<A>,
+    F: FnMut(A::Index),
+{
+    fn rsearch(&mut self, span: Span<&A>) -> Option<Range<A::Index>> {
+        let (hay, range) = span.into_parts();
+        let max_len = self.searcher.max_match_len();
+        let mut window_start = range.end;
+        let mut window_end = range.end;
+        let mut len = FIRST_WINDOW;
+        loop {
+            window_start = retreat(hay, window_start, range.start, len);
+            if window_start == range.start {
+                break;
+            }
+            let window = unsafe { Span::from_parts(hay, window_start..window_end) };
+            let mut probe = match self.searcher.try_clone() {
+                Some(probe) => probe,
+                None => break,
+            };
+            if let Some(found) = probe.rsearch(window) {
+                if max_len.is_none() {
+                    break;
+                }
+                self.searcher = probe;
+                (self.progress)(found.start);
+                return Some(found);
+            }
+            (self.progress)(window_start);
+            if let Some(max_len) = max_len {
+                window_end = advance(hay, window_start, window_end, max_len - 1);
+            }
+            len *= 2;
+        }
+        let found = self.searcher.rsearch(unsafe { Span::from_parts(hay, range.start..window_end) });
+        (self.progress)(found.as_ref().map_or(range.start, |r| r.start));
+        found
+    }
+
+    #[inline]
+    fn rconsume(&mut self, span: Span<&A>) -> Option<A::Index> {
+        let found = self.searcher.rconsume(span);
+        if let Some(start) = found {
+            (self.progress)(start);
+        }
+        found
+    }
+
+    #[inline]
+    fn trim_end(&mut self, hay: &A) -> A::Index {
+        let end = self.searcher.trim_end(hay);
+        (self.progress)(end);
+        end
+    }
+}
+
+unsafe impl<A, S, F> DoubleEndedSearcher<A> for ProgressSearcher<S, F>
+where
+    A: Hay + ?Sized,
+    S: DoubleEndedSearcher<A>,
+    F: FnMut(A::Index),
+{}
+
+impl<H, P, F> Pattern<H> for Progress<P, F>
+where
+    H: Haystack,
+    P: Pattern<H>,
+    F: FnMut(<H::Hay as Hay>::Index),
+{
+    type Searcher = ProgressSearcher<P::Searcher, F>;
+
+    #[inline]
+    fn into_searcher(self) -> Self::Searcher {
+        ProgressSearcher {
+            searcher: self.0.into_searcher(),
+            progress: self.1,
+        }
+    }
+
+    #[inline]
+    fn into_consumer(self) -> Self::Searcher {
+        ProgressSearcher {
+            searcher: self.0.into_consumer(),
+            progress: self.1,
+        }
+    }
+}
@@ -0,0 +1,148 @@
+use pattern::*;
+use haystack::{Hay, Haystack, Span};
+use std::ops::{Index, Range};
+
+/// A searcher reporting matches from an external stream of ranges.
+///
+/// This allows matches found by another engine (a regex crate, an index
+/// lookup, a GPU scanner…) to drive the algorithms in [`ext`](::ext) like
+/// `split`, `replace_with` and `trim`. The searcher is also a pattern of
+/// itself.
+///
+/// The ranges must be sorted and must not overlap. Ranges starting before
+/// the span being searched (e.g. overlapping the previous match) are skipped.
+/// Every reported range is checked to lie on codeword boundaries of the hay,
+/// and the searcher panics otherwise.
+///
+/// When searching from the back, the ranges are taken from the back of the
+/// iterator, so they can be consumed from both ends.
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate pattern_3;
+/// use pattern_3::RangesSearcher;
+/// use pattern_3::ext::split;
+///
+/// let found = vec![1..2, 3..5];
+/// let pieces = split("a,b--c", RangesSearcher::new(found)).collect::<Vec<_>>();
+/// assert_eq!(pieces, vec!["a", "b", "c"]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RangesSearcher<I> {
+    ranges: I,
+    // Ranges taken from `ranges` but not reported yet.
+    front: Option<Range<usize>>,
+    back: Option<Range<usize>>,
+}
+
+impl<I: Iterator<Item = Range<usize>>> RangesSearcher<I> {
+    /// Creates a searcher from a sorted sequence of non-overlapping ranges.
+    #[inline]
+    pub fn new<R: IntoIterator<IntoIter = I, Item = Range<usize>>>(ranges: R) -> Self {
+        RangesSearcher {
+            ranges: ranges.into_iter(),
+            front: None,
+            back: None,
+        }
+    }
+
+    /// Returns the next range starting within `range`, or `None` after
+    /// putting it back if it does not fit.
+    fn next_within<A>(&mut self, hay: &A, range: Range<usize>, at_start: bool) -> Option<Range<usize>>
+    where
+        A: Hay<Index = usize> + Index<Range<usize>, Output = A> + ?Sized,
+    {
+        loop {
+            let r = self.front.take().or_else(|| self.ranges.next()).or_else(|| self.back.take())?;
+            assert!(r.start <= r.end, "range {:?} is reversed", r);
+            if r.start < range.start {
+                continue;
+            }
+            if r.end > range.end || (at_start && r.start != range.start) {
+                self.front = Some(r);
+                return None;
+            }
+            // panics if the range is out of bounds or not on codeword boundaries.
+            let _ = &hay[r.clone()];
+            return Some(r);
+        }
+    }
+}
+
+impl<I: DoubleEndedIterator<Item = Range<usize>>> RangesSearcher<I> {
+    fn next_back_within<A>(&mut self, hay: &A, range: Range<usize>, at_end: bool) -> Option<Range<usize>>
+    where
+        A: Hay<Index = usize> + Index<Range<usize>, Output = A> + ?Sized,
+    {
+        loop {
+            let r = self.back.take().or_else(|| self.ranges.next_back()).or_else(|| self.front.take())?;
+            assert!(r.start <= r.end, "range {:?} is reversed", r);
+            if r.end > range.end {
+                continue;
+            }
+            if r.start < range.start || (at_end && r.end != range.end) {
+                self.back = Some(r);
+                return None;
+            }
+            let _ = &hay[r.clone()];
+            return Some(r);
+        }
+    }
+}
+
+unsafe impl<A, I> Searcher<A> for RangesSearcher<I>
+where
+    A: Hay<Index = usize> + Index<Range<usize>, Output = A> + ?Sized,
+    I: Iterator<Item = Range<usize>>,
+{
+    #[inline]
+    fn search(&mut self, span: Span<&A>) -> Option<Range<usize>> {
+        let (hay, range) = span.into_parts();
+        self.next_within(hay, range, false)
+    }
+
+    #[inline]
+    fn consume(&mut self, span: Span<&A>) -> Option<usize> {
+        let (hay, range) = span.into_parts();
+        self.next_within(hay, range, true).map(|r| r.end)
+    }
+}
+
+unsafe impl<A, I> ReverseSearcher<A> for RangesSearcher<I>
+where
+    A: Hay<Index = usize> + Index<Range<usize>, Output = A> + ?Sized,
+    I: DoubleEndedIterator<Item = Range<usize>>,
+{
+    #[inline]
+    fn rsearch(&mut self, span: Span<&A>) -> Option<Range<usize>> {
+        let (hay, range) = span.into_parts();
+        self.next_back_within(hay, range, false)
+    }
+
+    #[inline]
+    fn rconsume(&mut self, span: Span<&A>) -> Option<usize> {
+        let (hay, range) = span.into_parts();
+        self.next_back_within(hay, range, true).map(|r| r.start)
+    }
+}
+
+unsafe impl<A, I> DoubleEndedSearcher<A> for RangesSearcher<I>
+where
+    A: Hay<Index = usize> + Index<Range<usize>, Output = A> + ?Sized,
+    I: DoubleEndedIterator<Item = Range<usize>>,
+{}
+
+impl<H, I> Pattern<H> for RangesSearcher<I>
+where
+    H: Haystack,
+    H::Hay: Hay<Index = usize> + Index<Range<usize>, Output = H::Hay>,
+    I: Iterator<Item = Range<usize>>,
+{
+    type Searcher = Self;
+
+    #[inline]
+    fn into_searcher(self) -> Self {
+        self
+    }
+}
@@ -0,0 +1,232 @@
+use pattern::*;
+use haystack::{Hay, Haystack, Span};
+use byte_hay::EmptyMatches;
+use std::ops::{Bound, Range, RangeBounds};
+use super::{search_accepted, rsearch_accepted};
+
+/// A pattern matching between `min` and `max` consecutive occurrences of
+/// another pattern as one range.
+///
+/// Repetition is greedy: every match extends as far as the inner pattern
+/// keeps matching, up to `max` occurrences. Occurrences which cannot be
+/// extended to `min` are skipped. Searching from the back extends matches
+/// towards the front instead, so a run longer than `max` may be cut at
+/// different places depending on the search direction.
+///
+/// When `min` is 0 the pattern matches everywhere, with an empty match
+/// wherever the inner pattern does not occur. Like `str::split` with an empty
+/// needle, no empty match is reported right after a non-empty one.
+///
+/// Created by [`repeat()`].
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate pattern_3;
+/// use pattern_3::repeat;
+/// use pattern_3::ext::split;
+///
+/// let sections = "intro\n---\nbody - with -- dashes\n-----\nend";
+/// assert_eq!(
+///     split(sections, repeat('-', 3..)).collect::<Vec<_>>(),
+///     vec!["intro\n", "\nbody - with -- dashes\n", "\nend"],
+/// );
+///
+/// // optional separators between every character
+/// assert_eq!(split("a-bc", repeat('-', ..=1)).collect::<Vec<_>>(), vec!["", "a", "b", "c", ""]);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Repeat<P> {
+    pattern: P,
+    min: usize,
+    max: usize,
+}
+
+/// Creates a [`Repeat`] pattern matching between `count.start` and
+/// `count.end` consecutive occurrences of `pattern`.
+///
+/// # Panics
+///
+/// Panics if `count` is empty.
+pub fn repeat<P, R: RangeBounds<usize>>(pattern: P, count: R) -> Repeat<P> {
+    let min = match count.start_bound() {
+        Bound::Included(&m) => m,
+        Bound::Excluded(&m) => m.checked_add(1).expect("repeat count overflows"),
+        Bound::Unbounded => 0,
+    };
+    let max = match count.end_bound() {
+        Bound::Included(&n) => n,
+        Bound::Excluded(&n) => n.checked_sub(1).expect("repeat count is empty"),
+        Bound::Unbounded => usize::MAX,
+    };
+    assert!(min <= max, "repeat count is empty");
+    Repeat { pattern, min, max }
+}
+
+/// Searcher of a [`Repeat`] pattern.
+#[derive(Debug, Clone)]
+pub struct RepeatSearcher<S> {
+    searcher: S,
+    consumer: S,
+    min: usize,
+    max: usize,
+    // Only used when `min` is 0.
+    empty: EmptyMatches,
+}
+
+impl<S> RepeatSearcher<S> {
+    /// Consumes up to `max - count` more occurrences after `end` within
+    /// `..limit`, returning the new end and total count.
+    fn extend_forward<A>(
+        consumer: &mut S,
+        max: usize,
+        hay: &A,
+        mut end: A::Index,
+        limit: A::Index,
+        mut count: usize,
+    ) -> (A::Index, usize)
+    where
+        A: Hay + ?Sized,
+        S: Searcher<A>,
+    {
+        while count < max {
+            match consumer.consume(unsafe { Span::from_parts(hay, end..limit) }) {
+                Some(next) if next != end => end = next,
+                _ => break,
+            }
+            count += 1;
+        }
+        (end, count)
+    }
+
+    /// Consumes up to `max - count` more occurrences before `start` within
+    /// `limit..`, returning the new start and total count.
+    fn extend_backward<A>(
+        consumer: &mut S,
+        max: usize,
+        hay: &A,
+        mut start: A::Index,
+        limit: A::Index,
+        mut count: usize,
+    ) -> (A::Index, usize)
+    where
+        A: Hay + ?Sized,
+        S: ReverseSearcher<A>,
+    {
+        while count < max {
+            match consumer.rconsume(unsafe { Span::from_parts(hay, limit..start) }) {
+                Some(next) if next != start => start = next,
+                _ => break,
+            }
+            count += 1;
+        }
+        (start, count)
+    }
+}
+
+unsafe impl<A, S> Searcher<A> for RepeatSearcher<S>
+where
+    A: Hay + ?Sized,
+    S: Searcher<A>,
+{
+    fn search(&mut self, span: Span<&A>) -> Option<Range<A::Index>> {
+        let limit = span.original_range().end;
+        let RepeatSearcher { ref mut searcher, ref mut consumer, min, max, ref mut empty } = *self;
+        if min == 0 {
+            // Every position starts a match, greedily extended.
+            let (hay, range) = span.into_parts();
+            return empty.search(hay, range, |r| {
+                let (end, _) = Self::extend_forward(consumer, max, hay, r.start, limit, 0);
+                Some(r.start..end)
+            });
+        }
+        search_accepted(searcher, span, |hay, found| {
+            // An empty occurrence cannot be repeated.
+            if found.start == found.end {
+                return if min == 1 { Some(found) } else { None };
+            }
+            let (end, count) = Self::extend_forward(consumer, max, hay, found.end, limit, 1);
+            if count >= min { Some(found.start..end) } else { None }
+        })
+    }
+
+    fn consume(&mut self, span: Span<&A>) -> Option<A::Index> {
+        let (hay, range) = span.into_parts();
+        let (end, count) = Self::extend_forward(&mut self.consumer, self.max, hay, range.start, range.end, 0);
+        if count >= self.min {
+            Some(end)
+        } else if count == 0 && self.min == 1 {
+            // The inner pattern may still match an empty string here.
+            self.consumer.consume(unsafe { Span::from_parts(hay, range) })
+        } else {
+            None
+        }
+    }
+}
+
+unsafe impl<A, S> ReverseSearcher<A> for RepeatSearcher<S>
+where
+    A: Hay + ?Sized,
+    S: ReverseSearcher<A>,
+{
+    fn rsearch(&mut self, span: Span<&A>) -> Option<Range<A::Index>> {
+        let limit = span.original_range().start;
+        let RepeatSearcher { ref mut searcher, ref mut consumer, min, max, ref mut empty } = *self;
+        if min == 0 {
+            let (hay, range) = span.into_parts();
+            return empty.rsearch(hay, range, |r| {
+                let (start, _) = Self::extend_backward(consumer, max, hay, r.end, limit, 0);
+                Some(start..r.end)
+            });
+        }
+        rsearch_accepted(searcher, span, |hay, found| {
+            if found.start == found.end {
+                return if min == 1 { Some(found) } else { None };
+            }
+            let (start, count) = Self::extend_backward(consumer, max, hay, found.start, limit, 1);
+            if count >= min { Some(start..found.end) } else { None }
+        })
+    }
+
+    fn rconsume(&mut self, span: Span<&A>) -> Option<A::Index> {
+        let (hay, range) = span.into_parts();
+        let (start, count) = Self::extend_backward(&mut self.consumer, self.max, hay, range.end, range.start, 0);
+        if count >= self.min {
+            Some(start)
+        } else if count == 0 && self.min == 1 {
+            self.consumer.rconsume(unsafe { Span::from_parts(hay, range) })
+        } else {
+            None
+        }
+    }
+}
+
+impl<H, P> Pattern<H> for Repeat<P>
+where
+    H: Haystack,
+    P: Pattern<H> + Clone,
+{
+    type Searcher = RepeatSearcher<P::Searcher>;
+
+    #[inline]
+    fn into_searcher(self) -> Self::Searcher {
+        RepeatSearcher {
+            searcher: self.pattern.clone().into_searcher(),
+            consumer: self.pattern.into_consumer(),
+            min: self.min,
+            max: self.max,
+            empty: EmptyMatches::new(),
+        }
+    }
+
+    #[inline]
+    fn into_consumer(self) -> Self::Searcher {
+        RepeatSearcher {
+            searcher: self.pattern.clone().into_consumer(),
+            consumer: self.pattern.into_consumer(),
+            min: self.min,
+            max: self.max,
+            empty: EmptyMatches::new(),
+        }
+    }
+}
@@ -0,0 +1,150 @@
+use pattern::*;
+use haystack::{Hay, Haystack, Span};
+use std::ops::Range;
+
+/// A pattern matching maximal runs of consecutive matches of another pattern.
+///
+/// Adjacent matches of the inner pattern are merged into a single range, so
+/// an element-wise pattern like a `char` predicate matches whole blocks of
+/// matching codewords.
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate pattern_3;
+/// use pattern_3::Runs;
+/// use pattern_3::ext::{match_ranges, replace_with};
+///
+/// let text = "a  b\t\n c";
+/// let blocks = match_ranges(text, Runs(char::is_whitespace)).map(|(r, _)| r).collect::<Vec<_>>();
+/// assert_eq!(blocks, vec![1..3, 4..7]);
+///
+/// let mut collapsed = String::new();
+/// replace_with(text, Runs(char::is_whitespace), |_| " ", |s| collapsed.push_str(s));
+/// assert_eq!(collapsed, "a b c");
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Runs<P>(pub P);
+
+/// Searcher of a [`Runs`] pattern.
+#[derive(Debug, Clone)]
+pub struct RunsSearcher<S> {
+    searcher: S,
+    consumer: S,
+}
+
+impl<S> RunsSearcher<S> {
+    /// Extends a run ending at `end` as far as possible within `..limit`.
+    fn extend_forward<A>(&mut self, hay: &A, mut end: A::Index, limit: A::Index) -> A::Index
+    where
+        A: Hay + ?Sized,
+        S: Searcher<A>,
+    {
+        while let Some(next) = self.consumer.consume(unsafe { Span::from_parts(hay, end..limit) }) {
+            if next == end {
+                break;
+            }
+            end = next;
+        }
+        end
+    }
+
+    /// Extends a run starting at `start` as far as possible within `limit..`.
+    fn extend_backward<A>(&mut self, hay: &A, mut start: A::Index, limit: A::Index) -> A::Index
+    where
+        A: Hay + ?Sized,
+        S: ReverseSearcher<A>,
+    {
+        while let Some(next) = self.consumer.rconsume(unsafe { Span::from_parts(hay, limit..start) }) {
+            if next == start {
+                break;
+            }
+            start = next;
+        }
+        start
+    }
+}
+
+unsafe impl<A, S> Searcher<A> for RunsSearcher<S>
+where
+    A: Hay + ?Sized,
+    S: Searcher<A>,
+{
+    #[inline]
+    fn search(&mut self, span: Span<&A>) -> Option<Range<A::Index>> {
+        let (hay, range) = span.into_parts();
+        let found = self.searcher.search(unsafe { Span::from_parts(hay, range.clone()) })?;
+        if found.start == found.end {
+            return Some(found);
+        }
+        let end = self.extend_forward(hay, found.end, range.end);
+        Some(found.start..end)
+    }
+
+    #[inline]
+    fn consume(&mut self, span: Span<&A>) -> Option<A::Index> {
+        let (hay, range) = span.into_parts();
+        let end = self.consumer.consume(unsafe { Span::from_parts(hay, range.clone()) })?;
+        if end == range.start {
+            return Some(end);
+        }
+        Some(self.extend_forward(hay, end, range.end))
+    }
+}
+
+unsafe impl<A, S> ReverseSearcher<A> for RunsSearcher<S>
+where
+    A: Hay + ?Sized,
+    S: ReverseSearcher<A>,
+{
+    #[inline]
+    fn rsearch(&mut self, span: Span<&A>) -> Option<Range<A::Index>> {
+        let (hay, range) = span.into_parts();
+        let found = self.searcher.rsearch(unsafe { Span::from_parts(hay, range.clone()) })?;
+        if found.start == found.end {
+            return Some(found);
+        }
+        let start = self.extend_backward(hay, found.start, range.start);
+        Some(start..found.end)
+    }
+
+    #[inline]
+    fn rconsume(&mut self, span: Span<&A>) -> Option<A::Index> {
+        let (hay, range) = span.into_parts();
+        let start = self.consumer.rconsume(unsafe { Span::from_parts(hay, range.clone()) })?;
+        if start == range.end {
+            return Some(start);
+        }
+        Some(self.extend_backward(hay, start, range.start))
+    }
+}
+
+unsafe impl<A, S> DoubleEndedSearcher<A> for RunsSearcher<S>
+where
+    A: Hay + ?Sized,
+    S: DoubleEndedSearcher<A>,
+{}
+
+impl<H, P> Pattern<H> for Runs<P>
+where
+    H: Haystack,
+    P: Pattern<H> + Clone,
+{
+    type Searcher = RunsSearcher<P::Searcher>;
+
+    #[inline]
+    fn into_searcher(self) -> Self::Searcher {
+        RunsSearcher {
+            searcher: self.0.clone().into_searcher(),
+            consumer: self.0.into_consumer(),
+        }
+    }
+
+    #[inline]
+    fn into_consumer(self) -> Self::Searcher {
+        RunsSearcher {
+            searcher: self.0.clone().into_consumer(),
+            consumer: self.0.into_consumer(),
+        }
+    }
+}
@@ -0,0 +1,148 @@
+use pattern::*;
+use haystack::{Hay, Haystack, Span};
+use super::{search_accepted, rsearch_accepted};
+use std::ops::Range;
+
+/// A pattern matching one pattern immediately followed by another.
+///
+/// Every match of `P` is extended by consuming `Q` right after it, and the
+/// combined range is reported, like the regex concatenation `PQ`. Matches of
+/// `P` which are not followed by `Q` are skipped, and the search continues
+/// right after their start.
+///
+/// Searching from the back instead finds `Q` first and consumes `P` before
+/// it, so the two directions may disagree when matches of `P` and `Q` overlap.
+///
+/// The part of the last match made by each pattern is available through
+/// [`ThenSearcher::captures`].
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate pattern_3;
+/// use pattern_3::{Runs, Then};
+/// use pattern_3::ext::matches;
+///
+/// let flags = "--verbose --2 -3 --45";
+/// let numbered = Then("--", Runs(|c: char| c.is_ascii_digit()));
+/// assert_eq!(matches(flags, numbered).collect::<Vec<_>>(), vec!["--2", "--45"]);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Then<P, Q>(pub P, pub Q);
+
+/// Searcher of a [`Then`] pattern.
+#[derive(Debug, Clone)]
+pub struct ThenSearcher<S, T, I = usize> {
+    first: S,
+    second: T,
+    first_consumer: S,
+    second_consumer: T,
+    captures: Option<[Range<I>; 2]>,
+}
+
+impl<S, T, I: Clone> ThenSearcher<S, T, I> {
+    /// Returns the ranges matched by the first and the second pattern in the
+    /// last successful search or consume, or `None` if nothing has matched
+    /// yet.
+    ///
+    /// The ranges are relative to the hay of that search.
+    ///
+    /// ```rust
+    /// extern crate pattern_3;
+    /// use pattern_3::{Pattern, Runs, Searcher, Span, Then};
+    ///
+    /// let assignment = Then(Runs(char::is_alphanumeric), '=');
+    /// let mut searcher = Pattern::<&str>::into_searcher(assignment);
+    /// let hay = "x := key=value";
+    /// assert_eq!(searcher.search(Span::from(hay)), Some(5..9));
+    /// let [key, eq] = searcher.captures().unwrap();
+    /// assert_eq!((&hay[key], &hay[eq]), ("key", "="));
+    /// ```
+    #[inline]
+    pub fn captures(&self) -> Option<[Range<I>; 2]> {
+        self.captures.clone()
+    }
+}
+
+unsafe impl<A, S, T> Searcher<A> for ThenSearcher<S, T, A::Index>
+where
+    A: Hay + ?Sized,
+    S: Searcher<A>,
+    T: Searcher<A>,
+{
+    fn search(&mut self, span: Span<&A>) -> Option<Range<A::Index>> {
+        let end = span.original_range().end;
+        let ThenSearcher { ref mut first, ref mut second_consumer, ref mut captures, .. } = *self;
+        search_accepted(first, span, |hay, found| {
+            let rest = unsafe { Span::from_parts(hay, found.end..end) };
+            let end = second_consumer.consume(rest)?;
+            *captures = Some([found.clone(), found.end..end]);
+            Some(found.start..end)
+        })
+    }
+
+    fn consume(&mut self, span: Span<&A>) -> Option<A::Index> {
+        let (hay, range) = span.clone().into_parts();
+        let mid = self.first_consumer.consume(span)?;
+        let end = self.second_consumer.consume(unsafe { Span::from_parts(hay, mid..range.end) })?;
+        self.captures = Some([range.start..mid, mid..end]);
+        Some(end)
+    }
+}
+
+unsafe impl<A, S, T> ReverseSearcher<A> for ThenSearcher<S, T, A::Index>
+where
+    A: Hay + ?Sized,
+    S: ReverseSearcher<A>,
+    T: ReverseSearcher<A>,
+{
+    fn rsearch(&mut self, span: Span<&A>) -> Option<Range<A::Index>> {
+        let start = span.original_range().start;
+        let ThenSearcher { ref mut second, ref mut first_consumer, ref mut captures, .. } = *self;
+        rsearch_accepted(second, span, |hay, found| {
+            let rest = unsafe { Span::from_parts(hay, start..found.start) };
+            let start = first_consumer.rconsume(rest)?;
+            *captures = Some([start..found.start, found.clone()]);
+            Some(start..found.end)
+        })
+    }
+
+    fn rconsume(&mut self, span: Span<&A>) -> Option<A::Index> {
+        let (hay, range) = span.clone().into_parts();
+        let mid = self.second_consumer.rconsume(span)?;
+        let start = self.first_consumer.rconsume(unsafe { Span::from_parts(hay, range.start..mid) })?;
+        self.captures = Some([start..mid, mid..range.end]);
+        Some(start)
+    }
+}
+
+impl<H, P, Q> Pattern<H> for Then<P, Q>
+where
+    H: Haystack,
+    P: Pattern<H> + Clone,
+    Q: Pattern<H> + Clone,
+{
+    type Searcher = ThenSearcher<P::Searcher, Q::Searcher, <H::Hay as Hay>::Index>;
+
+    #[inline]
+    fn into_searcher(self) -> Self::Searcher {
+        ThenSearcher {
+            first: self.0.clone().into_searcher(),
+            second: self.1.clone().into_searcher(),
+            first_consumer: self.0.into_consumer(),
+            second_consumer: self.1.into_consumer(),
+            captures: None,
+        }
+    }
+
+    #[inline]
+    fn into_consumer(self) -> Self::Searcher {
+        ThenSearcher {
+            first: self.0.clone().into_consumer(),
+            second: self.1.clone().into_consumer(),
+            first_consumer: self.0.into_consumer(),
+            second_consumer: self.1.into_consumer(),
+            captures: None,
+        }
+    }
+}
@@ -0,0 +1,202 @@
+use pattern::*;
+use haystack::{SharedHaystack, Span};
+use std::ops::Range;
+
+/// A pattern matching single elements (or `char`s) approved by a predicate
+/// which also receives their position.
+///
+/// On `[T]` haystacks the predicate is a `FnMut(usize, &T) -> bool` called
+/// with the index of the element; on `str` haystacks it is a
+/// `FnMut(usize, char) -> bool` called with the byte offset of the `char`.
+/// Positions are always relative to the start of the whole haystack.
+///
+/// Unique haystacks such as `String` and `&mut str` are split apart after
+/// every match, which would shift the positions seen by the predicate, so
+/// this pattern only searches [`SharedHaystack`]s such as `&str` and `&[T]`.
+///
+/// ```compile_fail
+/// extern crate pattern_3;
+/// use pattern_3::with_index;
+/// use pattern_3::ext::split;
+///
+/// let line = String::from("a,b,c");
+/// split(line, with_index(|i: usize, c: char| i > 2 && c == ','));
+/// ```
+///
+/// ```compile_fail
+/// extern crate pattern_3;
+/// use pattern_3::with_index;
+/// use pattern_3::ext::split;
+///
+/// let mut line = String::from("a,b,c");
+/// split(&mut line[..], with_index(|i: usize, c: char| i > 2 && c == ','));
+/// ```
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate pattern_3;
+/// use pattern_3::with_index;
+/// use pattern_3::ext::split;
+///
+/// // Split on commas only after column 10.
+/// let line = "a,b,c,d,e,f,g,h";
+/// let fields = split(line, with_index(|i: usize, c: char| i >= 10 && c == ','));
+/// assert_eq!(fields.collect::<Vec<_>>(), vec!["a,b,c,d,e,f", "g", "h"]);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct WithIndex<F>(pub F);
+
+/// Creates a [`WithIndex`] pattern from a predicate over positions and
+/// elements.
+#[inline]
+pub fn with_index<F>(predicate: F) -> WithIndex<F> {
+    WithIndex(predicate)
+}
+
+/// Searcher of a [`WithIndex`] pattern.
+#[derive(Debug, Clone)]
+pub struct WithIndexSearcher<F> {
+    predicate: F,
+}
+
+unsafe impl<T, F> Searcher<[T]> for WithIndexSearcher<F>
+where
+    F: FnMut(usize, &T) -> bool,
+{
+    #[inline]
+    fn search(&mut self, span: Span<&[T]>) -> Option<Range<usize>> {
+        let (hay, range) = span.into_parts();
+        let start = range.start;
+        let predicate = &mut self.predicate;
+        let pos = hay[range].iter().enumerate().position(|(i, x)| predicate(start + i, x))?;
+        Some((pos + start)..(pos + start + 1))
+    }
+
+    #[inline]
+    fn consume(&mut self, span: Span<&[T]>) -> Option<usize> {
+        let (hay, range) = span.into_parts();
+        if range.start == range.end {
+            return None;
+        }
+        let x = unsafe { hay.get_unchecked(range.start) };
+        if (self.predicate)(range.start, x) {
+            Some(range.start + 1)
+        } else {
+            None
+        }
+    }
+}
+
+unsafe impl<T, F> ReverseSearcher<[T]> for WithIndexSearcher<F>
+where
+    F: FnMut(usize, &T) -> bool,
+{
+    #[inline]
+    fn rsearch(&mut self, span: Span<&[T]>) -> Option<Range<usize>> {
+        let (hay, range) = span.into_parts();
+        let start = range.start;
+        let predicate = &mut self.predicate;
+        let pos = hay[range].iter().enumerate().rposition(|(i, x)| predicate(start + i, x))?;
+        Some((pos + start)..(pos + start + 1))
+    }
+
+    #[inline]
+    fn rconsume(&mut self, span: Span<&[T]>) -> Option<usize> {
+        let (hay, range) = span.into_parts();
+        if range.start == range.end {
+            return None;
+        }
+        let last = range.end - 1;
+        let x = unsafe { hay.get_unchecked(last) };
+        if (self.predicate)(last, x) {
+            Some(last)
+        } else {
+            None
+        }
+    }
+}
+
+unsafe impl<T, F> DoubleEndedSearcher<[T]> for WithIndexSearcher<F>
+where
+    F: FnMut(usize, &T) -> bool,
+{}
+
+impl<T, F> FusedSearcher<[T]> for WithIndexSearcher<F>
+where
+    F: FnMut(usize, &T) -> bool,
+{}
+
+unsafe impl<F> Searcher<str> for WithIndexSearcher<F>
+where
+    F: FnMut(usize, char) -> bool,
+{
+    #[inline]
+    fn search(&mut self, span: Span<&str>) -> Option<Range<usize>> {
+        let (hay, range) = span.into_parts();
+        let start = range.start;
+        let predicate = &mut self.predicate;
+        let (i, c) = hay[range].char_indices().find(|&(i, c)| predicate(start + i, c))?;
+        Some((start + i)..(start + i + c.len_utf8()))
+    }
+
+    #[inline]
+    fn consume(&mut self, span: Span<&str>) -> Option<usize> {
+        let (hay, range) = span.into_parts();
+        let c = unsafe { hay.get_unchecked(range.clone()) }.chars().next()?;
+        if (self.predicate)(range.start, c) {
+            Some(range.start + c.len_utf8())
+        } else {
+            None
+        }
+    }
+}
+
+unsafe impl<F> ReverseSearcher<str> for WithIndexSearcher<F>
+where
+    F: FnMut(usize, char) -> bool,
+{
+    #[inline]
+    fn rsearch(&mut self, span: Span<&str>) -> Option<Range<usize>> {
+        let (hay, range) = span.into_parts();
+        let start = range.start;
+        let predicate = &mut self.predicate;
+        let (i, c) = hay[range].char_indices().rev().find(|&(i, c)| predicate(start + i, c))?;
+        Some((start + i)..(start + i + c.len_utf8()))
+    }
+
+    #[inline]
+    fn rconsume(&mut self, span: Span<&str>) -> Option<usize> {
+        let (hay, range) = span.into_parts();
+        let c = unsafe { hay.get_unchecked(range.clone()) }.chars().next_back()?;
+        let start = range.end - c.len_utf8();
+        if (self.predicate)(start, c) {
+            Some(start)
+        } else {
+            None
+        }
+    }
+}
+
+unsafe impl<F> DoubleEndedSearcher<str> for WithIndexSearcher<F>
+where
+    F: FnMut(usize, char) -> bool,
+{}
+
+impl<F> FusedSearcher<str> for WithIndexSearcher<F>
+where
+    F: FnMut(usize, char) -> bool,
+{}
+
+impl<H, F> Pattern<H> for WithIndex<F>
+where
+    H: SharedHaystack,
+    WithIndexSearcher<F>: Searcher<H::Hay>,
+{
+    type Searcher = WithIndexSearcher<F>;
+
+    #[inline]
+    fn into_searcher(self) -> Self::Searcher {
+        WithIndexSearcher { predicate: self.0 }
+    }
+}
@@ -0,0 +1,211 @@
+//! Deterministic automata from the `regex-automata` crate as patterns.
+
+use pattern::{Pattern, Searcher, ReverseSearcher};
+use haystack::{Haystack, Span};
+use byte_hay::{ByteHay, EmptyMatches, find_on_boundaries, rfind_on_boundaries};
+use regex_automata::{Anchored, Input, MatchError};
+use regex_automata::dfa::Automaton;
+use regex_automata::dfa::regex::Regex;
+#[cfg(feature = "std")]
+use regex_automata::dfa::dense;
+#[cfg(feature = "std")]
+use regex_automata::nfa::thompson;
+#[cfg(feature = "std")]
+use regex_automata::util::syntax;
+use std::ops::Range;
+
+/// A regular expression compiled into DFAs from the `regex-automata` crate.
+///
+/// Searching runs in time linear to the haystack, with no allocation or
+/// backtracking. Searching backwards uses an extra DFA compiled from the
+/// reversed expression, so unlike
+/// [`RegexSearcher`](struct.RegexSearcher.html) this pattern supports
+/// `rsplit`, `trim_end` and friends.
+///
+/// Forward matches are leftmost-first, as in the `regex` crate. A backward
+/// search finds the match starting the furthest right, extending it to the
+/// left as far as the expression prefers. Forward and backward iteration may
+/// therefore disagree on overlapping candidates.
+///
+/// The DFAs can also be deserialized without allocating through
+/// [`from_dfas`](#method.from_dfas), for use without `std`.
+///
+/// A DFA search fails if it meets a quit byte, e.g. a non-ASCII byte near a
+/// Unicode word boundary `\b` in a DFA built with
+/// `dense::Config::unicode_word_boundary`. Searchers cannot report errors, so
+/// a failed search reports no match and searching stops at the first quit
+/// byte. Debug builds panic instead, so that the lost matches do not go
+/// unnoticed. DFAs built by [`new`](#method.new) have no quit bytes.
+///
+/// ```rust
+/// extern crate pattern_3;
+/// use pattern_3::DfaPattern;
+/// use pattern_3::ext::{rsplitn, split, trim_end};
+///
+/// let sep = DfaPattern::new(r"\s*[,;]\s*").unwrap();
+/// assert_eq!(split("a , b;c", &sep).collect::<Vec<_>>(), vec!["a", "b", "c"]);
+/// assert_eq!(rsplitn("a , b;c", 2, &sep).collect::<Vec<_>>(), vec!["c", "a , b"]);
+///
+/// let digits = DfaPattern::new(r"[0-9]+").unwrap();
+/// assert_eq!(trim_end("order-2024", &digits), "order-");
+/// ```
+#[derive(Debug, Clone)]
+pub struct DfaPattern<A> {
+    regex: Regex<A>,
+    reverse_search: A,
+}
+
+#[cfg(feature = "std")]
+impl DfaPattern<dense::DFA<Vec<u32>>> {
+    /// Compiles the expression into dense DFAs.
+    pub fn new(pattern: &str) -> Result<Self, Box<dense::BuildError>> {
+        Self::build(pattern, true)
+    }
+
+    /// Compiles the expression into dense DFAs which may match invalid UTF-8,
+    /// e.g. `(?-u)\xff`.
+    ///
+    /// Matches which would split a character are skipped when searching a
+    /// `str`, so such a pattern is mostly useful for byte slices.
+    pub fn new_bytes(pattern: &str) -> Result<Self, Box<dense::BuildError>> {
+        Self::build(pattern, false)
+    }
+
+    fn build(pattern: &str, utf8: bool) -> Result<Self, Box<dense::BuildError>> {
+        let syntax = syntax::Config::new().utf8(utf8);
+        let regex = Regex::builder()
+            .syntax(syntax)
+            .thompson(thompson::Config::new().utf8(utf8))
+            .build(pattern)
+            .map_err(Box::new)?;
+        let reverse_search = dense::Builder::new()
+            .syntax(syntax)
+            .thompson(thompson::Config::new().utf8(utf8).reverse(true))
+            .build(pattern)
+            .map_err(Box::new)?;
+        Ok(DfaPattern { regex, reverse_search })
+    }
+}
+
+impl<A: Automaton> DfaPattern<A> {
+    /// Creates a pattern from a DFA regex and an extra DFA for searching
+    /// backwards.
+    ///
+    /// `reverse_search` must be compiled from the same expression with
+    /// `thompson::Config::reverse(true)` and leftmost-first match semantics,
+    /// supporting unanchored searches. The forward DFA of `regex` must support
+    /// both anchored and unanchored searches.
+    #[inline]
+    pub fn from_dfas(regex: Regex<A>, reverse_search: A) -> Self {
+        DfaPattern { regex, reverse_search }
+    }
+
+    /// Returns the DFA regex used for searching forwards.
+    #[inline]
+    pub fn regex(&self) -> &Regex<A> {
+        &self.regex
+    }
+
+    fn find(&self, hay: &[u8], range: Range<usize>) -> Option<Range<usize>> {
+        let input = Input::new(hay).range(range);
+        let m = self.regex.try_search(&input).unwrap_or_else(search_failed)?;
+        Some(m.range())
+    }
+
+    fn rfind(&self, hay: &[u8], range: Range<usize>) -> Option<Range<usize>> {
+        let input = Input::new(hay).range(range.clone());
+        let start = self.reverse_search.try_search_rev(&input).unwrap_or_else(search_failed)?.offset();
+        // The forward search matches too, unless it fails on a quit byte.
+        let end = self.consume(hay, start..range.end)?;
+        Some(start..end)
+    }
+
+    fn consume(&self, hay: &[u8], range: Range<usize>) -> Option<usize> {
+        let input = Input::new(hay).range(range).anchored(Anchored::Yes);
+        let end = self.regex.forward().try_search_fwd(&input).unwrap_or_else(search_failed)?;
+        Some(end.offset())
+    }
+
+    fn rconsume(&self, hay: &[u8], range: Range<usize>) -> Option<usize> {
+        let input = Input::new(hay).range(range).anchored(Anchored::Yes);
+        let start = self.regex.reverse().try_search_rev(&input).unwrap_or_else(search_failed)?;
+        Some(start.offset())
+    }
+}
+
+#[cold]
+#[inline(never)]
+fn search_failed<T>(err: MatchError) -> Option<T> {
+    debug_assert!(false, "DFA search failed: {}", err);
+    None
+}
+
+/// Searcher of a [`DfaPattern`](struct.DfaPattern.html).
+#[derive(Debug)]
+pub struct DfaSearcher<'p, A: 'p> {
+    pattern: &'p DfaPattern<A>,
+    empty: EmptyMatches,
+}
+
+impl<'p, A> Clone for DfaSearcher<'p, A> {
+    #[inline]
+    fn clone(&self) -> Self {
+        DfaSearcher { ..*self }
+    }
+}
+
+unsafe impl<'p, A: Automaton, T: ByteHay + ?Sized> Searcher<T> for DfaSearcher<'p, A> {
+    fn search(&mut self, span: Span<&T>) -> Option<Range<usize>> {
+        let (hay, range) = span.into_parts();
+        let pattern = self.pattern;
+        // only a DFA not in UTF-8 mode can split characters.
+        self.empty.search(hay, range, |r| {
+            find_on_boundaries(hay, r, |r| pattern.find(hay.as_bytes(), r))
+        })
+    }
+
+    fn consume(&mut self, span: Span<&T>) -> Option<usize> {
+        let (hay, range) = span.into_parts();
+        let end = self.pattern.consume(hay.as_bytes(), range)?;
+        if hay.is_boundary(end) {
+            Some(end)
+        } else {
+            None
+        }
+    }
+}
+
+unsafe impl<'p, A: Automaton, T: ByteHay + ?Sized> ReverseSearcher<T> for DfaSearcher<'p, A> {
+    fn rsearch(&mut self, span: Span<&T>) -> Option<Range<usize>> {
+        let (hay, range) = span.into_parts();
+        let pattern = self.pattern;
+        self.empty.rsearch(hay, range, |r| {
+            rfind_on_boundaries(hay, r, |r| pattern.rfind(hay.as_bytes(), r))
+        })
+    }
+
+    fn rconsume(&mut self, span: Span<&T>) -> Option<usize> {
+        let (hay, range) = span.into_parts();
+        let start = self.pattern.rconsume(hay.as_bytes(), range)?;
+        if hay.is_boundary(start) {
+            Some(start)
+        } else {
+            None
+        }
+    }
+}
+
+impl<'p, A: Automaton, H: Haystack> Pattern<H> for &'p DfaPattern<A>
+where
+    H::Hay: ByteHay,
+{
+    type Searcher = DfaSearcher<'p, A>;
+
+    #[inline]
+    fn into_searcher(self) -> Self::Searcher {
+        DfaSearcher {
+            pattern: self,
+            empty: EmptyMatches::new(),
+        }
+    }
+}
@@ -1,8 +1,21 @@
 use haystack::{Hay, Haystack, Span};
+#[cfg(feature = "std")]
+use haystack::SharedHaystack;
 use pattern::{Pattern, Searcher, ReverseSearcher, DoubleEndedSearcher};
+use combinators::LineHay;
+use slices::{AnyOf, MatchedNeedle};
 use std::iter::FusedIterator;
-use std::ops::Range;
+use std::ops::{self, Range, RangeFrom, RangeTo};
 use std::fmt;
+use std::marker::PhantomData;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+#[cfg(feature = "std")]
+use std::hash::Hash;
+#[cfg(feature = "std")]
+use strings::normalized::push_folded;
+#[cfg(feature = "std")]
+use slices::slice::border_table;
 
 macro_rules! generate_clone_and_debug {
     ($name:ident, $field:tt) => {
@@ -10,7 +23,6 @@ macro_rules! generate_clone_and_debug {
         where
             H: Haystack + Clone,
             S: Clone,
-            H::Target: Hay, // FIXME: RFC 2089 or 2289
         {
             fn clone(&self) -> Self {
                 $name { $field: self.$field.clone() }
@@ -24,7 +36,6 @@ macro_rules! generate_clone_and_debug {
         where
             H: Haystack + fmt::Debug,
             S: fmt::Debug,
-            H::Target: Hay, // FIXME: RFC 2089 or 2289
         {
             fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
                 f.debug_tuple(stringify!($name))
@@ -62,7 +73,6 @@ macro_rules! generate_pattern_iterators {
         $(#[$common_stability_attribute])*
         pub struct $forward_iterator<H, S>($internal_iterator<H, S>)
         where
-            H::Target: Hay, // FIXME: RFC 2089 or 2289
             H: Haystack;
 
         generate_clone_and_debug!($forward_iterator, 0);
@@ -71,8 +81,7 @@ macro_rules! generate_pattern_iterators {
         impl<H, S> Iterator for $forward_iterator<H, S>
         where
             H: Haystack,
-            S: Searcher<H::Target>,
-            H::Target: Hay, // FIXME: RFC 2089 or 2289
+            S: Searcher<H::Hay>,
         {
             type Item = $iterty;
 
@@ -86,7 +95,6 @@ macro_rules! generate_pattern_iterators {
         $(#[$common_stability_attribute])*
         pub struct $reverse_iterator<H, S>($internal_iterator<H, S>)
         where
-            H::Target: Hay, // FIXME: RFC 2089 or 2289
             H: Haystack;
 
         generate_clone_and_debug!($reverse_iterator, 0);
@@ -95,8 +103,7 @@ macro_rules! generate_pattern_iterators {
         impl<H, S> Iterator for $reverse_iterator<H, S>
         where
             H: Haystack,
-            S: ReverseSearcher<H::Target>,
-            H::Target: Hay, // FIXME: RFC 2089 or 2289
+            S: ReverseSearcher<H::Hay>,
         {
             type Item = $iterty;
 
@@ -110,16 +117,14 @@ macro_rules! generate_pattern_iterators {
         impl<H, S> FusedIterator for $forward_iterator<H, S>
         where
             H: Haystack,
-            S: Searcher<H::Target>,
-            H::Target: Hay, // FIXME: RFC 2089 or 2289
+            S: Searcher<H::Hay>,
         {}
 
         // #[stable(feature = "fused", since = "1.26.0")]
         impl<H, S> FusedIterator for $reverse_iterator<H, S>
         where
             H: Haystack,
-            S: ReverseSearcher<H::Target>,
-            H::Target: Hay, // FIXME: RFC 2089 or 2289
+            S: ReverseSearcher<H::Hay>,
         {}
 
         generate_pattern_iterators!($($t)* with $(#[$common_stability_attribute])*,
@@ -135,8 +140,7 @@ macro_rules! generate_pattern_iterators {
         impl<H, S> DoubleEndedIterator for $forward_iterator<H, S>
         where
             H: Haystack,
-            S: DoubleEndedSearcher<H::Target>,
-            H::Target: Hay, // FIXME: RFC 2089 or 2289
+            S: DoubleEndedSearcher<H::Hay>,
         {
             #[inline]
             fn next_back(&mut self) -> Option<Self::Item> {
@@ -148,8 +152,7 @@ macro_rules! generate_pattern_iterators {
         impl<H, S> DoubleEndedIterator for $reverse_iterator<H, S>
         where
             H: Haystack,
-            S: DoubleEndedSearcher<H::Target>,
-            H::Target: Hay, // FIXME: RFC 2089 or 2289
+            S: DoubleEndedSearcher<H::Hay>,
         {
             #[inline]
             fn next_back(&mut self) -> Option<Self::Item> {
@@ -172,9 +175,8 @@ pub fn starts_with<H, P>(haystack: H, pattern: P) -> bool
 where
     H: Haystack,
     P: Pattern<H>,
-    H::Target: Hay, // FIXME: RFC 2089 or 2289
 {
-    pattern.into_consumer().consume((*haystack).into()).is_some()
+    pattern.into_consumer().consume(haystack.hay().into()).is_some()
 }
 
 #[inline]
@@ -182,10 +184,134 @@ pub fn ends_with<H, P>(haystack: H, pattern: P) -> bool
 where
     H: Haystack,
     P: Pattern<H>,
-    P::Searcher: ReverseSearcher<H::Target>,
-    H::Target: Hay, // FIXME: RFC 2089 or 2289
+    P::Searcher: ReverseSearcher<H::Hay>,
 {
-    pattern.into_consumer().rconsume((*haystack).into()).is_some()
+    pattern.into_consumer().rconsume(haystack.hay().into()).is_some()
+}
+
+pub fn eq<H, P>(haystack: H, pattern: P) -> bool
+where
+    H: Haystack,
+    P: Pattern<H>,
+{
+    let hay = haystack.hay();
+    pattern.into_consumer().consume(hay.into()) == Some(hay.end_index())
+}
+
+pub fn prefix_match_len<H>(haystack: H, needle: &H::Hay) -> <H::Hay as Hay>::Index
+where
+    H: Haystack,
+    H::Hay: PartialEq,
+{
+    let hay = haystack.hay();
+    let mut i = hay.start_index();
+    let mut j = needle.start_index();
+    unsafe {
+        while i != hay.end_index() && j != needle.end_index() {
+            let next_i = hay.next_index(i);
+            let next_j = needle.next_index(j);
+            if Hay::slice_unchecked(hay, i..next_i) != Hay::slice_unchecked(needle, j..next_j) {
+                break;
+            }
+            i = next_i;
+            j = next_j;
+        }
+    }
+    j
+}
+
+// Returns the end (in `needle`) of the longest prefix of `needle` which is also
+// a suffix of `haystack`. A streaming searcher needs to carry this many items
+// over to the next chunk, as a match may start within them.
+#[cfg(feature = "std")]
+pub fn suffix_prefix_len<H>(haystack: H, needle: &H::Hay) -> <H::Hay as Hay>::Index
+where
+    H: Haystack,
+    H::Hay: PartialEq,
+{
+    let hay = haystack.hay();
+    unsafe {
+        // The codeword boundaries of the needle.
+        let mut bounds = vec![needle.start_index()];
+        let mut j = needle.start_index();
+        while j != needle.end_index() {
+            j = needle.next_index(j);
+            bounds.push(j);
+        }
+        let m = bounds.len() - 1;
+        let codeword = |k: usize| Hay::slice_unchecked(needle, bounds[k]..bounds[k + 1]);
+
+        let border = border_table(m, &codeword);
+
+        // Only the last `m` codewords of the haystack can be part of the
+        // answer.
+        let mut i = hay.end_index();
+        for _ in 0..m {
+            if i == hay.start_index() {
+                break;
+            }
+            i = hay.prev_index(i);
+        }
+
+        let mut q = 0;
+        while i != hay.end_index() {
+            let next_i = hay.next_index(i);
+            let c = Hay::slice_unchecked(hay, i..next_i);
+            while q > 0 && (q == m || codeword(q) != c) {
+                q = border[q - 1];
+            }
+            if q < m && codeword(q) == c {
+                q += 1;
+            }
+            i = next_i;
+        }
+        bounds[q]
+    }
+}
+
+//------------------------------------------------------------------------------
+// Const evaluation
+//------------------------------------------------------------------------------
+
+// Trait methods cannot be `const fn`, so these mirror the naive searcher over
+// byte slices. `str` arguments can be passed through `str::as_bytes`, which
+// never produces a match off a UTF-8 boundary.
+
+const fn bytes_match_at(hay: &[u8], index: usize, needle: &[u8]) -> bool {
+    let mut i = 0;
+    while i < needle.len() {
+        if hay[index + i] != needle[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+pub const fn const_starts_with(hay: &[u8], needle: &[u8]) -> bool {
+    hay.len() >= needle.len() && bytes_match_at(hay, 0, needle)
+}
+
+pub const fn const_ends_with(hay: &[u8], needle: &[u8]) -> bool {
+    hay.len() >= needle.len() && bytes_match_at(hay, hay.len() - needle.len(), needle)
+}
+
+pub const fn const_find(hay: &[u8], needle: &[u8]) -> Option<usize> {
+    if hay.len() < needle.len() {
+        return None;
+    }
+    let mut index = 0;
+    while index <= hay.len() - needle.len() {
+        if bytes_match_at(hay, index, needle) {
+            return Some(index);
+        }
+        index += 1;
+    }
+    None
+}
+
+pub const fn const_contains(hay: &[u8], needle: &[u8]) -> bool {
+    const_find(hay, needle).is_some()
 }
 
 //------------------------------------------------------------------------------
@@ -197,10 +323,9 @@ pub fn trim_start<H, P>(haystack: H, pattern: P) -> H
 where
     H: Haystack,
     P: Pattern<H>,
-    H::Target: Hay, // FIXME: RFC 2089 or 2289
 {
     let range = {
-        let hay = &*haystack;
+        let hay = haystack.hay();
         let start = pattern.into_consumer().trim_start(hay);
         let end = hay.end_index();
         start..end
@@ -212,11 +337,10 @@ pub fn trim_end<H, P>(haystack: H, pattern: P) -> H
 where
     H: Haystack,
     P: Pattern<H>,
-    P::Searcher: ReverseSearcher<H::Target>,
-    H::Target: Hay, // FIXME: RFC 2089 or 2289
+    P::Searcher: ReverseSearcher<H::Hay>,
 {
     let range = {
-        let hay = &*haystack;
+        let hay = haystack.hay();
         let start = hay.start_index();
         let end = pattern.into_consumer().trim_end(hay);
         start..end
@@ -228,12 +352,11 @@ pub fn trim<H, P>(haystack: H, pattern: P) -> H
 where
     H: Haystack,
     P: Pattern<H>,
-    P::Searcher: DoubleEndedSearcher<H::Target>,
-    H::Target: Hay, // FIXME: RFC 2089 or 2289
+    P::Searcher: DoubleEndedSearcher<H::Hay>,
 {
     let mut checker = pattern.into_consumer();
     let range = {
-        let hay = &*haystack;
+        let hay = haystack.hay();
         let end = checker.trim_end(hay);
         let hay = unsafe { Hay::slice_unchecked(hay, hay.start_index()..end) };
         let start = checker.trim_start(hay);
@@ -250,7 +373,6 @@ where
 struct MatchesInternal<H, S>
 where
     H: Haystack,
-    H::Target: Hay, // FIXME: RFC 2089 or 2289
 {
     searcher: S,
     rest: Span<H>,
@@ -259,8 +381,7 @@ where
 impl<H, S> MatchesInternal<H, S>
 where
     H: Haystack,
-    S: Searcher<H::Target>,
-    H::Target: Hay, // FIXME: RFC 2089 or 2289
+    S: Searcher<H::Hay>,
 {
     #[inline]
     fn next_spanned(&mut self) -> Option<Span<H>> {
@@ -280,8 +401,7 @@ where
 impl<H, S> MatchesInternal<H, S>
 where
     H: Haystack,
-    S: ReverseSearcher<H::Target>,
-    H::Target: Hay, // FIXME: RFC 2089 or 2289
+    S: ReverseSearcher<H::Hay>,
 {
     #[inline]
     fn next_back_spanned(&mut self) -> Option<Span<H>> {
@@ -313,7 +433,6 @@ pub fn matches<H, P>(haystack: H, pattern: P) -> Matches<H, P::Searcher>
 where
     H: Haystack,
     P: Pattern<H>,
-    H::Target: Hay, // FIXME: RFC 2089 or 2289
 {
     Matches(MatchesInternal {
         searcher: pattern.into_searcher(),
@@ -325,8 +444,7 @@ pub fn rmatches<H, P>(haystack: H, pattern: P) -> RMatches<H, P::Searcher>
 where
     H: Haystack,
     P: Pattern<H>,
-    P::Searcher: ReverseSearcher<H::Target>,
-    H::Target: Hay, // FIXME: RFC 2089 or 2289
+    P::Searcher: ReverseSearcher<H::Hay>,
 {
     RMatches(MatchesInternal {
         searcher: pattern.into_searcher(),
@@ -338,13 +456,46 @@ pub fn contains<H, P>(haystack: H, pattern: P) -> bool
 where
     H: Haystack,
     P: Pattern<H>,
-    H::Target: Hay, // FIXME: RFC 2089 or 2289
 {
     pattern.into_searcher()
-        .search((*haystack).into())
+        .search(haystack.hay().into())
         .is_some()
 }
 
+// Searches for all the needles at once with `AnyOf`, stopping at the first
+// match of any of them.
+pub fn contains_any<'p, H, N>(haystack: H, needles: &'p [&'p N]) -> bool
+where
+    H: Haystack,
+    N: ?Sized + 'p,
+    AnyOf<'p, N>: Pattern<H>,
+{
+    contains(haystack, AnyOf(needles))
+}
+
+// Counts the non-overlapping matches of each pattern on its own, so matches of
+// different patterns may overlap each other.
+#[cfg(feature = "std")]
+pub fn match_frequencies<H, P, I>(haystack: H, patterns: I) -> Vec<usize>
+where
+    H: Haystack,
+    P: Pattern<H>,
+    I: IntoIterator<Item = P>,
+{
+    let hay = haystack.hay();
+    patterns.into_iter().map(|pattern| {
+        let mut searcher = pattern.into_searcher();
+        let mut rest: Span<&H::Hay> = hay.into();
+        let mut count = 0;
+        while let Some(range) = searcher.search(rest.borrow()) {
+            let [_, _, right] = unsafe { rest.split_around(range) };
+            rest = right;
+            count += 1;
+        }
+        count
+    }).collect()
+}
+
 //------------------------------------------------------------------------------
 // MatchIndices
 //------------------------------------------------------------------------------
@@ -352,7 +503,6 @@ where
 struct MatchIndicesInternal<H, S>
 where
     H: Haystack,
-    H::Target: Hay, // FIXME: RFC 2089 or 2289
 {
     inner: MatchesInternal<H, S>,
 }
@@ -362,11 +512,10 @@ generate_clone_and_debug!(MatchIndicesInternal, inner);
 impl<H, S> MatchIndicesInternal<H, S>
 where
     H: Haystack,
-    S: Searcher<H::Target>,
-    H::Target: Hay, // FIXME: RFC 2089 or 2289
+    S: Searcher<H::Hay>,
 {
     #[inline]
-    fn next(&mut self) -> Option<(<H::Target as Hay>::Index, H)> {
+    fn next(&mut self) -> Option<(<H::Hay as Hay>::Index, H)> {
         let span = self.inner.next_spanned()?;
         let index = span.original_range().start;
         Some((index, Span::into(span)))
@@ -376,11 +525,10 @@ where
 impl<H, S> MatchIndicesInternal<H, S>
 where
     H: Haystack,
-    S: ReverseSearcher<H::Target>,
-    H::Target: Hay, // FIXME: RFC 2089 or 2289
+    S: ReverseSearcher<H::Hay>,
 {
     #[inline]
-    fn next_back(&mut self) -> Option<(<H::Target as Hay>::Index, H)> {
+    fn next_back(&mut self) -> Option<(<H::Hay as Hay>::Index, H)> {
         let span = self.inner.next_back_spanned()?;
         let index = span.original_range().start;
         Some((index, Span::into(span)))
@@ -394,7 +542,7 @@ generate_pattern_iterators! {
         struct RMatchIndices;
     stability:
     internal:
-        MatchIndicesInternal yielding ((<H::Target as Hay>::Index, H));
+        MatchIndicesInternal yielding ((<H::Hay as Hay>::Index, H));
     delegate double ended;
 }
 
@@ -402,7 +550,6 @@ pub fn match_indices<H, P>(haystack: H, pattern: P) -> MatchIndices<H, P::Search
 where
     H: Haystack,
     P: Pattern<H>,
-    H::Target: Hay, // FIXME: RFC 2089 or 2289
 {
     MatchIndices(MatchIndicesInternal {
         inner: matches(haystack, pattern).0,
@@ -413,8 +560,7 @@ pub fn rmatch_indices<H, P>(haystack: H, pattern: P) -> RMatchIndices<H, P::Sear
 where
     H: Haystack,
     P: Pattern<H>,
-    P::Searcher: ReverseSearcher<H::Target>,
-    H::Target: Hay, // FIXME: RFC 2089 or 2289
+    P::Searcher: ReverseSearcher<H::Hay>,
 {
     RMatchIndices(MatchIndicesInternal {
         inner: rmatches(haystack, pattern).0,
@@ -422,26 +568,24 @@ where
 }
 
 #[inline]
-pub fn find<H, P>(haystack: H, pattern: P) -> Option<<H::Target as Hay>::Index>
+pub fn find<H, P>(haystack: H, pattern: P) -> Option<<H::Hay as Hay>::Index>
 where
     H: Haystack,
     P: Pattern<H>,
-    H::Target: Hay, // FIXME: RFC 2089 or 2289
 {
     pattern.into_searcher()
-        .search((*haystack).into())
+        .search(haystack.hay().into())
         .map(|r| r.start)
 }
 
-pub fn rfind<H, P>(haystack: H, pattern: P) -> Option<<H::Target as Hay>::Index>
+pub fn rfind<H, P>(haystack: H, pattern: P) -> Option<<H::Hay as Hay>::Index>
 where
     H: Haystack,
     P: Pattern<H>,
-    P::Searcher: ReverseSearcher<H::Target>,
-    H::Target: Hay, // FIXME: RFC 2089 or 2289
+    P::Searcher: ReverseSearcher<H::Hay>,
 {
     pattern.into_searcher()
-        .rsearch((*haystack).into())
+        .rsearch(haystack.hay().into())
         .map(|r| r.start)
 }
 
@@ -452,7 +596,6 @@ where
 struct MatchRangesInternal<H, S>
 where
     H: Haystack,
-    H::Target: Hay, // FIXME: RFC 2089 or 2289
 {
     inner: MatchesInternal<H, S>,
 }
@@ -462,11 +605,10 @@ generate_clone_and_debug!(MatchRangesInternal, inner);
 impl<H, S> MatchRangesInternal<H, S>
 where
     H: Haystack,
-    S: Searcher<H::Target>,
-    H::Target: Hay, // FIXME: RFC 2089 or 2289
+    S: Searcher<H::Hay>,
 {
     #[inline]
-    fn next(&mut self) -> Option<(Range<<H::Target as Hay>::Index>, H)> {
+    fn next(&mut self) -> Option<(Range<<H::Hay as Hay>::Index>, H)> {
         let span = self.inner.next_spanned()?;
         let range = span.original_range();
         Some((range, Span::into(span)))
@@ -476,11 +618,10 @@ where
 impl<H, S> MatchRangesInternal<H, S>
 where
     H: Haystack,
-    S: ReverseSearcher<H::Target>,
-    H::Target: Hay, // FIXME: RFC 2089 or 2289
+    S: ReverseSearcher<H::Hay>,
 {
     #[inline]
-    fn next_back(&mut self) -> Option<(Range<<H::Target as Hay>::Index>, H)> {
+    fn next_back(&mut self) -> Option<(Range<<H::Hay as Hay>::Index>, H)> {
         let span = self.inner.next_back_spanned()?;
         let range = span.original_range();
         Some((range, Span::into(span)))
@@ -494,7 +635,7 @@ generate_pattern_iterators! {
         struct RMatchRanges;
     stability:
     internal:
-        MatchRangesInternal yielding ((Range<<H::Target as Hay>::Index>, H));
+        MatchRangesInternal yielding ((Range<<H::Hay as Hay>::Index>, H));
     delegate double ended;
 }
 
@@ -502,7 +643,6 @@ pub fn match_ranges<H, P>(haystack: H, pattern: P) -> MatchRanges<H, P::Searcher
 where
     H: Haystack,
     P: Pattern<H>,
-    H::Target: Hay, // FIXME: RFC 2089 or 2289
 {
     MatchRanges(MatchRangesInternal {
         inner: matches(haystack, pattern).0,
@@ -513,33 +653,468 @@ pub fn rmatch_ranges<H, P>(haystack: H, pattern: P) -> RMatchRanges<H, P::Search
 where
     H: Haystack,
     P: Pattern<H>,
-    P::Searcher: ReverseSearcher<H::Target>,
-    H::Target: Hay, // FIXME: RFC 2089 or 2289
+    P::Searcher: ReverseSearcher<H::Hay>,
 {
     RMatchRanges(MatchRangesInternal {
         inner: rmatches(haystack, pattern).0,
     })
 }
 
-pub fn find_range<H, P>(haystack: H, pattern: P) -> Option<Range<<H::Target as Hay>::Index>>
+pub fn find_range<H, P>(haystack: H, pattern: P) -> Option<Range<<H::Hay as Hay>::Index>>
 where
     H: Haystack,
     P: Pattern<H>,
-    H::Target: Hay, // FIXME: RFC 2089 or 2289
 {
     pattern.into_searcher()
-        .search((*haystack).into())
+        .search(haystack.hay().into())
 }
 
-pub fn rfind_range<H, P>(haystack: H, pattern: P) -> Option<Range<<H::Target as Hay>::Index>>
+pub fn rfind_range<H, P>(haystack: H, pattern: P) -> Option<Range<<H::Hay as Hay>::Index>>
 where
     H: Haystack,
     P: Pattern<H>,
-    P::Searcher: ReverseSearcher<H::Target>,
-    H::Target: Hay, // FIXME: RFC 2089 or 2289
+    P::Searcher: ReverseSearcher<H::Hay>,
 {
     pattern.into_searcher()
-        .rsearch((*haystack).into())
+        .rsearch(haystack.hay().into())
+}
+
+//------------------------------------------------------------------------------
+// MatchRangesWith
+//------------------------------------------------------------------------------
+
+pub struct MatchRangesWith<H, S>
+where
+    H: Haystack,
+{
+    inner: MatchesInternal<H, S>,
+}
+
+generate_clone_and_debug!(MatchRangesWith, inner);
+
+impl<H, S> Iterator for MatchRangesWith<H, S>
+where
+    H: Haystack,
+    S: Searcher<H::Hay> + MatchedNeedle,
+{
+    type Item = (Range<<H::Hay as Hay>::Index>, usize, H);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let span = self.inner.next_spanned()?;
+        let range = span.original_range();
+        Some((range, self.inner.searcher.matched_needle(), Span::into(span)))
+    }
+}
+
+impl<H, S> FusedIterator for MatchRangesWith<H, S>
+where
+    H: Haystack,
+    S: Searcher<H::Hay> + MatchedNeedle,
+{}
+
+// Like `match_ranges`, but also reports which needle of a multi-needle pattern
+// (e.g. `AnyOf`) each match is for.
+pub fn match_ranges_with<H, P>(haystack: H, pattern: P) -> MatchRangesWith<H, P::Searcher>
+where
+    H: Haystack,
+    P: Pattern<H>,
+    P::Searcher: MatchedNeedle,
+{
+    MatchRangesWith {
+        inner: matches(haystack, pattern).0,
+    }
+}
+
+//------------------------------------------------------------------------------
+// MatchRangesWithin
+//------------------------------------------------------------------------------
+
+#[derive(Debug, Clone)]
+pub struct MatchRangesWithin<H, S, I> {
+    haystack: H,
+    searcher: S,
+    template: S,
+    allowed: I,
+    rest: Option<Range<usize>>,
+    prev_end: usize,
+}
+
+impl<H, S, I> Iterator for MatchRangesWithin<H, S, I>
+where
+    H: Haystack,
+    S: Searcher<H::Hay> + Clone,
+    I: Iterator<Item = Range<usize>>,
+    H::Hay: Hay<Index = usize> + ops::Index<Range<usize>, Output = H::Hay>,
+{
+    type Item = Range<usize>;
+
+    fn next(&mut self) -> Option<Range<usize>> {
+        let hay = self.haystack.hay();
+        loop {
+            let rest = match self.rest.take() {
+                Some(rest) => rest,
+                None => {
+                    let range = self.allowed.next()?;
+                    assert!(
+                        self.prev_end <= range.start,
+                        "allowed ranges must be sorted and not overlapping",
+                    );
+                    // panics if the range is out of bounds or not on codeword boundaries.
+                    let _ = &hay[range.clone()];
+                    self.prev_end = range.end;
+                    self.searcher = self.template.clone();
+                    range
+                }
+            };
+            let span = unsafe { Span::from_parts(hay, rest.clone()) };
+            if let Some(range) = self.searcher.search(span) {
+                self.rest = Some(range.end..rest.end);
+                return Some(range);
+            }
+        }
+    }
+}
+
+impl<H, S, I> FusedIterator for MatchRangesWithin<H, S, I>
+where
+    H: Haystack,
+    S: Searcher<H::Hay> + Clone,
+    I: FusedIterator<Item = Range<usize>>,
+    H::Hay: Hay<Index = usize> + ops::Index<Range<usize>, Output = H::Hay>,
+{}
+
+// Matches are only reported if they lie entirely inside one of the `allowed`
+// ranges, which must be sorted and must not overlap.
+pub fn match_ranges_within<H, P, I>(
+    haystack: H,
+    allowed: I,
+    pattern: P,
+) -> MatchRangesWithin<H, P::Searcher, I::IntoIter>
+where
+    H: Haystack,
+    P: Pattern<H>,
+    P::Searcher: Clone,
+    I: IntoIterator<Item = Range<usize>>,
+    H::Hay: Hay<Index = usize> + ops::Index<Range<usize>, Output = H::Hay>,
+{
+    let searcher = pattern.into_searcher();
+    MatchRangesWithin {
+        haystack,
+        template: searcher.clone(),
+        searcher,
+        allowed: allowed.into_iter(),
+        rest: None,
+        prev_end: 0,
+    }
+}
+
+//------------------------------------------------------------------------------
+// Coalesce
+//------------------------------------------------------------------------------
+
+// Merges ranges which touch or overlap into maximal ranges, e.g. to highlight
+// or redact the matches of several patterns without fragmenting the output.
+// The ranges must be sorted by their start.
+#[derive(Debug, Clone)]
+pub struct Coalesced<I, T> {
+    ranges: I,
+    pending: Option<Range<T>>,
+}
+
+impl<I, T> Iterator for Coalesced<I, T>
+where
+    I: Iterator<Item = Range<T>>,
+    T: Ord + Copy,
+{
+    type Item = Range<T>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Range<T>> {
+        let mut current = self.pending.take().or_else(|| self.ranges.next())?;
+        for range in &mut self.ranges {
+            debug_assert!(current.start <= range.start, "ranges are not sorted by start");
+            if range.start > current.end {
+                self.pending = Some(range);
+                break;
+            }
+            if range.end > current.end {
+                current.end = range.end;
+            }
+        }
+        Some(current)
+    }
+}
+
+impl<I, T> FusedIterator for Coalesced<I, T>
+where
+    I: FusedIterator<Item = Range<T>>,
+    T: Ord + Copy,
+{}
+
+pub fn coalesce<I, T>(ranges: I) -> Coalesced<I::IntoIter, T>
+where
+    I: IntoIterator<Item = Range<T>>,
+    T: Ord + Copy,
+{
+    Coalesced {
+        ranges: ranges.into_iter(),
+        pending: None,
+    }
+}
+
+// Finds the matches of every pattern and returns the maximal ranges covered by
+// them, in order.
+#[cfg(feature = "std")]
+pub fn coalesced_match_ranges<H, P, I>(haystack: H, patterns: I) -> Vec<Range<<H::Hay as Hay>::Index>>
+where
+    H: Haystack,
+    <H::Hay as Hay>::Index: Ord,
+    P: Pattern<H>,
+    I: IntoIterator<Item = P>,
+{
+    let hay = haystack.hay();
+    let mut ranges = Vec::new();
+    for pattern in patterns {
+        let mut searcher = pattern.into_searcher();
+        let mut rest: Span<&H::Hay> = hay.into();
+        while let Some(range) = searcher.search(rest.borrow()) {
+            ranges.push(range.clone());
+            let [_, _, right] = unsafe { rest.split_around(range) };
+            rest = right;
+        }
+    }
+    ranges.sort_by(|a, b| a.start.cmp(&b.start));
+    coalesce(ranges).collect()
+}
+
+//------------------------------------------------------------------------------
+// VerifyMatches
+//------------------------------------------------------------------------------
+
+#[derive(Debug, Clone)]
+pub struct VerifyMatches<H, S, I> {
+    haystack: H,
+    consumer: S,
+    candidates: I,
+}
+
+impl<H, S, I> Iterator for VerifyMatches<H, S, I>
+where
+    H: Haystack,
+    S: Searcher<H::Hay>,
+    I: Iterator<Item = usize>,
+    H::Hay: Hay<Index = usize> + ops::Index<RangeFrom<usize>, Output = H::Hay>,
+{
+    type Item = Range<usize>;
+
+    fn next(&mut self) -> Option<Range<usize>> {
+        let hay = self.haystack.hay();
+        loop {
+            let start = self.candidates.next()?;
+            // panics if the candidate is out of bounds or not on a codeword boundary.
+            let _ = &hay[start..];
+            let span = unsafe { Span::from_parts(hay, start..hay.end_index()) };
+            if let Some(end) = self.consumer.consume(span) {
+                return Some(start..end);
+            }
+        }
+    }
+}
+
+impl<H, S, I> FusedIterator for VerifyMatches<H, S, I>
+where
+    H: Haystack,
+    S: Searcher<H::Hay>,
+    I: FusedIterator<Item = usize>,
+    H::Hay: Hay<Index = usize> + ops::Index<RangeFrom<usize>, Output = H::Hay>,
+{}
+
+// Checks whether the pattern occurs at each of the externally supplied
+// `candidates`, yielding the ranges of those that are confirmed. Candidates may
+// come in any order, and the confirmed ranges may overlap.
+pub fn verify_matches<H, P, I>(
+    haystack: H,
+    candidates: I,
+    pattern: P,
+) -> VerifyMatches<H, P::Searcher, I::IntoIter>
+where
+    H: Haystack,
+    P: Pattern<H>,
+    I: IntoIterator<Item = usize>,
+    H::Hay: Hay<Index = usize> + ops::Index<RangeFrom<usize>, Output = H::Hay>,
+{
+    VerifyMatches {
+        haystack,
+        consumer: pattern.into_consumer(),
+        candidates: candidates.into_iter(),
+    }
+}
+
+//------------------------------------------------------------------------------
+// Nearest match
+//------------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TieBreak {
+    Before,
+    After,
+}
+
+// Only occurrences lying entirely before or entirely after `index` are
+// considered. The distance is measured from `index` to the nearest end of the
+// occurrence.
+pub fn nearest_match<H, P>(
+    haystack: H,
+    index: usize,
+    pattern: P,
+    tie: TieBreak,
+) -> Option<Range<usize>>
+where
+    H: Haystack,
+    P: Pattern<H>,
+    P::Searcher: ReverseSearcher<H::Hay>,
+    H::Hay: Hay<Index = usize> + ops::Index<RangeTo<usize>, Output = H::Hay>,
+{
+    let hay = haystack.hay();
+    let end = hay.end_index();
+    // panics if `index` is out of bounds or not on a codeword boundary.
+    let _ = &hay[..index];
+
+    let mut searcher = pattern.into_searcher();
+    let before = searcher.rsearch(unsafe { Span::from_parts(hay, hay.start_index()..index) });
+    let after = searcher.search(unsafe { Span::from_parts(hay, index..end) });
+    match (before, after) {
+        (Some(before), Some(after)) => {
+            let before_distance = index - before.end;
+            let after_distance = after.start - index;
+            if before_distance < after_distance
+                || (before_distance == after_distance && tie == TieBreak::Before)
+            {
+                Some(before)
+            } else {
+                Some(after)
+            }
+        }
+        (before, None) => before,
+        (None, after) => after,
+    }
+}
+
+//------------------------------------------------------------------------------
+// Longest common substring
+//------------------------------------------------------------------------------
+
+// Returns the codeword boundaries of a hay, including the start and end.
+#[cfg(feature = "std")]
+fn codeword_bounds<A: Hay + ?Sized>(hay: &A) -> Vec<A::Index> {
+    let mut bounds = vec![hay.start_index()];
+    let mut i = hay.start_index();
+    while i != hay.end_index() {
+        i = unsafe { hay.next_index(i) };
+        bounds.push(i);
+    }
+    bounds
+}
+
+#[cfg(feature = "std")]
+struct SuffixAutomatonState<'a, A: ?Sized + 'a> {
+    // Length (in codewords) of the longest string reaching this state.
+    len: usize,
+    link: usize,
+    // End (in codewords) of the first occurrence of the strings of this state.
+    first_end: usize,
+    next: HashMap<&'a A, usize>,
+}
+
+// Returns the ranges of a longest common substring in `a` and `b`, comparing
+// whole codewords. Ties are broken by the earliest occurrence in `b`, then in
+// `a`. Built on a suffix automaton of `a`, so it runs in linear time.
+#[cfg(feature = "std")]
+pub fn longest_common_substring<H>(a: H, b: H) -> (Range<usize>, Range<usize>)
+where
+    H: Haystack,
+    H::Hay: Hay<Index = usize> + Hash + Eq,
+{
+    let (a, b) = (a.hay(), b.hay());
+    let bounds_a = codeword_bounds(a);
+    let bounds_b = codeword_bounds(b);
+    let codeword = |hay, bounds: &[usize], i: usize| unsafe {
+        Hay::slice_unchecked(hay, bounds[i]..bounds[i + 1])
+    };
+
+    let mut states = vec![SuffixAutomatonState {
+        len: 0,
+        link: usize::MAX,
+        first_end: 0,
+        next: HashMap::new(),
+    }];
+    let mut last = 0;
+    for i in 0..(bounds_a.len() - 1) {
+        let c = codeword(a, &bounds_a, i);
+        let cur = states.len();
+        states.push(SuffixAutomatonState {
+            len: states[last].len + 1,
+            link: 0,
+            first_end: i + 1,
+            next: HashMap::new(),
+        });
+        let mut p = last;
+        while p != usize::MAX && !states[p].next.contains_key(c) {
+            states[p].next.insert(c, cur);
+            p = states[p].link;
+        }
+        if p != usize::MAX {
+            let q = states[p].next[c];
+            if states[p].len + 1 == states[q].len {
+                states[cur].link = q;
+            } else {
+                let clone = states.len();
+                let state = SuffixAutomatonState {
+                    len: states[p].len + 1,
+                    link: states[q].link,
+                    first_end: states[q].first_end,
+                    next: states[q].next.clone(),
+                };
+                states.push(state);
+                while p != usize::MAX && states[p].next.get(c) == Some(&q) {
+                    states[p].next.insert(c, clone);
+                    p = states[p].link;
+                }
+                states[q].link = clone;
+                states[cur].link = clone;
+            }
+        }
+        last = cur;
+    }
+
+    let (mut state, mut len) = (0, 0);
+    let (mut best_len, mut best_state, mut best_end_b) = (0, 0, 0);
+    for i in 0..(bounds_b.len() - 1) {
+        let c = codeword(b, &bounds_b, i);
+        while state != 0 && !states[state].next.contains_key(c) {
+            state = states[state].link;
+            len = states[state].len;
+        }
+        match states[state].next.get(c) {
+            Some(&next) => {
+                state = next;
+                len += 1;
+            }
+            None => len = 0,
+        }
+        if len > best_len {
+            best_len = len;
+            best_state = state;
+            best_end_b = i + 1;
+        }
+    }
+
+    let end_a = states[best_state].first_end;
+    (
+        bounds_a[end_a - best_len]..bounds_a[end_a],
+        bounds_b[best_end_b - best_len]..bounds_b[best_end_b],
+    )
 }
 
 //------------------------------------------------------------------------------
@@ -550,7 +1125,6 @@ where
 struct SplitInternal<H, S>
 where
     H: Haystack,
-    H::Target: Hay, // FIXME: RFC 2089 or 2289
 {
     searcher: S,
     rest: Span<H>,
@@ -561,8 +1135,7 @@ where
 impl<H, S> SplitInternal<H, S>
 where
     H: Haystack,
-    S: Searcher<H::Target>,
-    H::Target: Hay, // FIXME: RFC 2089 or 2289
+    S: Searcher<H::Hay>,
 {
     #[inline]
     fn next(&mut self) -> Option<H> {
@@ -591,8 +1164,7 @@ where
 impl<H, S> SplitInternal<H, S>
 where
     H: Haystack,
-    S: ReverseSearcher<H::Target>,
-    H::Target: Hay, // FIXME: RFC 2089 or 2289
+    S: ReverseSearcher<H::Hay>,
 {
     #[inline]
     fn next_back(&mut self) -> Option<H> {
@@ -650,7 +1222,6 @@ pub fn split<H, P>(haystack: H, pattern: P) -> Split<H, P::Searcher>
 where
     H: Haystack,
     P: Pattern<H>,
-    H::Target: Hay, // FIXME: RFC 2089 or 2289
 {
     Split(SplitInternal {
         searcher: pattern.into_searcher(),
@@ -664,8 +1235,7 @@ pub fn rsplit<H, P>(haystack: H, pattern: P) -> RSplit<H, P::Searcher>
 where
     H: Haystack,
     P: Pattern<H>,
-    P::Searcher: ReverseSearcher<H::Target>,
-    H::Target: Hay, // FIXME: RFC 2089 or 2289
+    P::Searcher: ReverseSearcher<H::Hay>,
 {
     RSplit(SplitInternal {
         searcher: pattern.into_searcher(),
@@ -679,7 +1249,6 @@ pub fn split_terminator<H, P>(haystack: H, pattern: P) -> SplitTerminator<H, P::
 where
     H: Haystack,
     P: Pattern<H>,
-    H::Target: Hay, // FIXME: RFC 2089 or 2289
 {
     SplitTerminator(SplitInternal {
         searcher: pattern.into_searcher(),
@@ -693,8 +1262,7 @@ pub fn rsplit_terminator<H, P>(haystack: H, pattern: P) -> RSplitTerminator<H, P
 where
     H: Haystack,
     P: Pattern<H>,
-    P::Searcher: ReverseSearcher<H::Target>,
-    H::Target: Hay, // FIXME: RFC 2089 or 2289
+    P::Searcher: ReverseSearcher<H::Hay>,
 {
     RSplitTerminator(SplitInternal {
         searcher: pattern.into_searcher(),
@@ -705,68 +1273,259 @@ where
 }
 
 //------------------------------------------------------------------------------
-// SplitN
+// SplitRanges
 //------------------------------------------------------------------------------
 
-#[derive(Clone, Debug)]
-struct SplitNInternal<H, S>
+struct SplitRangesInternal<H, S>
 where
     H: Haystack,
-    H::Target: Hay, // FIXME: RFC 2089 or 2289
 {
     searcher: S,
-    rest: Span<H>,
-    n: usize,
+    haystack: H,
+    rest: Range<<H::Hay as Hay>::Index>,
+    finished: bool,
 }
 
-impl<H, S> SplitNInternal<H, S>
+impl<H, S> Clone for SplitRangesInternal<H, S>
 where
-    H: Haystack,
-    S: Searcher<H::Target>,
-    H::Target: Hay, // FIXME: RFC 2089 or 2289
+    H: Haystack + Clone,
+    S: Clone,
 {
-    #[inline]
-    fn next(&mut self) -> Option<H> {
-        let mut rest = self.rest.take();
-        match self.n {
-            0 => {
-                return None;
-            }
-            1 => {
-                self.n = 0;
-            }
-            n => {
-                match self.searcher.search(rest.borrow()) {
-                    Some(range) => {
-                        let [left, _, right] = unsafe { rest.split_around(range) };
-                        self.n = n - 1;
-                        self.rest = right;
-                        rest = left;
-                    }
-                    None => {
-                        self.n = 0;
-                    }
-                }
-            }
+    fn clone(&self) -> Self {
+        SplitRangesInternal {
+            searcher: self.searcher.clone(),
+            haystack: self.haystack.clone(),
+            rest: self.rest.clone(),
+            finished: self.finished,
         }
-        Some(Span::into(rest))
     }
 }
 
-impl<H, S> SplitNInternal<H, S>
+impl<H, S> fmt::Debug for SplitRangesInternal<H, S>
+where
+    H: Haystack + fmt::Debug,
+    S: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SplitRangesInternal")
+            .field("searcher", &self.searcher)
+            .field("haystack", &self.haystack)
+            .field("rest", &self.rest)
+            .field("finished", &self.finished)
+            .finish()
+    }
+}
+
+impl<H, S> SplitRangesInternal<H, S>
 where
     H: Haystack,
-    S: ReverseSearcher<H::Target>,
-    H::Target: Hay, // FIXME: RFC 2089 or 2289
+    S: Searcher<H::Hay>,
 {
     #[inline]
-    fn next_back(&mut self) -> Option<H> {
-        let mut rest = self.rest.take();
-        match self.n {
-            0 => {
-                return None;
-            }
-            1 => {
+    fn next(&mut self) -> Option<Range<<H::Hay as Hay>::Index>> {
+        if self.finished {
+            return None;
+        }
+
+        let span = unsafe { Span::from_parts(self.haystack.hay(), self.rest.clone()) };
+        match self.searcher.search(span) {
+            Some(subrange) => {
+                let piece = self.rest.start..subrange.start;
+                self.rest.start = subrange.end;
+                Some(piece)
+            }
+            None => {
+                self.finished = true;
+                Some(self.rest.clone())
+            }
+        }
+    }
+}
+
+impl<H, S> SplitRangesInternal<H, S>
+where
+    H: Haystack,
+    S: ReverseSearcher<H::Hay>,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<Range<<H::Hay as Hay>::Index>> {
+        if self.finished {
+            return None;
+        }
+
+        let span = unsafe { Span::from_parts(self.haystack.hay(), self.rest.clone()) };
+        match self.searcher.rsearch(span) {
+            Some(subrange) => {
+                let piece = subrange.end..self.rest.end;
+                self.rest.end = subrange.start;
+                Some(piece)
+            }
+            None => {
+                self.finished = true;
+                Some(self.rest.clone())
+            }
+        }
+    }
+}
+
+generate_pattern_iterators! {
+    forward:
+        struct SplitRanges;
+    reverse:
+        struct RSplitRanges;
+    stability:
+    internal:
+        SplitRangesInternal yielding (Range<<H::Hay as Hay>::Index>);
+    delegate double ended;
+}
+
+pub fn split_ranges<H, P>(haystack: H, pattern: P) -> SplitRanges<H, P::Searcher>
+where
+    H: Haystack,
+    P: Pattern<H>,
+{
+    let rest = haystack.hay().start_index()..haystack.hay().end_index();
+    SplitRanges(SplitRangesInternal {
+        searcher: pattern.into_searcher(),
+        haystack,
+        rest,
+        finished: false,
+    })
+}
+
+pub fn rsplit_ranges<H, P>(haystack: H, pattern: P) -> RSplitRanges<H, P::Searcher>
+where
+    H: Haystack,
+    P: Pattern<H>,
+    P::Searcher: ReverseSearcher<H::Hay>,
+{
+    let rest = haystack.hay().start_index()..haystack.hay().end_index();
+    RSplitRanges(SplitRangesInternal {
+        searcher: pattern.into_searcher(),
+        haystack,
+        rest,
+        finished: false,
+    })
+}
+
+//------------------------------------------------------------------------------
+// Split2
+//------------------------------------------------------------------------------
+
+#[derive(Debug, Clone)]
+pub struct Split2<H, R, F>
+where
+    H: Haystack,
+{
+    records: SplitInternal<H, R>,
+    field_searcher: F,
+}
+
+impl<H, R, F> Iterator for Split2<H, R, F>
+where
+    H: Haystack,
+    R: Searcher<H::Hay>,
+    F: Searcher<H::Hay> + Clone,
+{
+    type Item = Split<H, F>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let record = self.records.next()?;
+        Some(Split(SplitInternal {
+            searcher: self.field_searcher.clone(),
+            rest: record.into(),
+            finished: false,
+            allow_trailing_empty: true,
+        }))
+    }
+}
+
+impl<H, R, F> FusedIterator for Split2<H, R, F>
+where
+    H: Haystack,
+    R: Searcher<H::Hay>,
+    F: Searcher<H::Hay> + Clone,
+{}
+
+pub fn split2<H, R, F>(haystack: H, record_pattern: R, field_pattern: F) -> Split2<H, R::Searcher, F::Searcher>
+where
+    H: Haystack,
+    R: Pattern<H>,
+    F: Pattern<H>,
+    F::Searcher: Clone,
+{
+    Split2 {
+        records: SplitInternal {
+            searcher: record_pattern.into_searcher(),
+            rest: haystack.into(),
+            finished: false,
+            allow_trailing_empty: false,
+        },
+        field_searcher: field_pattern.into_searcher(),
+    }
+}
+
+//------------------------------------------------------------------------------
+// SplitN
+//------------------------------------------------------------------------------
+
+#[derive(Clone, Debug)]
+struct SplitNInternal<H, S>
+where
+    H: Haystack,
+{
+    searcher: S,
+    rest: Span<H>,
+    n: usize,
+}
+
+impl<H, S> SplitNInternal<H, S>
+where
+    H: Haystack,
+    S: Searcher<H::Hay>,
+{
+    #[inline]
+    fn next(&mut self) -> Option<H> {
+        let mut rest = self.rest.take();
+        match self.n {
+            0 => {
+                return None;
+            }
+            1 => {
+                self.n = 0;
+            }
+            n => {
+                match self.searcher.search(rest.borrow()) {
+                    Some(range) => {
+                        let [left, _, right] = unsafe { rest.split_around(range) };
+                        self.n = n - 1;
+                        self.rest = right;
+                        rest = left;
+                    }
+                    None => {
+                        self.n = 0;
+                    }
+                }
+            }
+        }
+        Some(Span::into(rest))
+    }
+}
+
+impl<H, S> SplitNInternal<H, S>
+where
+    H: Haystack,
+    S: ReverseSearcher<H::Hay>,
+{
+    #[inline]
+    fn next_back(&mut self) -> Option<H> {
+        let mut rest = self.rest.take();
+        match self.n {
+            0 => {
+                return None;
+            }
+            1 => {
                 self.n = 0;
             }
             n => {
@@ -802,7 +1561,6 @@ pub fn splitn<H, P>(haystack: H, n: usize, pattern: P) -> SplitN<H, P::Searcher>
 where
     H: Haystack,
     P: Pattern<H>,
-    H::Target: Hay, // FIXME: RFC 2089 or 2289
 {
     SplitN(SplitNInternal {
         searcher: pattern.into_searcher(),
@@ -815,8 +1573,7 @@ pub fn rsplitn<H, P>(haystack: H, n: usize, pattern: P) -> RSplitN<H, P::Searche
 where
     H: Haystack,
     P: Pattern<H>,
-    P::Searcher: ReverseSearcher<H::Target>,
-    H::Target: Hay, // FIXME: RFC 2089 or 2289
+    P::Searcher: ReverseSearcher<H::Hay>,
 {
     RSplitN(SplitNInternal {
         searcher: pattern.into_searcher(),
@@ -825,6 +1582,416 @@ where
     })
 }
 
+//------------------------------------------------------------------------------
+// Unique haystacks
+//------------------------------------------------------------------------------
+
+// The algorithms above work on any haystack, including unique borrows like
+// `&mut str`. These spell out the `&mut` case: every piece yielded is a
+// disjoint `&mut` borrow of the hay, sliced on codeword boundaries (e.g.
+// UTF-8 boundaries for `&mut str`), so the pieces can be modified in place.
+
+pub fn split_mut<'h, A, P>(haystack: &'h mut A, pattern: P) -> Split<&'h mut A, P::Searcher>
+where
+    A: Hay + ?Sized,
+    &'h mut A: Haystack<Hay = A>,
+    P: Pattern<&'h mut A>,
+{
+    split(haystack, pattern)
+}
+
+pub fn split_terminator_mut<'h, A, P>(haystack: &'h mut A, pattern: P) -> SplitTerminator<&'h mut A, P::Searcher>
+where
+    A: Hay + ?Sized,
+    &'h mut A: Haystack<Hay = A>,
+    P: Pattern<&'h mut A>,
+{
+    split_terminator(haystack, pattern)
+}
+
+pub fn splitn_mut<'h, A, P>(haystack: &'h mut A, n: usize, pattern: P) -> SplitN<&'h mut A, P::Searcher>
+where
+    A: Hay + ?Sized,
+    &'h mut A: Haystack<Hay = A>,
+    P: Pattern<&'h mut A>,
+{
+    splitn(haystack, n, pattern)
+}
+
+pub fn trim_start_mut<'h, A, P>(haystack: &'h mut A, pattern: P) -> &'h mut A
+where
+    A: Hay + ?Sized,
+    &'h mut A: Haystack<Hay = A>,
+    P: Pattern<&'h mut A>,
+{
+    trim_start(haystack, pattern)
+}
+
+pub fn trim_end_mut<'h, A, P>(haystack: &'h mut A, pattern: P) -> &'h mut A
+where
+    A: Hay + ?Sized,
+    &'h mut A: Haystack<Hay = A>,
+    P: Pattern<&'h mut A>,
+    P::Searcher: ReverseSearcher<A>,
+{
+    trim_end(haystack, pattern)
+}
+
+pub fn trim_mut<'h, A, P>(haystack: &'h mut A, pattern: P) -> &'h mut A
+where
+    A: Hay + ?Sized,
+    &'h mut A: Haystack<Hay = A>,
+    P: Pattern<&'h mut A>,
+    P::Searcher: DoubleEndedSearcher<A>,
+{
+    trim(haystack, pattern)
+}
+
+//------------------------------------------------------------------------------
+// SplitFrontBack
+//------------------------------------------------------------------------------
+
+// A cursor taking split pieces from either end, e.g. the first two and the last
+// two fields of a record, leaving the middle as one remainder.
+//
+// Each separator is searched for only inside the remainder, so the two ends
+// never overlap. Once the remainder contains no separator, it is the final
+// piece, whichever end takes it.
+#[derive(Debug, Clone)]
+pub struct SplitFrontBack<H, S>
+where
+    H: Haystack,
+{
+    searcher: S,
+    rest: Span<H>,
+    finished: bool,
+}
+
+impl<H, S> SplitFrontBack<H, S>
+where
+    H: Haystack,
+    S: Searcher<H::Hay>,
+{
+    #[inline]
+    pub fn next_front(&mut self) -> Option<H> {
+        if self.finished {
+            return None;
+        }
+        let mut rest = self.rest.take();
+        match self.searcher.search(rest.borrow()) {
+            Some(range) => {
+                let [left, _, right] = unsafe { rest.split_around(range) };
+                self.rest = right;
+                rest = left;
+            }
+            None => self.finished = true,
+        }
+        Some(Span::into(rest))
+    }
+
+    // The part of the haystack not yet taken by either end, or `None` once the
+    // final piece has been taken.
+    #[inline]
+    pub fn remainder(&self) -> Option<&H::Hay> {
+        if self.finished {
+            None
+        } else {
+            Some(self.rest.borrow().as_slice())
+        }
+    }
+
+    #[inline]
+    pub fn remainder_range(&self) -> Option<Range<<H::Hay as Hay>::Index>> {
+        if self.finished {
+            None
+        } else {
+            Some(self.rest.original_range())
+        }
+    }
+
+    #[inline]
+    pub fn into_remainder(self) -> Option<H> {
+        if self.finished {
+            None
+        } else {
+            Some(Span::into(self.rest))
+        }
+    }
+}
+
+impl<H, S> SplitFrontBack<H, S>
+where
+    H: Haystack,
+    S: ReverseSearcher<H::Hay>,
+{
+    #[inline]
+    pub fn next_back(&mut self) -> Option<H> {
+        if self.finished {
+            return None;
+        }
+        let mut rest = self.rest.take();
+        match self.searcher.rsearch(rest.borrow()) {
+            Some(range) => {
+                let [left, _, right] = unsafe { rest.split_around(range) };
+                self.rest = left;
+                rest = right;
+            }
+            None => self.finished = true,
+        }
+        Some(Span::into(rest))
+    }
+}
+
+pub fn split_front_back<H, P>(haystack: H, pattern: P) -> SplitFrontBack<H, P::Searcher>
+where
+    H: Haystack,
+    P: Pattern<H>,
+    P::Searcher: ReverseSearcher<H::Hay>,
+{
+    SplitFrontBack {
+        searcher: pattern.into_searcher(),
+        rest: haystack.into(),
+        finished: false,
+    }
+}
+
+//------------------------------------------------------------------------------
+// Splitter
+//------------------------------------------------------------------------------
+
+// Direction markers of a `Splitter`.
+#[derive(Debug, Clone, Copy)]
+pub struct Forward;
+#[derive(Debug, Clone, Copy)]
+pub struct Backward;
+
+// Builder of split iterators whose options can be freely combined.
+//
+//  * `max(n)`: yield at most `n` pieces, the last one being the unsplit rest.
+//  * `from_end()`: search for separators from the back.
+//  * `terminator()`: treat the pattern as a terminator rather than a
+//    separator, so an empty piece after the last match is not yielded.
+//  * `keep_terminator()`: include each match at the end of the piece it
+//    terminates. Implies `terminator()`.
+//  * `skip_empty()`: do not yield empty pieces at all. Skipped pieces do not
+//    count toward `max(n)`.
+#[derive(Debug, Clone)]
+pub struct Splitter<P, D = Forward> {
+    pattern: P,
+    max: Option<usize>,
+    terminator: bool,
+    keep_terminator: bool,
+    skip_empty: bool,
+    direction: PhantomData<D>,
+}
+
+impl<P> Splitter<P> {
+    pub fn new(pattern: P) -> Self {
+        Splitter {
+            pattern,
+            max: None,
+            terminator: false,
+            keep_terminator: false,
+            skip_empty: false,
+            direction: PhantomData,
+        }
+    }
+
+    pub fn from_end(self) -> Splitter<P, Backward> {
+        Splitter {
+            pattern: self.pattern,
+            max: self.max,
+            terminator: self.terminator,
+            keep_terminator: self.keep_terminator,
+            skip_empty: self.skip_empty,
+            direction: PhantomData,
+        }
+    }
+}
+
+impl<P, D> Splitter<P, D> {
+    pub fn max(mut self, n: usize) -> Self {
+        self.max = Some(n);
+        self
+    }
+
+    pub fn terminator(mut self) -> Self {
+        self.terminator = true;
+        self
+    }
+
+    pub fn keep_terminator(mut self) -> Self {
+        self.terminator = true;
+        self.keep_terminator = true;
+        self
+    }
+
+    pub fn skip_empty(mut self) -> Self {
+        self.skip_empty = true;
+        self
+    }
+
+    pub fn split<H>(self, haystack: H) -> Splits<H, P::Searcher, D>
+    where
+        H: Haystack,
+        P: Pattern<H>,
+    {
+        Splits {
+            searcher: self.pattern.into_searcher(),
+            rest: haystack.into(),
+            remaining: self.max,
+            terminator: self.terminator,
+            keep_terminator: self.keep_terminator,
+            skip_empty: self.skip_empty,
+            finished: false,
+            yielded_trailing: false,
+            pending: None,
+            direction: PhantomData,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Splits<H, S, D>
+where
+    H: Haystack,
+{
+    searcher: S,
+    rest: Span<H>,
+    remaining: Option<usize>,
+    terminator: bool,
+    keep_terminator: bool,
+    skip_empty: bool,
+    finished: bool,
+    // whether the piece at the end of the haystack has been dealt with, for
+    // splitting from the end.
+    yielded_trailing: bool,
+    // the start of the match which `rest` ends with, when splitting from the
+    // end and keeping terminators.
+    pending: Option<<H::Hay as Hay>::Index>,
+    direction: PhantomData<D>,
+}
+
+impl<H, S, D> Splits<H, S, D>
+where
+    H: Haystack,
+{
+    // Decides whether a piece is yielded, updating the limit accordingly.
+    #[inline]
+    fn accept(&mut self, piece: &Span<H>, trailing: bool) -> bool {
+        if piece.is_empty() && (self.skip_empty || (trailing && self.terminator)) {
+            return false;
+        }
+        if let Some(ref mut n) = self.remaining {
+            *n -= 1;
+        }
+        true
+    }
+}
+
+impl<H, S> Iterator for Splits<H, S, Forward>
+where
+    H: Haystack,
+    S: Searcher<H::Hay>,
+{
+    type Item = H;
+
+    fn next(&mut self) -> Option<H> {
+        loop {
+            if self.finished || self.remaining == Some(0) {
+                return None;
+            }
+            let mut rest = self.rest.take();
+            let found = if self.remaining == Some(1) {
+                None
+            } else {
+                self.searcher.search(rest.borrow())
+            };
+            let trailing = match found {
+                Some(range) => {
+                    let [left, _, right] = unsafe {
+                        if self.keep_terminator {
+                            rest.split_around(range.end..range.end)
+                        } else {
+                            rest.split_around(range)
+                        }
+                    };
+                    self.rest = right;
+                    rest = left;
+                    false
+                }
+                None => {
+                    self.finished = true;
+                    true
+                }
+            };
+            if self.accept(&rest, trailing) {
+                return Some(Span::into(rest));
+            }
+        }
+    }
+}
+
+impl<H, S> Iterator for Splits<H, S, Backward>
+where
+    H: Haystack,
+    S: ReverseSearcher<H::Hay>,
+{
+    type Item = H;
+
+    fn next(&mut self) -> Option<H> {
+        loop {
+            if self.finished || self.remaining == Some(0) {
+                return None;
+            }
+            let mut rest = self.rest.take();
+            let found = if self.remaining == Some(1) {
+                None
+            } else {
+                let (hay, range) = rest.borrow().into_parts();
+                let end = self.pending.take().unwrap_or(range.end);
+                self.searcher.rsearch(unsafe { Span::from_parts(hay, range.start..end) })
+            };
+            match found {
+                Some(range) => {
+                    // Splitting keeps the indices of the left part, so
+                    // `pending` stays valid for the next search.
+                    let [left, _, right] = unsafe {
+                        if self.keep_terminator {
+                            self.pending = Some(range.start);
+                            rest.split_around(range.end..range.end)
+                        } else {
+                            rest.split_around(range)
+                        }
+                    };
+                    self.rest = left;
+                    rest = right;
+                }
+                None => {
+                    self.finished = true;
+                }
+            }
+            let trailing = !self.yielded_trailing;
+            self.yielded_trailing = true;
+            if self.accept(&rest, trailing) {
+                return Some(Span::into(rest));
+            }
+        }
+    }
+}
+
+impl<H, S> FusedIterator for Splits<H, S, Forward>
+where
+    H: Haystack,
+    S: Searcher<H::Hay>,
+{}
+
+impl<H, S> FusedIterator for Splits<H, S, Backward>
+where
+    H: Haystack,
+    S: ReverseSearcher<H::Hay>,
+{}
+
 //------------------------------------------------------------------------------
 // Replace
 //------------------------------------------------------------------------------
@@ -835,7 +2002,6 @@ where
     P: Pattern<H>,
     F: FnMut(H) -> H,
     W: FnMut(H),
-    H::Target: Hay, // FIXME: RFC 2089 or 2289
 {
     let mut searcher = from.into_searcher();
     let mut src = Span::from(src);
@@ -848,13 +2014,49 @@ where
     writer(Span::into(src));
 }
 
+// Like `replace_with`, but also reports every edit to `edits` as the range
+// replaced in the original haystack and the length of its replacement, so
+// positions can be mapped between the original and the rewritten output.
+pub fn replace_with_edits<H, P, F, W, E>(src: H, from: P, mut replacer: F, mut writer: W, mut edits: E)
+where
+    H: Haystack,
+    P: Pattern<H>,
+    F: FnMut(H) -> H,
+    W: FnMut(H),
+    E: FnMut(Range<usize>, usize),
+    H::Hay: Hay<Index = usize>,
+{
+    let mut searcher = from.into_searcher();
+    let mut src = Span::from(src);
+    while let Some(range) = searcher.search(src.borrow()) {
+        let [left, middle, right] = unsafe { src.split_around(range) };
+        let original = middle.original_range();
+        writer(Span::into(left));
+        let replacement = replacer(Span::into(middle));
+        edits(original, replacement.hay().end_index() - replacement.hay().start_index());
+        writer(replacement);
+        src = right;
+    }
+    writer(Span::into(src));
+}
+
+#[cfg(feature = "std")]
+pub fn replace_edit_log<'h, P>(src: &'h str, from: P, to: &'h str) -> (String, Vec<(Range<usize>, usize)>)
+where
+    P: Pattern<&'h str>,
+{
+    let mut res = String::with_capacity(src.len());
+    let mut log = Vec::new();
+    replace_with_edits(src, from, |_| to, |h| res.push_str(h), |range, len| log.push((range, len)));
+    (res, log)
+}
+
 pub fn replacen_with<H, P, F, W>(src: H, from: P, mut replacer: F, mut n: usize, mut writer: W)
 where
     H: Haystack,
     P: Pattern<H>,
     F: FnMut(H) -> H,
     W: FnMut(H),
-    H::Target: Hay, // FIXME: RFC 2089 or 2289
 {
     let mut searcher = from.into_searcher();
     let mut src = Span::from(src);
@@ -874,3 +2076,308 @@ where
     }
     writer(Span::into(src));
 }
+
+#[cfg(feature = "std")]
+pub fn rreplacen_with<H, P, F, W>(src: H, from: P, mut replacer: F, n: usize, mut writer: W)
+where
+    H: Haystack,
+    P: Pattern<H>,
+    P::Searcher: ReverseSearcher<H::Hay>,
+    F: FnMut(H) -> H,
+    W: FnMut(H),
+{
+    // The matches are found back to front but must be written front to back,
+    // so the pieces after each match are buffered until the search is done.
+    let mut searcher = from.into_searcher();
+    let mut src = Span::from(src);
+    let mut tail = Vec::new();
+    while tail.len() < n {
+        if let Some(range) = searcher.rsearch(src.borrow()) {
+            let [left, middle, right] = unsafe { src.split_around(range) };
+            tail.push((middle, right));
+            src = left;
+        } else {
+            break;
+        }
+    }
+    writer(Span::into(src));
+    for (middle, right) in tail.into_iter().rev() {
+        writer(replacer(Span::into(middle)));
+        writer(Span::into(right));
+    }
+}
+
+#[cfg(feature = "std")]
+pub fn rreplacen<'h, P>(src: &'h str, from: P, to: &'h str, n: usize) -> String
+where
+    P: Pattern<&'h str>,
+    P::Searcher: ReverseSearcher<str>,
+{
+    let mut res = String::with_capacity(src.len());
+    rreplacen_with(src, from, |_| to, n, |h| res.push_str(h));
+    res
+}
+
+// Applies several pattern→replacement rules in a single left-to-right pass.
+// When several patterns match, the leftmost match wins; among matches starting
+// at the same position the longest wins, and among equally long matches the
+// pair listed first wins. Replaced text is never searched again.
+#[cfg(feature = "std")]
+pub fn replace_all_pairs_with<H, P, W>(src: H, pairs: &[(P, H)], mut writer: W)
+where
+    H: SharedHaystack,
+    P: Pattern<H> + Clone,
+    W: FnMut(H),
+    H::Hay: Hay<Index = usize>,
+{
+    let mut searchers = pairs.iter().map(|(p, _)| p.clone().into_searcher()).collect::<Vec<_>>();
+    // `None` if the searcher must be resumed, `Some(None)` if it is exhausted.
+    let mut found: Vec<Option<Option<Range<usize>>>> = vec![None; pairs.len()];
+    let mut prev_empty = None;
+    let mut src = Span::from(src);
+    loop {
+        let start = src.original_range().start;
+        let mut best: Option<(usize, Range<usize>)> = None;
+        for (i, searcher) in searchers.iter_mut().enumerate() {
+            let mut range = match found[i].take() {
+                Some(Some(ref range)) if range.start >= start => Some(range.clone()),
+                Some(None) => None,
+                Some(Some(ref range)) if range.start == range.end => {
+                    // Searchers remember their empty matches to avoid reporting
+                    // them twice, so a stale one needs a fresh searcher.
+                    *searcher = pairs[i].0.clone().into_searcher();
+                    searcher.search(src.borrow())
+                }
+                _ => searcher.search(src.borrow()),
+            };
+            // An empty match right after another empty match would not move
+            // forward, so give the searcher a chance to look past it.
+            if let Some(ref r) = range.clone() {
+                if r.start == r.end && prev_empty == Some(r.start) {
+                    range = searcher.search(src.borrow()).filter(|r2| r2 != r);
+                    if range.is_none() {
+                        continue;
+                    }
+                }
+            }
+            found[i] = Some(range.clone());
+            if let Some(range) = range {
+                let better = match best {
+                    None => true,
+                    Some((_, ref b)) => range.start < b.start || (range.start == b.start && range.end > b.end),
+                };
+                if better {
+                    best = Some((i, range));
+                }
+            }
+        }
+        let (i, range) = match best {
+            Some(best) => best,
+            None => break,
+        };
+        found[i] = None;
+        prev_empty = if range.start == range.end { Some(range.start) } else { None };
+        let [left, _, right] = unsafe { src.split_around(range) };
+        writer(Span::into(left));
+        writer(pairs[i].1.clone());
+        src = right;
+    }
+    writer(Span::into(src));
+}
+
+#[cfg(feature = "std")]
+pub fn replace_all_pairs<'h, P>(src: &'h str, pairs: &[(P, &'h str)]) -> String
+where
+    P: Pattern<&'h str> + Clone,
+{
+    let mut res = String::with_capacity(src.len());
+    replace_all_pairs_with(src, pairs, |h| res.push_str(h));
+    res
+}
+
+// An output buffer which can be reused across calls to `replace_into`.
+#[cfg(feature = "std")]
+pub trait ReplaceBuffer<A: ?Sized> {
+    fn clear(&mut self);
+    fn push_hay(&mut self, hay: &A);
+}
+
+#[cfg(feature = "std")]
+impl ReplaceBuffer<str> for String {
+    #[inline]
+    fn clear(&mut self) {
+        String::clear(self);
+    }
+
+    #[inline]
+    fn push_hay(&mut self, hay: &str) {
+        self.push_str(hay);
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Clone> ReplaceBuffer<[T]> for Vec<T> {
+    #[inline]
+    fn clear(&mut self) {
+        Vec::clear(self);
+    }
+
+    #[inline]
+    fn push_hay(&mut self, hay: &[T]) {
+        self.extend_from_slice(hay);
+    }
+}
+
+// Clears `buf` and writes the result into it, keeping its capacity.
+#[cfg(feature = "std")]
+pub fn replace_into<'h, A, P, B>(src: &'h A, from: P, to: &'h A, buf: &mut B)
+where
+    A: Hay + ?Sized,
+    P: Pattern<&'h A>,
+    B: ReplaceBuffer<A>,
+{
+    buf.clear();
+    replace_with(src, from, |_| to, |h| buf.push_hay(h));
+}
+
+//------------------------------------------------------------------------------
+// Case folding
+//------------------------------------------------------------------------------
+
+// Folds the case of a string, e.g. to prepare a needle for searching a
+// `NormalizedView::case_folded` view.
+#[cfg(feature = "std")]
+pub fn case_fold(s: &str) -> String {
+    let mut folded = String::with_capacity(s.len());
+    for c in s.chars() {
+        push_folded(c, &mut folded);
+    }
+    folded
+}
+
+//------------------------------------------------------------------------------
+// Extract
+//------------------------------------------------------------------------------
+
+// Writes every match in order, discarding the text between them.
+pub fn extract_with<H, P, W>(src: H, pattern: P, mut writer: W)
+where
+    H: Haystack,
+    P: Pattern<H>,
+    W: FnMut(H),
+{
+    let mut searcher = pattern.into_searcher();
+    let mut src = Span::from(src);
+    while let Some(range) = searcher.search(src.borrow()) {
+        let [_, middle, right] = unsafe { src.split_around(range) };
+        writer(Span::into(middle));
+        src = right;
+    }
+}
+
+// Concatenates every match into a new buffer, e.g. a `String` or a `Vec<T>`.
+#[cfg(feature = "std")]
+pub fn extract<'h, A, P, B>(src: &'h A, pattern: P) -> B
+where
+    A: Hay + ?Sized,
+    P: Pattern<&'h A>,
+    B: ReplaceBuffer<A> + Default,
+{
+    let mut buf = B::default();
+    extract_with(src, pattern, |h| buf.push_hay(h));
+    buf
+}
+
+//------------------------------------------------------------------------------
+// Trim line prefix
+//------------------------------------------------------------------------------
+
+// Removes one leading occurrence of `prefix` from every line, writing each
+// line (including its '\n') in order. A prefix never extends past the end of
+// its line.
+pub fn trim_line_prefix_with<H, P, W>(src: H, prefix: P, mut writer: W)
+where
+    H: Haystack,
+    H::Hay: LineHay,
+    P: Pattern<H>,
+    W: FnMut(H),
+{
+    let mut consumer = prefix.into_consumer();
+    let mut src = Span::from(src);
+    loop {
+        let (hay, range) = src.borrow().into_parts();
+        let line_end = hay.next_line_start(range.start).map_or(range.end, |i| i.min(range.end));
+        let line = unsafe { Span::from_parts(hay, range.start..line_end) };
+        let content_start = consumer.consume(line).unwrap_or(range.start);
+        // Split off the line first: the left part of a split keeps the
+        // indices of the whole, while unique haystacks rebase the right part.
+        let [line, _, rest] = unsafe { src.split_around(line_end..line_end) };
+        let [_, _, content] = unsafe { line.split_around(range.start..content_start) };
+        writer(Span::into(content));
+        if line_end == range.end {
+            break;
+        }
+        src = rest;
+    }
+}
+
+// Like Kotlin's `trimMargin`, but only removes the prefix; blank lines and
+// lines without the prefix are kept as they are.
+#[cfg(feature = "std")]
+pub fn trim_line_prefix<'h, A, P, B>(src: &'h A, prefix: P) -> B
+where
+    A: LineHay + ?Sized,
+    P: Pattern<&'h A>,
+    B: ReplaceBuffer<A> + Default,
+{
+    let mut buf = B::default();
+    trim_line_prefix_with(src, prefix, |h| buf.push_hay(h));
+    buf
+}
+
+//------------------------------------------------------------------------------
+// Case-preserving replace
+//------------------------------------------------------------------------------
+
+#[cfg(feature = "std")]
+fn push_with_case_of(res: &mut String, matched: &str, to: &str) {
+    let mut cased = matched.chars().filter(|c| c.is_lowercase() || c.is_uppercase());
+    let first = match cased.next() {
+        Some(c) => c,
+        None => return res.push_str(to),
+    };
+    let rest_upper = cased.clone().all(char::is_uppercase);
+    let rest_lower = cased.clone().all(char::is_lowercase);
+    let has_rest = cased.next().is_some();
+    if first.is_uppercase() && rest_upper && has_rest {
+        res.extend(to.chars().flat_map(char::to_uppercase));
+    } else if first.is_uppercase() && rest_lower {
+        let mut chars = to.chars();
+        if let Some(c) = chars.next() {
+            res.extend(c.to_uppercase());
+            res.extend(chars.flat_map(char::to_lowercase));
+        }
+    } else if first.is_lowercase() && rest_lower {
+        res.extend(to.chars().flat_map(char::to_lowercase));
+    } else {
+        res.push_str(to);
+    }
+}
+
+#[cfg(feature = "std")]
+pub fn replace_preserving_case<'h, P>(src: &'h str, from: P, to: &str) -> String
+where
+    P: Pattern<&'h str>,
+{
+    let mut res = String::with_capacity(src.len());
+    let mut searcher = from.into_searcher();
+    let mut src = Span::from(src);
+    while let Some(range) = searcher.search(src.borrow()) {
+        let [left, middle, right] = unsafe { src.split_around(range) };
+        res.push_str(Span::into(left));
+        push_with_case_of(&mut res, Span::into(middle), to);
+        src = right;
+    }
+    res.push_str(Span::into(src));
+    res
+}
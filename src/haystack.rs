@@ -113,9 +113,23 @@ pub trait Hay {
 /// underlying representation called a [`Hay`]. Multiple haystacks may share the
 /// same hay type, and thus share the same implementation of pattern search
 /// algorithms.
-pub trait Haystack: Deref + Sized where Self::Target: Hay {
+///
+/// The hay is exposed through [`hay()`](#tymethod.hay) rather than `Deref`, so
+/// a haystack is free to dereference to something else, or not at all. For
+/// instance a substring of an `Rc<String>` can be a haystack over `str`.
+pub trait Haystack: Sized {
+    /// The hay this haystack is borrowed as.
+    type Hay: Hay + ?Sized;
+
+    /// Borrows the hay of this haystack.
+    fn hay(&self) -> &Self::Hay;
+
     /// Creates an empty haystack.
-    fn empty() -> Self;
+    ///
+    /// Owned haystacks should allocate the result the same way as `self`
+    /// (e.g. in the same allocator), so that an empty haystack can stand in
+    /// for `self` without requiring the allocator to be `Default`.
+    fn empty(&self) -> Self;
 
     /// Splits the haystack into 3 slices around the given range.
     ///
@@ -149,7 +163,7 @@ pub trait Haystack: Deref + Sized where Self::Target: Hay {
     /// assert_eq!(middle, &mut [2, 3, 4, 5]);
     /// assert_eq!(right, &mut [6]);
     /// ```
-    unsafe fn split_around(self, range: Range<<Self::Target as Hay>::Index>) -> [Self; 3];
+    unsafe fn split_around(self, range: Range<<Self::Hay as Hay>::Index>) -> [Self; 3];
 
     /// Subslices this haystack.
     ///
@@ -157,7 +171,7 @@ pub trait Haystack: Deref + Sized where Self::Target: Hay {
     ///
     /// The starts and end indices of `range` must be valid indices for the
     /// haystack `self`.
-    unsafe fn slice_unchecked(self, range: Range<<Self::Target as Hay>::Index>) -> Self;
+    unsafe fn slice_unchecked(self, range: Range<<Self::Hay as Hay>::Index>) -> Self;
 
     /// Transforms the range from relative to self's parent to the original
     /// haystack it was sliced from.
@@ -203,9 +217,9 @@ pub trait Haystack: Deref + Sized where Self::Target: Hay {
     /// ```
     fn restore_range(
         &self,
-        original: Range<<Self::Target as Hay>::Index>,
-        parent: Range<<Self::Target as Hay>::Index>,
-    ) -> Range<<Self::Target as Hay>::Index>;
+        original: Range<<Self::Hay as Hay>::Index>,
+        parent: Range<<Self::Hay as Hay>::Index>,
+    ) -> Range<<Self::Hay as Hay>::Index>;
 }
 
 /// A haystack which can be shared and cheaply cloned (e.g. `&H`, `Rc<H>`).
@@ -214,9 +228,7 @@ pub trait Haystack: Deref + Sized where Self::Target: Hay {
 /// original haystack will be retained in full and cloned, rather than being
 /// sliced and splitted. Being a shared haystack allows searcher to see the
 /// entire haystack, including the consumed portion.
-pub trait SharedHaystack: Haystack + Clone
-where Self::Target: Hay // FIXME: RFC 2089 or 2289
-{}
+pub trait SharedHaystack: Haystack + Clone {}
 
 /// The borrowing behavior differs between a (unique) haystack and shared
 /// haystack. We use *specialization* to distinguish between these behavior:
@@ -229,35 +241,32 @@ where Self::Target: Hay // FIXME: RFC 2089 or 2289
 ///     searchers.
 ///
 /// This trait will never be public.
-trait SpanBehavior: Haystack
-where Self::Target: Hay // FIXME: RFC 2089 or 2289
-{
+trait SpanBehavior: Haystack {
     fn take(&mut self) -> Self;
 
     fn from_span(span: Span<Self>) -> Self;
 
-    unsafe fn split_around_for_span(self, subrange: Range<<Self::Target as Hay>::Index>) -> [Self; 3];
+    unsafe fn split_around_for_span(self, subrange: Range<<Self::Hay as Hay>::Index>) -> [Self; 3];
 
-    unsafe fn slice_unchecked_for_span(self, subrange: Range<<Self::Target as Hay>::Index>) -> Self;
+    unsafe fn slice_unchecked_for_span(self, subrange: Range<<Self::Hay as Hay>::Index>) -> Self;
 
     fn borrow_range(
         &self,
-        range: Range<<Self::Target as Hay>::Index>,
-    ) -> Range<<Self::Target as Hay>::Index>;
+        range: Range<<Self::Hay as Hay>::Index>,
+    ) -> Range<<Self::Hay as Hay>::Index>;
 
     fn do_restore_range(
         &self,
-        range: Range<<Self::Target as Hay>::Index>,
-        subrange: Range<<Self::Target as Hay>::Index>,
-    ) -> Range<<Self::Target as Hay>::Index>;
+        range: Range<<Self::Hay as Hay>::Index>,
+        subrange: Range<<Self::Hay as Hay>::Index>,
+    ) -> Range<<Self::Hay as Hay>::Index>;
 }
 
-impl<H: Haystack> SpanBehavior for H
-where H::Target: Hay // FIXME: RFC 2089 or 2289
-{
+impl<H: Haystack> SpanBehavior for H {
     #[inline]
     default fn take(&mut self) -> Self {
-        mem::replace(self, Self::empty())
+        let empty = self.empty();
+        mem::replace(self, empty)
     }
 
     #[inline]
@@ -266,33 +275,31 @@ where H::Target: Hay // FIXME: RFC 2089 or 2289
     }
 
     #[inline]
-    default fn borrow_range(&self, _: Range<<Self::Target as Hay>::Index>) -> Range<<Self::Target as Hay>::Index> {
-        self.start_index()..self.end_index()
+    default fn borrow_range(&self, _: Range<<Self::Hay as Hay>::Index>) -> Range<<Self::Hay as Hay>::Index> {
+        self.hay().start_index()..self.hay().end_index()
     }
 
     #[inline]
     default fn do_restore_range(
         &self,
-        range: Range<<Self::Target as Hay>::Index>,
-        subrange: Range<<Self::Target as Hay>::Index>,
-    ) -> Range<<Self::Target as Hay>::Index> {
+        range: Range<<Self::Hay as Hay>::Index>,
+        subrange: Range<<Self::Hay as Hay>::Index>,
+    ) -> Range<<Self::Hay as Hay>::Index> {
         self.restore_range(range, subrange)
     }
 
     #[inline]
-    default unsafe fn split_around_for_span(self, subrange: Range<<Self::Target as Hay>::Index>) -> [Self; 3] {
+    default unsafe fn split_around_for_span(self, subrange: Range<<Self::Hay as Hay>::Index>) -> [Self; 3] {
         self.split_around(subrange)
     }
 
     #[inline]
-    default unsafe fn slice_unchecked_for_span(self, subrange: Range<<Self::Target as Hay>::Index>) -> Self {
+    default unsafe fn slice_unchecked_for_span(self, subrange: Range<<Self::Hay as Hay>::Index>) -> Self {
         self.slice_unchecked(subrange)
     }
 }
 
-impl<H: SharedHaystack> SpanBehavior for H
-where H::Target: Hay // FIXME: RFC 2089 or 2289
-{
+impl<H: SharedHaystack> SpanBehavior for H {
     #[inline]
     fn take(&mut self) -> Self {
         self.clone()
@@ -306,26 +313,26 @@ where H::Target: Hay // FIXME: RFC 2089 or 2289
     }
 
     #[inline]
-    fn borrow_range(&self, range: Range<<Self::Target as Hay>::Index>) -> Range<<Self::Target as Hay>::Index> {
+    fn borrow_range(&self, range: Range<<Self::Hay as Hay>::Index>) -> Range<<Self::Hay as Hay>::Index> {
         range
     }
 
     #[inline]
     fn do_restore_range(
         &self,
-        _: Range<<Self::Target as Hay>::Index>,
-        subrange: Range<<Self::Target as Hay>::Index>,
-    ) -> Range<<Self::Target as Hay>::Index> {
+        _: Range<<Self::Hay as Hay>::Index>,
+        subrange: Range<<Self::Hay as Hay>::Index>,
+    ) -> Range<<Self::Hay as Hay>::Index> {
         subrange
     }
 
     #[inline]
-    unsafe fn split_around_for_span(self, _: Range<<Self::Target as Hay>::Index>) -> [Self; 3] {
+    unsafe fn split_around_for_span(self, _: Range<<Self::Hay as Hay>::Index>) -> [Self; 3] {
         [self.clone(), self.clone(), self]
     }
 
     #[inline]
-    unsafe fn slice_unchecked_for_span(self, _: Range<<Self::Target as Hay>::Index>) -> Self {
+    unsafe fn slice_unchecked_for_span(self, _: Range<<Self::Hay as Hay>::Index>) -> Self {
         self
     }
 }
@@ -334,32 +341,26 @@ where H::Target: Hay // FIXME: RFC 2089 or 2289
 
 /// A span is a haystack coupled with the original range where the haystack is found.
 #[derive(Debug, Clone)]
-pub struct Span<H: Haystack>
-where H::Target: Hay // FIXME: RFC 2089 or 2289
-{
+pub struct Span<H: Haystack> {
     haystack: H,
-    range: Range<<<H as Deref>::Target as Hay>::Index>,
+    range: Range<<<H as Haystack>::Hay as Hay>::Index>,
     //^ The `<H as Trait>` is to trick `#[derive]` not to generate
     //  the where bound for `H::Hay`.
 }
 
 /// Creates a span which covers the entire haystack.
-impl<H: Haystack> From<H> for Span<H>
-where H::Target: Hay // FIXME: RFC 2089 or 2289
-{
+impl<H: Haystack> From<H> for Span<H> {
     #[inline]
     fn from(haystack: H) -> Self {
-        let range = haystack.start_index()..haystack.end_index();
+        let range = haystack.hay().start_index()..haystack.hay().end_index();
         Self { haystack, range }
     }
 }
 
-impl<H: SharedHaystack> Span<H>
-where H::Target: Hay // FIXME: RFC 2089 or 2289
-{
+impl<H: SharedHaystack> Span<H> {
     /// Decomposes this span into the original haystack, and the range it focuses on.
     #[inline]
-    pub fn into_parts(self) -> (H, Range<<H::Target as Hay>::Index>) {
+    pub fn into_parts(self) -> (H, Range<<H::Hay as Hay>::Index>) {
         (self.haystack, self.range)
     }
 
@@ -369,11 +370,111 @@ where H::Target: Hay // FIXME: RFC 2089 or 2289
     ///
     /// The `range` must be a valid range relative to `haystack`.
     #[inline]
-    pub unsafe fn from_parts(haystack: H, range: Range<<H::Target as Hay>::Index>) -> Self {
+    pub unsafe fn from_parts(haystack: H, range: Range<<H::Hay as Hay>::Index>) -> Self {
         Self { haystack, range }
     }
 }
 
+impl<H: SharedHaystack> Span<H>
+where H::Hay: Hay<Index = usize>
+{
+    /// Partitions this span into `n` consecutive sub-spans of roughly equal
+    /// length, split on valid indices.
+    ///
+    /// Every sub-span except the last is extended by up to `overlap`
+    /// codewords past the start of the next one, so an occurrence of a needle
+    /// at most `overlap + 1` codewords long which straddles a boundary is
+    /// fully contained in the earlier sub-span. When merging results, keep
+    /// only the occurrences starting before the next sub-span's start to
+    /// avoid reporting them twice.
+    ///
+    /// Exactly `n` sub-spans are produced; some may be empty if this span is
+    /// shorter than `n` codewords. Finding the split points walks the
+    /// codewords of the span.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero.
+    ///
+    /// ```rust
+    /// use pattern_3::Span;
+    ///
+    /// let chunks = Span::from("αβγδε").chunks(2, 1).map(|s| s.into()).collect::<Vec<&str>>();
+    /// assert_eq!(chunks, vec!["αβγδ", "δε"]);
+    /// ```
+    #[inline]
+    pub fn chunks(self, n: usize, overlap: usize) -> SpanChunks<H> {
+        assert!(n > 0, "cannot split a span into zero chunks");
+        SpanChunks {
+            haystack: self.haystack,
+            start: self.range.start,
+            end: self.range.end,
+            remaining: n,
+            overlap,
+        }
+    }
+}
+
+/// Iterator over sub-spans of a span.
+///
+/// Created by [`Span::chunks`](struct.Span.html#method.chunks).
+#[derive(Debug, Clone)]
+pub struct SpanChunks<H> {
+    haystack: H,
+    start: usize,
+    end: usize,
+    remaining: usize,
+    overlap: usize,
+}
+
+impl<H: SharedHaystack> Iterator for SpanChunks<H>
+where H::Hay: Hay<Index = usize>
+{
+    type Item = Span<H>;
+
+    fn next(&mut self) -> Option<Span<H>> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let hay = self.haystack.hay();
+        let target = self.start + (self.end - self.start) / self.remaining;
+        let mut boundary = self.start;
+        let mut chunk_end;
+        unsafe {
+            if self.remaining == 1 {
+                boundary = self.end;
+            } else {
+                while boundary < target {
+                    boundary = hay.next_index(boundary);
+                }
+            }
+            chunk_end = boundary;
+            for _ in 0..self.overlap {
+                if chunk_end == self.end {
+                    break;
+                }
+                chunk_end = hay.next_index(chunk_end);
+            }
+        }
+        let span = Span {
+            haystack: self.haystack.clone(),
+            range: self.start..chunk_end,
+        };
+        self.start = boundary;
+        self.remaining -= 1;
+        Some(span)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<H: SharedHaystack> ExactSizeIterator for SpanChunks<H>
+where H::Hay: Hay<Index = usize>
+{}
+
 impl<'h> Span<&'h str> {
     /// Reinterprets the string span as a byte-array span.
     #[inline]
@@ -385,20 +486,46 @@ impl<'h> Span<&'h str> {
     }
 }
 
-impl<H: Haystack> Span<H>
-where H::Target: Hay // FIXME: RFC 2089 or 2289
-{
+impl<'h, A: Hay + ?Sized> Span<&'h A> {
+    /// Returns the focused portion of the hay.
+    ///
+    /// Index `i` of the returned slice corresponds to index
+    /// `self.original_range().start + i` of the whole hay. This is also what a
+    /// span dereferences to.
+    ///
+    /// ```rust
+    /// # use pattern_3::Span;
+    /// let span = unsafe { Span::from_parts("hello world", 6..11) };
+    /// assert_eq!(span.as_slice(), "world");
+    /// assert_eq!(span.len(), 5);
+    /// ```
+    #[inline]
+    pub fn as_slice(&self) -> &'h A {
+        unsafe { self.haystack.slice_unchecked(self.range.clone()) }
+    }
+}
+
+impl<'h, A: Hay + ?Sized> Deref for Span<&'h A> {
+    type Target = A;
+
+    #[inline]
+    fn deref(&self) -> &A {
+        self.as_slice()
+    }
+}
+
+impl<H: Haystack> Span<H> {
     /// The range of the span, relative to the ultimate original haystack it was sliced from.
     #[inline]
-    pub fn original_range(&self) -> Range<<H::Target as Hay>::Index> {
+    pub fn original_range(&self) -> Range<<H::Hay as Hay>::Index> {
         self.range.clone()
     }
 
     /// Borrows a shared span.
     #[inline]
-    pub fn borrow(&self) -> Span<&H::Target> {
+    pub fn borrow(&self) -> Span<&H::Hay> {
         Span {
-            haystack: &*self.haystack,
+            haystack: self.haystack.hay(),
             range: self.haystack.borrow_range(self.range.clone()),
         }
     }
@@ -443,7 +570,7 @@ where H::Target: Hay // FIXME: RFC 2089 or 2289
     /// # Some(()) })();
     /// ```
     #[inline]
-    pub unsafe fn split_around(self, subrange: Range<<H::Target as Hay>::Index>) -> [Self; 3] {
+    pub unsafe fn split_around(self, subrange: Range<<H::Hay as Hay>::Index>) -> [Self; 3] {
         let self_range = self.haystack.borrow_range(self.range.clone());
         let [left, middle, right] = self.haystack.split_around_for_span(subrange.clone());
 
@@ -464,7 +591,7 @@ where H::Target: Hay // FIXME: RFC 2089 or 2289
     ///
     /// `subrange` must be a valid range relative to `self.borrow()`.
     #[inline]
-    pub unsafe fn slice_unchecked(self, subrange: Range<<H::Target as Hay>::Index>) -> Self {
+    pub unsafe fn slice_unchecked(self, subrange: Range<<H::Hay as Hay>::Index>) -> Self {
         let haystack = self.haystack.slice_unchecked_for_span(subrange.clone());
         let range = haystack.do_restore_range(self.range, subrange);
         Self { haystack, range }
@@ -472,8 +599,15 @@ where H::Target: Hay // FIXME: RFC 2089 or 2289
 }
 
 impl<'a, A: Hay + ?Sized + 'a> Haystack for &'a A {
+    type Hay = A;
+
+    #[inline]
+    fn hay(&self) -> &A {
+        self
+    }
+
     #[inline]
-    fn empty() -> Self {
+    fn empty(&self) -> Self {
         A::empty()
     }
 
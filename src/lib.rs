@@ -16,6 +16,8 @@
     int_to_from_bytes,
     // some useful library features
 )]
+#![cfg_attr(feature = "std", feature(allocator_api))]
+// used to support `Vec<T, A>` with custom allocators as haystacks.
 #![cfg_attr(test, allow(warnings))]
 
 #![cfg_attr(not(feature = "std"), no_std)]
@@ -23,14 +25,116 @@
 extern crate core as std;
 
 extern crate memchr;
+#[cfg(feature = "bstr")]
+extern crate bstr;
+#[cfg(feature = "bytes")]
+extern crate bytes;
+#[cfg(feature = "nom")]
+extern crate nom;
+#[cfg(feature = "unicode")]
+extern crate unicode_normalization;
+#[cfg(feature = "ropey")]
+extern crate ropey;
+#[cfg(feature = "unicode-segmentation")]
+extern crate unicode_segmentation;
+#[cfg(feature = "regex")]
+extern crate regex;
+#[cfg(feature = "regex-automata")]
+extern crate regex_automata;
+#[cfg(feature = "aho-corasick")]
+extern crate aho_corasick;
 
 pub mod haystack;
 pub mod pattern;
 mod slices;
 mod strings;
+mod combinators;
+mod narrow;
+#[cfg(feature = "wtf8")]
 mod omgwtf8;
+#[cfg(feature = "std")]
+mod os_str;
+#[cfg(feature = "std")]
+mod paths;
+#[cfg(feature = "bstr")]
+mod byte_strings;
+#[cfg(feature = "nom")]
+mod nom_parser;
+#[cfg(feature = "regex")]
+mod regex_pattern;
+mod byte_hay;
+#[cfg(feature = "regex-automata")]
+mod dfa_pattern;
+#[cfg(feature = "aho-corasick")]
+mod aho_corasick_pattern;
 pub mod ext;
+pub mod rolling_hash;
 
-pub use haystack::{Hay, Haystack, SharedHaystack, Span};
+pub use haystack::{Hay, Haystack, SharedHaystack, Span, SpanChunks};
 pub use pattern::{Pattern, Searcher, ReverseSearcher, DoubleEndedSearcher};
+pub use pattern::{Fuse, FusedSearcher};
+pub use pattern::Not;
+#[cfg(feature = "wtf8")]
 pub use omgwtf8::Wtf8;
+pub use strings::Utf8Needle;
+pub use strings::{Utf8Bytes, Utf8BytesSearcher};
+pub use strings::{WhitespaceTolerant, WhitespaceTolerantSearcher};
+pub use strings::{Whitespace, WhitespaceSearcher};
+pub use strings::{LineTerminator, LineTerminatorSearcher};
+pub use strings::{AsciiStr, AsciiCharSearcher, AsciiMultiCharSearcher};
+pub use narrow::{Narrow, NarrowIndex, NarrowSearcher};
+pub use combinators::{Filtered, FilteredSearcher};
+pub use combinators::{LineAnchor, LineAnchored, LineAnchoredSearcher, LineHay};
+pub use combinators::{FollowedBy, PrecededBy, Lookahead, Lookbehind};
+pub use combinators::{Progress, ProgressSearcher};
+pub use combinators::RangesSearcher;
+pub use combinators::{repeat, Repeat, RepeatSearcher};
+pub use combinators::{Runs, RunsSearcher};
+pub use combinators::{Then, ThenSearcher};
+pub use combinators::{with_index, WithIndex, WithIndexSearcher};
+pub use slices::{Algorithm, Needle, SearcherBuilder};
+pub use slices::{ConstNeedle, ConstNeedleSearcher};
+pub use slices::{Sorted, SortedRun, SortedSliceSearcher};
+pub use slices::{Approx, ApproxSearcher};
+pub use slices::{AsciiWhitespaceByte, AsciiDigit, AsciiAlphanumeric, ByteClassSearcher};
+pub use slices::{AnyOf, AnyOfSearcher, LongestOf, MatchedNeedle};
+pub use slices::ByteSetSearcher;
+pub use slices::{AsciiCaseInsensitive, AsciiCaseInsensitiveSearcher};
+pub use slices::{Lossy, LossySearcher};
+pub use slices::{Lazy, LazyNeedle, LazyMatchIndices};
+#[cfg(feature = "std")]
+pub use slices::{Cached, ByteNeedle, StrNeedle, Lit, OwnedSliceSearcher};
+#[cfg(feature = "std")]
+pub use slices::{Rle, RleMatchIndices};
+#[cfg(feature = "std")]
+pub use slices::{ChunkedHay, ChunkedSearcher, ChunkedReverseSearcher, ChunkCursor};
+#[cfg(feature = "std")]
+pub use slices::{ChunkedMatchIndices, ChunkedMatchRanges, ChunkedRMatchIndices, ChunkedRMatchRanges};
+#[cfg(feature = "std")]
+pub use slices::{ChunkedSplitRanges, ChunkedRSplitRanges};
+#[cfg(feature = "std")]
+pub use slices::{Concat, ConcatMatchPositions};
+#[cfg(feature = "std")]
+pub use slices::{EitherStrand, EitherStrandSearcher, Strand, StrandMatchRanges};
+#[cfg(feature = "std")]
+pub use slices::CowSliceSearcher;
+#[cfg(feature = "std")]
+pub use slices::{Glob, GlobSearcher};
+#[cfg(feature = "std")]
+pub use strings::{ArcStr, RcStr};
+#[cfg(feature = "std")]
+pub use strings::{NormalizedView, NormalizedMatchRanges, NormalizedRMatchRanges};
+#[cfg(feature = "unicode-segmentation")]
+pub use strings::{Graphemes, GraphemeSearcher, GraphemeStrSearcher};
+#[cfg(feature = "phonetic")]
+pub use strings::{Soundex, SoundexSearcher};
+#[cfg(feature = "ropey")]
+pub use strings::rope_replace_with;
+#[cfg(feature = "nom")]
+pub use nom_parser::{NomPattern, NomSearcher};
+#[cfg(feature = "regex")]
+pub use regex_pattern::{RegexSearcher, BytesRegexSearcher};
+#[cfg(feature = "regex-automata")]
+pub use dfa_pattern::{DfaPattern, DfaSearcher};
+#[cfg(feature = "aho-corasick")]
+pub use aho_corasick_pattern::AhoCorasickSearcher;
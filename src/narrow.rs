@@ -0,0 +1,245 @@
+//! Hays with narrow indices.
+
+use pattern::*;
+use haystack::{Hay, Span};
+use std::fmt::Debug;
+use std::marker::PhantomData;
+use std::ops::Range;
+
+/// An unsigned integer narrower than `usize`, usable as the index of a
+/// [`Narrow`](struct.Narrow.html) hay.
+///
+/// # Safety
+///
+/// `from_usize` and `to_usize` must be inverses for every value up to
+/// `MAX`.
+pub unsafe trait NarrowIndex: Copy + Debug + Eq + Ord {
+    /// The largest value representable by this type.
+    const MAX: usize;
+
+    /// Converts an index not greater than `MAX`.
+    fn from_usize(index: usize) -> Self;
+
+    /// Widens the index back into a `usize`.
+    fn to_usize(self) -> usize;
+}
+
+macro_rules! impl_narrow_index {
+    ($($ty:ident)*) => {
+        $(
+            unsafe impl NarrowIndex for $ty {
+                const MAX: usize = $ty::MAX as usize;
+
+                #[inline]
+                fn from_usize(index: usize) -> Self {
+                    index as $ty
+                }
+
+                #[inline]
+                fn to_usize(self) -> usize {
+                    self as usize
+                }
+            }
+        )*
+    }
+}
+
+impl_narrow_index!(u8 u16 u32);
+
+/// A hay whose indices are stored as `u32` (or another
+/// [`NarrowIndex`](trait.NarrowIndex.html)) instead of `usize`.
+///
+/// This wraps any `usize`-indexed hay no longer than `I::MAX`, such as `str`
+/// or `[T]`. Matches are found by the searchers of the wrapped hay, but their
+/// ranges are reported with the narrow index type, so storing many of them
+/// takes half the memory on 64-bit targets.
+///
+/// ```rust
+/// extern crate pattern_3;
+/// use pattern_3::Narrow;
+/// use pattern_3::ext::match_ranges;
+/// use std::ops::Range;
+///
+/// let text = Narrow::<str>::new("one two three two one").unwrap();
+/// let ranges: Vec<Range<u32>> = match_ranges(text, "two").map(|(r, _)| r).collect();
+/// assert_eq!(ranges, vec![4..7, 14..17]);
+///
+/// let bytes = Narrow::<[u8], u8>::new(&[0; 300][..]);
+/// assert!(bytes.is_none());
+/// ```
+#[repr(transparent)]
+pub struct Narrow<A: ?Sized, I = u32> {
+    index: PhantomData<I>,
+    hay: A,
+}
+
+impl<A: Hay<Index = usize> + ?Sized, I: NarrowIndex> Narrow<A, I> {
+    /// Wraps the hay, or returns `None` if its indices do not fit in `I`.
+    #[inline]
+    pub fn new(hay: &A) -> Option<&Self> {
+        if hay.start_index() <= I::MAX && hay.end_index() <= I::MAX {
+            Some(unsafe { &*(hay as *const A as *const Self) })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the wrapped hay.
+    #[inline]
+    pub fn as_inner(&self) -> &A {
+        &self.hay
+    }
+}
+
+impl<A: Hay<Index = usize> + Debug + ?Sized, I> Debug for Narrow<A, I> {
+    fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+        self.hay.fmt(f)
+    }
+}
+
+#[inline]
+fn widen<I: NarrowIndex>(range: Range<I>) -> Range<usize> {
+    range.start.to_usize()..range.end.to_usize()
+}
+
+#[inline]
+fn narrow<I: NarrowIndex>(range: Range<usize>) -> Range<I> {
+    I::from_usize(range.start)..I::from_usize(range.end)
+}
+
+impl<A: Hay<Index = usize> + ?Sized, I: NarrowIndex> Hay for Narrow<A, I> {
+    type Index = I;
+
+    #[inline]
+    fn empty<'a>() -> &'a Self {
+        unsafe { &*(A::empty() as *const A as *const Self) }
+    }
+
+    #[inline]
+    fn start_index(&self) -> I {
+        I::from_usize(self.hay.start_index())
+    }
+
+    #[inline]
+    fn end_index(&self) -> I {
+        I::from_usize(self.hay.end_index())
+    }
+
+    #[inline]
+    unsafe fn next_index(&self, index: I) -> I {
+        I::from_usize(self.hay.next_index(index.to_usize()))
+    }
+
+    #[inline]
+    unsafe fn prev_index(&self, index: I) -> I {
+        I::from_usize(self.hay.prev_index(index.to_usize()))
+    }
+
+    #[inline]
+    unsafe fn slice_unchecked(&self, range: Range<I>) -> &Self {
+        let hay = self.hay.slice_unchecked(widen(range));
+        &*(hay as *const A as *const Self)
+    }
+}
+
+/// Searcher of a [`Narrow`](struct.Narrow.html) hay, forwarding to a searcher
+/// of the wrapped hay.
+#[derive(Debug, Clone)]
+pub struct NarrowSearcher<S>(S);
+
+unsafe impl<A, I, S> Searcher<Narrow<A, I>> for NarrowSearcher<S>
+where
+    A: Hay<Index = usize> + ?Sized,
+    I: NarrowIndex,
+    S: Searcher<A>,
+{
+    #[inline]
+    fn search(&mut self, span: Span<&Narrow<A, I>>) -> Option<Range<I>> {
+        let (hay, range) = span.into_parts();
+        let span = unsafe { Span::from_parts(&hay.hay, widen(range)) };
+        self.0.search(span).map(narrow)
+    }
+
+    #[inline]
+    fn consume(&mut self, span: Span<&Narrow<A, I>>) -> Option<I> {
+        let (hay, range) = span.into_parts();
+        let span = unsafe { Span::from_parts(&hay.hay, widen(range)) };
+        self.0.consume(span).map(I::from_usize)
+    }
+
+    #[inline]
+    fn trim_start(&mut self, hay: &Narrow<A, I>) -> I {
+        I::from_usize(self.0.trim_start(&hay.hay))
+    }
+}
+
+unsafe impl<A, I, S> ReverseSearcher<Narrow<A, I>> for NarrowSearcher<S>
+where
+    A: Hay<Index = usize> + ?Sized,
+    I: NarrowIndex,
+    S: ReverseSearcher<A>,
+{
+    #[inline]
+    fn rsearch(&mut self, span: Span<&Narrow<A, I>>) -> Option<Range<I>> {
+        let (hay, range) = span.into_parts();
+        let span = unsafe { Span::from_parts(&hay.hay, widen(range)) };
+        self.0.rsearch(span).map(narrow)
+    }
+
+    #[inline]
+    fn rconsume(&mut self, span: Span<&Narrow<A, I>>) -> Option<I> {
+        let (hay, range) = span.into_parts();
+        let span = unsafe { Span::from_parts(&hay.hay, widen(range)) };
+        self.0.rconsume(span).map(I::from_usize)
+    }
+
+    #[inline]
+    fn trim_end(&mut self, hay: &Narrow<A, I>) -> I {
+        I::from_usize(self.0.trim_end(&hay.hay))
+    }
+}
+
+unsafe impl<A, I, S> DoubleEndedSearcher<Narrow<A, I>> for NarrowSearcher<S>
+where
+    A: Hay<Index = usize> + ?Sized,
+    I: NarrowIndex,
+    S: DoubleEndedSearcher<A>,
+{}
+
+impl<A, I, S> FusedSearcher<Narrow<A, I>> for NarrowSearcher<S>
+where
+    A: Hay<Index = usize> + ?Sized,
+    I: NarrowIndex,
+    S: FusedSearcher<A>,
+{}
+
+// Implemented for the common needles only, since a blanket impl over every
+// pattern of the wrapped hay would overlap with the combinators.
+macro_rules! impl_pattern {
+    (<[$($gen:tt),*]> for $pat:ty, $hay:ty $(, where $($bound:tt)*)*) => {
+        impl<'h, $($gen,)* I: NarrowIndex> Pattern<&'h Narrow<$hay, I>> for $pat
+        $(where $($bound)*)*
+        {
+            type Searcher = NarrowSearcher<<$pat as Pattern<&'h $hay>>::Searcher>;
+
+            #[inline]
+            fn into_searcher(self) -> Self::Searcher {
+                NarrowSearcher(Pattern::<&'h $hay>::into_searcher(self))
+            }
+
+            #[inline]
+            fn into_consumer(self) -> Self::Searcher {
+                NarrowSearcher(Pattern::<&'h $hay>::into_consumer(self))
+            }
+        }
+    }
+}
+
+impl_pattern!(<['p]> for &'p str, str);
+#[cfg(feature = "std")]
+impl_pattern!(<['p]> for &'p String, str);
+impl_pattern!(<[]> for char, str);
+impl_pattern!(<['p]> for &'p [char], str);
+impl_pattern!(<[F]> for F, str, where F: FnMut(char) -> bool);
+impl_pattern!(<['p, T]> for &'p [T], [T], where T: PartialEq + 'p);
+impl_pattern!(<[T, F]> for F, [T], where F: FnMut(&T) -> bool);
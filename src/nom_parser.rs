@@ -0,0 +1,145 @@
+//! Adapter from `nom` parsers to patterns.
+
+use pattern::{Pattern, Searcher};
+use haystack::{Hay, Haystack, Span};
+use nom::IResult;
+use std::fmt;
+use std::marker::PhantomData;
+use std::ops::Range;
+
+/// A pattern recognized by a `nom` parser.
+///
+/// The parser is applied at the start of the span when consuming, and at every
+/// character boundary when searching. The range covered by the match is the
+/// input consumed by the parser; its output is discarded.
+///
+/// Since searchers may be applied to hays of any lifetime, the output and
+/// error types of the parser must not borrow from the input. Parsers like
+/// `tag` can be adapted using combinators such as `value` and an error type
+/// like `()`.
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate nom;
+/// extern crate pattern_3;
+///
+/// use nom::bytes::complete::tag;
+/// use nom::character::complete::digit1;
+/// use nom::combinator::value;
+/// use nom::sequence::pair;
+/// use pattern_3::NomPattern;
+/// use pattern_3::ext::split;
+///
+/// let delimiter = NomPattern::new(|i| value((), pair(tag::<_, _, ()>("--"), digit1))(i));
+/// assert_eq!(
+///     split("a--1b--23c-d", delimiter).collect::<Vec<_>>(),
+///     vec!["a", "b", "c-d"],
+/// );
+/// ```
+pub struct NomPattern<F, O, E> {
+    parser: F,
+    _marker: PhantomData<fn(&str) -> (O, E)>,
+}
+
+impl<F, O, E> NomPattern<F, O, E>
+where
+    F: FnMut(&str) -> IResult<&str, O, E>,
+{
+    /// Wraps a parser as a pattern.
+    #[inline]
+    pub fn new(parser: F) -> Self {
+        NomPattern {
+            parser,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<F: Clone, O, E> Clone for NomPattern<F, O, E> {
+    #[inline]
+    fn clone(&self) -> Self {
+        NomPattern {
+            parser: self.parser.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<F, O, E> fmt::Debug for NomPattern<F, O, E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("NomPattern").finish()
+    }
+}
+
+/// Searcher of a [`NomPattern`].
+pub struct NomSearcher<F, O, E> {
+    pattern: NomPattern<F, O, E>,
+    allow_empty_match: bool,
+}
+
+impl<F, O, E> NomSearcher<F, O, E>
+where
+    F: FnMut(&str) -> IResult<&str, O, E>,
+{
+    /// Runs the parser on `hay[start..end]`, returning the end index of the
+    /// consumed input.
+    #[inline]
+    fn parse_at(&mut self, hay: &str, start: usize, end: usize) -> Option<usize> {
+        let input = &hay[start..end];
+        let (rest, _) = (self.pattern.parser)(input).ok()?;
+        let consumed = input.len().checked_sub(rest.len());
+        assert!(
+            consumed.map_or(false, |c| input[c..].as_ptr() == rest.as_ptr()),
+            "the parser must return a suffix of its input",
+        );
+        Some(end - rest.len())
+    }
+}
+
+unsafe impl<F, O, E> Searcher<str> for NomSearcher<F, O, E>
+where
+    F: FnMut(&str) -> IResult<&str, O, E>,
+{
+    fn search(&mut self, span: Span<&str>) -> Option<Range<usize>> {
+        let (hay, range) = span.into_parts();
+        let mut start = range.start;
+        // an empty match is not allowed right where the previous match ended.
+        let mut allow_empty_match = self.allow_empty_match;
+        loop {
+            if let Some(end) = self.parse_at(hay, start, range.end) {
+                if end != start || allow_empty_match {
+                    self.allow_empty_match = false;
+                    return Some(start..end);
+                }
+            }
+            if start == range.end {
+                return None;
+            }
+            allow_empty_match = true;
+            start = unsafe { hay.next_index(start) };
+        }
+    }
+
+    #[inline]
+    fn consume(&mut self, span: Span<&str>) -> Option<usize> {
+        let (hay, range) = span.into_parts();
+        self.parse_at(hay, range.start, range.end)
+    }
+}
+
+impl<H, F, O, E> Pattern<H> for NomPattern<F, O, E>
+where
+    H: Haystack<Hay = str>,
+    F: FnMut(&str) -> IResult<&str, O, E>,
+{
+    type Searcher = NomSearcher<F, O, E>;
+
+    #[inline]
+    fn into_searcher(self) -> Self::Searcher {
+        NomSearcher {
+            pattern: self,
+            allow_empty_match: true,
+        }
+    }
+}
@@ -76,6 +76,16 @@ fn test_wtf8_next_last_index() {
     }
 }
 
+// On Windows, `OsStr` is encoded as WTF-8, which is a subset of OMG-WTF-8.
+#[cfg(all(windows, feature = "std"))]
+impl Wtf8 {
+    /// Views the encoded form of an OS string as WTF-8.
+    #[inline]
+    pub fn from_os_str(s: &::std::ffi::OsStr) -> &Wtf8 {
+        unsafe { Wtf8::from_bytes_unchecked(s.as_encoded_bytes()) }
+    }
+}
+
 impl<'h> Span<&'h Wtf8> {
     pub fn as_bytes(self) -> Span<&'h [u8]> {
         let (haystack, range) = self.into_parts();
@@ -1,7 +1,9 @@
 use pattern::*;
-use haystack::{Haystack, Span};
+use haystack::{Hay, Haystack, Span};
 use std::ops::Range;
-use slices::slice::{TwoWaySearcher, SliceSearcher, NaiveSearcher};
+use slices::slice::{TwoWaySearcher, SliceSearcher, NaiveSearcher, SliceHay};
+use slices::lossy::LossyCharEq;
+use strings::func::MultiCharEq;
 #[cfg(test)]
 use ext::{match_ranges, rmatch_ranges, starts_with, ends_with};
 
@@ -10,6 +12,13 @@ use super::wtf8::{HighSurrogate, LowSurrogate, ThreeByteSeq, Wtf8};
 // The TwoWaySearcher will not match unpaired surrogate at boundary, so no need
 // to convert logical range to physical range.
 
+impl SliceHay<u8> for Wtf8 {
+    #[inline]
+    fn as_slice(&self) -> &[u8] {
+        self.as_inner()
+    }
+}
+
 unsafe impl<'p> Searcher<Wtf8> for TwoWaySearcher<'p, u8> {
     #[inline]
     fn search(&mut self, span: Span<&Wtf8>) -> Option<Range<usize>> {
@@ -323,7 +332,7 @@ unsafe impl<'p> ReverseSearcher<Wtf8> for Wtf8Searcher<'p> {
     }
 }
 
-impl<'p, H: Haystack<Target = Wtf8>> Pattern<H> for &'p Wtf8 {
+impl<'p, H: Haystack<Hay = Wtf8>> Pattern<H> for &'p Wtf8 {
     type Searcher = Wtf8Searcher<'p>;
 
     fn into_searcher(self) -> Self::Searcher {
@@ -345,7 +354,7 @@ impl<'p, H: Haystack<Target = Wtf8>> Pattern<H> for &'p Wtf8 {
     }
 }
 
-// FIXME cannot impl `Pattern<(_: Haystack<Target = Wtf8>)>` due to RFC 1672 being postponed.
+// FIXME cannot impl `Pattern<(_: Haystack<Hay = Wtf8>)>` due to RFC 1672 being postponed.
 // (need to wait for chalk)
 impl<'h, 'p> Pattern<&'h Wtf8> for &'p str {
     type Searcher = SliceSearcher<'p, u8>;
@@ -358,3 +367,143 @@ impl<'h, 'p> Pattern<&'h Wtf8> for &'p str {
         SliceSearcher::new_consumer(self.as_bytes())
     }
 }
+
+//------------------------------------------------------------------------------
+// Char searcher
+//------------------------------------------------------------------------------
+
+// A supplementary code point is two codewords (its surrogate halves), which
+// are decoded together when both lie inside the span. Any other surrogate,
+// paired or not, is seen by the predicate as U+FFFD REPLACEMENT CHARACTER, like
+// `OsStr::to_string_lossy`.
+
+#[inline]
+fn decode_codeword(bytes: &[u8]) -> char {
+    match ::std::str::from_utf8(bytes) {
+        Ok(s) => s.chars().next().unwrap_or('\u{fffd}'),
+        Err(_) => '\u{fffd}',
+    }
+}
+
+/// Decodes the character starting at `start`, returning it with its end.
+#[inline]
+fn decode_first(hay: &Wtf8, start: usize, end: usize) -> (char, usize) {
+    let bytes = hay.as_inner();
+    let next = unsafe { hay.next_index(start) };
+    if bytes[start] >= 0xf0 && next - start == 2 && next < end {
+        let full = start + 4;
+        return (decode_codeword(&bytes[start..full]), full);
+    }
+    (decode_codeword(&bytes[start..next]), next)
+}
+
+/// Decodes the character ending at `end`, returning it with its start.
+#[inline]
+fn decode_last(hay: &Wtf8, start: usize, end: usize) -> (char, usize) {
+    let bytes = hay.as_inner();
+    let prev = unsafe { hay.prev_index(end) };
+    if bytes[prev] < 0xc0 && bytes[prev] >= 0x80 && end - prev == 2 && prev > start {
+        let full = prev - 2;
+        if bytes[full] >= 0xf0 {
+            return (decode_codeword(&bytes[full..end]), full);
+        }
+    }
+    (decode_codeword(&bytes[prev..end]), prev)
+}
+
+/// Searcher of `char` predicates over WTF-8 strings.
+#[derive(Clone, Debug)]
+pub struct Wtf8CharSearcher<F> {
+    predicate: F,
+}
+
+unsafe impl<F: FnMut(char) -> bool> Searcher<Wtf8> for Wtf8CharSearcher<F> {
+    #[inline]
+    fn search(&mut self, span: Span<&Wtf8>) -> Option<Range<usize>> {
+        let (hay, range) = span.into_parts();
+        let mut pos = range.start;
+        while pos < range.end {
+            let (c, next) = decode_first(hay, pos, range.end);
+            if (self.predicate)(c) {
+                return Some(pos..next);
+            }
+            pos = next;
+        }
+        None
+    }
+
+    #[inline]
+    fn consume(&mut self, span: Span<&Wtf8>) -> Option<usize> {
+        let (hay, range) = span.into_parts();
+        if range.start == range.end {
+            return None;
+        }
+        let (c, next) = decode_first(hay, range.start, range.end);
+        if (self.predicate)(c) {
+            Some(next)
+        } else {
+            None
+        }
+    }
+}
+
+unsafe impl<F: FnMut(char) -> bool> ReverseSearcher<Wtf8> for Wtf8CharSearcher<F> {
+    #[inline]
+    fn rsearch(&mut self, span: Span<&Wtf8>) -> Option<Range<usize>> {
+        let (hay, range) = span.into_parts();
+        let mut pos = range.end;
+        while pos > range.start {
+            let (c, prev) = decode_last(hay, range.start, pos);
+            if (self.predicate)(c) {
+                return Some(prev..pos);
+            }
+            pos = prev;
+        }
+        None
+    }
+
+    #[inline]
+    fn rconsume(&mut self, span: Span<&Wtf8>) -> Option<usize> {
+        let (hay, range) = span.into_parts();
+        if range.start == range.end {
+            return None;
+        }
+        let (c, prev) = decode_last(hay, range.start, range.end);
+        if (self.predicate)(c) {
+            Some(prev)
+        } else {
+            None
+        }
+    }
+}
+
+unsafe impl<F: FnMut(char) -> bool> DoubleEndedSearcher<Wtf8> for Wtf8CharSearcher<F> {}
+
+impl<F: FnMut(char) -> bool> FusedSearcher<Wtf8> for Wtf8CharSearcher<F> {}
+
+impl<'h> Pattern<&'h Wtf8> for char {
+    type Searcher = Wtf8CharSearcher<LossyCharEq>;
+
+    #[inline]
+    fn into_searcher(self) -> Self::Searcher {
+        Wtf8CharSearcher { predicate: LossyCharEq(self) }
+    }
+}
+
+impl<'h, 'p> Pattern<&'h Wtf8> for &'p [char] {
+    type Searcher = Wtf8CharSearcher<MultiCharEq<'p>>;
+
+    #[inline]
+    fn into_searcher(self) -> Self::Searcher {
+        Wtf8CharSearcher { predicate: MultiCharEq(self) }
+    }
+}
+
+impl<'h, F: FnMut(char) -> bool> Pattern<&'h Wtf8> for F {
+    type Searcher = Wtf8CharSearcher<F>;
+
+    #[inline]
+    fn into_searcher(self) -> Self::Searcher {
+        Wtf8CharSearcher { predicate: self }
+    }
+}
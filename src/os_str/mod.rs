@@ -0,0 +1,147 @@
+use haystack::{Hay, Span};
+use std::ffi::OsStr;
+use std::ops::Range;
+
+// The codewords of an `OsStr` are determined from its encoded bytes. Every
+// valid UTF-8 sequence is a codeword by itself, and each maximal run of bytes
+// in between forms a single codeword. This keeps every codeword boundary next
+// to valid UTF-8 (or at either end), which is where `OsStr` may be split
+// without breaking the contract of `OsStr::from_encoded_bytes_unchecked`. In
+// particular, the WTF-8 encoding of a surrogate is never split on Windows.
+
+/// Returns the width of the valid UTF-8 sequence starting at `index`, if any.
+#[inline]
+fn char_width_at(bytes: &[u8], index: usize) -> Option<usize> {
+    let width = match *bytes.get(index)? {
+        0x00..=0x7f => return Some(1),
+        0xc2..=0xdf => 2,
+        0xe0..=0xef => 3,
+        0xf0..=0xf4 => 4,
+        _ => return None,
+    };
+    let sequence = bytes.get(index..(index + width))?;
+    if ::std::str::from_utf8(sequence).is_ok() {
+        Some(width)
+    } else {
+        None
+    }
+}
+
+/// Returns the width of the valid UTF-8 sequence ending at `index`, if any.
+#[inline]
+fn char_width_before(bytes: &[u8], index: usize) -> Option<usize> {
+    (1..=index.min(4)).find(|&width| char_width_at(bytes, index - width) == Some(width))
+}
+
+/// Checks whether `index` lies on a codeword boundary of the encoded bytes.
+#[inline]
+pub(crate) fn is_boundary(bytes: &[u8], index: usize) -> bool {
+    index == 0
+        || index == bytes.len()
+        || char_width_at(bytes, index).is_some()
+        || char_width_before(bytes, index).is_some()
+}
+
+impl Hay for OsStr {
+    type Index = usize;
+
+    #[inline]
+    fn empty<'a>() -> &'a Self {
+        OsStr::new("")
+    }
+
+    #[inline]
+    fn start_index(&self) -> usize {
+        0
+    }
+
+    #[inline]
+    fn end_index(&self) -> usize {
+        self.len()
+    }
+
+    #[inline]
+    unsafe fn slice_unchecked(&self, range: Range<usize>) -> &Self {
+        OsStr::from_encoded_bytes_unchecked(self.as_encoded_bytes().get_unchecked(range))
+    }
+
+    #[inline]
+    unsafe fn next_index(&self, index: usize) -> usize {
+        let bytes = self.as_encoded_bytes();
+        match char_width_at(bytes, index) {
+            Some(width) => index + width,
+            None => ((index + 1)..bytes.len())
+                .find(|&i| is_boundary(bytes, i))
+                .unwrap_or(bytes.len()),
+        }
+    }
+
+    #[inline]
+    unsafe fn prev_index(&self, index: usize) -> usize {
+        let bytes = self.as_encoded_bytes();
+        match char_width_before(bytes, index) {
+            Some(width) => index - width,
+            None => (1..index).rev().find(|&i| is_boundary(bytes, i)).unwrap_or(0),
+        }
+    }
+}
+
+impl<'h> Span<&'h OsStr> {
+    /// Reinterprets the OS string span as a span of its encoded bytes.
+    #[inline]
+    pub fn as_bytes(self) -> Span<&'h [u8]> {
+        let (haystack, range) = self.into_parts();
+        unsafe {
+            Span::from_parts(haystack.as_encoded_bytes(), range)
+        }
+    }
+}
+
+#[test]
+fn test_os_str_next_prev_index() {
+    let string = OsStr::new("a\u{e9} \u{1f4a9}");
+    unsafe {
+        for w in [0, 1, 3, 4, 8].windows(2) {
+            let i = w[0];
+            let j = w[1];
+            assert_eq!(string.next_index(i), j);
+            assert_eq!(string.prev_index(j), i);
+        }
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn test_os_str_next_prev_index_invalid() {
+    use std::os::unix::ffi::OsStrExt;
+
+    let string = OsStr::from_bytes(b"\x80a\xc3\xa9\xe2\x82\xc3\xa9\xf0\x9f\x92\xa9\xa9");
+    unsafe {
+        for w in [0, 1, 2, 4, 6, 8, 12, 13].windows(2) {
+            let i = w[0];
+            let j = w[1];
+            assert_eq!(string.next_index(i), j);
+            assert_eq!(string.prev_index(j), i);
+        }
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn test_os_str_next_prev_index_surrogates() {
+    use std::os::unix::ffi::OsStrExt;
+
+    // WTF-8 surrogates and stray bytes are kept together until the next
+    // valid UTF-8 sequence.
+    let string = OsStr::from_bytes(b"a\xed\xa0\x80\xed\xb0\x80b\xff\xed\xa0\x80");
+    unsafe {
+        for w in [0, 1, 7, 8, 12].windows(2) {
+            let i = w[0];
+            let j = w[1];
+            assert_eq!(string.next_index(i), j);
+            assert_eq!(string.prev_index(j), i);
+        }
+    }
+}
+
+mod os_str_pat;
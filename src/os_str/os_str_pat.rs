@@ -0,0 +1,245 @@
+use pattern::*;
+use haystack::{Haystack, Span};
+use slices::slice::SliceSearcher;
+use memchr::{memchr, memrchr};
+use std::ffi::{OsStr, OsString};
+use std::ops::Range;
+
+use super::is_boundary;
+
+// A byte-wise match of a well-formed needle may still start or end in the
+// middle of a codeword of the haystack, e.g. a needle starting with a
+// continuation byte on Unix. Such matches are rejected and searching resumes
+// one byte after the rejected start.
+//
+// On Windows, the encoded bytes are WTF-8, which always joins a high surrogate
+// followed by a low surrogate into a single supplementary code point. This
+// means a needle ending with an unpaired high surrogate (or starting with an
+// unpaired low surrogate) never matches half of a surrogate pair in the
+// haystack, since the two halves cannot be sliced apart in an `OsStr`.
+
+#[derive(Debug, Clone)]
+pub struct OsStrSearcher<'p> {
+    inner: SliceSearcher<'p, u8>,
+}
+
+#[inline]
+fn is_boundary_range(bytes: &[u8], range: &Range<usize>) -> bool {
+    is_boundary(bytes, range.start) && is_boundary(bytes, range.end)
+}
+
+unsafe impl<'p> Searcher<OsStr> for OsStrSearcher<'p> {
+    #[inline]
+    fn search(&mut self, span: Span<&OsStr>) -> Option<Range<usize>> {
+        if let SliceSearcher::Empty(s) = &mut self.inner {
+            return s.search(span);
+        }
+        let (hay, range) = span.into_parts();
+        let bytes = hay.as_encoded_bytes();
+        let mut start = range.start;
+        loop {
+            let span = unsafe { Span::from_parts(bytes, start..range.end) };
+            let subrange = self.inner.search(span)?;
+            if is_boundary_range(bytes, &subrange) {
+                return Some(subrange);
+            }
+            start = subrange.start + 1;
+        }
+    }
+
+    #[inline]
+    fn consume(&mut self, span: Span<&OsStr>) -> Option<usize> {
+        let (hay, range) = span.into_parts();
+        let bytes = hay.as_encoded_bytes();
+        let end = self.inner.consume(unsafe { Span::from_parts(bytes, range) })?;
+        if is_boundary(bytes, end) {
+            Some(end)
+        } else {
+            None
+        }
+    }
+}
+
+unsafe impl<'p> ReverseSearcher<OsStr> for OsStrSearcher<'p> {
+    #[inline]
+    fn rsearch(&mut self, span: Span<&OsStr>) -> Option<Range<usize>> {
+        if let SliceSearcher::Empty(s) = &mut self.inner {
+            return s.rsearch(span);
+        }
+        let (hay, range) = span.into_parts();
+        let bytes = hay.as_encoded_bytes();
+        let mut end = range.end;
+        loop {
+            let span = unsafe { Span::from_parts(bytes, range.start..end) };
+            let subrange = self.inner.rsearch(span)?;
+            if is_boundary_range(bytes, &subrange) {
+                return Some(subrange);
+            }
+            end = subrange.end - 1;
+        }
+    }
+
+    #[inline]
+    fn rconsume(&mut self, span: Span<&OsStr>) -> Option<usize> {
+        let (hay, range) = span.into_parts();
+        let bytes = hay.as_encoded_bytes();
+        let start = self.inner.rconsume(unsafe { Span::from_parts(bytes, range) })?;
+        if is_boundary(bytes, start) {
+            Some(start)
+        } else {
+            None
+        }
+    }
+}
+
+macro_rules! impl_pattern {
+    (<[$($gen:tt)*]> for $pat:ty) => {
+        impl<$($gen)*, H: Haystack<Hay = OsStr>> Pattern<H> for $pat {
+            type Searcher = OsStrSearcher<'p>;
+
+            #[inline]
+            fn into_searcher(self) -> Self::Searcher {
+                OsStrSearcher {
+                    inner: SliceSearcher::new_searcher(self.as_encoded_bytes()),
+                }
+            }
+
+            #[inline]
+            fn into_consumer(self) -> Self::Searcher {
+                OsStrSearcher {
+                    inner: SliceSearcher::new_consumer(self.as_encoded_bytes()),
+                }
+            }
+        }
+    }
+}
+
+impl_pattern!(<['p]> for &'p OsStr);
+impl_pattern!(<['p]> for &'p OsString);
+impl_pattern!(<['q, 'p]> for &'q &'p OsStr);
+
+// A `str` or `char` needle never contains a surrogate, and WTF-8 encodes every
+// supplementary code point (including a surrogate pair joined in the hay) with
+// the same four bytes as UTF-8. Hence matching the UTF-8 bytes of the needle,
+// rejecting matches which split a codeword, is also correct on Windows.
+
+// `&str` and `char` are also patterns of every `Haystack<Hay = str>`, so these
+// impls are only for `&OsStr`, the one `OsStr` haystack.
+impl<'h, 'p> Pattern<&'h OsStr> for &'p str {
+    type Searcher = OsStrSearcher<'p>;
+
+    #[inline]
+    fn into_searcher(self) -> Self::Searcher {
+        OsStrSearcher {
+            inner: SliceSearcher::new_searcher(self.as_bytes()),
+        }
+    }
+
+    #[inline]
+    fn into_consumer(self) -> Self::Searcher {
+        OsStrSearcher {
+            inner: SliceSearcher::new_consumer(self.as_bytes()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct OsStrCharSearcher {
+    // safety invariant: `utf8_size` must be between 1 and 4
+    utf8_size: usize,
+    utf8_encoded: [u8; 4],
+}
+
+impl OsStrCharSearcher {
+    #[inline]
+    fn new(c: char) -> Self {
+        let mut utf8_encoded = [0u8; 4];
+        let utf8_size = c.encode_utf8(&mut utf8_encoded).len();
+        OsStrCharSearcher {
+            utf8_size,
+            utf8_encoded,
+        }
+    }
+
+    #[inline]
+    fn as_bytes(&self) -> &[u8] {
+        &self.utf8_encoded[..self.utf8_size]
+    }
+}
+
+unsafe impl Searcher<OsStr> for OsStrCharSearcher {
+    #[inline]
+    fn search(&mut self, span: Span<&OsStr>) -> Option<Range<usize>> {
+        let (hay, range) = span.into_parts();
+        let bytes = hay.as_encoded_bytes();
+        let needle = self.as_bytes();
+        let mut start = range.start;
+        loop {
+            let index = start + memchr(needle[0], &bytes[start..range.end])?;
+            let subrange = index..(index + needle.len());
+            if subrange.end <= range.end && bytes[subrange.clone()] == *needle && is_boundary_range(bytes, &subrange) {
+                return Some(subrange);
+            }
+            start = index + 1;
+        }
+    }
+
+    #[inline]
+    fn consume(&mut self, span: Span<&OsStr>) -> Option<usize> {
+        let (hay, range) = span.into_parts();
+        let bytes = hay.as_encoded_bytes();
+        let end = range.start + self.utf8_size;
+        if end <= range.end && bytes[range.start..end] == *self.as_bytes() && is_boundary(bytes, end) {
+            Some(end)
+        } else {
+            None
+        }
+    }
+}
+
+unsafe impl ReverseSearcher<OsStr> for OsStrCharSearcher {
+    #[inline]
+    fn rsearch(&mut self, span: Span<&OsStr>) -> Option<Range<usize>> {
+        let (hay, range) = span.into_parts();
+        let bytes = hay.as_encoded_bytes();
+        let needle = self.as_bytes();
+        let mut end = range.end;
+        loop {
+            let index = range.start + memrchr(needle[0], &bytes[range.start..end])?;
+            let subrange = index..(index + needle.len());
+            if subrange.end <= range.end && bytes[subrange.clone()] == *needle && is_boundary_range(bytes, &subrange) {
+                return Some(subrange);
+            }
+            end = index;
+        }
+    }
+
+    #[inline]
+    fn rconsume(&mut self, span: Span<&OsStr>) -> Option<usize> {
+        let (hay, range) = span.into_parts();
+        let bytes = hay.as_encoded_bytes();
+        if range.end - range.start < self.utf8_size {
+            return None;
+        }
+        let start = range.end - self.utf8_size;
+        if bytes[start..range.end] == *self.as_bytes() && is_boundary(bytes, start) {
+            Some(start)
+        } else {
+            None
+        }
+    }
+}
+
+unsafe impl DoubleEndedSearcher<OsStr> for OsStrCharSearcher {}
+
+impl FusedSearcher<OsStr> for OsStrCharSearcher {}
+
+impl<'h> Pattern<&'h OsStr> for char {
+    type Searcher = OsStrCharSearcher;
+
+    #[inline]
+    fn into_searcher(self) -> Self::Searcher {
+        OsStrCharSearcher::new(self)
+    }
+}
+
@@ -0,0 +1,117 @@
+use haystack::Hay;
+use std::ffi::OsStr;
+use std::ops::Range;
+use std::path::{Component, Path, is_separator};
+
+// The codewords of a `Path` are its components, each together with the
+// separators following it. A Windows prefix (e.g. `C:`) and the root directory
+// are codewords of their own. Indices are byte offsets into the encoded bytes,
+// so every codeword boundary is also a valid `OsStr` boundary.
+//
+// For example, `/usr//bin` is made of the codewords `/`, `usr//` and `bin`.
+
+#[inline]
+fn is_sep(b: u8) -> bool {
+    b.is_ascii() && is_separator(b as char)
+}
+
+#[inline]
+fn prefix_len(path: &Path) -> usize {
+    match path.components().next() {
+        Some(Component::Prefix(prefix)) => prefix.as_os_str().len(),
+        _ => 0,
+    }
+}
+
+#[inline]
+fn bytes_of(path: &Path) -> &[u8] {
+    path.as_os_str().as_encoded_bytes()
+}
+
+/// Returns the component of the codeword at `range`.
+#[inline]
+pub(crate) fn component_at(path: &Path, range: Range<usize>) -> Option<Component<'_>> {
+    unsafe { path.slice_unchecked(range) }.components().next()
+}
+
+impl Hay for Path {
+    type Index = usize;
+
+    #[inline]
+    fn empty<'a>() -> &'a Self {
+        Path::new("")
+    }
+
+    #[inline]
+    fn start_index(&self) -> usize {
+        0
+    }
+
+    #[inline]
+    fn end_index(&self) -> usize {
+        self.as_os_str().len()
+    }
+
+    #[inline]
+    unsafe fn slice_unchecked(&self, range: Range<usize>) -> &Self {
+        let bytes = bytes_of(self).get_unchecked(range);
+        Path::new(OsStr::from_encoded_bytes_unchecked(bytes))
+    }
+
+    #[inline]
+    unsafe fn next_index(&self, index: usize) -> usize {
+        if index == 0 {
+            let prefix = prefix_len(self);
+            if prefix != 0 {
+                return prefix;
+            }
+        }
+        let bytes = bytes_of(self);
+        let mut end = index;
+        while end < bytes.len() && !is_sep(bytes[end]) {
+            end += 1;
+        }
+        while end < bytes.len() && is_sep(bytes[end]) {
+            end += 1;
+        }
+        end
+    }
+
+    #[inline]
+    unsafe fn prev_index(&self, index: usize) -> usize {
+        let prefix = prefix_len(self);
+        if index == prefix {
+            return 0;
+        }
+        let bytes = bytes_of(self);
+        let mut start = index;
+        while start > prefix && is_sep(bytes[start - 1]) {
+            start -= 1;
+        }
+        while start > prefix && !is_sep(bytes[start - 1]) {
+            start -= 1;
+        }
+        start
+    }
+}
+
+#[test]
+fn test_path_next_prev_index() {
+    let path = Path::new("/usr//bin/./ls");
+    unsafe {
+        for w in [0, 1, 6, 10, 12, 14].windows(2) {
+            let i = w[0];
+            let j = w[1];
+            assert_eq!(path.next_index(i), j);
+            assert_eq!(path.prev_index(j), i);
+        }
+    }
+    let path = Path::new("a/b/");
+    unsafe {
+        assert_eq!(path.next_index(0), 2);
+        assert_eq!(path.next_index(2), 4);
+        assert_eq!(path.prev_index(4), 2);
+    }
+}
+
+mod path_pat;
@@ -0,0 +1,168 @@
+use pattern::*;
+use haystack::{Hay, Span};
+use std::ops::Range;
+use std::path::{Component, Path, PathBuf};
+
+use super::component_at;
+
+// Matching is done component by component, with the same normalization as
+// `Path::components()`: repeated separators are ignored, and so are `.`
+// components anywhere except at the start of the path.
+
+/// Matches the components of `needle` at the codeword boundary `start`,
+/// returning the end of the last matched codeword.
+fn match_at<'p, I>(hay: &Path, start: usize, end: usize, needle: I) -> Option<usize>
+where
+    I: IntoIterator<Item = Component<'p>>,
+{
+    let mut pos = start;
+    for expected in needle {
+        loop {
+            if pos == end {
+                return None;
+            }
+            let next = unsafe { hay.next_index(pos) };
+            let found = component_at(hay, pos..next);
+            if found == Some(Component::CurDir) && pos != 0 && expected != Component::CurDir {
+                pos = next;
+                continue;
+            }
+            if found != Some(expected) {
+                return None;
+            }
+            pos = next;
+            break;
+        }
+    }
+    Some(pos)
+}
+
+/// Needles which can be matched against the codewords of a path.
+pub trait PathNeedle {
+    /// Matches the needle at the codeword boundary `start`, within `..end`.
+    fn match_at(&self, hay: &Path, start: usize, end: usize) -> Option<usize>;
+}
+
+impl<'p> PathNeedle for &'p Path {
+    #[inline]
+    fn match_at(&self, hay: &Path, start: usize, end: usize) -> Option<usize> {
+        match_at(hay, start, end, self.components())
+    }
+}
+
+impl<'p> PathNeedle for Component<'p> {
+    #[inline]
+    fn match_at(&self, hay: &Path, start: usize, end: usize) -> Option<usize> {
+        match_at(hay, start, end, Some(*self))
+    }
+}
+
+/// Searcher of path and component patterns over paths.
+#[derive(Debug, Clone)]
+pub struct PathSearcher<N> {
+    needle: N,
+    empty: Option<EmptySearcher>,
+}
+
+impl<N> PathSearcher<N> {
+    #[inline]
+    fn new(needle: N, is_empty: bool) -> Self {
+        PathSearcher {
+            needle,
+            empty: if is_empty { Some(EmptySearcher::default()) } else { None },
+        }
+    }
+}
+
+unsafe impl<N: PathNeedle> Searcher<Path> for PathSearcher<N> {
+    #[inline]
+    fn search(&mut self, span: Span<&Path>) -> Option<Range<usize>> {
+        if let Some(empty) = &mut self.empty {
+            return empty.search(span);
+        }
+        let (hay, range) = span.into_parts();
+        let mut start = range.start;
+        while start != range.end {
+            if let Some(end) = self.needle.match_at(hay, start, range.end) {
+                return Some(start..end);
+            }
+            start = unsafe { hay.next_index(start) };
+        }
+        None
+    }
+
+    #[inline]
+    fn consume(&mut self, span: Span<&Path>) -> Option<usize> {
+        let (hay, range) = span.into_parts();
+        if self.empty.is_some() {
+            return Some(range.start);
+        }
+        self.needle.match_at(hay, range.start, range.end)
+    }
+}
+
+unsafe impl<N: PathNeedle> ReverseSearcher<Path> for PathSearcher<N> {
+    #[inline]
+    fn rsearch(&mut self, span: Span<&Path>) -> Option<Range<usize>> {
+        if let Some(empty) = &mut self.empty {
+            return empty.rsearch(span);
+        }
+        let (hay, range) = span.into_parts();
+        let mut start = range.end;
+        while start != range.start {
+            start = unsafe { hay.prev_index(start) };
+            if let Some(end) = self.needle.match_at(hay, start, range.end) {
+                return Some(start..end);
+            }
+        }
+        None
+    }
+
+    #[inline]
+    fn rconsume(&mut self, span: Span<&Path>) -> Option<usize> {
+        let (hay, range) = span.into_parts();
+        if self.empty.is_some() {
+            return Some(range.end);
+        }
+        let mut start = range.end;
+        while start != range.start {
+            start = unsafe { hay.prev_index(start) };
+            if self.needle.match_at(hay, start, range.end) == Some(range.end) {
+                return Some(start);
+            }
+        }
+        None
+    }
+}
+
+// A single component can never overlap another match.
+unsafe impl<'p> DoubleEndedSearcher<Path> for PathSearcher<Component<'p>> {}
+
+// FIXME cannot impl `Pattern<(_: Haystack<Hay = Path>)>` due to RFC 1672 being postponed.
+// (need to wait for chalk)
+impl<'h, 'p> Pattern<&'h Path> for &'p Path {
+    type Searcher = PathSearcher<&'p Path>;
+
+    #[inline]
+    fn into_searcher(self) -> Self::Searcher {
+        PathSearcher::new(self, self.components().next().is_none())
+    }
+}
+
+impl<'h, 'p> Pattern<&'h Path> for &'p PathBuf {
+    type Searcher = PathSearcher<&'p Path>;
+
+    #[inline]
+    fn into_searcher(self) -> Self::Searcher {
+        Pattern::<&'h Path>::into_searcher(self.as_path())
+    }
+}
+
+impl<'h, 'p> Pattern<&'h Path> for Component<'p> {
+    type Searcher = PathSearcher<Component<'p>>;
+
+    #[inline]
+    fn into_searcher(self) -> Self::Searcher {
+        PathSearcher::new(self, false)
+    }
+}
@@ -56,7 +56,7 @@ use std::ops::Range;
 ///     }
 /// }
 ///
-/// impl<H: Haystack<Target = [u8]>> pattern_3::Pattern<H> for Aaaa {
+/// impl<H: Haystack<Hay = [u8]>> pattern_3::Pattern<H> for Aaaa {
 ///     type Searcher = Self;
 ///     fn into_searcher(self) -> Self { self }
 /// }
@@ -107,6 +107,11 @@ pub unsafe trait Searcher<A: Hay + ?Sized> {
     ///
     /// If the pattern is not found, this method should return `None`.
     ///
+    /// Searching again after `None` has been returned is allowed and must not
+    /// cause undefined behavior, but the result is unspecified: a stateful
+    /// searcher (such as the searcher of an empty pattern) may still report a
+    /// match. Searchers implementing [`FusedSearcher`] guarantee `None`.
+    ///
     /// # Examples
     ///
     /// Search for the locations of a substring inside a string, using the
@@ -246,6 +251,10 @@ pub unsafe trait ReverseSearcher<A: Hay + ?Sized>: Searcher<A> {
     ///
     /// If the pattern is not found, this method should return `None`.
     ///
+    /// As with [`.search()`](Searcher::search), the result of searching again
+    /// after `None` is unspecified unless the searcher implements
+    /// [`FusedSearcher`].
+    ///
     /// # Examples
     ///
     /// Search for the locations of a substring inside a string, using the
@@ -427,6 +436,195 @@ pub unsafe trait ReverseSearcher<A: Hay + ?Sized>: Searcher<A> {
 /// ```
 pub unsafe trait DoubleEndedSearcher<A: Hay + ?Sized>: ReverseSearcher<A> {}
 
+/// A searcher which keeps returning `None` once exhausted.
+///
+/// After [`.search()`](Searcher::search) returns `None` for a span, every
+/// further call to `.search()` with that span or any span inside it must also
+/// return `None`. If the searcher is a [`ReverseSearcher`], the same holds for
+/// [`.rsearch()`](ReverseSearcher::rsearch). The two directions are
+/// independent: exhausting one end says nothing about the other.
+///
+/// Algorithms may then loop on a searcher without tracking exhaustion
+/// themselves. Use [`Fuse`] to obtain this guarantee for any searcher.
+pub trait FusedSearcher<A: Hay + ?Sized>: Searcher<A> {}
+
+/// Adapter which makes a searcher fused.
+///
+/// `Fuse` can wrap either a searcher or a pattern. As a pattern, it produces
+/// the fused searcher of the wrapped pattern.
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate pattern_3;
+/// use pattern_3::{Fuse, Pattern, Searcher, Span};
+///
+/// let mut searcher = Pattern::<&str>::into_searcher("");
+/// let span = Span::from("");
+/// assert_eq!(searcher.search(span.clone()), Some(0..0));
+/// assert_eq!(searcher.search(span.clone()), None);
+///
+/// let mut fused = Pattern::<&str>::into_searcher(Fuse::new("x"));
+/// assert_eq!(fused.search(Span::from("a")), None);
+/// assert_eq!(fused.search(Span::from("x")), None);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Fuse<S> {
+    inner: S,
+    front_done: bool,
+    back_done: bool,
+}
+
+impl<S> Fuse<S> {
+    /// Wraps a searcher or pattern.
+    #[inline]
+    pub fn new(inner: S) -> Self {
+        Fuse {
+            inner,
+            front_done: false,
+            back_done: false,
+        }
+    }
+
+    /// Returns the wrapped searcher or pattern.
+    #[inline]
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+unsafe impl<A: Hay + ?Sized, S: Searcher<A>> Searcher<A> for Fuse<S> {
+    #[inline]
+    fn search(&mut self, span: Span<&A>) -> Option<Range<A::Index>> {
+        if self.front_done {
+            return None;
+        }
+        let found = self.inner.search(span);
+        self.front_done = found.is_none();
+        found
+    }
+
+    #[inline]
+    fn consume(&mut self, span: Span<&A>) -> Option<A::Index> {
+        self.inner.consume(span)
+    }
+
+    #[inline]
+    fn trim_start(&mut self, hay: &A) -> A::Index {
+        self.inner.trim_start(hay)
+    }
+}
+
+unsafe impl<A: Hay + ?Sized, S: ReverseSearcher<A>> ReverseSearcher<A> for Fuse<S> {
+    #[inline]
+    fn rsearch(&mut self, span: Span<&A>) -> Option<Range<A::Index>> {
+        if self.back_done {
+            return None;
+        }
+        let found = self.inner.rsearch(span);
+        self.back_done = found.is_none();
+        found
+    }
+
+    #[inline]
+    fn rconsume(&mut self, span: Span<&A>) -> Option<A::Index> {
+        self.inner.rconsume(span)
+    }
+
+    #[inline]
+    fn trim_end(&mut self, hay: &A) -> A::Index {
+        self.inner.trim_end(hay)
+    }
+}
+
+unsafe impl<A: Hay + ?Sized, S: DoubleEndedSearcher<A>> DoubleEndedSearcher<A> for Fuse<S> {}
+
+impl<A: Hay + ?Sized, S: Searcher<A>> FusedSearcher<A> for Fuse<S> {}
+
+impl<H: Haystack, P: Pattern<H>> Pattern<H> for Fuse<P> {
+    type Searcher = Fuse<P::Searcher>;
+
+    #[inline]
+    fn into_searcher(self) -> Self::Searcher {
+        Fuse::new(self.inner.into_searcher())
+    }
+
+    #[inline]
+    fn into_consumer(self) -> Self::Searcher {
+        Fuse::new(self.inner.into_consumer())
+    }
+}
+
+/// A predicate-style pattern with its result inverted.
+///
+/// `Not(f)` matches every element (or `char`) which `f` rejects. It is itself
+/// a predicate, so it can be used wherever `f` can. Patterns which are
+/// implemented by a specialized searcher, like
+/// [`AsciiDigit`](::AsciiDigit), keep using it when negated.
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate pattern_3;
+/// use pattern_3::pattern::not;
+/// use pattern_3::ext::{trim_start, find};
+///
+/// assert_eq!(trim_start("--> x1", not(char::is_alphanumeric)), "x1");
+/// assert_eq!(find(&[0, 0, 3, 0][..], not(|x: &i32| *x == 0)), Some(2));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Not<P>(pub P);
+
+/// Inverts a predicate-style pattern. See [`Not`] for details.
+#[inline]
+pub fn not<P>(pattern: P) -> Not<P> {
+    Not(pattern)
+}
+
+impl<F: FnMut(char) -> bool> FnOnce<(char,)> for Not<F> {
+    type Output = bool;
+    #[inline]
+    extern "rust-call" fn call_once(mut self, args: (char,)) -> bool {
+        self.call_mut(args)
+    }
+}
+
+impl<F: FnMut(char) -> bool> FnMut<(char,)> for Not<F> {
+    #[inline]
+    extern "rust-call" fn call_mut(&mut self, (c,): (char,)) -> bool {
+        !(self.0)(c)
+    }
+}
+
+impl<F: Fn(char) -> bool> Fn<(char,)> for Not<F> {
+    #[inline]
+    extern "rust-call" fn call(&self, (c,): (char,)) -> bool {
+        !(self.0)(c)
+    }
+}
+
+impl<'a, T, F: FnMut(&T) -> bool> FnOnce<(&'a T,)> for Not<F> {
+    type Output = bool;
+    #[inline]
+    extern "rust-call" fn call_once(mut self, args: (&'a T,)) -> bool {
+        self.call_mut(args)
+    }
+}
+
+impl<'a, T, F: FnMut(&T) -> bool> FnMut<(&'a T,)> for Not<F> {
+    #[inline]
+    extern "rust-call" fn call_mut(&mut self, (x,): (&'a T,)) -> bool {
+        !(self.0)(x)
+    }
+}
+
+impl<'a, T, F: Fn(&T) -> bool> Fn<(&'a T,)> for Not<F> {
+    #[inline]
+    extern "rust-call" fn call(&self, (x,): (&'a T,)) -> bool {
+        !(self.0)(x)
+    }
+}
+
 /// A pattern, a type which can be converted into a searcher.
 ///
 /// When using search algorithms like [`split()`](::ext::split), users will
@@ -437,11 +635,9 @@ pub unsafe trait DoubleEndedSearcher<A: Hay + ?Sized>: ReverseSearcher<A> {}
 ///
 /// The relationship between `Searcher` and `Pattern` is similar to `Iterator`
 /// and `IntoIterator`.
-pub trait Pattern<H: Haystack>: Sized
-where H::Target: Hay // FIXME: RFC 2089 or 2289
-{
+pub trait Pattern<H: Haystack>: Sized {
     /// The searcher associated with this pattern.
-    type Searcher: Searcher<H::Target>;
+    type Searcher: Searcher<H::Hay>;
 
     /// Produces a searcher for this pattern.
     ///
@@ -544,3 +740,7 @@ unsafe impl<A: Hay + ?Sized> ReverseSearcher<A> for EmptySearcher {
 }
 
 unsafe impl<A: Hay + ?Sized> DoubleEndedSearcher<A> for EmptySearcher {}
+
+// An empty searcher only returns `None` once its span is empty and that end has
+// been reported, and every span inside an empty span is empty too.
+impl<A: Hay + ?Sized> FusedSearcher<A> for EmptySearcher {}
@@ -0,0 +1,96 @@
+//! Regular expressions from the `regex` crate as patterns.
+
+use pattern::{Pattern, Searcher};
+use haystack::{Haystack, Span};
+use byte_hay::EmptyMatches;
+use regex::{Regex, bytes};
+use std::ops::Range;
+
+macro_rules! regex_searcher {
+    ($(#[$attr:meta])* $name:ident, $regex:ty, $hay:ty) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone)]
+        pub struct $name<'p> {
+            regex: &'p $regex,
+            empty: EmptyMatches,
+        }
+
+        unsafe impl<'p> Searcher<$hay> for $name<'p> {
+            fn search(&mut self, span: Span<&$hay>) -> Option<Range<usize>> {
+                let (hay, range) = span.into_parts();
+                let (regex, text) = (self.regex, &hay[..range.end]);
+                self.empty.search(hay, range, |r| {
+                    let m = regex.find_at(text, r.start)?;
+                    Some(m.start()..m.end())
+                })
+            }
+
+            fn consume(&mut self, span: Span<&$hay>) -> Option<usize> {
+                let (hay, range) = span.into_parts();
+                // Matches are leftmost-first, so if any match starts at
+                // `range.start`, the first match found starts there too and
+                // is the one an anchored search would find.
+                let m = self.regex.find_at(&hay[..range.end], range.start)?;
+                if m.start() == range.start {
+                    Some(m.end())
+                } else {
+                    None
+                }
+            }
+        }
+
+        impl<'p, H: Haystack<Hay = $hay>> Pattern<H> for &'p $regex {
+            type Searcher = $name<'p>;
+
+            #[inline]
+            fn into_searcher(self) -> Self::Searcher {
+                $name {
+                    regex: self,
+                    empty: EmptyMatches::new(),
+                }
+            }
+        }
+    }
+}
+
+regex_searcher! {
+    /// Searcher of a `&Regex` in a string.
+    ///
+    /// Matches are found with `Regex::find_at`, so anchors and word boundaries
+    /// see the text before the searched span.
+    ///
+    /// Consuming, as in `starts_with` or `trim_start`, also uses
+    /// `Regex::find_at` and keeps the match only if it starts at the current
+    /// position, so it honors every option set through `RegexBuilder`. When
+    /// nothing matches there, it may scan ahead to the next match.
+    ///
+    /// Regular expressions cannot be run backwards, so this searcher is
+    /// forward-only: functions such as `rsplit` and `trim_end` are not
+    /// available.
+    ///
+    /// ```rust
+    /// extern crate pattern_3;
+    /// extern crate regex;
+    /// use pattern_3::ext::{split, trim_start, replace_with};
+    /// use regex::Regex;
+    ///
+    /// let sep = Regex::new(r"\s*[,;]\s*").unwrap();
+    /// assert_eq!(split("a , b;c ;  d", &sep).collect::<Vec<_>>(), vec!["a", "b", "c", "d"]);
+    ///
+    /// let digits = Regex::new(r"\d+").unwrap();
+    /// assert_eq!(trim_start("2024-01-15", &digits), "-01-15");
+    ///
+    /// let mut out = String::new();
+    /// replace_with("x1y22", &digits, |d| if d.len() > 1 { "#" } else { d }, |s| out.push_str(s));
+    /// assert_eq!(out, "x1y#");
+    /// ```
+    RegexSearcher, Regex, str
+}
+
+regex_searcher! {
+    /// Searcher of a `&regex::bytes::Regex` in a byte slice.
+    ///
+    /// This behaves like [`RegexSearcher`](struct.RegexSearcher.html), but
+    /// searches arbitrary bytes.
+    BytesRegexSearcher, bytes::Regex, [u8]
+}
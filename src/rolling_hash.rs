@@ -0,0 +1,170 @@
+//! Rolling hash over byte windows.
+//!
+//! This is the polynomial hash used by the Rabin–Karp algorithm. The hash of
+//! a window can be updated in constant time when bytes are appended at its end
+//! or removed from its start, which makes it useful for searching, for
+//! detecting near-duplicates, and for content-defined chunking.
+//!
+//! The hash of the bytes `b[0], …, b[n-1]` is `Σ b[i]·Bⁿ⁻¹⁻ⁱ mod 2³²`, for a
+//! fixed odd base `B`. Equal windows always have equal hashes, but windows
+//! with equal hashes must still be compared to confirm a match.
+//!
+//! # Examples
+//!
+//! ```rust
+//! extern crate pattern_3;
+//! use pattern_3::rolling_hash::{RollingHash, find};
+//!
+//! let mut hash = RollingHash::new();
+//! hash.push(b'x');
+//! hash.push(b'a');
+//! hash.push(b'b');
+//! hash.pop(b'x');
+//! assert_eq!(hash, RollingHash::of(b"ab"));
+//!
+//! assert_eq!(find(b"abracadabra", b"cad"), Some(4));
+//! ```
+
+use std::iter::FusedIterator;
+
+const BASE: u32 = 0x0100_0193;
+
+// Inverse of `BASE` modulo 2³², which exists since `BASE` is odd. Each Newton
+// iteration doubles the number of correct low bits.
+const BASE_INV: u32 = {
+    let mut x = BASE;
+    let mut i = 0;
+    while i < 5 {
+        x = x.wrapping_mul(2u32.wrapping_sub(BASE.wrapping_mul(x)));
+        i += 1;
+    }
+    x
+};
+
+/// The rolling hash of a window of bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RollingHash {
+    hash: u32,
+    // BASE to the power of the window length.
+    pow: u32,
+}
+
+impl Default for RollingHash {
+    #[inline]
+    fn default() -> Self {
+        RollingHash::new()
+    }
+}
+
+impl RollingHash {
+    /// Creates the hash of an empty window.
+    #[inline]
+    pub fn new() -> Self {
+        RollingHash { hash: 0, pow: 1 }
+    }
+
+    /// Computes the hash of a byte string.
+    #[inline]
+    pub fn of(bytes: &[u8]) -> Self {
+        let mut hash = RollingHash::new();
+        for &b in bytes {
+            hash.push(b);
+        }
+        hash
+    }
+
+    /// Appends a byte at the end of the window.
+    #[inline]
+    pub fn push(&mut self, byte: u8) {
+        self.hash = self.hash.wrapping_mul(BASE).wrapping_add(u32::from(byte));
+        self.pow = self.pow.wrapping_mul(BASE);
+    }
+
+    /// Removes the byte at the start of the window.
+    ///
+    /// `byte` must be the first byte of the window, otherwise the hash no
+    /// longer corresponds to any window.
+    #[inline]
+    pub fn pop(&mut self, byte: u8) {
+        self.pow = self.pow.wrapping_mul(BASE_INV);
+        self.hash = self.hash.wrapping_sub(u32::from(byte).wrapping_mul(self.pow));
+    }
+
+    /// Slides the window by one byte: removes `old` from its start and appends
+    /// `new` at its end.
+    #[inline]
+    pub fn roll(&mut self, old: u8, new: u8) {
+        self.hash = self.hash
+            .wrapping_sub(u32::from(old).wrapping_mul(self.pow.wrapping_mul(BASE_INV)))
+            .wrapping_mul(BASE)
+            .wrapping_add(u32::from(new));
+    }
+
+    /// Returns the hash value.
+    #[inline]
+    pub fn value(&self) -> u32 {
+        self.hash
+    }
+}
+
+/// Iterator over the hashes of every window of a fixed length, created by
+/// [`windows`].
+#[derive(Debug, Clone)]
+pub struct Windows<'h> {
+    hay: &'h [u8],
+    len: usize,
+    // Start of the next window.
+    index: usize,
+    hash: RollingHash,
+}
+
+impl<'h> Iterator for Windows<'h> {
+    type Item = (usize, RollingHash);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.index;
+        if index + self.len > self.hay.len() {
+            return None;
+        }
+        if index > 0 {
+            self.hash.roll(self.hay[index - 1], self.hay[index + self.len - 1]);
+        }
+        self.index += 1;
+        Some((index, self.hash))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.hay.len() + 1).saturating_sub(self.index + self.len);
+        (len, Some(len))
+    }
+}
+
+impl<'h> ExactSizeIterator for Windows<'h> {}
+
+impl<'h> FusedIterator for Windows<'h> {}
+
+/// Returns the start index and hash of every window of `len` bytes in `hay`.
+///
+/// ```rust
+/// extern crate pattern_3;
+/// use pattern_3::rolling_hash::{RollingHash, windows};
+///
+/// let hashes = windows(b"abab", 2).map(|(_, h)| h).collect::<Vec<_>>();
+/// assert_eq!(hashes, vec![RollingHash::of(b"ab"), RollingHash::of(b"ba"), RollingHash::of(b"ab")]);
+/// ```
+#[inline]
+pub fn windows(hay: &[u8], len: usize) -> Windows<'_> {
+    let hash = RollingHash::of(&hay[..len.min(hay.len())]);
+    Windows { hay, len, index: 0, hash }
+}
+
+/// Finds the first occurrence of `needle` in `hay` with the Rabin–Karp
+/// algorithm, comparing the window whenever the hashes agree.
+pub fn find(hay: &[u8], needle: &[u8]) -> Option<usize> {
+    let target = RollingHash::of(needle);
+    windows(hay, needle.len())
+        .find(|&(i, hash)| hash == target && &hay[i..(i + needle.len())] == needle)
+        .map(|(i, _)| i)
+}
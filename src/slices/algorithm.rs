@@ -0,0 +1,152 @@
+use pattern::*;
+use haystack::Haystack;
+
+use super::slice::SliceSearcher;
+
+/// Substring search algorithms for slice and string needles.
+///
+/// Every algorithm finds the same matches; they only differ in speed. See
+/// [`SearcherBuilder`] for how to choose one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Algorithm {
+    /// Let the searcher choose, as a plain `&[T]` or `&str` needle does.
+    /// Currently byte needles use SIMD where the CPU supports it, except
+    /// that BNDM is used for needles of 32 to 64 bytes of which at most half
+    /// are distinct. Without SIMD, BNDM is used for such needles from 8
+    /// bytes, and Boyer–Moore–Horspool for needles of at least 128 bytes.
+    /// Everything else uses Two-Way.
+    Auto,
+    /// The Two-Way algorithm: linear time and constant space.
+    TwoWay,
+    /// The Knuth–Morris–Pratt algorithm: linear time, with a table as long as
+    /// the needle. It does less work per item than Two-Way but never skips
+    /// items, so it only keeps up on short needles with many partial matches
+    /// in the haystack (see `benches/algorithms.rs`).
+    #[cfg(feature = "std")]
+    Kmp,
+    /// The Boyer–Moore–Horspool algorithm: skips ahead using a table of the
+    /// last occurrence of each byte in the needle. Fast for long needles over
+    /// large alphabets, but quadratic in the worst case. Needles of items
+    /// other than bytes use Two-Way instead.
+    Bmh,
+    /// The Backward Nondeterministic DAWG Matching algorithm: reads each
+    /// window backwards, tracking every place it could occur in the needle
+    /// with one bit per item, and skips past the items read once none is
+    /// left. Needles longer than 64 items, or of items other than bytes, use
+    /// Two-Way instead.
+    #[cfg(feature = "std")]
+    Bndm,
+    /// Compares many positions at once with SIMD instructions, detecting the
+    /// widest ones the CPU supports at runtime: AVX2 or SSE2 on x86-64 and
+    /// NEON on AArch64. Needles of items other than bytes, or searches on
+    /// other CPUs, use Two-Way instead.
+    Simd,
+}
+
+impl Default for Algorithm {
+    #[inline]
+    fn default() -> Self {
+        Algorithm::Auto
+    }
+}
+
+/// Builder of slice and string needles searched with a chosen algorithm.
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate pattern_3;
+/// use pattern_3::{Algorithm, SearcherBuilder};
+/// use pattern_3::ext::{find, rfind};
+///
+/// let kmp = SearcherBuilder::new().algorithm(Algorithm::Kmp);
+/// assert_eq!(find("aaaaab", kmp.needle("aab")), Some(3));
+/// assert_eq!(rfind(&b"aabaab"[..], kmp.needle(&b"aab"[..])), Some(3));
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SearcherBuilder {
+    algorithm: Algorithm,
+}
+
+impl SearcherBuilder {
+    /// Creates a builder choosing the algorithm automatically.
+    #[inline]
+    pub fn new() -> Self {
+        SearcherBuilder::default()
+    }
+
+    /// Sets the search algorithm.
+    #[inline]
+    pub fn algorithm(mut self, algorithm: Algorithm) -> Self {
+        self.algorithm = algorithm;
+        self
+    }
+
+    /// Creates a needle searched with the chosen algorithm.
+    #[inline]
+    pub fn needle<'p, N: ?Sized>(self, needle: &'p N) -> Needle<'p, N> {
+        Needle {
+            needle,
+            algorithm: self.algorithm,
+        }
+    }
+}
+
+/// A slice or string needle searched with a chosen algorithm.
+///
+/// Created by [`SearcherBuilder::needle`]. A `Needle<str>` is searched for in
+/// `str` haystacks and a `Needle<[T]>` in `[T]` haystacks.
+#[derive(Debug)]
+pub struct Needle<'p, N: ?Sized + 'p> {
+    needle: &'p N,
+    algorithm: Algorithm,
+}
+
+impl<'p, N: ?Sized + 'p> Clone for Needle<'p, N> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'p, N: ?Sized + 'p> Copy for Needle<'p, N> {}
+
+impl<'p, N: ?Sized + 'p> Needle<'p, N> {
+    /// Returns the chosen algorithm.
+    #[inline]
+    pub fn algorithm(&self) -> Algorithm {
+        self.algorithm
+    }
+}
+
+impl<'p, H: Haystack<Hay = str>> Pattern<H> for Needle<'p, str> {
+    type Searcher = SliceSearcher<'p, u8>;
+
+    #[inline]
+    fn into_searcher(self) -> Self::Searcher {
+        SliceSearcher::with_algorithm(self.needle.as_bytes(), self.algorithm)
+    }
+
+    #[inline]
+    fn into_consumer(self) -> Self::Searcher {
+        SliceSearcher::new_consumer(self.needle.as_bytes())
+    }
+}
+
+impl<'p, T, H> Pattern<H> for Needle<'p, [T]>
+where
+    T: PartialEq + 'p,
+    H: Haystack<Hay = [T]>,
+{
+    type Searcher = SliceSearcher<'p, T>;
+
+    #[inline]
+    fn into_searcher(self) -> Self::Searcher {
+        SliceSearcher::with_algorithm(self.needle, self.algorithm)
+    }
+
+    #[inline]
+    fn into_consumer(self) -> Self::Searcher {
+        SliceSearcher::new_consumer(self.needle)
+    }
+}
@@ -0,0 +1,675 @@
+use pattern::*;
+use haystack::{Haystack, Span};
+use byte_hay::{ByteHay, EmptyMatches};
+use std::ops::Range;
+
+/// A set of needles, matching whichever occurs first.
+///
+/// Among needles matching at the same position, the one listed first wins;
+/// see [`LongestOf`] for choosing the longest one instead. The needles are
+/// searched together with an Aho–Corasick automaton, built on the first
+/// search, so the whole set is found in a single pass over the haystack.
+///
+/// The index of the needle behind the last match is available through
+/// [`MatchedNeedle`](trait.MatchedNeedle.html), so
+/// [`match_ranges_with`](ext/fn.match_ranges_with.html) can report it
+/// without comparing the matched text again.
+///
+/// ```rust
+/// extern crate pattern_3;
+/// use pattern_3::AnyOf;
+/// use pattern_3::ext::{match_ranges_with, split};
+///
+/// let ops = AnyOf(&["<=", "<", "="]);
+/// let tokens = match_ranges_with("a<=b<c=d", ops).map(|(_, i, s)| (i, s)).collect::<Vec<_>>();
+/// assert_eq!(tokens, vec![(0, "<="), (1, "<"), (2, "=")]);
+/// assert_eq!(split("a<=b<c=d", ops).collect::<Vec<_>>(), vec!["a", "b", "c", "d"]);
+/// ```
+#[derive(Debug)]
+pub struct AnyOf<'p, N: ?Sized + 'p>(pub &'p [&'p N]);
+
+impl<'p, N: ?Sized + 'p> Clone for AnyOf<'p, N> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'p, N: ?Sized + 'p> Copy for AnyOf<'p, N> {}
+
+/// A set of needles, matching whichever occurs first and, among those
+/// starting there, the longest.
+///
+/// This is the POSIX "leftmost-longest" rule, so the order of the needles
+/// does not matter: unlike with [`AnyOf`], listing `"<"` before `"<="` will
+/// not split `<=` into two tokens. Needles of equal length matching at the
+/// same position are still resolved in favor of the one listed first.
+/// Searching from the back picks the longest needle ending at the rightmost
+/// position. The needles are searched with the same single-pass automaton as
+/// [`AnyOf`].
+///
+/// ```rust
+/// extern crate pattern_3;
+/// use pattern_3::{AnyOf, LongestOf};
+/// use pattern_3::ext::matches;
+///
+/// let ops = ["<", "=", "<="];
+/// assert_eq!(matches("a<=b", AnyOf(&ops)).collect::<Vec<_>>(), vec!["<", "="]);
+/// assert_eq!(matches("a<=b", LongestOf(&ops)).collect::<Vec<_>>(), vec!["<="]);
+/// ```
+#[derive(Debug)]
+pub struct LongestOf<'p, N: ?Sized + 'p>(pub &'p [&'p N]);
+
+impl<'p, N: ?Sized + 'p> Clone for LongestOf<'p, N> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'p, N: ?Sized + 'p> Copy for LongestOf<'p, N> {}
+
+/// A searcher which can tell which of several needles its last match was
+/// for.
+pub trait MatchedNeedle {
+    /// Returns the index of the needle matched by the last successful search.
+    ///
+    /// Panics if no search has succeeded yet.
+    fn matched_needle(&self) -> usize;
+}
+
+#[derive(Debug)]
+pub struct AnyOfSearcher<'p, N: ?Sized + 'p> {
+    needles: &'p [&'p N],
+    matched: Option<usize>,
+    // Whether the longest of the needles matching at a position wins rather
+    // than the first listed one.
+    longest: bool,
+    empty: EmptyMatches,
+    // Automata of the needles read forwards and backwards, built on first use.
+    #[cfg(feature = "std")]
+    forward: Option<Automaton>,
+    #[cfg(feature = "std")]
+    backward: Option<Automaton>,
+}
+
+impl<'p, N: ?Sized + 'p> Clone for AnyOfSearcher<'p, N> {
+    #[inline]
+    fn clone(&self) -> Self {
+        AnyOfSearcher {
+            #[cfg(feature = "std")]
+            forward: self.forward.clone(),
+            #[cfg(feature = "std")]
+            backward: self.backward.clone(),
+            ..*self
+        }
+    }
+}
+
+impl<'p, N: ?Sized + 'p> MatchedNeedle for AnyOfSearcher<'p, N> {
+    #[inline]
+    fn matched_needle(&self) -> usize {
+        self.matched.expect("no match has been found")
+    }
+}
+
+//------------------------------------------------------------------------------
+// Aho–Corasick automaton
+//------------------------------------------------------------------------------
+
+#[cfg(feature = "std")]
+const ROOT: usize = 0;
+
+// A trie node. Nodes store no elements: a node stands for the first `depth`
+// elements of needle `rep` (the last ones in a reversed automaton), so the
+// automaton only needs `PartialEq` on the elements.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone)]
+struct Node {
+    rep: usize,
+    depth: usize,
+    children: Vec<usize>,
+    fail: usize,
+    // The first listed needle spelled by this node.
+    needle: Option<usize>,
+    // The closest node on the failure chain spelling a needle, or `ROOT`.
+    dict: usize,
+}
+
+#[cfg(feature = "std")]
+#[derive(Debug, Clone)]
+struct Automaton {
+    nodes: Vec<Node>,
+    reversed: bool,
+}
+
+#[cfg(feature = "std")]
+impl Automaton {
+    fn new<T, N>(needles: &[&N], reversed: bool) -> Self
+    where
+        T: PartialEq,
+        N: AsRef<[T]> + ?Sized,
+    {
+        let root = Node { rep: 0, depth: 0, children: Vec::new(), fail: ROOT, needle: None, dict: ROOT };
+        let mut automaton = Automaton { nodes: vec![root], reversed };
+
+        for (i, needle) in needles.iter().enumerate() {
+            let len = needle.as_ref().len();
+            let mut node = ROOT;
+            for depth in 0..len {
+                let x = automaton.elem(needles, i, depth);
+                node = match automaton.child(needles, node, x) {
+                    Some(child) => child,
+                    None => {
+                        let child = automaton.nodes.len();
+                        automaton.nodes.push(Node {
+                            rep: i,
+                            depth: depth + 1,
+                            children: Vec::new(),
+                            fail: ROOT,
+                            needle: None,
+                            dict: ROOT,
+                        });
+                        automaton.nodes[node].children.push(child);
+                        child
+                    }
+                };
+            }
+            if automaton.nodes[node].needle.is_none() {
+                automaton.nodes[node].needle = Some(i);
+            }
+        }
+
+        // Nodes are created in the order of their parents, but not level by
+        // level, so compute the failure links in breadth-first order.
+        let mut queue = automaton.nodes[ROOT].children.clone();
+        let mut head = 0;
+        while head < queue.len() {
+            let node = queue[head];
+            head += 1;
+            for k in 0..automaton.nodes[node].children.len() {
+                let child = automaton.nodes[node].children[k];
+                let x = automaton.edge(needles, child);
+                let mut fail = automaton.nodes[node].fail;
+                let target = loop {
+                    if let Some(next) = automaton.child(needles, fail, x) {
+                        break next;
+                    }
+                    if fail == ROOT {
+                        break ROOT;
+                    }
+                    fail = automaton.nodes[fail].fail;
+                };
+                let dict = if automaton.nodes[target].needle.is_some() && target != ROOT {
+                    target
+                } else {
+                    automaton.nodes[target].dict
+                };
+                automaton.nodes[child].fail = target;
+                automaton.nodes[child].dict = dict;
+                queue.push(child);
+            }
+        }
+        automaton
+    }
+
+    // The `depth`-th element of needle `i` in the reading direction.
+    #[inline]
+    fn elem<'a, T, N>(&self, needles: &[&'a N], i: usize, depth: usize) -> &'a T
+    where
+        N: AsRef<[T]> + ?Sized,
+    {
+        let needle = needles[i].as_ref();
+        if self.reversed {
+            &needle[needle.len() - 1 - depth]
+        } else {
+            &needle[depth]
+        }
+    }
+
+    // The element on the edge leading into `node`.
+    #[inline]
+    fn edge<'a, T, N>(&self, needles: &[&'a N], node: usize) -> &'a T
+    where
+        N: AsRef<[T]> + ?Sized,
+    {
+        let node = &self.nodes[node];
+        self.elem(needles, node.rep, node.depth - 1)
+    }
+
+    #[inline]
+    fn child<T, N>(&self, needles: &[&N], node: usize, x: &T) -> Option<usize>
+    where
+        T: PartialEq,
+        N: AsRef<[T]> + ?Sized,
+    {
+        self.nodes[node].children.iter().cloned().find(|&c| self.edge(needles, c) == x)
+    }
+
+    #[inline]
+    fn next<T, N>(&self, needles: &[&N], mut state: usize, x: &T) -> usize
+    where
+        T: PartialEq,
+        N: AsRef<[T]> + ?Sized,
+    {
+        loop {
+            if let Some(next) = self.child(needles, state, x) {
+                return next;
+            }
+            if state == ROOT {
+                return ROOT;
+            }
+            state = self.nodes[state].fail;
+        }
+    }
+
+    #[inline]
+    fn has_empty(&self) -> bool {
+        self.nodes[ROOT].needle.is_some()
+    }
+
+    // Whether the needle `i` of length `len` beats the chosen one at the same
+    // position.
+    #[inline]
+    fn beats(longest: bool, (i, len): (usize, usize), (j, chosen_len): (usize, usize)) -> bool {
+        if longest { len > chosen_len } else { i < j }
+    }
+
+    // Orders matches by how far out they are in the reading direction, the
+    // first one being the smallest.
+    #[inline]
+    fn rank(&self, m: &Range<usize>) -> usize {
+        if self.reversed { usize::MAX - m.end } else { m.start }
+    }
+
+    /// Finds the first needle in `hay[range]` (the last one if the automaton
+    /// is reversed) whose outer end is a boundary, returning the needle and
+    /// its range. Empty needles are not considered.
+    fn find<T, N, B>(&self, needles: &[&N], hay: &[T], range: Range<usize>, longest: bool, is_boundary: &B) -> Option<(usize, Range<usize>)>
+    where
+        T: PartialEq,
+        N: AsRef<[T]> + ?Sized,
+        B: Fn(usize) -> bool,
+    {
+        let mut state = ROOT;
+        let mut chosen: Option<(usize, Range<usize>)> = None;
+        let len = range.end - range.start;
+        for k in 0..len {
+            // `pos` is the index of the scanned element.
+            let (pos, x) = if self.reversed {
+                (range.end - 1 - k, &hay[range.end - 1 - k])
+            } else {
+                (range.start + k, &hay[range.start + k])
+            };
+            state = self.next(needles, state, x);
+            let depth = self.nodes[state].depth;
+            // Every needle still in progress starts after the chosen one.
+            if let Some((_, ref m)) = chosen {
+                let done = if self.reversed { pos + depth < m.end } else { pos + 1 - depth > m.start };
+                if done {
+                    break;
+                }
+            }
+            let mut node = if self.nodes[state].needle.is_some() { state } else { self.nodes[state].dict };
+            while node != ROOT {
+                let i = self.nodes[node].needle.unwrap();
+                let n = self.nodes[node].depth;
+                let m = if self.reversed { pos..(pos + n) } else { (pos + 1 - n)..(pos + 1) };
+                // Shorter needles ending here start later, so only the
+                // longest one with a valid boundary can be chosen.
+                if chosen.as_ref().is_some_and(|c| self.rank(&m) > self.rank(&c.1)) {
+                    break;
+                }
+                if is_boundary(if self.reversed { m.end } else { m.start }) {
+                    let better = chosen.as_ref().is_none_or(|c| {
+                        self.rank(&m) < self.rank(&c.1) || Self::beats(longest, (i, n), (c.0, c.1.len()))
+                    });
+                    if better {
+                        chosen = Some((i, m));
+                    }
+                    break;
+                }
+                node = self.nodes[node].dict;
+            }
+        }
+        chosen
+    }
+
+    /// Finds the needle at the start of `hay[range]` (the end if the
+    /// automaton is reversed), returning the needle and its length.
+    fn find_anchored<T, N>(&self, needles: &[&N], hay: &[T], range: Range<usize>, longest: bool) -> Option<(usize, usize)>
+    where
+        T: PartialEq,
+        N: AsRef<[T]> + ?Sized,
+    {
+        let mut chosen = self.nodes[ROOT].needle.map(|i| (i, 0));
+        let mut node = ROOT;
+        let len = range.end - range.start;
+        for k in 0..len {
+            let x = if self.reversed { &hay[range.end - 1 - k] } else { &hay[range.start + k] };
+            node = match self.child(needles, node, x) {
+                Some(child) => child,
+                None => break,
+            };
+            if let Some(i) = self.nodes[node].needle {
+                let n = self.nodes[node].depth;
+                if chosen.is_none_or(|c| Self::beats(longest, (i, n), c)) {
+                    chosen = Some((i, n));
+                }
+            }
+        }
+        chosen
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'p, N: ?Sized + 'p> AnyOfSearcher<'p, N> {
+    #[inline]
+    fn automaton<T>(&mut self, reversed: bool) -> &Automaton
+    where
+        T: PartialEq,
+        N: AsRef<[T]>,
+    {
+        let needles = self.needles;
+        let slot = if reversed { &mut self.backward } else { &mut self.forward };
+        slot.get_or_insert_with(|| Automaton::new(needles, reversed))
+    }
+
+    fn search_in<T, B>(&mut self, hay: &[T], range: Range<usize>, is_boundary: B) -> Option<Range<usize>>
+    where
+        T: PartialEq,
+        N: AsRef<[T]>,
+        B: Fn(usize) -> bool,
+    {
+        let (needles, longest) = (self.needles, self.longest);
+        self.automaton::<T>(false);
+        let automaton = self.forward.as_ref().unwrap();
+        let mut empty = self.empty;
+        let mut matched = None;
+        let m = empty.search(hay, range, |r| {
+            let (i, m) = if automaton.has_empty() {
+                // Every boundary has a match, so it is the first one.
+                let start = (r.start..=r.end).find(|&i| is_boundary(i))?;
+                let (i, len) = automaton.find_anchored(needles, hay, start..r.end, longest)?;
+                (i, start..(start + len))
+            } else {
+                automaton.find(needles, hay, r, longest, &is_boundary)?
+            };
+            matched = Some(i);
+            Some(m)
+        });
+        self.empty = empty;
+        if m.is_some() {
+            self.matched = matched;
+        }
+        m
+    }
+
+    fn rsearch_in<T, B>(&mut self, hay: &[T], range: Range<usize>, is_boundary: B) -> Option<Range<usize>>
+    where
+        T: PartialEq,
+        N: AsRef<[T]>,
+        B: Fn(usize) -> bool,
+    {
+        let (needles, longest) = (self.needles, self.longest);
+        self.automaton::<T>(true);
+        let automaton = self.backward.as_ref().unwrap();
+        let mut empty = self.empty;
+        let mut matched = None;
+        let m = empty.rsearch(hay, range, |r| {
+            let (i, m) = if automaton.has_empty() {
+                let end = (r.start..=r.end).rev().find(|&i| is_boundary(i))?;
+                let (i, len) = automaton.find_anchored(needles, hay, r.start..end, longest)?;
+                (i, (end - len)..end)
+            } else {
+                automaton.find(needles, hay, r, longest, &is_boundary)?
+            };
+            matched = Some(i);
+            Some(m)
+        });
+        self.empty = empty;
+        if m.is_some() {
+            self.matched = matched;
+        }
+        m
+    }
+
+    fn consume_in<T>(&mut self, hay: &[T], range: Range<usize>) -> Option<usize>
+    where
+        T: PartialEq,
+        N: AsRef<[T]>,
+    {
+        let (needles, longest) = (self.needles, self.longest);
+        let (i, len) = self.automaton::<T>(false).find_anchored(needles, hay, range.clone(), longest)?;
+        self.matched = Some(i);
+        Some(range.start + len)
+    }
+
+    fn rconsume_in<T>(&mut self, hay: &[T], range: Range<usize>) -> Option<usize>
+    where
+        T: PartialEq,
+        N: AsRef<[T]>,
+    {
+        let (needles, longest) = (self.needles, self.longest);
+        let (i, len) = self.automaton::<T>(true).find_anchored(needles, hay, range.clone(), longest)?;
+        self.matched = Some(i);
+        Some(range.end - len)
+    }
+}
+
+//------------------------------------------------------------------------------
+// Naive search, without std
+//------------------------------------------------------------------------------
+
+#[cfg(not(feature = "std"))]
+impl<'p, N: ?Sized + 'p> AnyOfSearcher<'p, N> {
+    /// Chooses among the needles for which `matches` holds, returning the
+    /// chosen needle and its length.
+    #[inline]
+    fn choose<T, F>(&self, mut matches: F) -> Option<(usize, usize)>
+    where
+        N: AsRef<[T]>,
+        F: FnMut(&[T]) -> bool,
+    {
+        let mut chosen: Option<(usize, usize)> = None;
+        for (i, n) in self.needles.iter().enumerate() {
+            let n = n.as_ref();
+            if chosen.is_none_or(|(_, len)| n.len() > len) && matches(n) {
+                chosen = Some((i, n.len()));
+                if !self.longest {
+                    break;
+                }
+            }
+        }
+        chosen
+    }
+
+    /// Returns the chosen needle occurring at `hay[start..end]`'s start, and
+    /// its length.
+    #[inline]
+    fn needle_at<T>(&self, hay: &[T], start: usize, end: usize) -> Option<(usize, usize)>
+    where
+        T: PartialEq,
+        N: AsRef<[T]>,
+    {
+        let hay = &hay[start..end];
+        self.choose(|n| hay.starts_with(n))
+    }
+
+    /// Returns the chosen needle occurring at `hay[start..end]`'s end, and
+    /// its length.
+    #[inline]
+    fn needle_before<T>(&self, hay: &[T], start: usize, end: usize) -> Option<(usize, usize)>
+    where
+        T: PartialEq,
+        N: AsRef<[T]>,
+    {
+        let hay = &hay[start..end];
+        self.choose(|n| hay.ends_with(n))
+    }
+
+    fn search_in<T, B>(&mut self, hay: &[T], range: Range<usize>, is_boundary: B) -> Option<Range<usize>>
+    where
+        T: PartialEq,
+        N: AsRef<[T]>,
+        B: Fn(usize) -> bool,
+    {
+        let mut empty = self.empty;
+        let mut matched = None;
+        let m = empty.search(hay, range, |r| {
+            (r.start..=r.end).filter(|&start| is_boundary(start)).find_map(|start| {
+                let (i, len) = self.needle_at(hay, start, r.end)?;
+                matched = Some(i);
+                Some(start..(start + len))
+            })
+        });
+        self.empty = empty;
+        if m.is_some() {
+            self.matched = matched;
+        }
+        m
+    }
+
+    fn rsearch_in<T, B>(&mut self, hay: &[T], range: Range<usize>, is_boundary: B) -> Option<Range<usize>>
+    where
+        T: PartialEq,
+        N: AsRef<[T]>,
+        B: Fn(usize) -> bool,
+    {
+        let mut empty = self.empty;
+        let mut matched = None;
+        let m = empty.rsearch(hay, range, |r| {
+            (r.start..=r.end).rev().filter(|&end| is_boundary(end)).find_map(|end| {
+                let (i, len) = self.needle_before(hay, r.start, end)?;
+                matched = Some(i);
+                Some((end - len)..end)
+            })
+        });
+        self.empty = empty;
+        if m.is_some() {
+            self.matched = matched;
+        }
+        m
+    }
+
+    fn consume_in<T>(&mut self, hay: &[T], range: Range<usize>) -> Option<usize>
+    where
+        T: PartialEq,
+        N: AsRef<[T]>,
+    {
+        let (i, len) = self.needle_at(hay, range.start, range.end)?;
+        self.matched = Some(i);
+        Some(range.start + len)
+    }
+
+    fn rconsume_in<T>(&mut self, hay: &[T], range: Range<usize>) -> Option<usize>
+    where
+        T: PartialEq,
+        N: AsRef<[T]>,
+    {
+        let (i, len) = self.needle_before(hay, range.start, range.end)?;
+        self.matched = Some(i);
+        Some(range.end - len)
+    }
+}
+
+unsafe impl<'p, T: PartialEq + 'p> Searcher<[T]> for AnyOfSearcher<'p, [T]> {
+    #[inline]
+    fn search(&mut self, span: Span<&[T]>) -> Option<Range<usize>> {
+        let (hay, range) = span.into_parts();
+        self.search_in(hay, range, |_| true)
+    }
+
+    #[inline]
+    fn consume(&mut self, span: Span<&[T]>) -> Option<usize> {
+        let (hay, range) = span.into_parts();
+        self.consume_in(hay, range)
+    }
+}
+
+unsafe impl<'p, T: PartialEq + 'p> ReverseSearcher<[T]> for AnyOfSearcher<'p, [T]> {
+    #[inline]
+    fn rsearch(&mut self, span: Span<&[T]>) -> Option<Range<usize>> {
+        let (hay, range) = span.into_parts();
+        self.rsearch_in(hay, range, |_| true)
+    }
+
+    #[inline]
+    fn rconsume(&mut self, span: Span<&[T]>) -> Option<usize> {
+        let (hay, range) = span.into_parts();
+        self.rconsume_in(hay, range)
+    }
+}
+
+// Non-empty needles are valid UTF-8, so they can only match between
+// characters of a `str`; empty needles are checked against character
+// boundaries.
+unsafe impl<'p, T: ByteHay + ?Sized> Searcher<T> for AnyOfSearcher<'p, str> {
+    #[inline]
+    fn search(&mut self, span: Span<&T>) -> Option<Range<usize>> {
+        let (hay, range) = span.into_parts();
+        self.search_in(hay.as_bytes(), range, |i| hay.is_boundary(i))
+    }
+
+    #[inline]
+    fn consume(&mut self, span: Span<&T>) -> Option<usize> {
+        let (hay, range) = span.into_parts();
+        self.consume_in(hay.as_bytes(), range)
+    }
+}
+
+unsafe impl<'p, T: ByteHay + ?Sized> ReverseSearcher<T> for AnyOfSearcher<'p, str> {
+    #[inline]
+    fn rsearch(&mut self, span: Span<&T>) -> Option<Range<usize>> {
+        let (hay, range) = span.into_parts();
+        self.rsearch_in(hay.as_bytes(), range, |i| hay.is_boundary(i))
+    }
+
+    #[inline]
+    fn rconsume(&mut self, span: Span<&T>) -> Option<usize> {
+        let (hay, range) = span.into_parts();
+        self.rconsume_in(hay.as_bytes(), range)
+    }
+}
+
+impl<'p, N: ?Sized + 'p, H: Haystack> Pattern<H> for AnyOf<'p, N>
+where
+    AnyOfSearcher<'p, N>: Searcher<H::Hay>,
+{
+    type Searcher = AnyOfSearcher<'p, N>;
+
+    #[inline]
+    fn into_searcher(self) -> Self::Searcher {
+        AnyOfSearcher {
+            needles: self.0,
+            matched: None,
+            longest: false,
+            empty: EmptyMatches::new(),
+            #[cfg(feature = "std")]
+            forward: None,
+            #[cfg(feature = "std")]
+            backward: None,
+        }
+    }
+}
+
+impl<'p, N: ?Sized + 'p, H: Haystack> Pattern<H> for LongestOf<'p, N>
+where
+    AnyOfSearcher<'p, N>: Searcher<H::Hay>,
+{
+    type Searcher = AnyOfSearcher<'p, N>;
+
+    #[inline]
+    fn into_searcher(self) -> Self::Searcher {
+        AnyOfSearcher {
+            needles: self.0,
+            matched: None,
+            longest: true,
+            empty: EmptyMatches::new(),
+            #[cfg(feature = "std")]
+            forward: None,
+            #[cfg(feature = "std")]
+            backward: None,
+        }
+    }
+}
@@ -0,0 +1,144 @@
+use pattern::*;
+use haystack::Span;
+use std::ops::Range;
+#[cfg(feature = "std")]
+use std::alloc::Allocator;
+
+/// Matches sub-sequences of a float slice which are element-wise within
+/// `epsilon` of `needle`.
+///
+/// Two elements `a` and `b` are considered equal if `|a - b| <= epsilon`.
+/// `NaN` never matches anything.
+///
+/// As approximate equality is not transitive, the matches are found by
+/// comparing the needle at every position.
+///
+/// ```rust
+/// extern crate pattern_3;
+/// use pattern_3::Approx;
+/// use pattern_3::ext::find;
+///
+/// let signal = &[0.0, 0.49, 1.02, 0.51, 0.0][..];
+/// let template = &[0.5, 1.0, 0.5][..];
+/// assert_eq!(find(signal, Approx { needle: template, epsilon: 0.05 }), Some(1));
+/// assert_eq!(find(signal, Approx { needle: template, epsilon: 0.001 }), None);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Approx<'p, T: 'p> {
+    pub needle: &'p [T],
+    pub epsilon: T,
+}
+
+#[derive(Debug, Clone)]
+pub struct ApproxSearcher<'p, T: 'p> {
+    needle: &'p [T],
+    epsilon: T,
+    empty: EmptySearcher,
+}
+
+macro_rules! impl_float {
+    ($t:ty) => {
+        impl<'p> ApproxSearcher<'p, $t> {
+            /// Checks whether the needle occurs at `hay[index..(index + needle.len())]`.
+            #[inline]
+            fn is_match_at(&self, hay: &[$t], index: usize) -> bool {
+                let epsilon = self.epsilon;
+                hay[index..(index + self.needle.len())]
+                    .iter()
+                    .zip(self.needle)
+                    .all(|(a, b)| {
+                        let d = a - b;
+                        d <= epsilon && -d <= epsilon
+                    })
+            }
+        }
+
+        unsafe impl<'p> Searcher<[$t]> for ApproxSearcher<'p, $t> {
+            #[inline]
+            fn search(&mut self, span: Span<&[$t]>) -> Option<Range<usize>> {
+                let len = self.needle.len();
+                if len == 0 {
+                    return self.empty.search(span);
+                }
+                let (hay, range) = span.into_parts();
+                let mut index = range.start;
+                while index + len <= range.end {
+                    if self.is_match_at(hay, index) {
+                        return Some(index..(index + len));
+                    }
+                    index += 1;
+                }
+                None
+            }
+
+            #[inline]
+            fn consume(&mut self, span: Span<&[$t]>) -> Option<usize> {
+                let (hay, range) = span.into_parts();
+                let end = range.start + self.needle.len();
+                if end <= range.end && self.is_match_at(hay, range.start) {
+                    Some(end)
+                } else {
+                    None
+                }
+            }
+        }
+
+        unsafe impl<'p> ReverseSearcher<[$t]> for ApproxSearcher<'p, $t> {
+            #[inline]
+            fn rsearch(&mut self, span: Span<&[$t]>) -> Option<Range<usize>> {
+                let len = self.needle.len();
+                if len == 0 {
+                    return self.empty.rsearch(span);
+                }
+                let (hay, range) = span.into_parts();
+                let mut index = range.end;
+                while range.start + len <= index {
+                    let start = index - len;
+                    if self.is_match_at(hay, start) {
+                        return Some(start..index);
+                    }
+                    index -= 1;
+                }
+                None
+            }
+
+            #[inline]
+            fn rconsume(&mut self, span: Span<&[$t]>) -> Option<usize> {
+                let (hay, range) = span.into_parts();
+                let len = self.needle.len();
+                if range.start + len <= range.end && self.is_match_at(hay, range.end - len) {
+                    Some(range.end - len)
+                } else {
+                    None
+                }
+            }
+        }
+
+        impl_pattern!(<['p, 'h]> &'h [$t], $t);
+        impl_pattern!(<['p, 'h]> &'h mut [$t], $t);
+        #[cfg(feature = "std")]
+        impl_pattern!(<['p, A: Allocator + Clone]> Vec<$t, A>, $t);
+        #[cfg(feature = "std")]
+        impl_pattern!(<['p, A: Allocator + Clone]> Box<[$t], A>, $t);
+    }
+}
+
+macro_rules! impl_pattern {
+    (<[$($gen:tt)*]> $ty:ty, $t:ty) => {
+        impl<$($gen)*> Pattern<$ty> for Approx<'p, $t> {
+            type Searcher = ApproxSearcher<'p, $t>;
+
+            #[inline]
+            fn into_searcher(self) -> Self::Searcher {
+                ApproxSearcher {
+                    needle: self.needle,
+                    epsilon: self.epsilon,
+                    empty: EmptySearcher::default(),
+                }
+            }
+        }
+    }
+}
+
+impl_float!(f32);
+impl_float!(f64);
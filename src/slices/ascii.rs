@@ -0,0 +1,174 @@
+use pattern::*;
+use haystack::Span;
+use std::fmt;
+use std::ops::Range;
+#[cfg(feature = "std")]
+use std::alloc::Allocator;
+
+const WHITESPACE: u8 = 0;
+const DIGIT: u8 = 1;
+const ALPHANUMERIC: u8 = 2;
+
+const fn class_table(class: u8) -> [bool; 256] {
+    let mut table = [false; 256];
+    let mut i = 0;
+    while i < 256 {
+        let b = i as u8;
+        table[i] = match class {
+            WHITESPACE => b.is_ascii_whitespace(),
+            DIGIT => b.is_ascii_digit(),
+            _ => b.is_ascii_alphanumeric(),
+        };
+        i += 1;
+    }
+    table
+}
+
+static WHITESPACE_TABLE: [bool; 256] = class_table(WHITESPACE);
+static DIGIT_TABLE: [bool; 256] = class_table(DIGIT);
+static ALPHANUMERIC_TABLE: [bool; 256] = class_table(ALPHANUMERIC);
+
+/// Searcher for a class of bytes, backed by a lookup table.
+#[derive(Clone, Copy)]
+pub struct ByteClassSearcher {
+    name: &'static str,
+    table: &'static [bool; 256],
+    negated: bool,
+}
+
+impl fmt::Debug for ByteClassSearcher {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ByteClassSearcher")
+            .field("class", &self.name)
+            .field("negated", &self.negated)
+            .finish()
+    }
+}
+
+impl ByteClassSearcher {
+    #[inline]
+    fn contains(&self, b: u8) -> bool {
+        self.table[b as usize] != self.negated
+    }
+}
+
+unsafe impl Searcher<[u8]> for ByteClassSearcher {
+    #[inline]
+    fn search(&mut self, span: Span<&[u8]>) -> Option<Range<usize>> {
+        let (hay, range) = span.into_parts();
+        let start = range.start;
+        let pos = hay[range].iter().position(|&b| self.contains(b))?;
+        Some((start + pos)..(start + pos + 1))
+    }
+
+    #[inline]
+    fn consume(&mut self, span: Span<&[u8]>) -> Option<usize> {
+        let (hay, range) = span.into_parts();
+        if range.start < range.end && self.contains(hay[range.start]) {
+            Some(range.start + 1)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn trim_start(&mut self, hay: &[u8]) -> usize {
+        hay.iter().position(|&b| !self.contains(b)).unwrap_or(hay.len())
+    }
+}
+
+unsafe impl ReverseSearcher<[u8]> for ByteClassSearcher {
+    #[inline]
+    fn rsearch(&mut self, span: Span<&[u8]>) -> Option<Range<usize>> {
+        let (hay, range) = span.into_parts();
+        let start = range.start;
+        let pos = hay[range].iter().rposition(|&b| self.contains(b))?;
+        Some((start + pos)..(start + pos + 1))
+    }
+
+    #[inline]
+    fn rconsume(&mut self, span: Span<&[u8]>) -> Option<usize> {
+        let (hay, range) = span.into_parts();
+        if range.start < range.end && self.contains(hay[range.end - 1]) {
+            Some(range.end - 1)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn trim_end(&mut self, hay: &[u8]) -> usize {
+        hay.iter().rposition(|&b| !self.contains(b)).map_or(0, |p| p + 1)
+    }
+}
+
+unsafe impl DoubleEndedSearcher<[u8]> for ByteClassSearcher {}
+
+macro_rules! byte_class {
+    ($(#[$attr:meta])* $name:ident => $table:ident) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+        pub struct $name;
+
+        impl_pattern!($name => $table, <['h]> &'h [u8]);
+        impl_pattern!($name => $table, <['h]> &'h mut [u8]);
+        #[cfg(feature = "std")]
+        impl_pattern!($name => $table, <[A: Allocator + Clone]> Vec<u8, A>);
+        #[cfg(feature = "std")]
+        impl_pattern!($name => $table, <[A: Allocator + Clone]> Box<[u8], A>);
+    }
+}
+
+macro_rules! impl_pattern {
+    ($name:ident => $table:ident, <[$($gen:tt)*]> $ty:ty) => {
+        impl<$($gen)*> Pattern<$ty> for $name {
+            type Searcher = ByteClassSearcher;
+
+            #[inline]
+            fn into_searcher(self) -> Self::Searcher {
+                ByteClassSearcher {
+                    name: stringify!($name),
+                    table: &$table,
+                    negated: false,
+                }
+            }
+        }
+
+        impl<$($gen)*> Pattern<$ty> for Not<$name> {
+            type Searcher = ByteClassSearcher;
+
+            #[inline]
+            fn into_searcher(self) -> Self::Searcher {
+                ByteClassSearcher {
+                    name: stringify!($name),
+                    table: &$table,
+                    negated: true,
+                }
+            }
+        }
+    }
+}
+
+byte_class! {
+    /// Matches a single ASCII whitespace byte, as defined by
+    /// `u8::is_ascii_whitespace`.
+    ///
+    /// ```rust
+    /// extern crate pattern_3;
+    /// use pattern_3::AsciiWhitespaceByte;
+    /// use pattern_3::ext::trim;
+    ///
+    /// assert_eq!(trim(&b" \tkey = value\r\n"[..], AsciiWhitespaceByte), b"key = value");
+    /// ```
+    AsciiWhitespaceByte => WHITESPACE_TABLE
+}
+
+byte_class! {
+    /// Matches a single ASCII decimal digit byte.
+    AsciiDigit => DIGIT_TABLE
+}
+
+byte_class! {
+    /// Matches a single ASCII letter or decimal digit byte.
+    AsciiAlphanumeric => ALPHANUMERIC_TABLE
+}
@@ -0,0 +1,194 @@
+use pattern::*;
+use haystack::{Haystack, Span};
+use std::ops::Range;
+
+use super::slice::FastSkipByteset;
+
+/// A `&str` or `&[u8]` needle matched ignoring ASCII case.
+///
+/// Windows are compared with `eq_ignore_ascii_case`. Like
+/// [`ConstNeedle`](struct.ConstNeedle.html), the searcher skips a whole
+/// needle length whenever the last (or, when searching backwards, first) byte
+/// of a window occurs in the needle in neither case.
+///
+/// ```rust
+/// extern crate pattern_3;
+/// use pattern_3::AsciiCaseInsensitive;
+/// use pattern_3::ext::{find, matches};
+///
+/// let log = "warn: disk full\nERROR: write failed\nError: retry";
+/// assert_eq!(find(log, AsciiCaseInsensitive("error")), Some(16));
+/// assert_eq!(matches(log, AsciiCaseInsensitive("error")).collect::<Vec<_>>(), vec!["ERROR", "Error"]);
+/// assert_eq!(find(&b"Content-Length: 3"[..], AsciiCaseInsensitive(&b"content-length"[..])), Some(0));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct AsciiCaseInsensitive<N>(pub N);
+
+#[derive(Debug, Clone)]
+pub struct AsciiCaseInsensitiveSearcher<'p> {
+    needle: &'p [u8],
+    byteset: FastSkipByteset,
+    empty: EmptySearcher,
+}
+
+impl<'p> AsciiCaseInsensitiveSearcher<'p> {
+    #[inline]
+    fn new(needle: &'p [u8]) -> Self {
+        let byteset = needle.iter().fold(0, |a, b| {
+            a | 1 << (b.to_ascii_lowercase() & 63) | 1 << (b.to_ascii_uppercase() & 63)
+        });
+        Self {
+            needle,
+            byteset,
+            empty: EmptySearcher::default(),
+        }
+    }
+
+    #[inline]
+    fn byteset_contains(&self, b: u8) -> bool {
+        (self.byteset >> (b & 63)) & 1 != 0
+    }
+
+    /// Checks whether the needle occurs at `hay[index..(index + needle.len())]`.
+    ///
+    /// The caller must ensure `index + needle.len() <= hay.len()`.
+    #[inline]
+    unsafe fn is_match_at(&self, hay: &[u8], index: usize) -> bool {
+        hay.get_unchecked(index..(index + self.needle.len())).eq_ignore_ascii_case(self.needle)
+    }
+}
+
+unsafe impl<'p> Searcher<[u8]> for AsciiCaseInsensitiveSearcher<'p> {
+    #[inline]
+    fn search(&mut self, span: Span<&[u8]>) -> Option<Range<usize>> {
+        let n = self.needle.len();
+        if n == 0 {
+            return self.empty.search(span);
+        }
+        let (hay, range) = span.into_parts();
+        let mut index = range.start;
+        while index + n <= range.end {
+            unsafe {
+                if !self.byteset_contains(*hay.get_unchecked(index + n - 1)) {
+                    index += n;
+                    continue;
+                }
+                if self.is_match_at(hay, index) {
+                    return Some(index..(index + n));
+                }
+            }
+            index += 1;
+        }
+        None
+    }
+
+    #[inline]
+    fn consume(&mut self, span: Span<&[u8]>) -> Option<usize> {
+        let (hay, range) = span.into_parts();
+        let end = range.start + self.needle.len();
+        if end <= range.end && unsafe { self.is_match_at(hay, range.start) } {
+            Some(end)
+        } else {
+            None
+        }
+    }
+}
+
+unsafe impl<'p> ReverseSearcher<[u8]> for AsciiCaseInsensitiveSearcher<'p> {
+    #[inline]
+    fn rsearch(&mut self, span: Span<&[u8]>) -> Option<Range<usize>> {
+        let n = self.needle.len();
+        if n == 0 {
+            return self.empty.rsearch(span);
+        }
+        let (hay, range) = span.into_parts();
+        let mut index = range.end;
+        while range.start + n <= index {
+            let start = index - n;
+            unsafe {
+                if !self.byteset_contains(*hay.get_unchecked(start)) {
+                    index = start;
+                    continue;
+                }
+                if self.is_match_at(hay, start) {
+                    return Some(start..index);
+                }
+            }
+            index -= 1;
+        }
+        None
+    }
+
+    #[inline]
+    fn rconsume(&mut self, span: Span<&[u8]>) -> Option<usize> {
+        let (hay, range) = span.into_parts();
+        let n = self.needle.len();
+        if range.start + n > range.end {
+            return None;
+        }
+        let start = range.end - n;
+        if unsafe { self.is_match_at(hay, start) } {
+            Some(start)
+        } else {
+            None
+        }
+    }
+}
+
+// Only ASCII letters are compared loosely, so a match of a UTF-8 needle starts
+// and ends at character boundaries, while the empty needle steps over whole
+// characters through `str`'s own indices.
+unsafe impl<'p> Searcher<str> for AsciiCaseInsensitiveSearcher<'p> {
+    #[inline]
+    fn search(&mut self, span: Span<&str>) -> Option<Range<usize>> {
+        if self.needle.is_empty() {
+            return self.empty.search(span);
+        }
+        let (hay, range) = span.into_parts();
+        self.search(unsafe { Span::from_parts(hay.as_bytes(), range) })
+    }
+
+    #[inline]
+    fn consume(&mut self, span: Span<&str>) -> Option<usize> {
+        let (hay, range) = span.into_parts();
+        self.consume(unsafe { Span::from_parts(hay.as_bytes(), range) })
+    }
+}
+
+unsafe impl<'p> ReverseSearcher<str> for AsciiCaseInsensitiveSearcher<'p> {
+    #[inline]
+    fn rsearch(&mut self, span: Span<&str>) -> Option<Range<usize>> {
+        if self.needle.is_empty() {
+            return self.empty.rsearch(span);
+        }
+        let (hay, range) = span.into_parts();
+        self.rsearch(unsafe { Span::from_parts(hay.as_bytes(), range) })
+    }
+
+    #[inline]
+    fn rconsume(&mut self, span: Span<&str>) -> Option<usize> {
+        let (hay, range) = span.into_parts();
+        self.rconsume(unsafe { Span::from_parts(hay.as_bytes(), range) })
+    }
+}
+
+impl<'p, H: Haystack> Pattern<H> for AsciiCaseInsensitive<&'p str>
+where
+    AsciiCaseInsensitiveSearcher<'p>: Searcher<H::Hay>,
+{
+    type Searcher = AsciiCaseInsensitiveSearcher<'p>;
+
+    #[inline]
+    fn into_searcher(self) -> Self::Searcher {
+        AsciiCaseInsensitiveSearcher::new(self.0.as_bytes())
+    }
+}
+
+impl<'p, H: Haystack<Hay = [u8]>> Pattern<H> for AsciiCaseInsensitive<&'p [u8]> {
+    type Searcher = AsciiCaseInsensitiveSearcher<'p>;
+
+    #[inline]
+    fn into_searcher(self) -> Self::Searcher {
+        AsciiCaseInsensitiveSearcher::new(self.0)
+    }
+}
@@ -0,0 +1,153 @@
+use pattern::*;
+use haystack::Span;
+use memchr::{memchr, memchr2, memchr3, memrchr, memrchr2, memrchr3};
+use std::ops::Range;
+#[cfg(feature = "std")]
+use std::alloc::Allocator;
+
+/// Searcher of any byte in a small set, dispatching to `memchr`, `memchr2` or
+/// `memchr3`.
+///
+/// This is the searcher of `u8` patterns on byte slices, and of `[u8; 2]` and
+/// `[u8; 3]` patterns matching a single byte equal to any of the elements,
+/// like `[char; N]` patterns on strings.
+///
+/// ```rust
+/// extern crate pattern_3;
+/// use pattern_3::ext::{split, rfind};
+///
+/// assert_eq!(rfind(&b"a/b/c"[..], b'/'), Some(3));
+/// let fields = split(&b"a,b;c d"[..], [b',', b';', b' ']).collect::<Vec<_>>();
+/// assert_eq!(fields, vec![&b"a"[..], b"b", b"c", b"d"]);
+/// assert_eq!(rfind(&b"key=value\n"[..], [b'=', b'\n']), Some(9));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ByteSetSearcher {
+    // Unused trailing slots repeat the first byte.
+    bytes: [u8; 3],
+    len: usize,
+}
+
+impl ByteSetSearcher {
+    #[inline]
+    fn new(set: &[u8]) -> Self {
+        let mut bytes = [set[0]; 3];
+        bytes[..set.len()].copy_from_slice(set);
+        ByteSetSearcher { bytes, len: set.len() }
+    }
+
+    #[inline]
+    fn position(&self, hay: &[u8]) -> Option<usize> {
+        let [a, b, c] = self.bytes;
+        match self.len {
+            1 => memchr(a, hay),
+            2 => memchr2(a, b, hay),
+            _ => memchr3(a, b, c, hay),
+        }
+    }
+
+    #[inline]
+    fn rposition(&self, hay: &[u8]) -> Option<usize> {
+        let [a, b, c] = self.bytes;
+        match self.len {
+            1 => memrchr(a, hay),
+            2 => memrchr2(a, b, hay),
+            _ => memrchr3(a, b, c, hay),
+        }
+    }
+
+    #[inline]
+    fn contains(&self, byte: u8) -> bool {
+        self.bytes.contains(&byte)
+    }
+}
+
+unsafe impl Searcher<[u8]> for ByteSetSearcher {
+    #[inline]
+    fn search(&mut self, span: Span<&[u8]>) -> Option<Range<usize>> {
+        let (hay, range) = span.into_parts();
+        let index = range.start + self.position(&hay[range])?;
+        Some(index..(index + 1))
+    }
+
+    #[inline]
+    fn consume(&mut self, span: Span<&[u8]>) -> Option<usize> {
+        let (hay, range) = span.into_parts();
+        if range.start < range.end && self.contains(hay[range.start]) {
+            Some(range.start + 1)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn trim_start(&mut self, hay: &[u8]) -> usize {
+        hay.iter().position(|b| !self.contains(*b)).unwrap_or(hay.len())
+    }
+}
+
+unsafe impl ReverseSearcher<[u8]> for ByteSetSearcher {
+    #[inline]
+    fn rsearch(&mut self, span: Span<&[u8]>) -> Option<Range<usize>> {
+        let (hay, range) = span.into_parts();
+        let index = range.start + self.rposition(&hay[range])?;
+        Some(index..(index + 1))
+    }
+
+    #[inline]
+    fn rconsume(&mut self, span: Span<&[u8]>) -> Option<usize> {
+        let (hay, range) = span.into_parts();
+        if range.start < range.end && self.contains(hay[range.end - 1]) {
+            Some(range.end - 1)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn trim_end(&mut self, hay: &[u8]) -> usize {
+        hay.iter().rposition(|b| !self.contains(*b)).map_or(0, |i| i + 1)
+    }
+}
+
+unsafe impl DoubleEndedSearcher<[u8]> for ByteSetSearcher {}
+
+impl FusedSearcher<[u8]> for ByteSetSearcher {}
+
+macro_rules! impl_pattern {
+    (<[$($gen:tt)*]> $ty:ty) => {
+        impl<$($gen)*> Pattern<$ty> for u8 {
+            type Searcher = ByteSetSearcher;
+
+            #[inline]
+            fn into_searcher(self) -> Self::Searcher {
+                ByteSetSearcher::new(&[self])
+            }
+        }
+
+        impl<$($gen)*> Pattern<$ty> for [u8; 2] {
+            type Searcher = ByteSetSearcher;
+
+            #[inline]
+            fn into_searcher(self) -> Self::Searcher {
+                ByteSetSearcher::new(&self)
+            }
+        }
+
+        impl<$($gen)*> Pattern<$ty> for [u8; 3] {
+            type Searcher = ByteSetSearcher;
+
+            #[inline]
+            fn into_searcher(self) -> Self::Searcher {
+                ByteSetSearcher::new(&self)
+            }
+        }
+    }
+}
+
+impl_pattern!(<['h]> &'h [u8]);
+impl_pattern!(<['h]> &'h mut [u8]);
+#[cfg(feature = "std")]
+impl_pattern!(<[A: Allocator + Clone]> Vec<u8, A>);
+#[cfg(feature = "std")]
+impl_pattern!(<[A: Allocator + Clone]> Box<[u8], A>);
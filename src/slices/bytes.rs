@@ -0,0 +1,67 @@
+use pattern::*;
+use haystack::{Haystack, SharedHaystack};
+use bytes::Bytes;
+use std::ops::Range;
+
+use super::func::ElemSearcher;
+use super::slice::SliceSearcher;
+
+impl Haystack for Bytes {
+    type Hay = [u8];
+
+    #[inline]
+    fn hay(&self) -> &[u8] {
+        self
+    }
+
+    #[inline]
+    fn empty(&self) -> Self {
+        Bytes::new()
+    }
+
+    #[inline]
+    unsafe fn slice_unchecked(self, range: Range<usize>) -> Self {
+        self.slice(range)
+    }
+
+    #[inline]
+    unsafe fn split_around(self, range: Range<usize>) -> [Self; 3] {
+        [
+            self.slice(..range.start),
+            self.slice(range.clone()),
+            self.slice(range.end..),
+        ]
+    }
+
+    #[inline]
+    fn restore_range(&self, range: Range<usize>, subrange: Range<usize>) -> Range<usize> {
+        (subrange.start + range.start)..(subrange.end + range.start)
+    }
+}
+
+/// `Bytes` is reference-counted, so cloning the whole buffer is cheap. Pieces
+/// are only sliced out (sharing the same buffer) when they are yielded.
+impl SharedHaystack for Bytes {}
+
+impl<'p> Pattern<Bytes> for &'p [u8] {
+    type Searcher = SliceSearcher<'p, u8>;
+
+    #[inline]
+    fn into_searcher(self) -> Self::Searcher {
+        SliceSearcher::new_searcher(self)
+    }
+
+    #[inline]
+    fn into_consumer(self) -> Self::Searcher {
+        SliceSearcher::new_consumer(self)
+    }
+}
+
+impl<F: FnMut(&u8) -> bool> Pattern<Bytes> for F {
+    type Searcher = ElemSearcher<F>;
+
+    #[inline]
+    fn into_searcher(self) -> Self::Searcher {
+        Pattern::<&[u8]>::into_searcher(self)
+    }
+}
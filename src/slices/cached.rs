@@ -0,0 +1,94 @@
+use pattern::*;
+use haystack::Haystack;
+use std::alloc::Allocator;
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use super::slice::{SliceSearcher, TwoWaySearcher, TwoWayParams};
+
+/// A byte or string needle whose preprocessing is memoized per thread.
+///
+/// Constructing a substring searcher computes a critical factorization of the
+/// needle, which costs about as much as scanning a short haystack. When the
+/// same needle is searched for over and over (e.g. a user-supplied pattern
+/// matched against every line of a file), wrapping it in `Cached` performs
+/// this preprocessing only once per thread.
+///
+/// The cache is keyed by the needle contents and holds a bounded number of
+/// entries; it is flushed when full.
+///
+/// ```rust
+/// extern crate pattern_3;
+/// use pattern_3::Cached;
+/// use pattern_3::ext::find;
+///
+/// let needle = String::from("needle");
+/// for line in &["haystack", "a needle in a haystack"] {
+///     let _ = find(*line, Cached(&*needle));
+/// }
+/// assert_eq!(find("a needle", Cached(&*needle)), Some(2));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Cached<'p, N: ?Sized + 'p>(pub &'p N);
+
+const CACHE_CAPACITY: usize = 64;
+
+thread_local! {
+    static CACHE: RefCell<HashMap<Box<[u8]>, TwoWayParams>> = RefCell::new(HashMap::new());
+}
+
+fn cached_searcher(needle: &[u8]) -> SliceSearcher<'_, u8> {
+    if needle.is_empty() {
+        return SliceSearcher::new_searcher(needle);
+    }
+    let params = CACHE.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        if let Some(params) = cache.get(needle) {
+            return *params;
+        }
+        let params = TwoWaySearcher::new(needle).params();
+        if cache.len() >= CACHE_CAPACITY {
+            cache.clear();
+        }
+        cache.insert(needle.into(), params);
+        params
+    });
+    SliceSearcher::TwoWay(TwoWaySearcher::from_params(needle, params))
+}
+
+impl<'p, H: Haystack<Hay = str>> Pattern<H> for Cached<'p, str> {
+    type Searcher = SliceSearcher<'p, u8>;
+
+    #[inline]
+    fn into_searcher(self) -> Self::Searcher {
+        cached_searcher(self.0.as_bytes())
+    }
+
+    #[inline]
+    fn into_consumer(self) -> Self::Searcher {
+        SliceSearcher::new_consumer(self.0.as_bytes())
+    }
+}
+
+macro_rules! impl_pattern {
+    (<[$($gen:tt)*]> $ty:ty) => {
+        impl<$($gen)*> Pattern<$ty> for Cached<'p, [u8]> {
+            type Searcher = SliceSearcher<'p, u8>;
+
+            #[inline]
+            fn into_searcher(self) -> Self::Searcher {
+                cached_searcher(self.0)
+            }
+
+            #[inline]
+            fn into_consumer(self) -> Self::Searcher {
+                SliceSearcher::new_consumer(self.0)
+            }
+        }
+    }
+}
+
+impl_pattern!(<['p, 'h]> &'h [u8]);
+impl_pattern!(<['p, 'h]> &'h mut [u8]);
+impl_pattern!(<['p, A: Allocator + Clone]> Vec<u8, A>);
+impl_pattern!(<['p, A: Allocator + Clone]> Box<[u8], A>);
@@ -0,0 +1,700 @@
+use pattern::{Pattern, Searcher, ReverseSearcher};
+use haystack::Span;
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::iter::FusedIterator;
+use std::ops::Range;
+
+use super::func::ElemSearcher;
+use super::owned::OwnedSliceSearcher;
+use super::slice::{SliceSearcher, TwoWayHay, TwoWaySearcher};
+
+/// A sequence stored as several contiguous chunks, such as a rope, a
+/// segmented buffer, a ring buffer (`VecDeque`) or a `Vec<Vec<u8>>`.
+///
+/// Like [`Rle`](struct.Rle.html), a chunked sequence is not a
+/// [`Hay`](trait.Hay.html), since a sub-range spanning several chunks cannot be
+/// lent out by reference. Instead, the provided methods search it with the
+/// patterns of slices whose searcher implements
+/// [`ChunkedSearcher`](trait.ChunkedSearcher.html): sub-slice needles are
+/// found by the Two-Way algorithm carrying its state across chunk boundaries,
+/// so matches spanning several chunks are found without copying. Searching
+/// from the back (`rfind`, `rmatch_ranges`, `rsplit_ranges`, `trim_end` and
+/// so on) needs a searcher implementing
+/// [`ChunkedReverseSearcher`](trait.ChunkedReverseSearcher.html) too.
+///
+/// Positions address the logical (concatenated) sequence. Chunks may be empty.
+///
+/// ```rust
+/// extern crate pattern_3;
+/// use pattern_3::ChunkedHay;
+///
+/// let segments = vec![b"the qu".to_vec(), b"ick".to_vec(), vec![], b" brown qu".to_vec(), b"ick".to_vec()];
+/// assert_eq!(segments.len(), 5);
+/// assert_eq!(ChunkedHay::len(&segments), 21);
+/// assert_eq!(segments.find(&b"quick"[..]), Some(4));
+/// assert_eq!(segments.match_indices(&b"k b"[..]).collect::<Vec<_>>(), vec![8]);
+/// assert_eq!(segments.match_indices(&b"quick"[..]).collect::<Vec<_>>(), vec![4, 16]);
+/// assert_eq!(segments.find(|b: &u8| *b == b' '), Some(3));
+/// ```
+pub trait ChunkedHay {
+    /// The item type of the sequence.
+    type Item;
+
+    /// Calls `f` with every chunk, in order.
+    fn for_each_chunk<'h, F>(&'h self, f: F)
+    where
+        F: FnMut(&'h [Self::Item]);
+
+    /// Returns the logical length of the sequence.
+    fn len(&self) -> usize {
+        let mut len = 0;
+        self.for_each_chunk(|chunk| len += chunk.len());
+        len
+    }
+
+    /// Returns whether the sequence contains no items.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the start of the first match of `pattern`.
+    fn find<'h, P>(&'h self, pattern: P) -> Option<usize>
+    where
+        P: Pattern<&'h [Self::Item]>,
+        P::Searcher: ChunkedSearcher<Self::Item>,
+    {
+        self.match_indices(pattern).next()
+    }
+
+    /// Returns an iterator over the starts of non-overlapping matches of
+    /// `pattern`, in order.
+    fn match_indices<'h, P>(&'h self, pattern: P) -> ChunkedMatchIndices<'h, Self::Item, P::Searcher>
+    where
+        P: Pattern<&'h [Self::Item]>,
+        P::Searcher: ChunkedSearcher<Self::Item>,
+    {
+        ChunkedMatchIndices {
+            ranges: self.match_ranges(pattern),
+        }
+    }
+
+    /// Returns an iterator over the ranges of non-overlapping matches of
+    /// `pattern`, in order.
+    fn match_ranges<'h, P>(&'h self, pattern: P) -> ChunkedMatchRanges<'h, Self::Item, P::Searcher>
+    where
+        P: Pattern<&'h [Self::Item]>,
+        P::Searcher: ChunkedSearcher<Self::Item>,
+    {
+        ChunkedMatchRanges {
+            cursor: ChunkCursor::new(self),
+            searcher: pattern.into_searcher(),
+            position: 0,
+        }
+    }
+
+    /// Returns the start of the last match of `pattern`.
+    fn rfind<'h, P>(&'h self, pattern: P) -> Option<usize>
+    where
+        P: Pattern<&'h [Self::Item]>,
+        P::Searcher: ChunkedReverseSearcher<Self::Item>,
+    {
+        self.rmatch_indices(pattern).next()
+    }
+
+    /// Returns an iterator over the starts of non-overlapping matches of
+    /// `pattern`, in reverse order.
+    fn rmatch_indices<'h, P>(&'h self, pattern: P) -> ChunkedRMatchIndices<'h, Self::Item, P::Searcher>
+    where
+        P: Pattern<&'h [Self::Item]>,
+        P::Searcher: ChunkedReverseSearcher<Self::Item>,
+    {
+        ChunkedRMatchIndices {
+            ranges: self.rmatch_ranges(pattern),
+        }
+    }
+
+    /// Returns an iterator over the ranges of non-overlapping matches of
+    /// `pattern`, in reverse order.
+    fn rmatch_ranges<'h, P>(&'h self, pattern: P) -> ChunkedRMatchRanges<'h, Self::Item, P::Searcher>
+    where
+        P: Pattern<&'h [Self::Item]>,
+        P::Searcher: ChunkedReverseSearcher<Self::Item>,
+    {
+        let cursor = ChunkCursor::new(self);
+        let end = Some(cursor.len());
+        ChunkedRMatchRanges {
+            cursor,
+            searcher: pattern.into_searcher(),
+            end,
+        }
+    }
+
+    /// Returns an iterator over the ranges of the pieces between the matches
+    /// of `pattern`, in order.
+    fn split_ranges<'h, P>(&'h self, pattern: P) -> ChunkedSplitRanges<'h, Self::Item, P::Searcher>
+    where
+        P: Pattern<&'h [Self::Item]>,
+        P::Searcher: ChunkedSearcher<Self::Item>,
+    {
+        ChunkedSplitRanges {
+            ranges: self.match_ranges(pattern),
+            start: Some(0),
+        }
+    }
+
+    /// Returns an iterator over the ranges of the pieces between the matches
+    /// of `pattern`, in reverse order.
+    fn rsplit_ranges<'h, P>(&'h self, pattern: P) -> ChunkedRSplitRanges<'h, Self::Item, P::Searcher>
+    where
+        P: Pattern<&'h [Self::Item]>,
+        P::Searcher: ChunkedReverseSearcher<Self::Item>,
+    {
+        let ranges = self.rmatch_ranges(pattern);
+        let end = Some(ranges.cursor.len());
+        ChunkedRSplitRanges { ranges, end }
+    }
+
+    /// Returns the position of the first item not covered by matches of
+    /// `pattern` repeated from the start.
+    fn trim_start<'h, P>(&'h self, pattern: P) -> usize
+    where
+        P: Pattern<&'h [Self::Item]>,
+        P::Searcher: ChunkedSearcher<Self::Item>,
+    {
+        let mut start = 0;
+        for range in self.match_ranges(pattern) {
+            if range.start != start || range.start == range.end {
+                break;
+            }
+            start = range.end;
+        }
+        start
+    }
+
+    /// Returns the position after the last item not covered by matches of
+    /// `pattern` repeated from the end.
+    fn trim_end<'h, P>(&'h self, pattern: P) -> usize
+    where
+        P: Pattern<&'h [Self::Item]>,
+        P::Searcher: ChunkedReverseSearcher<Self::Item>,
+    {
+        let mut ranges = self.rmatch_ranges(pattern);
+        let mut end = ranges.cursor.len();
+        for range in &mut ranges {
+            if range.end != end || range.start == range.end {
+                break;
+            }
+            end = range.start;
+        }
+        end
+    }
+}
+
+impl<'a, T> ChunkedHay for [&'a [T]] {
+    type Item = T;
+
+    #[inline]
+    fn for_each_chunk<'h, F: FnMut(&'h [T])>(&'h self, f: F) {
+        self.iter().cloned().for_each(f)
+    }
+}
+
+impl<T> ChunkedHay for [Vec<T>] {
+    type Item = T;
+
+    #[inline]
+    fn for_each_chunk<'h, F: FnMut(&'h [T])>(&'h self, f: F) {
+        self.iter().map(|chunk| &**chunk).for_each(f)
+    }
+}
+
+impl<T> ChunkedHay for Vec<Vec<T>> {
+    type Item = T;
+
+    #[inline]
+    fn for_each_chunk<'h, F: FnMut(&'h [T])>(&'h self, f: F) {
+        self[..].for_each_chunk(f)
+    }
+}
+
+impl<T> ChunkedHay for VecDeque<T> {
+    type Item = T;
+
+    #[inline]
+    fn for_each_chunk<'h, F: FnMut(&'h [T])>(&'h self, mut f: F) {
+        let (front, back) = self.as_slices();
+        f(front);
+        f(back);
+    }
+}
+
+impl<'a> ChunkedHay for [&'a str] {
+    type Item = u8;
+
+    #[inline]
+    fn for_each_chunk<'h, F: FnMut(&'h [u8])>(&'h self, f: F) {
+        self.iter().map(|chunk| chunk.as_bytes()).for_each(f)
+    }
+}
+
+impl ChunkedHay for [String] {
+    type Item = u8;
+
+    #[inline]
+    fn for_each_chunk<'h, F: FnMut(&'h [u8])>(&'h self, f: F) {
+        self.iter().map(|chunk| chunk.as_bytes()).for_each(f)
+    }
+}
+
+/// Random access into the items of a [`ChunkedHay`](trait.ChunkedHay.html).
+///
+/// The cursor remembers the chunk of the last access, since searchers mostly
+/// read nearby items.
+pub struct ChunkCursor<'h, T: 'h> {
+    chunks: Vec<&'h [T]>,
+    // The logical start of every chunk, followed by the total length.
+    offsets: Vec<usize>,
+    current: Cell<usize>,
+}
+
+impl<'h, T: 'h> ChunkCursor<'h, T> {
+    fn new<C: ChunkedHay<Item = T> + ?Sized>(hay: &'h C) -> Self {
+        let mut chunks = Vec::new();
+        let mut offsets = vec![0];
+        let mut offset = 0;
+        hay.for_each_chunk(|chunk| {
+            offset += chunk.len();
+            chunks.push(chunk);
+            offsets.push(offset);
+        });
+        ChunkCursor {
+            chunks,
+            offsets,
+            current: Cell::new(0),
+        }
+    }
+
+    /// Returns the logical length of the sequence.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.offsets[self.offsets.len() - 1]
+    }
+
+    /// Returns whether the sequence contains no items.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the item at logical position `index`.
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&'h T> {
+        if index < self.len() {
+            Some(unsafe { self.item(index) })
+        } else {
+            None
+        }
+    }
+
+    /// Calls `f` with the parts of the chunks covering `range`, in order,
+    /// together with the logical position of their start. Stops as soon as
+    /// `f` returns `Some`.
+    pub fn find_map_chunks<R, F>(&self, range: Range<usize>, mut f: F) -> Option<R>
+    where
+        F: FnMut(usize, &'h [T]) -> Option<R>,
+    {
+        if range.start >= range.end {
+            return None;
+        }
+        let (first, _) = self.locate(range.start);
+        for c in first..self.chunks.len() {
+            let offset = self.offsets[c];
+            if offset >= range.end {
+                break;
+            }
+            let start = range.start.max(offset) - offset;
+            let end = range.end.min(self.offsets[c + 1]) - offset;
+            if let Some(result) = f(offset + start, &self.chunks[c][start..end]) {
+                return Some(result);
+            }
+        }
+        None
+    }
+
+    /// Calls `f` with the parts of the chunks covering `range`, in reverse
+    /// order, together with the logical position of their start. Stops as
+    /// soon as `f` returns `Some`.
+    pub fn rfind_map_chunks<R, F>(&self, range: Range<usize>, mut f: F) -> Option<R>
+    where
+        F: FnMut(usize, &'h [T]) -> Option<R>,
+    {
+        if range.start >= range.end {
+            return None;
+        }
+        let (last, _) = self.locate(range.end - 1);
+        for c in (0..=last).rev() {
+            let offset = self.offsets[c];
+            if self.offsets[c + 1] <= range.start {
+                break;
+            }
+            let start = range.start.max(offset) - offset;
+            let end = range.end.min(self.offsets[c + 1]) - offset;
+            if let Some(result) = f(offset + start, &self.chunks[c][start..end]) {
+                return Some(result);
+            }
+        }
+        None
+    }
+
+    /// Returns the item at index `i`, which must be in bounds.
+    #[inline]
+    unsafe fn item(&self, i: usize) -> &'h T {
+        let mut c = self.current.get();
+        if !(self.offsets[c] <= i && i < self.offsets[c + 1]) {
+            // The last chunk starting at or before `i` is the non-empty one
+            // containing it.
+            c = self.offsets.partition_point(|&o| o <= i) - 1;
+            self.current.set(c);
+        }
+        self.chunks.get_unchecked(c).get_unchecked(i - self.offsets[c])
+    }
+
+    /// Converts a logical position into a (chunk index, offset) pair,
+    /// preferring the chunk which starts at a boundary position.
+    fn locate(&self, i: usize) -> (usize, usize) {
+        if self.chunks.is_empty() {
+            return (0, i);
+        }
+        let c = (self.offsets.partition_point(|&o| o <= i) - 1).min(self.chunks.len() - 1);
+        (c, i - self.offsets[c])
+    }
+}
+
+impl<'h, T: 'h> TwoWayHay<T> for ChunkCursor<'h, T> {
+    #[inline]
+    unsafe fn item(&self, i: usize) -> &T {
+        ChunkCursor::item(self, i)
+    }
+}
+
+/// Searchers which can search a [`ChunkedHay`](trait.ChunkedHay.html) in
+/// place.
+///
+/// This is implemented for the searchers of sub-slice needles (`&[T]`, and
+/// `&str` in bytes), of element predicates (`FnMut(&T) -> bool`) and of owned
+/// byte needles (`ByteNeedle`, and `char` in bytes).
+pub trait ChunkedSearcher<T> {
+    /// Returns the range of the first match within `range` of the sequence.
+    fn search_chunks(&mut self, cursor: &ChunkCursor<T>, range: Range<usize>) -> Option<Range<usize>>;
+}
+
+impl<'p, T: PartialEq + 'p> ChunkedSearcher<T> for SliceSearcher<'p, T> {
+    fn search_chunks(&mut self, cursor: &ChunkCursor<T>, range: Range<usize>) -> Option<Range<usize>> {
+        let needle = self.needle();
+        if needle.is_empty() {
+            return Some(range.start..range.start);
+        }
+        if let SliceSearcher::TwoWay(searcher) = self {
+            return searcher.next(cursor, range);
+        }
+        // The other algorithms read the hay as a single slice.
+        *self = SliceSearcher::TwoWay(TwoWaySearcher::new(needle));
+        self.search_chunks(cursor, range)
+    }
+}
+
+/// Searchers which can search a [`ChunkedHay`](trait.ChunkedHay.html) in
+/// place from the back.
+pub trait ChunkedReverseSearcher<T>: ChunkedSearcher<T> {
+    /// Returns the range of the last match within `range` of the sequence.
+    fn rsearch_chunks(&mut self, cursor: &ChunkCursor<T>, range: Range<usize>) -> Option<Range<usize>>;
+}
+
+impl<'p, T: PartialEq + 'p> ChunkedReverseSearcher<T> for SliceSearcher<'p, T> {
+    fn rsearch_chunks(&mut self, cursor: &ChunkCursor<T>, range: Range<usize>) -> Option<Range<usize>> {
+        let needle = self.needle();
+        if needle.is_empty() {
+            return Some(range.end..range.end);
+        }
+        if let SliceSearcher::TwoWay(searcher) = self {
+            return searcher.next_back(cursor, range);
+        }
+        *self = SliceSearcher::TwoWay(TwoWaySearcher::new(needle));
+        self.rsearch_chunks(cursor, range)
+    }
+}
+
+impl ChunkedSearcher<u8> for OwnedSliceSearcher {
+    #[inline]
+    fn search_chunks(&mut self, cursor: &ChunkCursor<u8>, range: Range<usize>) -> Option<Range<usize>> {
+        match self.two_way() {
+            Some(mut searcher) => searcher.search_chunks(cursor, range),
+            None => Some(range.start..range.start),
+        }
+    }
+}
+
+impl ChunkedReverseSearcher<u8> for OwnedSliceSearcher {
+    #[inline]
+    fn rsearch_chunks(&mut self, cursor: &ChunkCursor<u8>, range: Range<usize>) -> Option<Range<usize>> {
+        match self.two_way() {
+            Some(mut searcher) => searcher.rsearch_chunks(cursor, range),
+            None => Some(range.end..range.end),
+        }
+    }
+}
+
+impl<T, F: FnMut(&T) -> bool> ChunkedSearcher<T> for ElemSearcher<F> {
+    #[inline]
+    fn search_chunks(&mut self, cursor: &ChunkCursor<T>, range: Range<usize>) -> Option<Range<usize>> {
+        // A match is a single item, so it never crosses a chunk boundary.
+        cursor.find_map_chunks(range, |offset, chunk| {
+            let span = Span::from(chunk);
+            let found = self.search(span)?;
+            Some((offset + found.start)..(offset + found.end))
+        })
+    }
+}
+
+impl<T, F: FnMut(&T) -> bool> ChunkedReverseSearcher<T> for ElemSearcher<F> {
+    #[inline]
+    fn rsearch_chunks(&mut self, cursor: &ChunkCursor<T>, range: Range<usize>) -> Option<Range<usize>> {
+        cursor.rfind_map_chunks(range, |offset, chunk| {
+            let span = Span::from(chunk);
+            let found = self.rsearch(span)?;
+            Some((offset + found.start)..(offset + found.end))
+        })
+    }
+}
+
+/// Iterator over the ranges of non-overlapping matches of a pattern in a
+/// [`ChunkedHay`](trait.ChunkedHay.html).
+pub struct ChunkedMatchRanges<'h, T: 'h, S> {
+    cursor: ChunkCursor<'h, T>,
+    searcher: S,
+    // Past the end once the search is finished.
+    position: usize,
+}
+
+impl<'h, T: 'h, S: ChunkedSearcher<T>> Iterator for ChunkedMatchRanges<'h, T, S> {
+    type Item = Range<usize>;
+
+    fn next(&mut self) -> Option<Range<usize>> {
+        let len = self.cursor.len();
+        if self.position > len {
+            return None;
+        }
+        match self.searcher.search_chunks(&self.cursor, self.position..len) {
+            Some(range) => {
+                // Step over an empty match so it is not found again.
+                self.position = if range.start == range.end { range.end + 1 } else { range.end };
+                Some(range)
+            }
+            None => {
+                self.position = len + 1;
+                None
+            }
+        }
+    }
+}
+
+impl<'h, T: 'h, S: ChunkedSearcher<T>> FusedIterator for ChunkedMatchRanges<'h, T, S> {}
+
+/// Iterator over the starts of non-overlapping matches of a pattern in a
+/// [`ChunkedHay`](trait.ChunkedHay.html).
+pub struct ChunkedMatchIndices<'h, T: 'h, S> {
+    ranges: ChunkedMatchRanges<'h, T, S>,
+}
+
+impl<'h, T: 'h, S: ChunkedSearcher<T>> Iterator for ChunkedMatchIndices<'h, T, S> {
+    type Item = usize;
+
+    #[inline]
+    fn next(&mut self) -> Option<usize> {
+        self.ranges.next().map(|range| range.start)
+    }
+}
+
+impl<'h, T: 'h, S: ChunkedSearcher<T>> FusedIterator for ChunkedMatchIndices<'h, T, S> {}
+
+/// Iterator over the ranges of non-overlapping matches of a pattern in a
+/// [`ChunkedHay`](trait.ChunkedHay.html), in reverse order.
+pub struct ChunkedRMatchRanges<'h, T: 'h, S> {
+    cursor: ChunkCursor<'h, T>,
+    searcher: S,
+    // The end of the part left to search, or `None` once the search is
+    // finished.
+    end: Option<usize>,
+}
+
+impl<'h, T: 'h, S: ChunkedReverseSearcher<T>> Iterator for ChunkedRMatchRanges<'h, T, S> {
+    type Item = Range<usize>;
+
+    fn next(&mut self) -> Option<Range<usize>> {
+        let end = self.end?;
+        match self.searcher.rsearch_chunks(&self.cursor, 0..end) {
+            Some(range) => {
+                // Step over an empty match so it is not found again.
+                self.end = if range.start == range.end { range.start.checked_sub(1) } else { Some(range.start) };
+                Some(range)
+            }
+            None => {
+                self.end = None;
+                None
+            }
+        }
+    }
+}
+
+impl<'h, T: 'h, S: ChunkedReverseSearcher<T>> FusedIterator for ChunkedRMatchRanges<'h, T, S> {}
+
+/// Iterator over the starts of non-overlapping matches of a pattern in a
+/// [`ChunkedHay`](trait.ChunkedHay.html), in reverse order.
+pub struct ChunkedRMatchIndices<'h, T: 'h, S> {
+    ranges: ChunkedRMatchRanges<'h, T, S>,
+}
+
+impl<'h, T: 'h, S: ChunkedReverseSearcher<T>> Iterator for ChunkedRMatchIndices<'h, T, S> {
+    type Item = usize;
+
+    #[inline]
+    fn next(&mut self) -> Option<usize> {
+        self.ranges.next().map(|range| range.start)
+    }
+}
+
+impl<'h, T: 'h, S: ChunkedReverseSearcher<T>> FusedIterator for ChunkedRMatchIndices<'h, T, S> {}
+
+/// Iterator over the ranges of the pieces between the matches of a pattern
+/// in a [`ChunkedHay`](trait.ChunkedHay.html).
+pub struct ChunkedSplitRanges<'h, T: 'h, S> {
+    ranges: ChunkedMatchRanges<'h, T, S>,
+    // The start of the next piece, or `None` once the last one is yielded.
+    start: Option<usize>,
+}
+
+impl<'h, T: 'h, S: ChunkedSearcher<T>> Iterator for ChunkedSplitRanges<'h, T, S> {
+    type Item = Range<usize>;
+
+    fn next(&mut self) -> Option<Range<usize>> {
+        let start = self.start?;
+        match self.ranges.next() {
+            Some(range) => {
+                self.start = Some(range.end);
+                Some(start..range.start)
+            }
+            None => {
+                self.start = None;
+                Some(start..self.ranges.cursor.len())
+            }
+        }
+    }
+}
+
+impl<'h, T: 'h, S: ChunkedSearcher<T>> FusedIterator for ChunkedSplitRanges<'h, T, S> {}
+
+/// Iterator over the ranges of the pieces between the matches of a pattern
+/// in a [`ChunkedHay`](trait.ChunkedHay.html), in reverse order.
+pub struct ChunkedRSplitRanges<'h, T: 'h, S> {
+    ranges: ChunkedRMatchRanges<'h, T, S>,
+    // The end of the next piece, or `None` once the last one is yielded.
+    end: Option<usize>,
+}
+
+impl<'h, T: 'h, S: ChunkedReverseSearcher<T>> Iterator for ChunkedRSplitRanges<'h, T, S> {
+    type Item = Range<usize>;
+
+    fn next(&mut self) -> Option<Range<usize>> {
+        let end = self.end?;
+        match self.ranges.next() {
+            Some(range) => {
+                self.end = Some(range.start);
+                Some(range.end..end)
+            }
+            None => {
+                self.end = None;
+                Some(0..end)
+            }
+        }
+    }
+}
+
+impl<'h, T: 'h, S: ChunkedReverseSearcher<T>> FusedIterator for ChunkedRSplitRanges<'h, T, S> {}
+
+/// A chunked sequence whose matches are reported as (piece index, offset)
+/// pairs, for searching fragmented buffers without concatenating them first.
+///
+/// Piece indices count empty pieces too. A match starting exactly at a piece
+/// boundary is reported at the start of the following non-empty piece.
+///
+/// ```rust
+/// extern crate pattern_3;
+/// use pattern_3::Concat;
+///
+/// let pieces = ["GET /ind", "ex.html HT", "TP/1.1\r\n", "Host: x\r\n"];
+/// let concat = Concat::new(&pieces[..]);
+/// assert_eq!(concat.find(&b"index"[..]), Some((0, 5)));
+/// assert_eq!(concat.find(&b"HTTP"[..]), Some((1, 8)));
+/// assert_eq!(concat.match_positions(&b"\r\n"[..]).collect::<Vec<_>>(), vec![(2, 6), (3, 7)]);
+/// ```
+pub struct Concat<'h, C: ?Sized + 'h> {
+    pieces: &'h C,
+}
+
+impl<'h, C: ChunkedHay + ?Sized + 'h> Concat<'h, C> {
+    #[inline]
+    pub fn new(pieces: &'h C) -> Self {
+        Concat { pieces }
+    }
+
+    /// Returns the position of the first match of `pattern`.
+    #[inline]
+    pub fn find<P>(&self, pattern: P) -> Option<(usize, usize)>
+    where
+        P: Pattern<&'h [C::Item]>,
+        P::Searcher: ChunkedSearcher<C::Item>,
+    {
+        self.match_positions(pattern).next()
+    }
+
+    /// Returns an iterator over the positions of non-overlapping matches of
+    /// `pattern`, in order.
+    #[inline]
+    pub fn match_positions<P>(&self, pattern: P) -> ConcatMatchPositions<'h, C::Item, P::Searcher>
+    where
+        P: Pattern<&'h [C::Item]>,
+        P::Searcher: ChunkedSearcher<C::Item>,
+    {
+        ConcatMatchPositions {
+            ranges: self.pieces.match_ranges(pattern),
+        }
+    }
+}
+
+impl<'h, C: ?Sized + 'h> Clone for Concat<'h, C> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'h, C: ?Sized + 'h> Copy for Concat<'h, C> {}
+
+/// Iterator over the (piece index, offset) positions of non-overlapping
+/// matches of a pattern in a [`Concat`](struct.Concat.html).
+pub struct ConcatMatchPositions<'h, T: 'h, S> {
+    ranges: ChunkedMatchRanges<'h, T, S>,
+}
+
+impl<'h, T: 'h, S: ChunkedSearcher<T>> Iterator for ConcatMatchPositions<'h, T, S> {
+    type Item = (usize, usize);
+
+    #[inline]
+    fn next(&mut self) -> Option<(usize, usize)> {
+        let range = self.ranges.next()?;
+        Some(self.ranges.cursor.locate(range.start))
+    }
+}
+
+impl<'h, T: 'h, S: ChunkedSearcher<T>> FusedIterator for ConcatMatchPositions<'h, T, S> {}
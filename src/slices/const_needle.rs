@@ -0,0 +1,156 @@
+use pattern::*;
+use haystack::Span;
+use std::ops::Range;
+#[cfg(feature = "std")]
+use std::alloc::Allocator;
+
+use super::slice::{FastSkipByteset, FastSkipOptimization};
+
+/// A needle whose length is known at compile time.
+///
+/// Tiny needles like `b"\r\n"` do not benefit from the critical factorization
+/// computed by the Two-Way searcher. A `ConstNeedle` instead compares whole
+/// `[T; N]` windows, skipping ahead by `N` whenever the last (or, when
+/// searching backwards, first) element of a window does not occur in the
+/// needle at all.
+///
+/// ```rust
+/// extern crate pattern_3;
+/// use pattern_3::ConstNeedle;
+/// use pattern_3::ext::split;
+///
+/// let lines = split(&b"a\r\nb\r\nc"[..], ConstNeedle(*b"\r\n")).collect::<Vec<_>>();
+/// assert_eq!(lines, vec![&b"a"[..], &b"b"[..], &b"c"[..]]);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct ConstNeedle<T, const N: usize>(pub [T; N]);
+
+#[derive(Debug, Clone)]
+pub struct ConstNeedleSearcher<T, const N: usize> {
+    needle: [T; N],
+    byteset: FastSkipByteset,
+    empty: EmptySearcher,
+}
+
+impl<T: PartialEq, const N: usize> ConstNeedleSearcher<T, N> {
+    #[inline]
+    fn new(needle: [T; N]) -> Self {
+        let byteset = needle.iter().fold(0, |a, b| b.byteset_mask() | a);
+        Self {
+            needle,
+            byteset,
+            empty: EmptySearcher::default(),
+        }
+    }
+
+    #[inline]
+    fn byteset_contains(&self, item: &T) -> bool {
+        (self.byteset & item.byteset_mask()) != 0
+    }
+
+    /// Checks whether the needle occurs at `hay[index..(index + N)]`.
+    ///
+    /// The caller must ensure `index + N <= hay.len()`.
+    #[inline]
+    unsafe fn is_match_at(&self, hay: &[T], index: usize) -> bool {
+        let window = &*(hay.as_ptr().add(index) as *const [T; N]);
+        *window == self.needle
+    }
+}
+
+unsafe impl<T: PartialEq, const N: usize> Searcher<[T]> for ConstNeedleSearcher<T, N> {
+    #[inline]
+    fn search(&mut self, span: Span<&[T]>) -> Option<Range<usize>> {
+        if N == 0 {
+            return self.empty.search(span);
+        }
+        let (hay, range) = span.into_parts();
+        let mut index = range.start;
+        while index + N <= range.end {
+            unsafe {
+                if !self.byteset_contains(hay.get_unchecked(index + N - 1)) {
+                    index += N;
+                    continue;
+                }
+                if self.is_match_at(hay, index) {
+                    return Some(index..(index + N));
+                }
+            }
+            index += 1;
+        }
+        None
+    }
+
+    #[inline]
+    fn consume(&mut self, span: Span<&[T]>) -> Option<usize> {
+        let (hay, range) = span.into_parts();
+        let end = range.start + N;
+        if end <= range.end && unsafe { self.is_match_at(hay, range.start) } {
+            Some(end)
+        } else {
+            None
+        }
+    }
+}
+
+unsafe impl<T: PartialEq, const N: usize> ReverseSearcher<[T]> for ConstNeedleSearcher<T, N> {
+    #[inline]
+    fn rsearch(&mut self, span: Span<&[T]>) -> Option<Range<usize>> {
+        if N == 0 {
+            return self.empty.rsearch(span);
+        }
+        let (hay, range) = span.into_parts();
+        let mut index = range.end;
+        while range.start + N <= index {
+            let start = index - N;
+            unsafe {
+                if !self.byteset_contains(hay.get_unchecked(start)) {
+                    index = start;
+                    continue;
+                }
+                if self.is_match_at(hay, start) {
+                    return Some(start..index);
+                }
+            }
+            index -= 1;
+        }
+        None
+    }
+
+    #[inline]
+    fn rconsume(&mut self, span: Span<&[T]>) -> Option<usize> {
+        let (hay, range) = span.into_parts();
+        if range.start + N > range.end {
+            return None;
+        }
+        let start = range.end - N;
+        if unsafe { self.is_match_at(hay, start) } {
+            Some(start)
+        } else {
+            None
+        }
+    }
+}
+
+macro_rules! impl_pattern {
+    (<[$($gen:tt)*]> $ty:ty) => {
+        impl<$($gen)*> Pattern<$ty> for ConstNeedle<T, N>
+        where
+            T: PartialEq,
+        {
+            type Searcher = ConstNeedleSearcher<T, N>;
+
+            #[inline]
+            fn into_searcher(self) -> Self::Searcher {
+                ConstNeedleSearcher::new(self.0)
+            }
+        }
+    }
+}
+
+impl_pattern!(<['h, T, const N: usize]> &'h [T]);
+impl_pattern!(<['h, T, const N: usize]> &'h mut [T]);
+#[cfg(feature = "std")]
+impl_pattern!(<[T, const N: usize, A: Allocator + Clone]> Vec<T, A>);
+#[cfg(feature = "std")]
+impl_pattern!(<[T, const N: usize, A: Allocator + Clone]> Box<[T], A>);
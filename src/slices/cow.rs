@@ -0,0 +1,129 @@
+use pattern::*;
+use haystack::{Haystack, Span};
+use std::alloc::Allocator;
+use std::borrow::Cow;
+use std::ops::Range;
+
+use super::slice::{SliceSearcher, TwoWaySearcher, TwoWayParams};
+
+/// Searcher for `Cow` needles.
+///
+/// A borrowed needle stays borrowed, and an owned needle is moved into the
+/// searcher without being copied.
+#[derive(Debug, Clone)]
+pub struct CowSliceSearcher<'p, T: Clone + 'p> {
+    needle: Cow<'p, [T]>,
+    params: Option<TwoWayParams>,
+    empty: EmptySearcher,
+}
+
+impl<'p, T: Clone + PartialEq + 'p> CowSliceSearcher<'p, T> {
+    fn new(needle: Cow<'p, [T]>) -> Self {
+        let params = if needle.is_empty() {
+            None
+        } else {
+            Some(TwoWaySearcher::new(&*needle).params())
+        };
+        CowSliceSearcher {
+            needle,
+            params,
+            empty: EmptySearcher::default(),
+        }
+    }
+
+    // The Two-Way searcher keeps no state between matches, so it can be
+    // rebuilt from the precomputed parameters for every search.
+    #[inline]
+    fn two_way(&self) -> Option<SliceSearcher<'_, T>> {
+        let params = self.params?;
+        Some(SliceSearcher::TwoWay(TwoWaySearcher::from_params(&self.needle, params)))
+    }
+
+    #[inline]
+    fn naive(&self) -> SliceSearcher<'_, T> {
+        SliceSearcher::new_consumer(&self.needle)
+    }
+}
+
+// Implemented for each hay separately, since a bound like
+// `for<'q> SliceSearcher<'q, T>: Searcher<A>` would require `T: 'static`.
+macro_rules! impl_searcher {
+    (<[$($gen:tt)*]> $hay:ty, $t:ty) => {
+        unsafe impl<$($gen)*> Searcher<$hay> for CowSliceSearcher<'p, $t> {
+            #[inline]
+            fn search(&mut self, span: Span<&$hay>) -> Option<Range<usize>> {
+                match self.two_way() {
+                    Some(mut searcher) => searcher.search(span),
+                    None => self.empty.search(span),
+                }
+            }
+
+            #[inline]
+            fn consume(&mut self, span: Span<&$hay>) -> Option<usize> {
+                self.naive().consume(span)
+            }
+
+            #[inline]
+            fn trim_start(&mut self, hay: &$hay) -> usize {
+                self.naive().trim_start(hay)
+            }
+        }
+
+        unsafe impl<$($gen)*> ReverseSearcher<$hay> for CowSliceSearcher<'p, $t> {
+            #[inline]
+            fn rsearch(&mut self, span: Span<&$hay>) -> Option<Range<usize>> {
+                match self.two_way() {
+                    Some(mut searcher) => searcher.rsearch(span),
+                    None => self.empty.rsearch(span),
+                }
+            }
+
+            #[inline]
+            fn rconsume(&mut self, span: Span<&$hay>) -> Option<usize> {
+                self.naive().rconsume(span)
+            }
+
+            #[inline]
+            fn trim_end(&mut self, hay: &$hay) -> usize {
+                self.naive().trim_end(hay)
+            }
+        }
+    }
+}
+
+impl_searcher!(<['p, T: Clone + PartialEq + 'p]> [T], T);
+impl_searcher!(<['p]> str, u8);
+
+impl<'p, H: Haystack<Hay = str>> Pattern<H> for Cow<'p, str> {
+    type Searcher = CowSliceSearcher<'p, u8>;
+
+    #[inline]
+    fn into_searcher(self) -> Self::Searcher {
+        let needle = match self {
+            Cow::Borrowed(s) => Cow::Borrowed(s.as_bytes()),
+            Cow::Owned(s) => Cow::Owned(s.into_bytes()),
+        };
+        CowSliceSearcher::new(needle)
+    }
+}
+
+macro_rules! impl_pattern {
+    (<[$($gen:tt)*]> $ty:ty) => {
+        impl<$($gen)*> Pattern<$ty> for Cow<'p, [T]>
+        where
+            T: Clone + PartialEq,
+        {
+            type Searcher = CowSliceSearcher<'p, T>;
+
+            #[inline]
+            fn into_searcher(self) -> Self::Searcher {
+                CowSliceSearcher::new(self)
+            }
+        }
+    }
+}
+
+impl_pattern!(<['p, 'h, T]> &'h [T]);
+impl_pattern!(<['p, 'h, T]> &'h mut [T]);
+impl_pattern!(<['p, T, A: Allocator + Clone]> Vec<T, A>);
+impl_pattern!(<['p, T, A: Allocator + Clone]> Box<[T], A>);
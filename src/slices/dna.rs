@@ -0,0 +1,242 @@
+use pattern::*;
+use haystack::Span;
+use std::alloc::Allocator;
+use std::iter::FusedIterator;
+use std::ops::Range;
+
+use super::slice::{FastSkipByteset, FastSkipOptimization};
+
+/// The strand of a DNA sequence an occurrence was found on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Strand {
+    /// The needle itself occurred.
+    Forward,
+    /// The reverse complement of the needle occurred.
+    Reverse,
+}
+
+/// Returns the complement of a nucleotide, including IUPAC ambiguity codes.
+/// Other bytes are returned unchanged. Case is preserved.
+fn complement(b: u8) -> u8 {
+    let c = match b.to_ascii_uppercase() {
+        b'A' => b'T',
+        b'T' => b'A',
+        b'U' => b'A',
+        b'C' => b'G',
+        b'G' => b'C',
+        b'R' => b'Y',
+        b'Y' => b'R',
+        b'K' => b'M',
+        b'M' => b'K',
+        b'B' => b'V',
+        b'V' => b'B',
+        b'D' => b'H',
+        b'H' => b'D',
+        _ => return b,
+    };
+    if b.is_ascii_lowercase() { c.to_ascii_lowercase() } else { c }
+}
+
+/// Matches a DNA sequence on either strand: the needle itself, or its
+/// reverse complement.
+///
+/// Both orientations are tested in a single scan of the haystack. Use
+/// [`match_ranges`](#method.match_ranges) to find out which strand every
+/// occurrence is on. When both orientations occur at the same position (the
+/// needle is its own reverse complement), the occurrence is reported on the
+/// forward strand.
+///
+/// ```rust
+/// extern crate pattern_3;
+/// use pattern_3::{EitherStrand, Strand};
+///
+/// let genome = &b"TTGAATTCAAGGTCAC"[..];
+/// let found = EitherStrand(b"GTGAC").match_ranges(genome).collect::<Vec<_>>();
+/// assert_eq!(found, vec![(11..16, Strand::Reverse)]);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct EitherStrand<'p>(pub &'p [u8]);
+
+impl<'p> EitherStrand<'p> {
+    /// Returns an iterator over the non-overlapping occurrences of the needle
+    /// on either strand, along with the strand each was found on.
+    pub fn match_ranges<'h>(self, hay: &'h [u8]) -> StrandMatchRanges<'h, 'p> {
+        StrandMatchRanges {
+            hay,
+            position: 0,
+            searcher: EitherStrandSearcher::new(self.0),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EitherStrandSearcher<'p> {
+    forward: &'p [u8],
+    reverse: Vec<u8>,
+    byteset: FastSkipByteset,
+    empty: EmptySearcher,
+}
+
+impl<'p> EitherStrandSearcher<'p> {
+    fn new(forward: &'p [u8]) -> Self {
+        let reverse = forward.iter().rev().map(|&b| complement(b)).collect::<Vec<_>>();
+        let byteset = forward.iter().chain(&reverse).fold(0, |a, b| b.byteset_mask() | a);
+        EitherStrandSearcher {
+            forward,
+            reverse,
+            byteset,
+            empty: EmptySearcher::default(),
+        }
+    }
+
+    /// Returns the strand of the needle occurring at `hay[index..]`, if any.
+    #[inline]
+    fn strand_at(&self, hay: &[u8], index: usize) -> Option<Strand> {
+        let window = &hay[index..(index + self.forward.len())];
+        if window == self.forward {
+            Some(Strand::Forward)
+        } else if window == &*self.reverse {
+            Some(Strand::Reverse)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn byteset_contains(&self, b: u8) -> bool {
+        (self.byteset & b.byteset_mask()) != 0
+    }
+
+    fn search_strand(&self, hay: &[u8], range: Range<usize>) -> Option<(usize, Strand)> {
+        let len = self.forward.len();
+        let mut index = range.start;
+        while index + len <= range.end {
+            if !self.byteset_contains(hay[index + len - 1]) {
+                index += len;
+                continue;
+            }
+            if let Some(strand) = self.strand_at(hay, index) {
+                return Some((index, strand));
+            }
+            index += 1;
+        }
+        None
+    }
+}
+
+unsafe impl<'p> Searcher<[u8]> for EitherStrandSearcher<'p> {
+    #[inline]
+    fn search(&mut self, span: Span<&[u8]>) -> Option<Range<usize>> {
+        let len = self.forward.len();
+        if len == 0 {
+            return self.empty.search(span);
+        }
+        let (hay, range) = span.into_parts();
+        let (index, _) = self.search_strand(hay, range)?;
+        Some(index..(index + len))
+    }
+
+    #[inline]
+    fn consume(&mut self, span: Span<&[u8]>) -> Option<usize> {
+        let (hay, range) = span.into_parts();
+        let end = range.start + self.forward.len();
+        if end <= range.end && self.strand_at(hay, range.start).is_some() {
+            Some(end)
+        } else {
+            None
+        }
+    }
+}
+
+unsafe impl<'p> ReverseSearcher<[u8]> for EitherStrandSearcher<'p> {
+    #[inline]
+    fn rsearch(&mut self, span: Span<&[u8]>) -> Option<Range<usize>> {
+        let len = self.forward.len();
+        if len == 0 {
+            return self.empty.rsearch(span);
+        }
+        let (hay, range) = span.into_parts();
+        let mut index = range.end;
+        while range.start + len <= index {
+            let start = index - len;
+            if !self.byteset_contains(hay[start]) {
+                index = start;
+                continue;
+            }
+            if self.strand_at(hay, start).is_some() {
+                return Some(start..index);
+            }
+            index -= 1;
+        }
+        None
+    }
+
+    #[inline]
+    fn rconsume(&mut self, span: Span<&[u8]>) -> Option<usize> {
+        let (hay, range) = span.into_parts();
+        let len = self.forward.len();
+        if range.start + len <= range.end && self.strand_at(hay, range.end - len).is_some() {
+            Some(range.end - len)
+        } else {
+            None
+        }
+    }
+}
+
+/// Iterator over the occurrences of a DNA needle on either strand.
+///
+/// Created by [`EitherStrand::match_ranges`](struct.EitherStrand.html#method.match_ranges).
+#[derive(Debug, Clone)]
+pub struct StrandMatchRanges<'h, 'p> {
+    hay: &'h [u8],
+    // Moved past the end of `hay` once the iterator is exhausted.
+    position: usize,
+    searcher: EitherStrandSearcher<'p>,
+}
+
+impl<'h, 'p> Iterator for StrandMatchRanges<'h, 'p> {
+    type Item = (Range<usize>, Strand);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let len = self.searcher.forward.len();
+        if self.position > self.hay.len() {
+            return None;
+        }
+        if len == 0 {
+            let index = self.position;
+            self.position += 1;
+            return Some((index..index, Strand::Forward));
+        }
+        let range = self.position..self.hay.len();
+        match self.searcher.search_strand(self.hay, range) {
+            Some((index, strand)) => {
+                self.position = index + len;
+                Some((index..(index + len), strand))
+            }
+            None => {
+                self.position = self.hay.len() + 1;
+                None
+            }
+        }
+    }
+}
+
+impl<'h, 'p> FusedIterator for StrandMatchRanges<'h, 'p> {}
+
+macro_rules! impl_pattern {
+    (<[$($gen:tt)*]> $ty:ty) => {
+        impl<$($gen)*> Pattern<$ty> for EitherStrand<'p> {
+            type Searcher = EitherStrandSearcher<'p>;
+
+            #[inline]
+            fn into_searcher(self) -> Self::Searcher {
+                EitherStrandSearcher::new(self.0)
+            }
+        }
+    }
+}
+
+impl_pattern!(<['p, 'h]> &'h [u8]);
+impl_pattern!(<['p, 'h]> &'h mut [u8]);
+impl_pattern!(<['p, A: Allocator + Clone]> Vec<u8, A>);
+impl_pattern!(<['p, A: Allocator + Clone]> Box<[u8], A>);
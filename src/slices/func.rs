@@ -1,11 +1,22 @@
 use pattern::*;
 use haystack::Span;
+use combinators::progress::BoundedMatches;
 use std::ops::Range;
+#[cfg(feature = "std")]
+use std::alloc::Allocator;
 
+#[derive(Clone)]
 pub struct ElemSearcher<F> {
     predicate: F,
 }
 
+impl<F> BoundedMatches for ElemSearcher<F> {
+    #[inline]
+    fn max_match_len(&self) -> Option<usize> {
+        Some(1)
+    }
+}
+
 macro_rules! impl_pattern {
     (<[$($gen:tt)*]> $ty:ty) => {
         impl<$($gen)*> Pattern<$ty> for F
@@ -27,7 +38,9 @@ macro_rules! impl_pattern {
 impl_pattern!(<['h, T, F]> &'h [T]);
 impl_pattern!(<['h, T, F]> &'h mut [T]);
 #[cfg(feature = "std")]
-impl_pattern!(<[T, F]> Vec<T>);
+impl_pattern!(<[T, F, A: Allocator + Clone]> Vec<T, A>);
+#[cfg(feature = "std")]
+impl_pattern!(<[T, F, A: Allocator + Clone]> Box<[T], A>);
 
 unsafe impl<T, F> Searcher<[T]> for ElemSearcher<F>
 where
@@ -104,3 +117,8 @@ unsafe impl<T, F> DoubleEndedSearcher<[T]> for ElemSearcher<F>
 where
     F: FnMut(&T) -> bool,
 {}
+
+impl<T, F> FusedSearcher<[T]> for ElemSearcher<F>
+where
+    F: FnMut(&T) -> bool,
+{}
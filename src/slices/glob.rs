@@ -0,0 +1,247 @@
+use pattern::*;
+use haystack::{Haystack, Span};
+use byte_hay::ByteHay;
+use std::ops::Range;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Literal(Box<[u8]>),
+    // `?`, matching a single codeword.
+    Any,
+}
+
+/// A shell-style wildcard pattern.
+///
+/// `*` matches any sequence of codewords, `?` matches a single one (a `char`
+/// in a `str`, a byte in a `[u8]`), and `\` escapes the next character.
+///
+/// When searching, `*` matches as little as possible, like `.*?` in a regular
+/// expression, so every match is the shortest one starting at the leftmost
+/// (or, when searching backwards, ending at the rightmost) position. A glob
+/// made of stars only therefore behaves like the empty pattern.
+///
+/// ```rust
+/// extern crate pattern_3;
+/// use pattern_3::Glob;
+/// use pattern_3::ext::{find_range, matches};
+///
+/// let glob = Glob::new("img_??.*g");
+/// assert_eq!(matches("img_01.png img_2.jpg img_03.jpeg", &glob).collect::<Vec<_>>(), vec!["img_01.png", "img_03.jpeg"]);
+/// assert_eq!(find_range("see [note]", &Glob::new(r"\[*]")), Some(4..10));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Glob {
+    // The token sequences between stars. A glob without stars has a single
+    // segment.
+    segments: Vec<Vec<Token>>,
+}
+
+impl Glob {
+    /// Compiles a wildcard pattern. A trailing `\` matches itself.
+    pub fn new(pattern: &str) -> Self {
+        let mut segments = vec![Vec::new()];
+        let mut literal = String::new();
+        let mut chars = pattern.chars();
+        while let Some(c) = chars.next() {
+            let token = match c {
+                '*' => None,
+                '?' => Some(Token::Any),
+                '\\' => {
+                    literal.push(chars.next().unwrap_or('\\'));
+                    continue;
+                }
+                c => {
+                    literal.push(c);
+                    continue;
+                }
+            };
+            let segment = segments.last_mut().unwrap();
+            if !literal.is_empty() {
+                segment.push(Token::Literal(literal.into_bytes().into_boxed_slice()));
+                literal = String::new();
+            }
+            match token {
+                Some(token) => segment.push(token),
+                None => segments.push(Vec::new()),
+            }
+        }
+        if !literal.is_empty() {
+            segments.last_mut().unwrap().push(Token::Literal(literal.into_bytes().into_boxed_slice()));
+        }
+        Glob { segments }
+    }
+
+    // Whether the glob is made of stars only and matches empty strings alone.
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.segments.iter().all(|s| s.is_empty())
+    }
+}
+
+/// Returns the end of the segment matched at `pos`.
+fn match_segment<T: ByteHay + ?Sized>(segment: &[Token], hay: &T, mut pos: usize, end: usize) -> Option<usize> {
+    for token in segment {
+        match token {
+            Token::Literal(literal) => {
+                if !hay.as_bytes()[pos..end].starts_with(literal) {
+                    return None;
+                }
+                pos += literal.len();
+            }
+            Token::Any => {
+                if pos == end {
+                    return None;
+                }
+                pos = unsafe { hay.next_index(pos) };
+            }
+        }
+    }
+    Some(pos)
+}
+
+/// Returns the start of the segment matched ending at `pos`.
+fn rmatch_segment<T: ByteHay + ?Sized>(segment: &[Token], hay: &T, start: usize, mut pos: usize) -> Option<usize> {
+    for token in segment.iter().rev() {
+        match token {
+            Token::Literal(literal) => {
+                if !hay.as_bytes()[start..pos].ends_with(literal) {
+                    return None;
+                }
+                pos -= literal.len();
+            }
+            Token::Any => {
+                if pos == start {
+                    return None;
+                }
+                pos = unsafe { hay.prev_index(pos) };
+            }
+        }
+    }
+    Some(pos)
+}
+
+#[derive(Debug, Clone)]
+pub struct GlobSearcher<'p> {
+    glob: &'p Glob,
+    empty: EmptySearcher,
+}
+
+impl<'p> GlobSearcher<'p> {
+    /// Matches the segments after the first one, each at its earliest
+    /// position from `pos`, returning the end of the match.
+    fn match_rest<T: ByteHay + ?Sized>(&self, hay: &T, mut pos: usize, end: usize) -> Option<usize> {
+        for segment in &self.glob.segments[1..] {
+            loop {
+                if hay.is_boundary(pos) {
+                    if let Some(e) = match_segment(segment, hay, pos, end) {
+                        pos = e;
+                        break;
+                    }
+                }
+                if pos == end {
+                    return None;
+                }
+                pos += 1;
+            }
+        }
+        Some(pos)
+    }
+
+    /// Matches the segments before the last one, each at its latest position
+    /// up to `pos`, returning the start of the match.
+    fn rmatch_rest<T: ByteHay + ?Sized>(&self, hay: &T, start: usize, mut pos: usize) -> Option<usize> {
+        let segments = &self.glob.segments;
+        for segment in segments[..(segments.len() - 1)].iter().rev() {
+            loop {
+                if hay.is_boundary(pos) {
+                    if let Some(s) = rmatch_segment(segment, hay, start, pos) {
+                        pos = s;
+                        break;
+                    }
+                }
+                if pos == start {
+                    return None;
+                }
+                pos -= 1;
+            }
+        }
+        Some(pos)
+    }
+}
+
+unsafe impl<'p, T: ByteHay + ?Sized> Searcher<T> for GlobSearcher<'p> {
+    fn search(&mut self, span: Span<&T>) -> Option<Range<usize>> {
+        if self.glob.is_empty() {
+            return self.empty.search(span);
+        }
+        let (hay, range) = span.into_parts();
+        let first = &self.glob.segments[0];
+        let mut start = range.start;
+        loop {
+            if let Some(pos) = match_segment(first, hay, start, range.end) {
+                match self.match_rest(hay, pos, range.end) {
+                    Some(end) => return Some(start..end),
+                    // a later start would leave even less room for the rest.
+                    None if self.glob.segments.len() > 1 => return None,
+                    None => {}
+                }
+            }
+            if start == range.end {
+                return None;
+            }
+            start = unsafe { hay.next_index(start) };
+        }
+    }
+
+    fn consume(&mut self, span: Span<&T>) -> Option<usize> {
+        let (hay, range) = span.into_parts();
+        let pos = match_segment(&self.glob.segments[0], hay, range.start, range.end)?;
+        self.match_rest(hay, pos, range.end)
+    }
+}
+
+unsafe impl<'p, T: ByteHay + ?Sized> ReverseSearcher<T> for GlobSearcher<'p> {
+    fn rsearch(&mut self, span: Span<&T>) -> Option<Range<usize>> {
+        if self.glob.is_empty() {
+            return self.empty.rsearch(span);
+        }
+        let (hay, range) = span.into_parts();
+        let last = &self.glob.segments[self.glob.segments.len() - 1];
+        let mut end = range.end;
+        loop {
+            if let Some(pos) = rmatch_segment(last, hay, range.start, end) {
+                match self.rmatch_rest(hay, range.start, pos) {
+                    Some(start) => return Some(start..end),
+                    None if self.glob.segments.len() > 1 => return None,
+                    None => {}
+                }
+            }
+            if end == range.start {
+                return None;
+            }
+            end = unsafe { hay.prev_index(end) };
+        }
+    }
+
+    fn rconsume(&mut self, span: Span<&T>) -> Option<usize> {
+        let (hay, range) = span.into_parts();
+        let last = &self.glob.segments[self.glob.segments.len() - 1];
+        let pos = rmatch_segment(last, hay, range.start, range.end)?;
+        self.rmatch_rest(hay, range.start, pos)
+    }
+}
+
+impl<'p, H: Haystack> Pattern<H> for &'p Glob
+where
+    H::Hay: ByteHay,
+{
+    type Searcher = GlobSearcher<'p>;
+
+    #[inline]
+    fn into_searcher(self) -> Self::Searcher {
+        GlobSearcher {
+            glob: self,
+            empty: EmptySearcher::default(),
+        }
+    }
+}
@@ -0,0 +1,137 @@
+use std::iter::FusedIterator;
+
+/// A lazily produced sequence, searched without collecting it.
+///
+/// The iterator is cloned to look ahead from every candidate position, so it
+/// should be cheap to clone (e.g. a `Chars`, a `Map` over a range, or a
+/// `Cycle().take()`). Positions are expressed as item counts from the start.
+///
+/// Like [`Rle`](struct.Rle.html), this is not a [`Hay`](trait.Hay.html), since
+/// an iterator cannot lend out a sub-range of itself by reference. The
+/// supported needles are single elements (`char` and `u8`), predicates
+/// `FnMut(&T) -> bool` and short slices `&[T]`; see
+/// [`LazyNeedle`](trait.LazyNeedle.html).
+///
+/// ```rust
+/// extern crate pattern_3;
+/// use pattern_3::Lazy;
+///
+/// let squares = Lazy((1..).map(|i: u32| i * i).take(100));
+/// assert_eq!(squares.find(|x: &u32| *x > 50), Some(7));
+/// assert_eq!(squares.find(&[49, 64][..]), Some(6));
+/// assert!(squares.starts_with(&[1, 4, 9][..]));
+///
+/// let text = Lazy("banana".chars());
+/// assert_eq!(text.match_indices('a').collect::<Vec<_>>(), vec![1, 3, 5]);
+/// assert_eq!(text.match_indices(&['a', 'n', 'a'][..]).collect::<Vec<_>>(), vec![1]);
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct Lazy<I>(pub I);
+
+/// Needles which can be matched against a lazily produced sequence.
+pub trait LazyNeedle<T> {
+    /// If the needle matches at the start of `items`, returns the number of
+    /// items it covers.
+    fn match_prefix<I: Iterator<Item = T>>(&mut self, items: I) -> Option<usize>;
+}
+
+impl<'p, T: PartialEq + 'p> LazyNeedle<T> for &'p [T] {
+    #[inline]
+    fn match_prefix<I: Iterator<Item = T>>(&mut self, mut items: I) -> Option<usize> {
+        for expected in self.iter() {
+            if items.next()? != *expected {
+                return None;
+            }
+        }
+        Some(self.len())
+    }
+}
+
+impl<T, F: FnMut(&T) -> bool> LazyNeedle<T> for F {
+    #[inline]
+    fn match_prefix<I: Iterator<Item = T>>(&mut self, mut items: I) -> Option<usize> {
+        let item = items.next()?;
+        if self(&item) { Some(1) } else { None }
+    }
+}
+
+macro_rules! impl_elem_needle {
+    ($ty:ty) => {
+        impl LazyNeedle<$ty> for $ty {
+            #[inline]
+            fn match_prefix<I: Iterator<Item = $ty>>(&mut self, mut items: I) -> Option<usize> {
+                if items.next()? == *self { Some(1) } else { None }
+            }
+        }
+    }
+}
+
+impl_elem_needle!(char);
+impl_elem_needle!(u8);
+
+impl<I: Iterator + Clone> Lazy<I> {
+    /// Returns the position of the first occurrence of `needle`.
+    #[inline]
+    pub fn find<N: LazyNeedle<I::Item>>(&self, needle: N) -> Option<usize> {
+        self.match_indices(needle).next()
+    }
+
+    /// Returns whether `needle` occurs at the start of the sequence.
+    #[inline]
+    pub fn starts_with<N: LazyNeedle<I::Item>>(&self, mut needle: N) -> bool {
+        needle.match_prefix(self.0.clone()).is_some()
+    }
+
+    /// Returns an iterator over the positions of non-overlapping occurrences
+    /// of `needle`.
+    #[inline]
+    pub fn match_indices<N: LazyNeedle<I::Item>>(&self, needle: N) -> LazyMatchIndices<I, N> {
+        LazyMatchIndices {
+            items: self.0.clone(),
+            needle,
+            position: 0,
+            finished: false,
+        }
+    }
+}
+
+/// Iterator over the positions of non-overlapping occurrences of a needle in
+/// a [`Lazy`](struct.Lazy.html) sequence.
+#[derive(Clone, Debug)]
+pub struct LazyMatchIndices<I, N> {
+    items: I,
+    needle: N,
+    position: usize,
+    finished: bool,
+}
+
+impl<I: Iterator + Clone, N: LazyNeedle<I::Item>> Iterator for LazyMatchIndices<I, N> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        while !self.finished {
+            let start = self.position;
+            match self.needle.match_prefix(self.items.clone()) {
+                Some(len) if len > 0 => {
+                    self.items.nth(len - 1);
+                    self.position += len;
+                    return Some(start);
+                }
+                matched => {
+                    // An empty match still advances by one item, so that it
+                    // is reported at every position including the end.
+                    if self.items.next().is_none() {
+                        self.finished = true;
+                    }
+                    self.position += 1;
+                    if matched.is_some() {
+                        return Some(start);
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+impl<I: Iterator + Clone, N: LazyNeedle<I::Item>> FusedIterator for LazyMatchIndices<I, N> {}
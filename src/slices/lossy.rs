@@ -0,0 +1,219 @@
+use pattern::*;
+use haystack::Span;
+use strings::func::MultiCharEq;
+use std::char;
+use std::ops::Range;
+#[cfg(feature = "std")]
+use std::alloc::Allocator;
+
+const REPLACEMENT: char = '\u{fffd}';
+
+/// Decodes the first character of a non-empty byte string, returning the
+/// character and its length in bytes.
+///
+/// An invalid sequence is decoded as a single U+FFFD REPLACEMENT CHARACTER
+/// covering its maximal subpart, the same substitution used by `bstr` and
+/// `String::from_utf8_lossy`.
+fn decode_first(bytes: &[u8]) -> (char, usize) {
+    let b0 = bytes[0];
+    let (len, lo, hi) = match b0 {
+        0x00..=0x7f => return (b0 as char, 1),
+        0xc2..=0xdf => (2, 0x80, 0xbf),
+        0xe0 => (3, 0xa0, 0xbf),
+        0xed => (3, 0x80, 0x9f),
+        0xe1..=0xef => (3, 0x80, 0xbf),
+        0xf0 => (4, 0x90, 0xbf),
+        0xf1..=0xf3 => (4, 0x80, 0xbf),
+        0xf4 => (4, 0x80, 0x8f),
+        _ => return (REPLACEMENT, 1),
+    };
+    let mut code = (b0 as u32) & (0x7f >> len);
+    for i in 1..len {
+        let (lo, hi) = if i == 1 { (lo, hi) } else { (0x80, 0xbf) };
+        match bytes.get(i) {
+            Some(&b) if lo <= b && b <= hi => code = (code << 6) | (b as u32 & 0x3f),
+            _ => return (REPLACEMENT, i),
+        }
+    }
+    (unsafe { char::from_u32_unchecked(code) }, len)
+}
+
+/// Decodes the last character of a non-empty byte string, returning the
+/// character and its length in bytes.
+///
+/// This agrees with the segmentation produced by repeatedly calling
+/// `decode_first` from the front.
+fn decode_last(bytes: &[u8]) -> (char, usize) {
+    let end = bytes.len();
+    let limit = end.saturating_sub(4);
+    let mut start = end - 1;
+    while start > limit && bytes[start] & 0xc0 == 0x80 {
+        start -= 1;
+    }
+    match decode_first(&bytes[start..]) {
+        (c, len) if start + len == end => (c, len),
+        _ => (REPLACEMENT, 1),
+    }
+}
+
+/// Predicate matching a single `char`, used as the `char` pattern on byte
+/// strings.
+#[derive(Copy, Clone, Debug)]
+pub struct LossyCharEq(pub(crate) char);
+
+impl FnOnce<(char,)> for LossyCharEq {
+    type Output = bool;
+    #[inline]
+    extern "rust-call" fn call_once(self, args: (char,)) -> bool {
+        self.call(args)
+    }
+}
+
+impl FnMut<(char,)> for LossyCharEq {
+    #[inline]
+    extern "rust-call" fn call_mut(&mut self, args: (char,)) -> bool {
+        self.call(args)
+    }
+}
+
+impl Fn<(char,)> for LossyCharEq {
+    #[inline]
+    extern "rust-call" fn call(&self, (c,): (char,)) -> bool {
+        self.0 == c
+    }
+}
+
+/// A `char`, `&[char]` or `char` predicate applied to byte slices decoded as
+/// lossy UTF-8.
+///
+/// A closure `FnMut(&u8) -> bool` already matches a single byte of a `[u8]`
+/// haystack, and a bare `char` or `&[char]` is a pattern of every
+/// `Haystack<Hay = str>`, so these have to be wrapped to select the lossy
+/// decoding instead.
+///
+/// Every invalid UTF-8 sequence (maximal subpart) is seen by the predicate as
+/// U+FFFD REPLACEMENT CHARACTER, and a match covers the bytes of that
+/// sequence, mirroring `bstr`.
+///
+/// ```rust
+/// extern crate pattern_3;
+/// use pattern_3::Lossy;
+/// use pattern_3::ext::{find, trim};
+///
+/// # fn main() {
+/// let hay = &b" \t\xffna\xc3\xafve \n"[..];
+/// assert_eq!(trim(hay, Lossy(char::is_whitespace)), &b"\xffna\xc3\xafve"[..]);
+/// assert_eq!(find(hay, Lossy('ï')), Some(5));
+/// assert_eq!(find(hay, Lossy('\u{fffd}')), Some(2));
+/// # }
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct Lossy<P>(pub P);
+
+/// Searcher of `char` predicates over byte slices, decoded as lossy UTF-8.
+#[derive(Clone, Debug)]
+pub struct LossySearcher<F> {
+    predicate: F,
+}
+
+unsafe impl<F: FnMut(char) -> bool> Searcher<[u8]> for LossySearcher<F> {
+    #[inline]
+    fn search(&mut self, span: Span<&[u8]>) -> Option<Range<usize>> {
+        let (hay, range) = span.into_parts();
+        let mut pos = range.start;
+        while pos < range.end {
+            let (c, len) = decode_first(&hay[pos..range.end]);
+            if (self.predicate)(c) {
+                return Some(pos..(pos + len));
+            }
+            pos += len;
+        }
+        None
+    }
+
+    #[inline]
+    fn consume(&mut self, span: Span<&[u8]>) -> Option<usize> {
+        let (hay, range) = span.into_parts();
+        if range.start == range.end {
+            return None;
+        }
+        let (c, len) = decode_first(&hay[range.clone()]);
+        if (self.predicate)(c) {
+            Some(range.start + len)
+        } else {
+            None
+        }
+    }
+}
+
+unsafe impl<F: FnMut(char) -> bool> ReverseSearcher<[u8]> for LossySearcher<F> {
+    #[inline]
+    fn rsearch(&mut self, span: Span<&[u8]>) -> Option<Range<usize>> {
+        let (hay, range) = span.into_parts();
+        let mut pos = range.end;
+        while pos > range.start {
+            let (c, len) = decode_last(&hay[range.start..pos]);
+            if (self.predicate)(c) {
+                return Some((pos - len)..pos);
+            }
+            pos -= len;
+        }
+        None
+    }
+
+    #[inline]
+    fn rconsume(&mut self, span: Span<&[u8]>) -> Option<usize> {
+        let (hay, range) = span.into_parts();
+        if range.start == range.end {
+            return None;
+        }
+        let (c, len) = decode_last(&hay[range.clone()]);
+        if (self.predicate)(c) {
+            Some(range.end - len)
+        } else {
+            None
+        }
+    }
+}
+
+unsafe impl<F: FnMut(char) -> bool> DoubleEndedSearcher<[u8]> for LossySearcher<F> {}
+
+impl<F: FnMut(char) -> bool> FusedSearcher<[u8]> for LossySearcher<F> {}
+
+macro_rules! impl_pattern {
+    (<[$($gen:tt)*]> $ty:ty) => {
+        impl<$($gen)*> Pattern<$ty> for Lossy<char> {
+            type Searcher = LossySearcher<LossyCharEq>;
+
+            #[inline]
+            fn into_searcher(self) -> Self::Searcher {
+                LossySearcher { predicate: LossyCharEq(self.0) }
+            }
+        }
+
+        impl<'p, $($gen)*> Pattern<$ty> for Lossy<&'p [char]> {
+            type Searcher = LossySearcher<MultiCharEq<'p>>;
+
+            #[inline]
+            fn into_searcher(self) -> Self::Searcher {
+                LossySearcher { predicate: MultiCharEq(self.0) }
+            }
+        }
+
+        impl<$($gen)* F: FnMut(char) -> bool> Pattern<$ty> for Lossy<F> {
+            type Searcher = LossySearcher<F>;
+
+            #[inline]
+            fn into_searcher(self) -> Self::Searcher {
+                LossySearcher { predicate: self.0 }
+            }
+        }
+    }
+}
+
+impl_pattern!(<['h,]> &'h [u8]);
+impl_pattern!(<['h,]> &'h mut [u8]);
+#[cfg(feature = "std")]
+impl_pattern!(<[A: Allocator + Clone,]> Vec<u8, A>);
+#[cfg(feature = "std")]
+impl_pattern!(<[A: Allocator + Clone,]> Box<[u8], A>);
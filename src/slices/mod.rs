@@ -1,5 +1,7 @@
 use haystack::{Hay, Haystack};
 use std::ops::Range;
+#[cfg(feature = "std")]
+use std::alloc::Allocator;
 
 impl<T> Hay for [T] {
     type Index = usize;
@@ -36,8 +38,15 @@ impl<T> Hay for [T] {
 }
 
 impl<'h, T: 'h> Haystack for &'h mut [T] {
+    type Hay = [T];
+
     #[inline]
-    fn empty() -> Self {
+    fn hay(&self) -> &[T] {
+        self
+    }
+
+    #[inline]
+    fn empty(&self) -> Self {
         &mut []
     }
 
@@ -60,10 +69,17 @@ impl<'h, T: 'h> Haystack for &'h mut [T] {
 }
 
 #[cfg(feature = "std")]
-impl<T> Haystack for Vec<T> {
+impl<T, A: Allocator + Clone> Haystack for Vec<T, A> {
+    type Hay = [T];
+
     #[inline]
-    fn empty() -> Self {
-        Vec::new()
+    fn hay(&self) -> &[T] {
+        self
+    }
+
+    #[inline]
+    fn empty(&self) -> Self {
+        Vec::new_in(self.allocator().clone())
     }
 
     #[inline]
@@ -86,5 +102,94 @@ impl<T> Haystack for Vec<T> {
     }
 }
 
+#[cfg(feature = "std")]
+impl<T, A: Allocator + Clone> Haystack for Box<[T], A> {
+    type Hay = [T];
+
+    #[inline]
+    fn hay(&self) -> &[T] {
+        self
+    }
+
+    #[inline]
+    fn empty(&self) -> Self {
+        Vec::new_in(Box::allocator(self).clone()).into_boxed_slice()
+    }
+
+    #[inline]
+    unsafe fn slice_unchecked(self, range: Range<usize>) -> Self {
+        Haystack::slice_unchecked(self.into_vec(), range).into_boxed_slice()
+    }
+
+    #[inline]
+    unsafe fn split_around(self, range: Range<usize>) -> [Self; 3] {
+        let [left, middle, right] = self.into_vec().split_around(range);
+        [left.into_boxed_slice(), middle.into_boxed_slice(), right.into_boxed_slice()]
+    }
+
+    #[inline]
+    fn restore_range(&self, range: Range<usize>, subrange: Range<usize>) -> Range<usize> {
+        (subrange.start + range.start)..(subrange.end + range.start)
+    }
+}
+
 mod func;
 pub(crate) mod slice;
+mod algorithm;
+mod simd;
+mod const_needle;
+mod sorted;
+mod approx;
+mod ascii;
+mod ascii_case;
+mod any_of;
+mod byte_set;
+pub(crate) mod lossy;
+mod lazy;
+#[cfg(feature = "std")]
+mod cached;
+#[cfg(feature = "std")]
+mod owned;
+#[cfg(feature = "std")]
+mod rle;
+#[cfg(feature = "std")]
+mod chunked;
+#[cfg(feature = "std")]
+mod dna;
+#[cfg(feature = "std")]
+mod cow;
+#[cfg(feature = "std")]
+mod glob;
+#[cfg(feature = "bytes")]
+mod bytes;
+
+pub use self::algorithm::{Algorithm, Needle, SearcherBuilder};
+pub use self::const_needle::{ConstNeedle, ConstNeedleSearcher};
+pub use self::sorted::{Sorted, SortedRun, SortedSliceSearcher};
+pub use self::approx::{Approx, ApproxSearcher};
+pub use self::ascii::{AsciiWhitespaceByte, AsciiDigit, AsciiAlphanumeric, ByteClassSearcher};
+pub use self::any_of::{AnyOf, AnyOfSearcher, LongestOf, MatchedNeedle};
+pub use self::byte_set::ByteSetSearcher;
+pub use self::ascii_case::{AsciiCaseInsensitive, AsciiCaseInsensitiveSearcher};
+pub use self::lossy::{Lossy, LossySearcher};
+pub use self::lazy::{Lazy, LazyNeedle, LazyMatchIndices};
+#[cfg(feature = "std")]
+pub use self::cached::Cached;
+#[cfg(feature = "std")]
+pub use self::owned::{ByteNeedle, StrNeedle, Lit, OwnedSliceSearcher};
+#[cfg(feature = "std")]
+pub use self::rle::{Rle, RleMatchIndices};
+#[cfg(feature = "std")]
+pub use self::chunked::{ChunkedHay, ChunkedSearcher, ChunkedReverseSearcher, ChunkCursor};
+#[cfg(feature = "std")]
+pub use self::chunked::{ChunkedMatchIndices, ChunkedMatchRanges, ChunkedRMatchIndices, ChunkedRMatchRanges};
+#[cfg(feature = "std")]
+pub use self::chunked::{ChunkedSplitRanges, ChunkedRSplitRanges};
+#[cfg(feature = "std")]
+pub use self::chunked::{Concat, ConcatMatchPositions};
+#[cfg(feature = "std")]
+pub use self::dna::{EitherStrand, EitherStrandSearcher, Strand, StrandMatchRanges};
+#[cfg(feature = "std")]
+pub use self::cow::CowSliceSearcher;
+#[cfg(feature = "std")]
+pub use self::glob::{Glob, GlobSearcher};
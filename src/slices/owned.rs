@@ -0,0 +1,246 @@
+use pattern::*;
+use haystack::{Hay, Haystack, Span};
+use std::fmt;
+use std::ops::{Deref, Range};
+
+use super::slice::{SliceSearcher, SliceHay, TwoWaySearcher, TwoWayParams};
+
+//------------------------------------------------------------------------------
+// Needle storage
+//------------------------------------------------------------------------------
+
+const INLINE_CAPACITY: usize = 23;
+
+/// Byte storage which keeps short needles inline instead of allocating.
+#[derive(Clone)]
+enum NeedleBuf {
+    Inline(u8, [u8; INLINE_CAPACITY]),
+    Heap(Box<[u8]>),
+}
+
+impl NeedleBuf {
+    fn new(bytes: &[u8]) -> Self {
+        if bytes.len() <= INLINE_CAPACITY {
+            let mut buf = [0; INLINE_CAPACITY];
+            buf[..bytes.len()].copy_from_slice(bytes);
+            NeedleBuf::Inline(bytes.len() as u8, buf)
+        } else {
+            NeedleBuf::Heap(bytes.into())
+        }
+    }
+}
+
+impl Deref for NeedleBuf {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &[u8] {
+        match self {
+            NeedleBuf::Inline(len, buf) => &buf[..(*len as usize)],
+            NeedleBuf::Heap(buf) => buf,
+        }
+    }
+}
+
+//------------------------------------------------------------------------------
+// Owned searcher
+//------------------------------------------------------------------------------
+
+/// Searcher for owned needles.
+///
+/// The needle is preprocessed once when the needle is created. As the Two-Way
+/// searcher keeps no state between matches, a borrowing searcher can be
+/// rebuilt from these constants for every search without repeating the work.
+#[derive(Clone)]
+pub struct OwnedSliceSearcher {
+    needle: NeedleBuf,
+    params: Option<TwoWayParams>,
+    empty: EmptySearcher,
+}
+
+impl fmt::Debug for OwnedSliceSearcher {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("OwnedSliceSearcher")
+            .field("needle", &&*self.needle)
+            .finish()
+    }
+}
+
+impl OwnedSliceSearcher {
+    pub(crate) fn new(bytes: &[u8]) -> Self {
+        let needle = NeedleBuf::new(bytes);
+        let params = if needle.is_empty() {
+            None
+        } else {
+            Some(TwoWaySearcher::new(&*needle).params())
+        };
+        OwnedSliceSearcher {
+            needle,
+            params,
+            empty: EmptySearcher::default(),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn two_way(&self) -> Option<SliceSearcher<'_, u8>> {
+        let params = self.params?;
+        Some(SliceSearcher::TwoWay(TwoWaySearcher::from_params(&self.needle, params)))
+    }
+
+    #[inline]
+    fn naive(&self) -> SliceSearcher<'_, u8> {
+        SliceSearcher::new_consumer(&self.needle)
+    }
+}
+
+unsafe impl<A> Searcher<A> for OwnedSliceSearcher
+where
+    A: Hay<Index = usize> + ?Sized,
+    for<'p> SliceSearcher<'p, u8>: Searcher<A>,
+{
+    #[inline]
+    fn search(&mut self, span: Span<&A>) -> Option<Range<usize>> {
+        match self.two_way() {
+            Some(mut searcher) => searcher.search(span),
+            None => self.empty.search(span),
+        }
+    }
+
+    #[inline]
+    fn consume(&mut self, span: Span<&A>) -> Option<usize> {
+        self.naive().consume(span)
+    }
+
+    #[inline]
+    fn trim_start(&mut self, hay: &A) -> usize {
+        self.naive().trim_start(hay)
+    }
+}
+
+unsafe impl<A> ReverseSearcher<A> for OwnedSliceSearcher
+where
+    A: Hay<Index = usize> + ?Sized,
+    for<'p> SliceSearcher<'p, u8>: ReverseSearcher<A>,
+{
+    #[inline]
+    fn rsearch(&mut self, span: Span<&A>) -> Option<Range<usize>> {
+        match self.two_way() {
+            Some(mut searcher) => searcher.rsearch(span),
+            None => self.empty.rsearch(span),
+        }
+    }
+
+    #[inline]
+    fn rconsume(&mut self, span: Span<&A>) -> Option<usize> {
+        self.naive().rconsume(span)
+    }
+
+    #[inline]
+    fn trim_end(&mut self, hay: &A) -> usize {
+        self.naive().trim_end(hay)
+    }
+}
+
+//------------------------------------------------------------------------------
+// Owned needles
+//------------------------------------------------------------------------------
+
+/// An owned byte-string needle.
+///
+/// Needles up to 23 bytes long are stored inline, so creating and cloning
+/// them does not allocate.
+///
+/// ```rust
+/// extern crate pattern_3;
+/// use pattern_3::ByteNeedle;
+/// use pattern_3::ext::split;
+///
+/// let delim = ByteNeedle::new(b"\r\n");
+/// let lines = split(&b"a\r\nb"[..], delim.clone()).collect::<Vec<_>>();
+/// assert_eq!(lines, vec![&b"a"[..], &b"b"[..]]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ByteNeedle(OwnedSliceSearcher);
+
+impl ByteNeedle {
+    pub fn new(needle: &[u8]) -> Self {
+        ByteNeedle(OwnedSliceSearcher::new(needle))
+    }
+
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0.needle
+    }
+}
+
+/// An owned string needle.
+///
+/// Needles up to 23 bytes long are stored inline, so creating and cloning
+/// them does not allocate.
+#[derive(Debug, Clone)]
+pub struct StrNeedle(OwnedSliceSearcher);
+
+impl StrNeedle {
+    pub fn new(needle: &str) -> Self {
+        StrNeedle(OwnedSliceSearcher::new(needle.as_bytes()))
+    }
+
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        unsafe { ::std::str::from_utf8_unchecked(&self.0.needle) }
+    }
+}
+
+/// Wraps any string or byte-string type to be used as a needle.
+///
+/// This allows owned or shared containers such as `String`, `Rc<str>` and
+/// `Cow<str>` to be passed as patterns directly. `Lit(n)` searches any
+/// haystack whose hay `n` can be borrowed as, e.g. `str` for `AsRef<str>` and
+/// `[u8]` for `AsRef<[u8]>`. The needle is copied into the searcher, which
+/// does not allocate for needles up to 23 bytes long.
+///
+/// ```rust
+/// extern crate pattern_3;
+/// use pattern_3::Lit;
+/// use pattern_3::ext::{find, split};
+/// use std::rc::Rc;
+///
+/// let sep: Rc<str> = Rc::from(", ");
+/// assert_eq!(split("a, b", Lit(sep)).collect::<Vec<_>>(), vec!["a", "b"]);
+/// assert_eq!(find(&b"key=value"[..], Lit(String::from("="))), Some(3));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Lit<N>(pub N);
+
+impl<H, N> Pattern<H> for Lit<N>
+where
+    H: Haystack,
+    H::Hay: SliceHay<u8>,
+    N: AsRef<H::Hay>,
+    OwnedSliceSearcher: Searcher<H::Hay>,
+{
+    type Searcher = OwnedSliceSearcher;
+
+    #[inline]
+    fn into_searcher(self) -> Self::Searcher {
+        OwnedSliceSearcher::new(self.0.as_ref().as_slice())
+    }
+}
+
+impl<H: Haystack<Hay = str>> Pattern<H> for StrNeedle {
+    type Searcher = OwnedSliceSearcher;
+
+    #[inline]
+    fn into_searcher(self) -> Self::Searcher {
+        self.0
+    }
+}
+
+impl<H: Haystack<Hay = [u8]>> Pattern<H> for ByteNeedle {
+    type Searcher = OwnedSliceSearcher;
+
+    #[inline]
+    fn into_searcher(self) -> Self::Searcher {
+        self.0
+    }
+}
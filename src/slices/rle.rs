@@ -0,0 +1,250 @@
+use std::iter::FusedIterator;
+
+/// A run-length encoded sequence.
+///
+/// The sequence is stored as runs of `(value, count)`. Positions passed to
+/// and returned from the methods below address the logical (expanded)
+/// sequence, but searching works on whole runs and never materializes the
+/// expanded sequence, so a search costs time proportional to the number of
+/// runs rather than the logical length.
+///
+/// `Rle` is not a [`Hay`](../trait.Hay.html): a hay must be able to lend out
+/// any sub-range of itself by reference, and a range starting or ending in
+/// the middle of a run cannot be represented without copying that run.
+///
+/// ```rust
+/// extern crate pattern_3;
+/// use pattern_3::Rle;
+///
+/// let rle = Rle::from_runs(vec![(0, 1000), (7, 2), (0, 5000), (7, 2), (0, 3)]);
+/// assert_eq!(rle.len(), 6007);
+/// assert_eq!(rle.find(&[0, 7, 7, 0]), Some(999));
+/// assert_eq!(rle.rfind(&[7, 7]), Some(6002));
+/// assert_eq!(rle.match_indices(&[0, 7]).collect::<Vec<_>>(), vec![999, 6001]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Rle<T> {
+    runs: Vec<(T, usize)>,
+    // The logical end position of every run.
+    ends: Vec<usize>,
+}
+
+impl<T: PartialEq> Rle<T> {
+    /// Creates a sequence from runs of `(value, count)`.
+    ///
+    /// Empty runs are dropped and adjacent runs of equal values are merged.
+    pub fn from_runs<I>(runs: I) -> Self
+    where
+        I: IntoIterator<Item = (T, usize)>,
+    {
+        let mut rle = Rle { runs: Vec::new(), ends: Vec::new() };
+        for (value, count) in runs {
+            rle.push_run(value, count);
+        }
+        rle
+    }
+
+    /// Run-length encodes a slice.
+    pub fn encode(slice: &[T]) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_runs(slice.iter().map(|x| (x.clone(), 1)))
+    }
+
+    /// Appends `count` copies of `value` to the end of the sequence.
+    pub fn push_run(&mut self, value: T, count: usize) {
+        if count == 0 {
+            return;
+        }
+        let len = self.len();
+        if let Some(last) = self.runs.last_mut() {
+            if last.0 == value {
+                last.1 += count;
+                *self.ends.last_mut().unwrap() += count;
+                return;
+            }
+        }
+        self.runs.push((value, count));
+        self.ends.push(len + count);
+    }
+}
+
+impl<T> Rle<T> {
+    /// Returns the logical length of the sequence.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.ends.last().map_or(0, |&end| end)
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.runs.is_empty()
+    }
+
+    /// Returns the runs of the sequence. Adjacent runs never hold equal
+    /// values.
+    #[inline]
+    pub fn runs(&self) -> &[(T, usize)] {
+        &self.runs
+    }
+
+    /// Returns the element at a logical position.
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&T> {
+        let run = self.ends.partition_point(|&end| end <= index);
+        self.runs.get(run).map(|r| &r.0)
+    }
+
+    #[inline]
+    fn run_start(&self, run: usize) -> usize {
+        if run == 0 { 0 } else { self.ends[run - 1] }
+    }
+}
+
+impl<T: PartialEq> Rle<T> {
+    /// Returns the first logical position where `needle` occurs.
+    pub fn find(&self, needle: &[T]) -> Option<usize> {
+        self.match_indices(needle).next()
+    }
+
+    /// Returns the last logical position where `needle` occurs.
+    pub fn rfind(&self, needle: &[T]) -> Option<usize> {
+        let needle = NeedleRuns::new(needle);
+        if needle.runs.is_empty() {
+            return Some(self.len());
+        }
+        let k = needle.runs.len();
+        for run in (0..self.runs.len()).rev() {
+            if k == 1 {
+                let (value, count) = needle.runs[0];
+                let (ref hay_value, hay_count) = self.runs[run];
+                if hay_value == value && hay_count >= count {
+                    return Some(self.ends[run] - count);
+                }
+            } else if run + 1 >= k {
+                // `run` is the hay run holding the last needle run.
+                if let Some(start) = self.match_at_run(&needle, run + 1 - k) {
+                    return Some(start);
+                }
+            }
+        }
+        None
+    }
+
+    /// Returns an iterator over the starting positions of non-overlapping
+    /// occurrences of `needle`.
+    pub fn match_indices<'a>(&'a self, needle: &'a [T]) -> RleMatchIndices<'a, T> {
+        RleMatchIndices {
+            rle: self,
+            needle: NeedleRuns::new(needle),
+            run: 0,
+            position: 0,
+            finished: false,
+        }
+    }
+
+    /// Checks whether `needle`, which has at least two runs, occurs with its
+    /// first run ending at the end of hay run `run`. Returns the start of the
+    /// occurrence.
+    fn match_at_run(&self, needle: &NeedleRuns<T>, run: usize) -> Option<usize> {
+        let k = needle.runs.len();
+        if run + k > self.runs.len() {
+            return None;
+        }
+        for (i, &(value, count)) in needle.runs.iter().enumerate() {
+            let (ref hay_value, hay_count) = self.runs[run + i];
+            let fits = if i == 0 || i == k - 1 {
+                hay_count >= count
+            } else {
+                hay_count == count
+            };
+            if hay_value != value || !fits {
+                return None;
+            }
+        }
+        Some(self.ends[run] - needle.runs[0].1)
+    }
+}
+
+/// A needle compressed into runs.
+#[derive(Debug, Clone)]
+struct NeedleRuns<'a, T: 'a> {
+    runs: Vec<(&'a T, usize)>,
+    len: usize,
+}
+
+impl<'a, T: PartialEq + 'a> NeedleRuns<'a, T> {
+    fn new(needle: &'a [T]) -> Self {
+        let mut runs: Vec<(&'a T, usize)> = Vec::new();
+        for x in needle {
+            match runs.last_mut() {
+                Some(last) if last.0 == x => last.1 += 1,
+                _ => runs.push((x, 1)),
+            }
+        }
+        NeedleRuns { runs, len: needle.len() }
+    }
+}
+
+/// Iterator over the starting positions of non-overlapping occurrences of a
+/// needle in an [`Rle`](struct.Rle.html).
+///
+/// Created by [`Rle::match_indices`](struct.Rle.html#method.match_indices).
+#[derive(Debug, Clone)]
+pub struct RleMatchIndices<'a, T: 'a> {
+    rle: &'a Rle<T>,
+    needle: NeedleRuns<'a, T>,
+    // The run containing `position`.
+    run: usize,
+    // The earliest position the next occurrence may start at.
+    position: usize,
+    finished: bool,
+}
+
+impl<'a, T: PartialEq + 'a> Iterator for RleMatchIndices<'a, T> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        if self.finished {
+            return None;
+        }
+        let rle = self.rle;
+        if self.needle.runs.is_empty() {
+            // the empty needle matches at every position.
+            let position = self.position;
+            if position == rle.len() {
+                self.finished = true;
+            } else {
+                self.position += 1;
+            }
+            return Some(position);
+        }
+        while self.run < rle.runs.len() {
+            let run = self.run;
+            let start = if self.needle.runs.len() == 1 {
+                let (value, count) = self.needle.runs[0];
+                let start = self.position.max(rle.run_start(run));
+                if rle.runs[run].0 == *value && rle.ends[run] - start >= count {
+                    Some(start)
+                } else {
+                    None
+                }
+            } else {
+                rle.match_at_run(&self.needle, run).filter(|&start| start >= self.position)
+            };
+            if let Some(start) = start {
+                self.position = start + self.needle.len;
+                while self.run < rle.runs.len() && rle.ends[self.run] <= self.position {
+                    self.run += 1;
+                }
+                return Some(start);
+            }
+            self.run += 1;
+        }
+        self.finished = true;
+        None
+    }
+}
+
+impl<'a, T: PartialEq + 'a> FusedIterator for RleMatchIndices<'a, T> {}
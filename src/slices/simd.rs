@@ -0,0 +1,384 @@
+// Without a supported instruction set the kernels are never instantiated.
+#![cfg_attr(
+    not(any(target_arch = "x86_64", all(target_arch = "aarch64", target_feature = "neon"))),
+    allow(dead_code)
+)]
+
+use pattern::*;
+use haystack::Span;
+use std::ops::Range;
+
+use super::slice::{NaiveSearcher, TwoWaySearcher};
+
+//------------------------------------------------------------------------------
+// Vector kernels
+//------------------------------------------------------------------------------
+
+/// A register of bytes compared all at once.
+trait Vector: Copy {
+    /// Number of bytes in the register.
+    const LANES: usize;
+    /// Number of bits each byte takes in a candidate mask.
+    const STRIDE: u32;
+
+    unsafe fn splat(byte: u8) -> Self;
+
+    /// Returns a mask of the lanes `i` where `*first_at.add(i)` equals the
+    /// lane of `first` and `*last_at.add(i)` equals the lane of `last`.
+    unsafe fn candidates(first: Self, last: Self, first_at: *const u8, last_at: *const u8) -> u64;
+}
+
+#[cfg(target_arch = "x86_64")]
+mod x86 {
+    use std::arch::x86_64::*;
+    use std::ops::Range;
+    use super::Vector;
+
+    #[derive(Clone, Copy)]
+    pub struct Sse2(__m128i);
+
+    impl Vector for Sse2 {
+        const LANES: usize = 16;
+        const STRIDE: u32 = 1;
+
+        #[inline(always)]
+        unsafe fn splat(byte: u8) -> Self {
+            Sse2(_mm_set1_epi8(byte as i8))
+        }
+
+        #[inline(always)]
+        unsafe fn candidates(first: Self, last: Self, first_at: *const u8, last_at: *const u8) -> u64 {
+            let eq_first = _mm_cmpeq_epi8(first.0, _mm_loadu_si128(first_at as *const __m128i));
+            let eq_last = _mm_cmpeq_epi8(last.0, _mm_loadu_si128(last_at as *const __m128i));
+            _mm_movemask_epi8(_mm_and_si128(eq_first, eq_last)) as u32 as u64
+        }
+    }
+
+    // AVX2 is only detected at runtime, which needs `std`.
+    #[cfg(feature = "std")]
+    #[derive(Clone, Copy)]
+    pub struct Avx2(__m256i);
+
+    #[cfg(feature = "std")]
+    impl Vector for Avx2 {
+        const LANES: usize = 32;
+        const STRIDE: u32 = 1;
+
+        #[inline(always)]
+        unsafe fn splat(byte: u8) -> Self {
+            Avx2(_mm256_set1_epi8(byte as i8))
+        }
+
+        #[inline(always)]
+        unsafe fn candidates(first: Self, last: Self, first_at: *const u8, last_at: *const u8) -> u64 {
+            let eq_first = _mm256_cmpeq_epi8(first.0, _mm256_loadu_si256(first_at as *const __m256i));
+            let eq_last = _mm256_cmpeq_epi8(last.0, _mm256_loadu_si256(last_at as *const __m256i));
+            _mm256_movemask_epi8(_mm256_and_si256(eq_first, eq_last)) as u32 as u64
+        }
+    }
+
+    #[target_feature(enable = "sse2")]
+    pub unsafe fn find_sse2(hay: &[u8], needle: &[u8], range: Range<usize>) -> Option<usize> {
+        super::find::<Sse2>(hay, needle, range)
+    }
+
+    #[target_feature(enable = "sse2")]
+    pub unsafe fn rfind_sse2(hay: &[u8], needle: &[u8], range: Range<usize>) -> Option<usize> {
+        super::rfind::<Sse2>(hay, needle, range)
+    }
+
+    #[cfg(feature = "std")]
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn find_avx2(hay: &[u8], needle: &[u8], range: Range<usize>) -> Option<usize> {
+        super::find::<Avx2>(hay, needle, range)
+    }
+
+    #[cfg(feature = "std")]
+    #[target_feature(enable = "avx2")]
+    pub unsafe fn rfind_avx2(hay: &[u8], needle: &[u8], range: Range<usize>) -> Option<usize> {
+        super::rfind::<Avx2>(hay, needle, range)
+    }
+}
+
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+mod arm {
+    use std::arch::aarch64::*;
+    use std::ops::Range;
+    use super::Vector;
+
+    #[derive(Clone, Copy)]
+    pub struct Neon(uint8x16_t);
+
+    impl Vector for Neon {
+        const LANES: usize = 16;
+        const STRIDE: u32 = 4;
+
+        #[inline(always)]
+        unsafe fn splat(byte: u8) -> Self {
+            Neon(vdupq_n_u8(byte))
+        }
+
+        #[inline(always)]
+        unsafe fn candidates(first: Self, last: Self, first_at: *const u8, last_at: *const u8) -> u64 {
+            let eq_first = vceqq_u8(first.0, vld1q_u8(first_at));
+            let eq_last = vceqq_u8(last.0, vld1q_u8(last_at));
+            // NEON has no movemask; narrowing keeps one nibble per byte.
+            let m = vandq_u8(eq_first, eq_last);
+            let narrowed = vshrn_n_u16(vreinterpretq_u16_u8(m), 4);
+            vget_lane_u64(vreinterpret_u64_u8(narrowed), 0)
+        }
+    }
+
+    pub unsafe fn find_neon(hay: &[u8], needle: &[u8], range: Range<usize>) -> Option<usize> {
+        super::find::<Neon>(hay, needle, range)
+    }
+
+    pub unsafe fn rfind_neon(hay: &[u8], needle: &[u8], range: Range<usize>) -> Option<usize> {
+        super::rfind::<Neon>(hay, needle, range)
+    }
+}
+
+/// Returns how many items of `needle` match `hay` from the start.
+#[inline(always)]
+fn common_prefix(needle: &[u8], hay: &[u8]) -> usize {
+    needle.iter().zip(hay).take_while(|&(a, b)| a == b).count()
+}
+
+/// Returns how many items of `needle` match `hay` from the end.
+#[inline(always)]
+fn common_suffix(needle: &[u8], hay: &[u8]) -> usize {
+    needle.iter().rev().zip(hay.iter().rev()).take_while(|&(a, b)| a == b).count()
+}
+
+/// Finds the start of the first match of a non-empty `needle` in
+/// `hay[range]`.
+///
+/// Each register checks `V::LANES` positions at once for the first and last
+/// byte of the needle, and only the positions where both match are compared
+/// in full.
+#[inline(always)]
+unsafe fn find<V: Vector>(hay: &[u8], needle: &[u8], range: Range<usize>) -> Option<usize> {
+    let len = needle.len();
+    let last = len - 1;
+    let lane = (1 << V::STRIDE) - 1;
+    let first_byte = V::splat(needle[0]);
+    let last_byte = V::splat(needle[last]);
+    let mut position = range.start;
+    let mut wasted = 0;
+    while position + last + V::LANES <= range.end {
+        let ptr = hay.as_ptr().add(position);
+        let mut mask = V::candidates(first_byte, last_byte, ptr, ptr.add(last));
+        while mask != 0 {
+            let bit = mask.trailing_zeros() / V::STRIDE * V::STRIDE;
+            let start = position + (bit / V::STRIDE) as usize;
+            let matched = common_prefix(needle, hay.get_unchecked(start..(start + len)));
+            if matched == len {
+                return Some(start);
+            }
+            // Like `BmhSearcher::next`, give up on partial matches once they
+            // cost more than the haystack scanned, and finish with Two-Way.
+            wasted += matched;
+            if wasted > start - range.start + len {
+                return TwoWaySearcher::new(needle).next(hay, start..range.end).map(|r| r.start);
+            }
+            mask &= !(lane << bit);
+        }
+        position += V::LANES;
+    }
+    while position + len <= range.end {
+        if hay.get_unchecked(position..(position + len)) == needle {
+            return Some(position);
+        }
+        position += 1;
+    }
+    None
+}
+
+/// Finds the start of the last match of a non-empty `needle` in `hay[range]`.
+/// See `find`.
+#[inline(always)]
+unsafe fn rfind<V: Vector>(hay: &[u8], needle: &[u8], range: Range<usize>) -> Option<usize> {
+    let len = needle.len();
+    let last = len - 1;
+    let lane = (1 << V::STRIDE) - 1;
+    let first_byte = V::splat(needle[0]);
+    let last_byte = V::splat(needle[last]);
+    // Matches ending at or before `end` are left to search.
+    let mut end = range.end;
+    let mut wasted = 0;
+    while end >= range.start + last + V::LANES {
+        let position = end - last - V::LANES;
+        let ptr = hay.as_ptr().add(position);
+        let mut mask = V::candidates(first_byte, last_byte, ptr, ptr.add(last));
+        while mask != 0 {
+            let bit = (63 - mask.leading_zeros()) / V::STRIDE * V::STRIDE;
+            let start = position + (bit / V::STRIDE) as usize;
+            let matched = common_suffix(needle, hay.get_unchecked(start..(start + len)));
+            if matched == len {
+                return Some(start);
+            }
+            // See `find`.
+            wasted += matched;
+            if wasted > range.end - (start + len) + len {
+                return TwoWaySearcher::new(needle)
+                    .next_back(hay, range.start..(start + len))
+                    .map(|r| r.start);
+            }
+            mask &= !(lane << bit);
+        }
+        end -= V::LANES;
+    }
+    while end >= range.start + len {
+        if hay.get_unchecked((end - len)..end) == needle {
+            return Some(end - len);
+        }
+        end -= 1;
+    }
+    None
+}
+
+//------------------------------------------------------------------------------
+// SIMD searcher
+//------------------------------------------------------------------------------
+
+/// Items whose slices a `SimdSearcher` can search as bytes.
+pub(crate) trait AsBytes: Sized {
+    fn as_bytes(slice: &[Self]) -> Option<&[u8]>;
+}
+
+impl<T> AsBytes for T {
+    #[inline]
+    default fn as_bytes(_: &[T]) -> Option<&[u8]> { None }
+}
+
+impl AsBytes for u8 {
+    #[inline]
+    fn as_bytes(slice: &[u8]) -> Option<&[u8]> { Some(slice) }
+}
+
+/// Finds a match of a non-empty needle in a range of the haystack.
+type Kernel = unsafe fn(&[u8], &[u8], Range<usize>) -> Option<usize>;
+
+/// Returns the forward and reverse kernels of the widest instruction set
+/// available on this CPU. AVX2 can only be detected at runtime with the `std`
+/// feature.
+#[inline]
+fn detect() -> Option<(Kernel, Kernel)> {
+    #[cfg(all(target_arch = "x86_64", feature = "std"))]
+    {
+        if is_x86_feature_detected!("avx2") {
+            return Some((x86::find_avx2, x86::rfind_avx2));
+        }
+    }
+    #[cfg(target_arch = "x86_64")]
+    {
+        // SSE2 is part of the x86-64 baseline.
+        Some((x86::find_sse2, x86::rfind_sse2))
+    }
+    #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+    {
+        Some((arm::find_neon, arm::rfind_neon))
+    }
+    #[cfg(not(any(
+        target_arch = "x86_64",
+        all(target_arch = "aarch64", target_feature = "neon"),
+    )))]
+    {
+        None
+    }
+}
+
+/// Searcher comparing many positions of a byte haystack at once with SIMD
+/// instructions.
+///
+/// The widest instruction set available is detected when the searcher is
+/// created: AVX2 or SSE2 on x86-64 and NEON on AArch64. If partial matches
+/// make the search degrade towards quadratic time, it continues with Two-Way.
+#[derive(Debug)]
+pub struct SimdSearcher<'p, T: 'p> {
+    needle: &'p [T],
+    find: Kernel,
+    rfind: Kernel,
+}
+
+impl<'p, T: 'p> Clone for SimdSearcher<'p, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'p, T: 'p> Copy for SimdSearcher<'p, T> {}
+
+impl<'p, T> SimdSearcher<'p, T>
+where
+    T: PartialEq + 'p,
+{
+    /// Creates a searcher for a non-empty needle, or returns `None` if its
+    /// items are not bytes or this CPU has no supported instruction set.
+    #[inline]
+    pub(crate) fn new(needle: &'p [T]) -> Option<Self> {
+        T::as_bytes(needle)?;
+        let (find, rfind) = detect()?;
+        Some(SimdSearcher { needle, find, rfind })
+    }
+
+    #[inline]
+    fn bytes<'a>(&self, hay: &'a [T]) -> (&'a [u8], &'p [u8]) {
+        // Only byte needles get a `SimdSearcher`, so the haystack is bytes too.
+        match (T::as_bytes(hay), T::as_bytes(self.needle)) {
+            (Some(hay), Some(needle)) => (hay, needle),
+            _ => unreachable!(),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn next(&self, hay: &[T], range: Range<usize>) -> Option<Range<usize>> {
+        let (hay, needle) = self.bytes(hay);
+        let start = unsafe { (self.find)(hay, needle, range) }?;
+        Some(start..(start + needle.len()))
+    }
+
+    #[inline]
+    pub(crate) fn next_back(&self, hay: &[T], range: Range<usize>) -> Option<Range<usize>> {
+        let (hay, needle) = self.bytes(hay);
+        let start = unsafe { (self.rfind)(hay, needle, range) }?;
+        Some(start..(start + needle.len()))
+    }
+
+    #[inline]
+    pub(crate) fn needle(&self) -> &'p [T] {
+        self.needle
+    }
+}
+
+unsafe impl<'p, T> Searcher<[T]> for SimdSearcher<'p, T>
+where
+    T: PartialEq + 'p,
+{
+    #[inline]
+    fn search(&mut self, span: Span<&[T]>) -> Option<Range<usize>> {
+        let (hay, range) = span.into_parts();
+        self.next(hay, range)
+    }
+
+    #[cold]
+    fn consume(&mut self, span: Span<&[T]>) -> Option<usize> {
+        NaiveSearcher(self.needle).consume(span)
+    }
+}
+
+unsafe impl<'p, T> ReverseSearcher<[T]> for SimdSearcher<'p, T>
+where
+    T: PartialEq + 'p,
+{
+    #[inline]
+    fn rsearch(&mut self, span: Span<&[T]>) -> Option<Range<usize>> {
+        let (hay, range) = span.into_parts();
+        self.next_back(hay, range)
+    }
+
+    #[cold]
+    fn rconsume(&mut self, span: Span<&[T]>) -> Option<usize> {
+        NaiveSearcher(self.needle).rconsume(span)
+    }
+}
@@ -3,14 +3,20 @@ use haystack::{Hay, Span};
 use std::cmp::{Ordering, max, min};
 use std::usize;
 use std::ops::Range;
+#[cfg(feature = "std")]
+use std::alloc::Allocator;
+
+use super::algorithm::Algorithm;
+use super::simd::SimdSearcher;
+use combinators::progress::BoundedMatches;
 
 //------------------------------------------------------------------------------
 // Two way searcher helpers
 //------------------------------------------------------------------------------
 
-type FastSkipByteset = u64;
+pub(crate) type FastSkipByteset = u64;
 
-trait FastSkipOptimization {
+pub(crate) trait FastSkipOptimization {
     fn byteset_mask(&self) -> FastSkipByteset;
 }
 
@@ -148,6 +154,46 @@ impl<T: Ord> MaximalSuffix for T {
     }
 }
 
+// Compute the KMP failure function of the sequence `at(0), at(1), ...,
+// at(len - 1)`: entry `i` is the length of the longest proper border (prefix
+// which is also a suffix) of the first `i + 1` items.
+#[cfg(feature = "std")]
+pub(crate) fn border_table<'a, T, F>(len: usize, at: F) -> Box<[usize]>
+where
+    T: PartialEq + ?Sized + 'a,
+    F: Fn(usize) -> &'a T,
+{
+    let mut border = vec![0; len];
+    let mut k = 0;
+    for i in 1..len {
+        while k > 0 && at(i) != at(k) {
+            k = border[k - 1];
+        }
+        if at(i) == at(k) {
+            k += 1;
+        }
+        border[i] = k;
+    }
+    border.into_boxed_slice()
+}
+
+// Compute the exact period of `arr` using the KMP failure function.
+//
+// This is only used for long needles in the long period case, where the
+// approximate period from the critical factorization may be much shorter than
+// the real one. Both are correct to shift by, but the exact period allows the
+// searcher to skip further after a mismatch in the left part of the needle.
+#[cfg(feature = "std")]
+fn exact_period<T: PartialEq>(arr: &[T]) -> usize {
+    let border = border_table(arr.len(), |i| &arr[i]);
+    arr.len() - border[arr.len() - 1]
+}
+
+// Needles at least this long get their exact period computed in the long
+// period case. Below this the allocation costs more than it could save.
+#[cfg(feature = "std")]
+const EXACT_PERIOD_MIN_LEN: usize = 256;
+
 //------------------------------------------------------------------------------
 // Two way searcher
 //------------------------------------------------------------------------------
@@ -155,6 +201,32 @@ impl<T: Ord> MaximalSuffix for T {
 struct LongPeriod;
 struct ShortPeriod;
 
+/// Random access to the items searched by a `TwoWaySearcher`, so that
+/// haystacks which are not a single slice can share the algorithm.
+pub(crate) trait TwoWayHay<T> {
+    /// Returns the item at index `i`, which must be in bounds.
+    unsafe fn item(&self, i: usize) -> &T;
+}
+
+impl<T> TwoWayHay<T> for [T] {
+    #[inline]
+    unsafe fn item(&self, i: usize) -> &T {
+        self.get_unchecked(i)
+    }
+}
+
+/// Hays which a `SliceSearcher` searches as a plain slice of items.
+pub(crate) trait SliceHay<T>: Hay<Index = usize> {
+    fn as_slice(&self) -> &[T];
+}
+
+impl<T> SliceHay<T> for [T] {
+    #[inline]
+    fn as_slice(&self) -> &[T] {
+        self
+    }
+}
+
 trait Period {
     const IS_LONG_PERIOD: bool;
 }
@@ -189,6 +261,17 @@ pub struct TwoWaySearcher<'p, T: 'p> {
     memory_back: usize,
 }
 
+/// The preprocessed constants of a `TwoWaySearcher`, without the needle.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct TwoWayParams {
+    crit_pos: usize,
+    crit_pos_back: usize,
+    period: usize,
+    byteset: FastSkipByteset,
+    long_period: bool,
+}
+
 impl<'p, T: 'p> Clone for TwoWaySearcher<'p, T> {
     fn clone(&self) -> Self {
         *self
@@ -202,7 +285,10 @@ where
     T: PartialEq + 'p,
 {
     #[inline]
-    fn do_next<P: Period>(&mut self, hay: &[T], range: Range<usize>) -> Option<Range<usize>> {
+    fn do_next<P: Period, H>(&mut self, hay: &H, range: Range<usize>) -> Option<Range<usize>>
+    where
+        H: TwoWayHay<T> + ?Sized,
+    {
         let needle = self.needle;
 
         let mut position = range.start;
@@ -215,7 +301,7 @@ where
                 return None;
             }
             // let tail_item = &hay[i]; // using get_unchecked here would be slower
-            let tail_item = unsafe { hay.get_unchecked(i) };
+            let tail_item = unsafe { hay.item(i) };
 
             // Quickly skip by large portions unrelated to our substring
             if !self.byteset_contains(tail_item) {
@@ -233,7 +319,7 @@ where
                 max(self.crit_pos, self.memory)
             };
             for i in start..needle.len() {
-                if unsafe { needle.get_unchecked(i) != hay.get_unchecked(position + i) } {
+                if unsafe { needle.get_unchecked(i) != hay.item(position + i) } {
                     position += i - self.crit_pos + 1;
                     if !P::IS_LONG_PERIOD {
                         self.memory = 0;
@@ -245,7 +331,7 @@ where
             // See if the left part of the needle matches
             let start = if P::IS_LONG_PERIOD { 0 } else { self.memory };
             for i in (start..self.crit_pos).rev() {
-                if unsafe { needle.get_unchecked(i) != hay.get_unchecked(position + i) } {
+                if unsafe { needle.get_unchecked(i) != hay.item(position + i) } {
                     position += self.period;
                     if !P::IS_LONG_PERIOD {
                         self.memory = needle.len() - self.period;
@@ -264,16 +350,22 @@ where
     }
 
     #[inline]
-    pub(crate) fn next(&mut self, hay: &[T], range: Range<usize>) -> Option<Range<usize>> {
+    pub(crate) fn next<H>(&mut self, hay: &H, range: Range<usize>) -> Option<Range<usize>>
+    where
+        H: TwoWayHay<T> + ?Sized,
+    {
         if self.memory != usize::MAX {
-            self.do_next::<ShortPeriod>(hay, range)
+            self.do_next::<ShortPeriod, _>(hay, range)
         } else {
-            self.do_next::<LongPeriod>(hay, range)
+            self.do_next::<LongPeriod, _>(hay, range)
         }
     }
 
     #[inline]
-    fn do_next_back<P: Period>(&mut self, hay: &[T], range: Range<usize>) -> Option<Range<usize>> {
+    fn do_next_back<P: Period, H>(&mut self, hay: &H, range: Range<usize>) -> Option<Range<usize>>
+    where
+        H: TwoWayHay<T> + ?Sized,
+    {
         let needle = self.needle;
         let mut end = range.end;
         'search: loop {
@@ -284,7 +376,7 @@ where
             if needle.len() + range.start > end {
                 return None;
             }
-            let front_item = unsafe { hay.get_unchecked(end.wrapping_sub(needle.len())) };
+            let front_item = unsafe { hay.item(end.wrapping_sub(needle.len())) };
 
             // Quickly skip by large portions unrelated to our substring
             if !self.byteset_contains(front_item) {
@@ -302,7 +394,7 @@ where
                 min(self.crit_pos_back, self.memory_back)
             };
             for i in (0..crit).rev() {
-                if unsafe { needle.get_unchecked(i) != hay.get_unchecked(end - needle.len() + i) } {
+                if unsafe { needle.get_unchecked(i) != hay.item(end - needle.len() + i) } {
                     end -= self.crit_pos_back - i;
                     if !P::IS_LONG_PERIOD {
                         self.memory_back = needle.len();
@@ -314,7 +406,7 @@ where
             // See if the right part of the needle matches
             let needle_end = if P::IS_LONG_PERIOD { needle.len() } else { self.memory_back };
             for i in self.crit_pos_back..needle_end {
-                if unsafe { needle.get_unchecked(i) != hay.get_unchecked(end - needle.len() + i) } {
+                if unsafe { needle.get_unchecked(i) != hay.item(end - needle.len() + i) } {
                     end -= self.period;
                     if !P::IS_LONG_PERIOD {
                         self.memory_back = self.period;
@@ -332,11 +424,14 @@ where
     }
 
     #[inline]
-    pub(crate) fn next_back(&mut self, hay: &[T], range: Range<usize>) -> Option<Range<usize>> {
+    pub(crate) fn next_back<H>(&mut self, hay: &H, range: Range<usize>) -> Option<Range<usize>>
+    where
+        H: TwoWayHay<T> + ?Sized,
+    {
         if self.memory != usize::MAX {
-            self.do_next_back::<ShortPeriod>(hay, range)
+            self.do_next_back::<ShortPeriod, _>(hay, range)
         } else {
-            self.do_next_back::<LongPeriod>(hay, range)
+            self.do_next_back::<LongPeriod, _>(hay, range)
         }
     }
 
@@ -382,10 +477,18 @@ where
                 memory_back: needle.len(),
             }
         } else {
+            #[allow(unused_mut)]
+            let mut period = max(crit_pos, needle.len() - crit_pos) + 1;
+            #[cfg(feature = "std")]
+            {
+                if needle.len() >= EXACT_PERIOD_MIN_LEN {
+                    period = max(period, exact_period(needle));
+                }
+            }
             Self {
                 crit_pos,
                 crit_pos_back: crit_pos,
-                period: max(crit_pos, needle.len() - crit_pos) + 1,
+                period,
                 byteset,
                 needle,
                 memory: usize::MAX, // Dummy value to signify that the period is long
@@ -394,6 +497,39 @@ where
         }
     }
 
+    #[cfg(feature = "std")]
+    #[inline]
+    pub(crate) fn params(&self) -> TwoWayParams {
+        TwoWayParams {
+            crit_pos: self.crit_pos,
+            crit_pos_back: self.crit_pos_back,
+            period: self.period,
+            byteset: self.byteset,
+            long_period: self.memory == usize::MAX,
+        }
+    }
+
+    /// Creates a searcher from constants previously computed for an equal
+    /// needle.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub(crate) fn from_params(needle: &'p [T], params: TwoWayParams) -> Self {
+        let (memory, memory_back) = if params.long_period {
+            (usize::MAX, usize::MAX)
+        } else {
+            (0, needle.len())
+        };
+        Self {
+            crit_pos: params.crit_pos,
+            crit_pos_back: params.crit_pos_back,
+            period: params.period,
+            byteset: params.byteset,
+            needle,
+            memory,
+            memory_back,
+        }
+    }
+
     #[inline]
     fn byteset_create(needle: &[T]) -> FastSkipByteset {
         needle.iter().fold(0, |a, b| b.byteset_mask() | a)
@@ -441,7 +577,7 @@ where
 //------------------------------------------------------------------------------
 
 #[derive(Debug)]
-pub struct NaiveSearcher<'p, T: 'p>(&'p [T]);
+pub struct NaiveSearcher<'p, T: 'p>(pub(crate) &'p [T]);
 
 impl<'p, T: 'p> Clone for NaiveSearcher<'p, T> {
     fn clone(&self) -> Self {
@@ -509,6 +645,505 @@ where
     }
 }
 
+//------------------------------------------------------------------------------
+// Knuth-Morris-Pratt searcher
+//------------------------------------------------------------------------------
+
+/// Searcher using the Knuth–Morris–Pratt algorithm.
+///
+/// Every item of the haystack is compared a bounded number of times, with
+/// less bookkeeping per item than Two-Way, but unlike Two-Way no item is ever
+/// skipped.
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct KmpSearcher<'p, T: 'p> {
+    needle: &'p [T],
+    /// Failure function of the needle.
+    border: Box<[usize]>,
+    /// Failure function of the reversed needle.
+    border_back: Box<[usize]>,
+}
+
+#[cfg(feature = "std")]
+impl<'p, T: 'p> Clone for KmpSearcher<'p, T> {
+    fn clone(&self) -> Self {
+        KmpSearcher {
+            needle: self.needle,
+            border: self.border.clone(),
+            border_back: self.border_back.clone(),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'p, T> KmpSearcher<'p, T>
+where
+    T: PartialEq + 'p,
+{
+    #[inline]
+    pub(crate) fn new(needle: &'p [T]) -> Self {
+        let len = needle.len();
+        KmpSearcher {
+            needle,
+            border: border_table(len, |i| &needle[i]),
+            border_back: border_table(len, |i| &needle[len - 1 - i]),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn next(&self, hay: &[T], range: Range<usize>) -> Option<Range<usize>> {
+        let needle = self.needle;
+        let mut matched = 0;
+        for i in range {
+            let item = unsafe { hay.get_unchecked(i) };
+            while matched > 0 && unsafe { needle.get_unchecked(matched) } != item {
+                matched = self.border[matched - 1];
+            }
+            if unsafe { needle.get_unchecked(matched) } == item {
+                matched += 1;
+                if matched == needle.len() {
+                    return Some((i + 1 - matched)..(i + 1));
+                }
+            }
+        }
+        None
+    }
+
+    #[inline]
+    pub(crate) fn next_back(&self, hay: &[T], range: Range<usize>) -> Option<Range<usize>> {
+        let needle = self.needle;
+        let last = needle.len() - 1;
+        let mut matched = 0;
+        for i in range.rev() {
+            let item = unsafe { hay.get_unchecked(i) };
+            while matched > 0 && unsafe { needle.get_unchecked(last - matched) } != item {
+                matched = self.border_back[matched - 1];
+            }
+            if unsafe { needle.get_unchecked(last - matched) } == item {
+                matched += 1;
+                if matched == needle.len() {
+                    return Some(i..(i + matched));
+                }
+            }
+        }
+        None
+    }
+}
+
+#[cfg(feature = "std")]
+unsafe impl<'p, T> Searcher<[T]> for KmpSearcher<'p, T>
+where
+    T: PartialEq + 'p,
+{
+    #[inline]
+    fn search(&mut self, span: Span<&[T]>) -> Option<Range<usize>> {
+        let (hay, range) = span.into_parts();
+        self.next(hay, range)
+    }
+
+    #[cold]
+    fn consume(&mut self, span: Span<&[T]>) -> Option<usize> {
+        NaiveSearcher(self.needle).consume(span)
+    }
+}
+
+#[cfg(feature = "std")]
+unsafe impl<'p, T> ReverseSearcher<[T]> for KmpSearcher<'p, T>
+where
+    T: PartialEq + 'p,
+{
+    #[inline]
+    fn rsearch(&mut self, span: Span<&[T]>) -> Option<Range<usize>> {
+        let (hay, range) = span.into_parts();
+        self.next_back(hay, range)
+    }
+
+    #[cold]
+    fn rconsume(&mut self, span: Span<&[T]>) -> Option<usize> {
+        NaiveSearcher(self.needle).rconsume(span)
+    }
+}
+
+//------------------------------------------------------------------------------
+// Boyer-Moore-Horspool searcher
+//------------------------------------------------------------------------------
+
+/// Items which can index the byte tables of a `BmhSearcher` or `BndmSearcher`.
+pub(crate) trait BadCharacter {
+    fn bad_character(&self) -> Option<u8>;
+}
+
+impl<T: ?Sized> BadCharacter for T {
+    #[inline]
+    default fn bad_character(&self) -> Option<u8> { None }
+}
+
+impl BadCharacter for u8 {
+    #[inline]
+    fn bad_character(&self) -> Option<u8> { Some(*self) }
+}
+
+// Without SIMD, byte needles at least this long are searched with
+// Boyer-Moore-Horspool by default. A long needle covers most of the Two-Way
+// byteset, leaving Two-Way unable to skip, while Horspool skips further the
+// longer the needle is (see `benches/algorithms.rs`).
+const BMH_MIN_LEN: usize = 128;
+
+/// Searcher using the Boyer–Moore–Horspool algorithm.
+///
+/// After each window the searcher shifts by how far the last (or, in reverse,
+/// first) item of the window is from its nearest occurrence in the needle,
+/// which is often the whole needle length when the alphabet is large. If
+/// partial matches make this degrade towards quadratic time, the search
+/// continues with Two-Way.
+#[derive(Debug)]
+pub struct BmhSearcher<'p, T: 'p> {
+    needle: &'p [T],
+    /// Shift after a window ending with the given byte, capped at 255.
+    shift: [u8; 256],
+    /// Shift back after a window starting with the given byte, capped at 255.
+    shift_back: [u8; 256],
+}
+
+impl<'p, T: 'p> Clone for BmhSearcher<'p, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'p, T: 'p> Copy for BmhSearcher<'p, T> {}
+
+impl<'p, T> BmhSearcher<'p, T>
+where
+    T: PartialEq + 'p,
+{
+    /// Creates a searcher for a non-empty needle, or returns `None` if its
+    /// items are not bytes.
+    #[inline]
+    pub(crate) fn new(needle: &'p [T]) -> Option<Self> {
+        let len = needle.len();
+        let cap = |shift: usize| min(shift, 255) as u8;
+        let mut shift = [cap(len); 256];
+        let mut shift_back = [cap(len); 256];
+        for (i, item) in needle[..(len - 1)].iter().enumerate() {
+            shift[item.bad_character()? as usize] = cap(len - 1 - i);
+        }
+        for (i, item) in needle[1..].iter().enumerate().rev() {
+            shift_back[item.bad_character()? as usize] = cap(i + 1);
+        }
+        Some(BmhSearcher {
+            needle,
+            shift,
+            shift_back,
+        })
+    }
+
+    #[inline]
+    fn shift_after(table: &[u8; 256], item: &T) -> usize {
+        match item.bad_character() {
+            Some(b) => table[b as usize] as usize,
+            None => 1,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn next(&self, hay: &[T], range: Range<usize>) -> Option<Range<usize>> {
+        let needle = self.needle;
+        let last = needle.len() - 1;
+        let mut position = range.start;
+        let mut wasted = 0;
+        while position + needle.len() <= range.end {
+            let tail_item = unsafe { hay.get_unchecked(position + last) };
+            if tail_item == unsafe { needle.get_unchecked(last) } {
+                let mut i = 0;
+                while i < last && unsafe { hay.get_unchecked(position + i) == needle.get_unchecked(i) } {
+                    i += 1;
+                }
+                if i == last {
+                    return Some(position..(position + needle.len()));
+                }
+                // Horspool is quadratic when windows keep almost matching.
+                // Once partial matches cost more than we have skipped, finish
+                // with Two-Way to stay linear.
+                wasted += i;
+                if wasted > position - range.start + needle.len() {
+                    return TwoWaySearcher::new(needle).next(hay, position..range.end);
+                }
+            }
+            position += Self::shift_after(&self.shift, tail_item);
+        }
+        None
+    }
+
+    #[inline]
+    pub(crate) fn next_back(&self, hay: &[T], range: Range<usize>) -> Option<Range<usize>> {
+        let needle = self.needle;
+        let mut end = range.end;
+        let mut wasted = 0;
+        while end >= range.start + needle.len() {
+            let start = end - needle.len();
+            let front_item = unsafe { hay.get_unchecked(start) };
+            if front_item == unsafe { needle.get_unchecked(0) } {
+                let mut i = needle.len() - 1;
+                while i > 0 && unsafe { hay.get_unchecked(start + i) == needle.get_unchecked(i) } {
+                    i -= 1;
+                }
+                if i == 0 {
+                    return Some(start..end);
+                }
+                // See `next`.
+                wasted += needle.len() - 1 - i;
+                if wasted > range.end - end + needle.len() {
+                    return TwoWaySearcher::new(needle).next_back(hay, range.start..end);
+                }
+            }
+            end -= Self::shift_after(&self.shift_back, front_item);
+        }
+        None
+    }
+}
+
+unsafe impl<'p, T> Searcher<[T]> for BmhSearcher<'p, T>
+where
+    T: PartialEq + 'p,
+{
+    #[inline]
+    fn search(&mut self, span: Span<&[T]>) -> Option<Range<usize>> {
+        let (hay, range) = span.into_parts();
+        self.next(hay, range)
+    }
+
+    #[cold]
+    fn consume(&mut self, span: Span<&[T]>) -> Option<usize> {
+        NaiveSearcher(self.needle).consume(span)
+    }
+}
+
+unsafe impl<'p, T> ReverseSearcher<[T]> for BmhSearcher<'p, T>
+where
+    T: PartialEq + 'p,
+{
+    #[inline]
+    fn rsearch(&mut self, span: Span<&[T]>) -> Option<Range<usize>> {
+        let (hay, range) = span.into_parts();
+        self.next_back(hay, range)
+    }
+
+    #[cold]
+    fn rconsume(&mut self, span: Span<&[T]>) -> Option<usize> {
+        NaiveSearcher(self.needle).rconsume(span)
+    }
+}
+
+//------------------------------------------------------------------------------
+// Backward nondeterministic DAWG matching searcher
+//------------------------------------------------------------------------------
+
+// Longest needle a `BndmSearcher` can search for, one bit per item.
+#[cfg(feature = "std")]
+const BNDM_MAX_LEN: usize = 64;
+
+// Byte needles at least this long, and repeating their bytes enough (see
+// `has_small_alphabet`), are searched with BNDM by default. Shorter needles
+// do not skip far enough to pay for building the masks.
+#[cfg(feature = "std")]
+const BNDM_MIN_LEN: usize = 8;
+
+// As `BNDM_MIN_LEN`, when the needle could be searched with SIMD instead.
+// Checking 16 or 32 positions at once beats BNDM on shorter needles.
+#[cfg(feature = "std")]
+const BNDM_MIN_LEN_SIMD: usize = 32;
+
+// Whether at most half of the items of a byte needle are distinct. This
+// suggests a haystack over a small alphabet, where the Two-Way byteset rarely
+// skips but BNDM still does (see `benches/algorithms.rs`).
+#[cfg(feature = "std")]
+fn has_small_alphabet<T>(needle: &[T]) -> bool {
+    let mut seen = [false; 256];
+    let mut distinct = 0;
+    for item in needle {
+        let b = match item.bad_character() {
+            Some(b) => b as usize,
+            None => return false,
+        };
+        if !seen[b] {
+            seen[b] = true;
+            distinct += 1;
+        }
+    }
+    distinct * 2 <= needle.len()
+}
+
+/// Searcher using the Backward Nondeterministic DAWG Matching algorithm.
+///
+/// Each window is read from its end (or, in reverse, its start) while a
+/// single `u64` tracks every position of the needle where the items read so
+/// far occur. Once no position is left, the window is shifted past them.
+/// If partial matches make this degrade towards quadratic time, the search
+/// continues with Two-Way.
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct BndmSearcher<'p, T: 'p> {
+    needle: &'p [T],
+    /// Bit `len - 1 - i` is set in the entry of byte `needle[i]`.
+    masks: Box<[u64; 256]>,
+    /// Bit `i` is set in the entry of byte `needle[i]`.
+    masks_back: Box<[u64; 256]>,
+}
+
+#[cfg(feature = "std")]
+impl<'p, T: 'p> Clone for BndmSearcher<'p, T> {
+    fn clone(&self) -> Self {
+        BndmSearcher {
+            needle: self.needle,
+            masks: self.masks.clone(),
+            masks_back: self.masks_back.clone(),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'p, T> BndmSearcher<'p, T>
+where
+    T: PartialEq + 'p,
+{
+    /// Creates a searcher for a non-empty needle, or returns `None` if it is
+    /// longer than 64 items or its items are not bytes.
+    #[inline]
+    pub(crate) fn new(needle: &'p [T]) -> Option<Self> {
+        let len = needle.len();
+        if len > BNDM_MAX_LEN {
+            return None;
+        }
+        let mut masks = Box::new([0; 256]);
+        let mut masks_back = Box::new([0; 256]);
+        for (i, item) in needle.iter().enumerate() {
+            let b = item.bad_character()? as usize;
+            masks[b] |= 1 << (len - 1 - i);
+            masks_back[b] |= 1 << i;
+        }
+        Some(BndmSearcher {
+            needle,
+            masks,
+            masks_back,
+        })
+    }
+
+    #[inline]
+    fn mask(table: &[u64; 256], item: &T) -> u64 {
+        // Only byte needles get a `BndmSearcher`, so the haystack is bytes too.
+        match item.bad_character() {
+            Some(b) => table[b as usize],
+            None => 0,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn next(&self, hay: &[T], range: Range<usize>) -> Option<Range<usize>> {
+        let len = self.needle.len();
+        let prefix_bit = 1 << (len - 1);
+        let mut position = range.start;
+        let mut wasted = 0;
+        while position + len <= range.end {
+            // `j` items of the window are left unread, and the window can be
+            // shifted by `shift` without skipping over a match.
+            let mut j = len;
+            let mut shift = len;
+            let mut state = !0u64;
+            while j > 0 {
+                state &= Self::mask(&self.masks, unsafe { hay.get_unchecked(position + j - 1) });
+                if state == 0 {
+                    break;
+                }
+                j -= 1;
+                if state & prefix_bit != 0 {
+                    if j == 0 {
+                        return Some(position..(position + len));
+                    }
+                    shift = j;
+                }
+                state <<= 1;
+            }
+            // See `BmhSearcher::next`.
+            wasted += (len - j).saturating_sub(shift);
+            if wasted > position - range.start + len {
+                return TwoWaySearcher::new(self.needle).next(hay, position..range.end);
+            }
+            position += shift;
+        }
+        None
+    }
+
+    #[inline]
+    pub(crate) fn next_back(&self, hay: &[T], range: Range<usize>) -> Option<Range<usize>> {
+        let len = self.needle.len();
+        let suffix_bit = 1 << (len - 1);
+        let mut end = range.end;
+        let mut wasted = 0;
+        while end >= range.start + len {
+            let start = end - len;
+            // `j` items of the window have been read. See `next`.
+            let mut j = 0;
+            let mut shift = len;
+            let mut state = !0u64;
+            while j < len {
+                state &= Self::mask(&self.masks_back, unsafe { hay.get_unchecked(start + j) });
+                if state == 0 {
+                    break;
+                }
+                j += 1;
+                if state & suffix_bit != 0 {
+                    if j == len {
+                        return Some(start..end);
+                    }
+                    shift = len - j;
+                }
+                state <<= 1;
+            }
+            wasted += j.saturating_sub(shift);
+            if wasted > range.end - end + len {
+                return TwoWaySearcher::new(self.needle).next_back(hay, range.start..end);
+            }
+            end -= shift;
+        }
+        None
+    }
+}
+
+#[cfg(feature = "std")]
+unsafe impl<'p, T> Searcher<[T]> for BndmSearcher<'p, T>
+where
+    T: PartialEq + 'p,
+{
+    #[inline]
+    fn search(&mut self, span: Span<&[T]>) -> Option<Range<usize>> {
+        let (hay, range) = span.into_parts();
+        self.next(hay, range)
+    }
+
+    #[cold]
+    fn consume(&mut self, span: Span<&[T]>) -> Option<usize> {
+        NaiveSearcher(self.needle).consume(span)
+    }
+}
+
+#[cfg(feature = "std")]
+unsafe impl<'p, T> ReverseSearcher<[T]> for BndmSearcher<'p, T>
+where
+    T: PartialEq + 'p,
+{
+    #[inline]
+    fn rsearch(&mut self, span: Span<&[T]>) -> Option<Range<usize>> {
+        let (hay, range) = span.into_parts();
+        self.next_back(hay, range)
+    }
+
+    #[cold]
+    fn rconsume(&mut self, span: Span<&[T]>) -> Option<usize> {
+        NaiveSearcher(self.needle).rconsume(span)
+    }
+}
+
 //------------------------------------------------------------------------------
 // Slice searcher
 //------------------------------------------------------------------------------
@@ -518,16 +1153,39 @@ pub enum SliceSearcher<'p, T: 'p> {
     TwoWay(TwoWaySearcher<'p, T>),
     Empty(EmptySearcher),
     Naive(NaiveSearcher<'p, T>),
+    #[cfg(feature = "std")]
+    Kmp(KmpSearcher<'p, T>),
+    Bmh(BmhSearcher<'p, T>),
+    #[cfg(feature = "std")]
+    Bndm(BndmSearcher<'p, T>),
+    Simd(SimdSearcher<'p, T>),
 }
 
 impl<'p, T: PartialEq + 'p> SliceSearcher<'p, T> {
     #[inline]
     pub fn new_searcher(slice: &'p [T]) -> Self {
         if slice.is_empty() {
-            SliceSearcher::Empty(EmptySearcher::default())
-        } else {
-            SliceSearcher::TwoWay(TwoWaySearcher::new(slice))
+            return SliceSearcher::Empty(EmptySearcher::default());
+        }
+        let simd = SimdSearcher::new(slice);
+        #[cfg(feature = "std")]
+        {
+            let min_len = if simd.is_some() { BNDM_MIN_LEN_SIMD } else { BNDM_MIN_LEN };
+            if min_len <= slice.len() && slice.len() <= BNDM_MAX_LEN && has_small_alphabet(slice) {
+                if let Some(s) = BndmSearcher::new(slice) {
+                    return SliceSearcher::Bndm(s);
+                }
+            }
+        }
+        if let Some(s) = simd {
+            return SliceSearcher::Simd(s);
         }
+        if slice.len() >= BMH_MIN_LEN {
+            if let Some(s) = BmhSearcher::new(slice) {
+                return SliceSearcher::Bmh(s);
+            }
+        }
+        SliceSearcher::TwoWay(TwoWaySearcher::new(slice))
     }
 
     #[inline]
@@ -535,12 +1193,45 @@ impl<'p, T: PartialEq + 'p> SliceSearcher<'p, T> {
         SliceSearcher::Naive(NaiveSearcher(slice))
     }
 
+    /// Creates a searcher using the given algorithm.
+    #[inline]
+    pub(crate) fn with_algorithm(slice: &'p [T], algorithm: Algorithm) -> Self {
+        if slice.is_empty() {
+            return SliceSearcher::Empty(EmptySearcher::default());
+        }
+        match algorithm {
+            Algorithm::Auto => Self::new_searcher(slice),
+            Algorithm::TwoWay => SliceSearcher::TwoWay(TwoWaySearcher::new(slice)),
+            #[cfg(feature = "std")]
+            Algorithm::Kmp => SliceSearcher::Kmp(KmpSearcher::new(slice)),
+            Algorithm::Bmh => match BmhSearcher::new(slice) {
+                Some(s) => SliceSearcher::Bmh(s),
+                None => SliceSearcher::TwoWay(TwoWaySearcher::new(slice)),
+            },
+            #[cfg(feature = "std")]
+            Algorithm::Bndm => match BndmSearcher::new(slice) {
+                Some(s) => SliceSearcher::Bndm(s),
+                None => SliceSearcher::TwoWay(TwoWaySearcher::new(slice)),
+            },
+            Algorithm::Simd => match SimdSearcher::new(slice) {
+                Some(s) => SliceSearcher::Simd(s),
+                None => SliceSearcher::TwoWay(TwoWaySearcher::new(slice)),
+            },
+        }
+    }
+
     #[inline]
     pub fn needle(&self) -> &'p [T] {
         match self {
             SliceSearcher::TwoWay(s) => s.needle,
             SliceSearcher::Empty(_) => &[],
             SliceSearcher::Naive(s) => s.0,
+            #[cfg(feature = "std")]
+            SliceSearcher::Kmp(s) => s.needle,
+            SliceSearcher::Bmh(s) => s.needle,
+            #[cfg(feature = "std")]
+            SliceSearcher::Bndm(s) => s.needle,
+            SliceSearcher::Simd(s) => s.needle(),
         }
     }
 }
@@ -552,15 +1243,49 @@ impl<'p, T: 'p> Clone for SliceSearcher<'p, T> {
             SliceSearcher::TwoWay(s) => SliceSearcher::TwoWay(*s),
             SliceSearcher::Empty(s) => SliceSearcher::Empty(s.clone()),
             SliceSearcher::Naive(s) => SliceSearcher::Naive(*s),
+            #[cfg(feature = "std")]
+            SliceSearcher::Kmp(s) => SliceSearcher::Kmp(s.clone()),
+            SliceSearcher::Bmh(s) => SliceSearcher::Bmh(*s),
+            #[cfg(feature = "std")]
+            SliceSearcher::Bndm(s) => SliceSearcher::Bndm(s.clone()),
+            SliceSearcher::Simd(s) => SliceSearcher::Simd(*s),
+        }
+    }
+}
+
+impl<'p, T: PartialEq + 'p> BoundedMatches for SliceSearcher<'p, T> {
+    #[inline]
+    fn max_match_len(&self) -> Option<usize> {
+        match self.needle().len() {
+            0 => None,
+            len => Some(len),
         }
     }
 }
 
 macro_rules! forward {
-    (searcher: $self:expr, $s:ident => $e:expr) => {
+    (searcher: $self:expr, $span:expr, $s:ident => $e:expr, $next:ident) => {
         match $self {
             SliceSearcher::TwoWay($s) => $e,
             SliceSearcher::Empty($s) => $e,
+            #[cfg(feature = "std")]
+            SliceSearcher::Kmp(s) => {
+                let (hay, range) = $span.into_parts();
+                s.$next(hay.as_slice(), range)
+            }
+            SliceSearcher::Bmh(s) => {
+                let (hay, range) = $span.into_parts();
+                s.$next(hay.as_slice(), range)
+            }
+            #[cfg(feature = "std")]
+            SliceSearcher::Bndm(s) => {
+                let (hay, range) = $span.into_parts();
+                s.$next(hay.as_slice(), range)
+            }
+            SliceSearcher::Simd(s) => {
+                let (hay, range) = $span.into_parts();
+                s.$next(hay.as_slice(), range)
+            }
             _ => panic!("can only be used with a searcher"),
         }
     };
@@ -574,13 +1299,14 @@ macro_rules! forward {
 
 unsafe impl<'p, T, A> Searcher<A> for SliceSearcher<'p, T>
 where
-    A: Hay<Index = usize> + ?Sized,
+    A: SliceHay<T> + ?Sized,
+    T: PartialEq + 'p,
     TwoWaySearcher<'p, T>: Searcher<A>,
     NaiveSearcher<'p, T>: Searcher<A>,
 {
     #[inline]
     fn search(&mut self, span: Span<&A>) -> Option<Range<usize>> {
-        forward!(searcher: self, s => s.search(span))
+        forward!(searcher: self, span, s => s.search(span), next)
     }
 
     #[inline]
@@ -596,13 +1322,14 @@ where
 
 unsafe impl<'p, T, A> ReverseSearcher<A> for SliceSearcher<'p, T>
 where
-    A: Hay<Index = usize> + ?Sized,
+    A: SliceHay<T> + ?Sized,
+    T: PartialEq + 'p,
     TwoWaySearcher<'p, T>: ReverseSearcher<A>,
     NaiveSearcher<'p, T>: ReverseSearcher<A>,
 {
     #[inline]
     fn rsearch(&mut self, span: Span<&A>) -> Option<Range<usize>> {
-        forward!(searcher: self, s => s.rsearch(span))
+        forward!(searcher: self, span, s => s.rsearch(span), next_back)
     }
 
     #[inline]
@@ -640,4 +1367,6 @@ macro_rules! impl_pattern {
 impl_pattern!(<['p, 'h, T]> &'h [T]);
 impl_pattern!(<['p, 'h, T]> &'h mut [T]);
 #[cfg(feature = "std")]
-impl_pattern!(<['p, T]> Vec<T>);
+impl_pattern!(<['p, T, A: Allocator + Clone]> Vec<T, A>);
+#[cfg(feature = "std")]
+impl_pattern!(<['p, T, A: Allocator + Clone]> Box<[T], A>);
@@ -0,0 +1,177 @@
+use pattern::*;
+use haystack::Span;
+use std::ops::Range;
+#[cfg(feature = "std")]
+use std::alloc::Allocator;
+
+/// Matches single elements equal to a value, in a haystack sorted in
+/// ascending order.
+///
+/// All elements equal to the value are adjacent in a sorted slice, so they
+/// are located by binary search in *O*(log *n*) time instead of by scanning.
+/// Searching an unsorted haystack will not cause memory unsafety, but the
+/// results are unspecified.
+///
+/// ```rust
+/// extern crate pattern_3;
+/// use pattern_3::Sorted;
+/// use pattern_3::ext::{find, trim_start};
+///
+/// let hay = &[1, 1, 2, 3, 3, 3, 5][..];
+/// assert_eq!(find(hay, Sorted(3)), Some(3));
+/// assert_eq!(find(hay, Sorted(4)), None);
+/// assert_eq!(trim_start(hay, Sorted(1)), &[2, 3, 3, 3, 5]);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Sorted<T>(pub T);
+
+/// Matches whole runs of elements equal to a value, in a haystack sorted in
+/// ascending order.
+///
+/// See [`Sorted`](struct.Sorted.html) for details.
+///
+/// ```rust
+/// extern crate pattern_3;
+/// use pattern_3::SortedRun;
+/// use pattern_3::ext::find_range;
+///
+/// let hay = &[1, 1, 2, 3, 3, 3, 5][..];
+/// assert_eq!(find_range(hay, SortedRun(3)), Some(3..6));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct SortedRun<T>(pub T);
+
+#[derive(Debug, Clone)]
+pub struct SortedSliceSearcher<T> {
+    value: T,
+    run: bool,
+}
+
+impl<T: Ord> SortedSliceSearcher<T> {
+    /// Returns the range of elements equal to the value within `hay[range]`.
+    #[inline]
+    fn equal_range(&self, hay: &[T], range: Range<usize>) -> Range<usize> {
+        let rest = &hay[range.clone()];
+        let start = range.start + rest.partition_point(|x| *x < self.value);
+        let end = range.start + rest.partition_point(|x| *x <= self.value);
+        // an unsorted haystack could make the bounds cross.
+        start..end.max(start)
+    }
+
+    #[inline]
+    fn is_match_at(&self, hay: &[T], index: usize) -> bool {
+        hay.get(index) == Some(&self.value)
+    }
+}
+
+unsafe impl<T: Ord> Searcher<[T]> for SortedSliceSearcher<T> {
+    #[inline]
+    fn search(&mut self, span: Span<&[T]>) -> Option<Range<usize>> {
+        let (hay, range) = span.into_parts();
+        let end = range.end;
+        let found = self.equal_range(hay, range);
+        if found.start >= end || !self.is_match_at(hay, found.start) {
+            None
+        } else if self.run {
+            Some(found.start..found.end.max(found.start + 1))
+        } else {
+            Some(found.start..(found.start + 1))
+        }
+    }
+
+    #[inline]
+    fn consume(&mut self, span: Span<&[T]>) -> Option<usize> {
+        let (hay, range) = span.into_parts();
+        if range.start >= range.end || !self.is_match_at(hay, range.start) {
+            None
+        } else if self.run {
+            Some(self.equal_range(hay, range.clone()).end.max(range.start + 1))
+        } else {
+            Some(range.start + 1)
+        }
+    }
+
+    #[inline]
+    fn trim_start(&mut self, hay: &[T]) -> usize {
+        if self.is_match_at(hay, 0) {
+            self.equal_range(hay, 0..hay.len()).end.max(1)
+        } else {
+            0
+        }
+    }
+}
+
+unsafe impl<T: Ord> ReverseSearcher<[T]> for SortedSliceSearcher<T> {
+    #[inline]
+    fn rsearch(&mut self, span: Span<&[T]>) -> Option<Range<usize>> {
+        let (hay, range) = span.into_parts();
+        let start = range.start;
+        let found = self.equal_range(hay, range);
+        if found.end <= start || !self.is_match_at(hay, found.end - 1) {
+            None
+        } else if self.run {
+            Some(found.start.min(found.end - 1)..found.end)
+        } else {
+            Some((found.end - 1)..found.end)
+        }
+    }
+
+    #[inline]
+    fn rconsume(&mut self, span: Span<&[T]>) -> Option<usize> {
+        let (hay, range) = span.into_parts();
+        if range.start >= range.end || !self.is_match_at(hay, range.end - 1) {
+            None
+        } else if self.run {
+            Some(self.equal_range(hay, range.clone()).start.min(range.end - 1))
+        } else {
+            Some(range.end - 1)
+        }
+    }
+
+    #[inline]
+    fn trim_end(&mut self, hay: &[T]) -> usize {
+        let len = hay.len();
+        if len > 0 && self.is_match_at(hay, len - 1) {
+            self.equal_range(hay, 0..len).start.min(len - 1)
+        } else {
+            len
+        }
+    }
+}
+
+unsafe impl<T: Ord> DoubleEndedSearcher<[T]> for SortedSliceSearcher<T> {}
+
+macro_rules! impl_pattern {
+    (<[$($gen:tt)*]> $ty:ty) => {
+        impl<$($gen)*> Pattern<$ty> for Sorted<T>
+        where
+            T: Ord,
+        {
+            type Searcher = SortedSliceSearcher<T>;
+
+            #[inline]
+            fn into_searcher(self) -> Self::Searcher {
+                SortedSliceSearcher { value: self.0, run: false }
+            }
+        }
+
+        impl<$($gen)*> Pattern<$ty> for SortedRun<T>
+        where
+            T: Ord,
+        {
+            type Searcher = SortedSliceSearcher<T>;
+
+            #[inline]
+            fn into_searcher(self) -> Self::Searcher {
+                SortedSliceSearcher { value: self.0, run: true }
+            }
+        }
+    }
+}
+
+impl_pattern!(<['h, T]> &'h [T]);
+impl_pattern!(<['h, T]> &'h mut [T]);
+#[cfg(feature = "std")]
+impl_pattern!(<[T, A: Allocator + Clone]> Vec<T, A>);
+#[cfg(feature = "std")]
+impl_pattern!(<[T, A: Allocator + Clone]> Box<[T], A>);
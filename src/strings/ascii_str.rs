@@ -0,0 +1,374 @@
+use pattern::*;
+use haystack::{Hay, Span};
+use slices::slice::{TwoWaySearcher, NaiveSearcher, SliceSearcher, SliceHay};
+use strings::func::MultiCharEq;
+use memchr::{memchr, memrchr};
+use std::fmt;
+use std::ops::Range;
+
+/// A string known to contain only ASCII characters.
+///
+/// Every byte is a character, so every index is a codeword boundary and the
+/// `char` searchers work on bytes directly, skipping UTF-8 decoding.
+///
+/// ```rust
+/// extern crate pattern_3;
+/// use pattern_3::AsciiStr;
+/// use pattern_3::ext::{find, split, trim};
+///
+/// # fn main() {
+/// let line = AsciiStr::from_ascii("  GET /index.html HTTP/1.1 ").unwrap();
+/// let fields = split(trim(line, ' '), ' ').map(AsciiStr::as_str).collect::<Vec<_>>();
+/// assert_eq!(fields, vec!["GET", "/index.html", "HTTP/1.1"]);
+/// assert_eq!(find(line, |c: char| c.is_ascii_digit()), Some(23));
+/// assert!(AsciiStr::from_ascii("naïve").is_none());
+/// # }
+/// ```
+#[derive(PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct AsciiStr([u8]);
+
+impl AsciiStr {
+    /// Returns the bytes as an `AsciiStr`, or `None` if any of them is not
+    /// ASCII.
+    #[inline]
+    pub fn from_ascii<B: AsRef<[u8]> + ?Sized>(bytes: &B) -> Option<&Self> {
+        let bytes = bytes.as_ref();
+        if bytes.is_ascii() {
+            Some(unsafe { Self::from_ascii_unchecked(bytes) })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the bytes as an `AsciiStr` without checking them.
+    ///
+    /// # Safety
+    ///
+    /// Every byte must be ASCII.
+    #[inline]
+    pub unsafe fn from_ascii_unchecked(bytes: &[u8]) -> &Self {
+        &*(bytes as *const [u8] as *const AsciiStr)
+    }
+
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        unsafe { ::std::str::from_utf8_unchecked(&self.0) }
+    }
+
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl fmt::Debug for AsciiStr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl fmt::Display for AsciiStr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self.as_str(), f)
+    }
+}
+
+impl AsRef<str> for AsciiStr {
+    #[inline]
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<[u8]> for AsciiStr {
+    #[inline]
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl Hay for AsciiStr {
+    type Index = usize;
+
+    #[inline]
+    fn empty<'a>() -> &'a Self {
+        unsafe { AsciiStr::from_ascii_unchecked(&[]) }
+    }
+
+    #[inline]
+    fn start_index(&self) -> usize {
+        0
+    }
+
+    #[inline]
+    fn end_index(&self) -> usize {
+        self.0.len()
+    }
+
+    #[inline]
+    unsafe fn slice_unchecked(&self, range: Range<usize>) -> &Self {
+        AsciiStr::from_ascii_unchecked(self.0.get_unchecked(range))
+    }
+
+    #[inline]
+    unsafe fn next_index(&self, index: usize) -> usize {
+        index + 1
+    }
+
+    #[inline]
+    unsafe fn prev_index(&self, index: usize) -> usize {
+        index - 1
+    }
+}
+
+//------------------------------------------------------------------------------
+// Substring searchers
+//------------------------------------------------------------------------------
+
+impl SliceHay<u8> for AsciiStr {
+    #[inline]
+    fn as_slice(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+unsafe impl<'p> Searcher<AsciiStr> for TwoWaySearcher<'p, u8> {
+    #[inline]
+    fn search(&mut self, span: Span<&AsciiStr>) -> Option<Range<usize>> {
+        let (hay, range) = span.into_parts();
+        self.next(&hay.0, range)
+    }
+
+    #[inline]
+    fn consume(&mut self, span: Span<&AsciiStr>) -> Option<usize> {
+        let (hay, range) = span.into_parts();
+        self.consume(unsafe { Span::from_parts(&hay.0, range) })
+    }
+}
+
+unsafe impl<'p> ReverseSearcher<AsciiStr> for TwoWaySearcher<'p, u8> {
+    #[inline]
+    fn rsearch(&mut self, span: Span<&AsciiStr>) -> Option<Range<usize>> {
+        let (hay, range) = span.into_parts();
+        self.next_back(&hay.0, range)
+    }
+
+    #[inline]
+    fn rconsume(&mut self, span: Span<&AsciiStr>) -> Option<usize> {
+        let (hay, range) = span.into_parts();
+        self.rconsume(unsafe { Span::from_parts(&hay.0, range) })
+    }
+}
+
+unsafe impl<'p> Searcher<AsciiStr> for NaiveSearcher<'p, u8> {
+    #[inline]
+    fn search(&mut self, span: Span<&AsciiStr>) -> Option<Range<usize>> {
+        let (hay, range) = span.into_parts();
+        self.search(unsafe { Span::from_parts(&hay.0, range) })
+    }
+
+    #[inline]
+    fn consume(&mut self, span: Span<&AsciiStr>) -> Option<usize> {
+        let (hay, range) = span.into_parts();
+        self.consume(unsafe { Span::from_parts(&hay.0, range) })
+    }
+}
+
+unsafe impl<'p> ReverseSearcher<AsciiStr> for NaiveSearcher<'p, u8> {
+    #[inline]
+    fn rsearch(&mut self, span: Span<&AsciiStr>) -> Option<Range<usize>> {
+        let (hay, range) = span.into_parts();
+        self.rsearch(unsafe { Span::from_parts(&hay.0, range) })
+    }
+
+    #[inline]
+    fn rconsume(&mut self, span: Span<&AsciiStr>) -> Option<usize> {
+        let (hay, range) = span.into_parts();
+        self.rconsume(unsafe { Span::from_parts(&hay.0, range) })
+    }
+}
+
+impl<'h, 'p> Pattern<&'h AsciiStr> for &'p str {
+    type Searcher = SliceSearcher<'p, u8>;
+
+    #[inline]
+    fn into_searcher(self) -> Self::Searcher {
+        SliceSearcher::new_searcher(self.as_bytes())
+    }
+
+    #[inline]
+    fn into_consumer(self) -> Self::Searcher {
+        SliceSearcher::new_consumer(self.as_bytes())
+    }
+}
+
+impl<'h, 'p> Pattern<&'h AsciiStr> for &'p AsciiStr {
+    type Searcher = SliceSearcher<'p, u8>;
+
+    #[inline]
+    fn into_searcher(self) -> Self::Searcher {
+        SliceSearcher::new_searcher(&self.0)
+    }
+
+    #[inline]
+    fn into_consumer(self) -> Self::Searcher {
+        SliceSearcher::new_consumer(&self.0)
+    }
+}
+
+//------------------------------------------------------------------------------
+// Char searchers
+//------------------------------------------------------------------------------
+
+/// Searcher of a single `char` in an [`AsciiStr`](struct.AsciiStr.html).
+///
+/// A non-ASCII `char` never matches.
+#[derive(Debug, Clone)]
+pub struct AsciiCharSearcher {
+    byte: Option<u8>,
+}
+
+unsafe impl Searcher<AsciiStr> for AsciiCharSearcher {
+    #[inline]
+    fn search(&mut self, span: Span<&AsciiStr>) -> Option<Range<usize>> {
+        let (hay, range) = span.into_parts();
+        let index = range.start + memchr(self.byte?, &hay.0[range])?;
+        Some(index..(index + 1))
+    }
+
+    #[inline]
+    fn consume(&mut self, span: Span<&AsciiStr>) -> Option<usize> {
+        let (hay, range) = span.into_parts();
+        if range.start < range.end && Some(hay.0[range.start]) == self.byte {
+            Some(range.start + 1)
+        } else {
+            None
+        }
+    }
+}
+
+unsafe impl ReverseSearcher<AsciiStr> for AsciiCharSearcher {
+    #[inline]
+    fn rsearch(&mut self, span: Span<&AsciiStr>) -> Option<Range<usize>> {
+        let (hay, range) = span.into_parts();
+        let index = range.start + memrchr(self.byte?, &hay.0[range])?;
+        Some(index..(index + 1))
+    }
+
+    #[inline]
+    fn rconsume(&mut self, span: Span<&AsciiStr>) -> Option<usize> {
+        let (hay, range) = span.into_parts();
+        if range.start < range.end && Some(hay.0[range.end - 1]) == self.byte {
+            Some(range.end - 1)
+        } else {
+            None
+        }
+    }
+}
+
+unsafe impl DoubleEndedSearcher<AsciiStr> for AsciiCharSearcher {}
+
+impl FusedSearcher<AsciiStr> for AsciiCharSearcher {}
+
+/// Searcher of `char` predicates in an [`AsciiStr`](struct.AsciiStr.html),
+/// testing every byte as a `char`.
+#[derive(Debug, Clone)]
+pub struct AsciiMultiCharSearcher<F> {
+    predicate: F,
+}
+
+unsafe impl<F: FnMut(char) -> bool> Searcher<AsciiStr> for AsciiMultiCharSearcher<F> {
+    #[inline]
+    fn search(&mut self, span: Span<&AsciiStr>) -> Option<Range<usize>> {
+        let (hay, range) = span.into_parts();
+        let predicate = &mut self.predicate;
+        let index = range.start + hay.0[range].iter().position(|&b| predicate(b as char))?;
+        Some(index..(index + 1))
+    }
+
+    #[inline]
+    fn consume(&mut self, span: Span<&AsciiStr>) -> Option<usize> {
+        let (hay, range) = span.into_parts();
+        if range.start < range.end && (self.predicate)(hay.0[range.start] as char) {
+            Some(range.start + 1)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn trim_start(&mut self, hay: &AsciiStr) -> usize {
+        let predicate = &mut self.predicate;
+        hay.0.iter().position(|&b| !predicate(b as char)).unwrap_or(hay.0.len())
+    }
+}
+
+unsafe impl<F: FnMut(char) -> bool> ReverseSearcher<AsciiStr> for AsciiMultiCharSearcher<F> {
+    #[inline]
+    fn rsearch(&mut self, span: Span<&AsciiStr>) -> Option<Range<usize>> {
+        let (hay, range) = span.into_parts();
+        let predicate = &mut self.predicate;
+        let index = range.start + hay.0[range].iter().rposition(|&b| predicate(b as char))?;
+        Some(index..(index + 1))
+    }
+
+    #[inline]
+    fn rconsume(&mut self, span: Span<&AsciiStr>) -> Option<usize> {
+        let (hay, range) = span.into_parts();
+        if range.start < range.end && (self.predicate)(hay.0[range.end - 1] as char) {
+            Some(range.end - 1)
+        } else {
+            None
+        }
+    }
+
+    #[inline]
+    fn trim_end(&mut self, hay: &AsciiStr) -> usize {
+        let predicate = &mut self.predicate;
+        hay.0.iter().rposition(|&b| !predicate(b as char)).map_or(0, |i| i + 1)
+    }
+}
+
+unsafe impl<F: FnMut(char) -> bool> DoubleEndedSearcher<AsciiStr> for AsciiMultiCharSearcher<F> {}
+
+impl<F: FnMut(char) -> bool> FusedSearcher<AsciiStr> for AsciiMultiCharSearcher<F> {}
+
+impl<'h> Pattern<&'h AsciiStr> for char {
+    type Searcher = AsciiCharSearcher;
+
+    #[inline]
+    fn into_searcher(self) -> Self::Searcher {
+        AsciiCharSearcher {
+            byte: if self.is_ascii() { Some(self as u8) } else { None },
+        }
+    }
+}
+
+impl<'h, F: FnMut(char) -> bool> Pattern<&'h AsciiStr> for F {
+    type Searcher = AsciiMultiCharSearcher<F>;
+
+    #[inline]
+    fn into_searcher(self) -> Self::Searcher {
+        AsciiMultiCharSearcher { predicate: self }
+    }
+}
+
+impl<'h, 'p> Pattern<&'h AsciiStr> for &'p [char] {
+    type Searcher = AsciiMultiCharSearcher<MultiCharEq<'p>>;
+
+    #[inline]
+    fn into_searcher(self) -> Self::Searcher {
+        AsciiMultiCharSearcher { predicate: MultiCharEq(self) }
+    }
+}
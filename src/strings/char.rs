@@ -1,6 +1,9 @@
 use pattern::*;
 use haystack::{Haystack, Span};
 use memchr::{memchr, memrchr};
+use combinators::progress::BoundedMatches;
+#[cfg(feature = "std")]
+use slices::OwnedSliceSearcher;
 use std::ops::Range;
 
 #[derive(Debug, Clone)]
@@ -21,6 +24,11 @@ impl CharSearcher {
         &self.utf8_encoded[..self.utf8_size]
     }
 
+    #[inline]
+    fn lead_byte(&self) -> u8 {
+        self.utf8_encoded[0]
+    }
+
     #[inline]
     fn last_byte(&self) -> u8 {
         self.utf8_encoded[self.utf8_size - 1]
@@ -38,16 +46,42 @@ impl CharSearcher {
     }
 }
 
+impl BoundedMatches for CharSearcher {
+    #[inline]
+    fn max_match_len(&self) -> Option<usize> {
+        Some(1)
+    }
+}
+
 unsafe impl Searcher<str> for CharSearcher {
     #[inline]
     fn search(&mut self, span: Span<&str>) -> Option<Range<usize>> {
         let (hay, range) = span.into_parts();
-        let mut finger = range.start;
-        let bytes = hay.as_bytes();
+        let bytes = &hay.as_bytes()[..range.end];
+        let start = range.start;
+        if self.utf8_size == 1 {
+            let index = memchr(self.lead_byte(), &bytes[start..])? + start;
+            return Some(index..(index + 1));
+        }
+
+        // Try the lead byte first. In text written in the same script as the
+        // needle it is shared by almost every char, so once it produces a
+        // false candidate, fall back to the last byte for the rest of the
+        // search.
+        let index = memchr(self.lead_byte(), &bytes[start..])? + start;
+        let end = index + self.utf8_size;
+        if end > range.end {
+            return None;
+        }
+        if &bytes[index..end] == self.as_bytes() {
+            return Some(index..end);
+        }
+
+        let mut finger = index + 1;
         loop {
-            let index = memchr(self.last_byte(), &bytes[finger..range.end])?;
-            finger += index + 1;
-            if finger >= self.utf8_size {
+            let index = memchr(self.last_byte(), &bytes[finger..])? + finger;
+            finger = index + 1;
+            if finger >= start + self.utf8_size {
                 let found = &bytes[(finger - self.utf8_size)..finger];
                 if found == self.as_bytes() {
                     return Some((finger - self.utf8_size)..finger);
@@ -74,7 +108,21 @@ unsafe impl ReverseSearcher<str> for CharSearcher {
     fn rsearch(&mut self, span: Span<&str>) -> Option<Range<usize>> {
         let (hay, range) = span.into_parts();
         let start = range.start;
-        let mut bytes = hay[range].as_bytes();
+        let mut bytes = &hay.as_bytes()[start..range.end];
+        if self.utf8_size == 1 {
+            let index = memrchr(self.lead_byte(), bytes)? + start;
+            return Some(index..(index + 1));
+        }
+
+        // Same strategy as `search`: the lead byte gets one chance to be a
+        // useful prefilter before we fall back to the last byte.
+        let index = memrchr(self.lead_byte(), bytes)?;
+        let end = index + self.utf8_size;
+        if end <= bytes.len() && &bytes[index..end] == self.as_bytes() {
+            return Some((index + start)..(end + start));
+        }
+
+        bytes = &bytes[..index];
         loop {
             let index = memrchr(self.last_byte(), bytes)? + 1;
             if index >= self.utf8_size {
@@ -108,7 +156,9 @@ unsafe impl ReverseSearcher<str> for CharSearcher {
 
 unsafe impl DoubleEndedSearcher<str> for CharSearcher {}
 
-impl<H: Haystack<Target = str>> Pattern<H> for char {
+impl FusedSearcher<str> for CharSearcher {}
+
+impl<H: Haystack<Hay = str>> Pattern<H> for char {
     type Searcher = CharSearcher;
 
     #[inline]
@@ -116,3 +166,16 @@ impl<H: Haystack<Target = str>> Pattern<H> for char {
         CharSearcher::new(self)
     }
 }
+
+// Like a string needle, a `char` finds its UTF-8 encoding in a byte string.
+// The encoding is copied into the searcher, since the `char` does not outlive
+// it.
+#[cfg(feature = "std")]
+impl<'h> Pattern<&'h [u8]> for char {
+    type Searcher = OwnedSliceSearcher;
+
+    #[inline]
+    fn into_searcher(self) -> Self::Searcher {
+        OwnedSliceSearcher::new(self.encode_utf8(&mut [0; 4]).as_bytes())
+    }
+}
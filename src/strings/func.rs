@@ -1,9 +1,14 @@
 use pattern::*;
-use haystack::Span;
-use std::ops::Range;
+use haystack::{Haystack, Span};
+use combinators::progress::BoundedMatches;
+use std::ops::{Range, RangeInclusive};
+#[cfg(feature = "std")]
+use std::borrow::Cow;
+#[cfg(feature = "std")]
+use super::{ArcStr, RcStr};
 
 #[derive(Copy, Clone, Debug)]
-pub struct MultiCharEq<'p>(&'p [char]);
+pub struct MultiCharEq<'p>(pub(crate) &'p [char]);
 
 impl<'p> FnOnce<(char,)> for MultiCharEq<'p> {
     type Output = bool;
@@ -27,10 +32,67 @@ impl<'p> Fn<(char,)> for MultiCharEq<'p> {
     }
 }
 
+/// Predicate matching the `char`s within a range, used as the pattern of
+/// `Range<char>` and `RangeInclusive<char>`.
+#[derive(Copy, Clone, Debug)]
+pub struct CharRangeEq {
+    start: u32,
+    len: u32,
+}
+
+impl From<Range<char>> for CharRangeEq {
+    #[inline]
+    fn from(range: Range<char>) -> Self {
+        let start = range.start as u32;
+        CharRangeEq { start, len: (range.end as u32).saturating_sub(start) }
+    }
+}
+
+impl From<RangeInclusive<char>> for CharRangeEq {
+    #[inline]
+    fn from(range: RangeInclusive<char>) -> Self {
+        if range.is_empty() {
+            return CharRangeEq { start: 0, len: 0 };
+        }
+        let start = *range.start() as u32;
+        CharRangeEq { start, len: *range.end() as u32 - start + 1 }
+    }
+}
+
+impl FnOnce<(char,)> for CharRangeEq {
+    type Output = bool;
+    #[inline]
+    extern "rust-call" fn call_once(self, args: (char,)) -> bool {
+        self.call(args)
+    }
+}
+
+impl FnMut<(char,)> for CharRangeEq {
+    #[inline]
+    extern "rust-call" fn call_mut(&mut self, args: (char,)) -> bool {
+        self.call(args)
+    }
+}
+
+impl Fn<(char,)> for CharRangeEq {
+    #[inline]
+    extern "rust-call" fn call(&self, (c,): (char,)) -> bool {
+        (c as u32).wrapping_sub(self.start) < self.len
+    }
+}
+
+#[derive(Clone)]
 pub struct MultiCharSearcher<F> {
     predicate: F,
 }
 
+impl<F> BoundedMatches for MultiCharSearcher<F> {
+    #[inline]
+    fn max_match_len(&self) -> Option<usize> {
+        Some(1)
+    }
+}
+
 unsafe impl<F: FnMut(char) -> bool> Searcher<str> for MultiCharSearcher<F> {
     #[inline]
     fn search(&mut self, span: Span<&str>) -> Option<Range<usize>> {
@@ -113,6 +175,29 @@ unsafe impl<F: FnMut(char) -> bool> ReverseSearcher<str> for MultiCharSearcher<F
 
 unsafe impl<F: FnMut(char) -> bool> DoubleEndedSearcher<str> for MultiCharSearcher<F> {}
 
+impl<F: FnMut(char) -> bool> FusedSearcher<str> for MultiCharSearcher<F> {}
+
+impl<H: Haystack<Hay = str>> Pattern<H> for Range<char> {
+    type Searcher = MultiCharSearcher<CharRangeEq>;
+
+    #[inline]
+    fn into_searcher(self) -> Self::Searcher {
+        MultiCharSearcher { predicate: CharRangeEq::from(self) }
+    }
+}
+
+impl<H: Haystack<Hay = str>> Pattern<H> for RangeInclusive<char> {
+    type Searcher = MultiCharSearcher<CharRangeEq>;
+
+    #[inline]
+    fn into_searcher(self) -> Self::Searcher {
+        MultiCharSearcher { predicate: CharRangeEq::from(self) }
+    }
+}
+
+// FIXME cannot impl `Pattern<(_: Haystack<Hay = str>)>` due to RFC 1672 being postponed.
+// (need to wait for chalk) Until then, closures and `&[char]` would overlap
+// with their impls for slices.
 macro_rules! impl_pattern {
     ($ty:ty) => {
         impl<'h, F: FnMut(char) -> bool> Pattern<$ty> for F {
@@ -137,3 +222,13 @@ macro_rules! impl_pattern {
 
 impl_pattern!(&'h str);
 impl_pattern!(&'h mut str);
+#[cfg(feature = "std")]
+impl_pattern!(Box<str>);
+#[cfg(feature = "std")]
+impl_pattern!(String);
+#[cfg(feature = "std")]
+impl_pattern!(Cow<'h, str>);
+#[cfg(feature = "std")]
+impl_pattern!(ArcStr);
+#[cfg(feature = "std")]
+impl_pattern!(RcStr);
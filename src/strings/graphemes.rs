@@ -0,0 +1,270 @@
+use pattern::*;
+use haystack::{Hay, Span};
+use slices::slice::TwoWaySearcher;
+use unicode_segmentation::{GraphemeCursor, UnicodeSegmentation};
+use std::ops::Range;
+
+/// A string whose codewords are extended grapheme clusters.
+///
+/// Patterns searched in a `Graphemes` hay can only match whole clusters, so
+/// `split` and `trim` never cut a user-perceived character in half. Indices
+/// are byte offsets into the string, and are always cluster boundaries.
+///
+/// ```rust
+/// extern crate pattern_3;
+/// use pattern_3::Graphemes;
+/// use pattern_3::ext::{find, split, trim_end};
+///
+/// # fn main() {
+/// // "e" followed by a combining acute accent is a single cluster.
+/// let text = Graphemes::new("cafe\u{301} / cafe");
+/// assert_eq!(find(text, "cafe"), Some(9));
+/// assert_eq!(split(text, " / ").map(|g| g.as_str()).collect::<Vec<_>>(), vec!["cafe\u{301}", "cafe"]);
+/// assert_eq!(trim_end(Graphemes::new("flag🇯🇵"), |g: &str| !g.is_ascii()).as_str(), "flag");
+/// # }
+/// ```
+#[derive(Debug, PartialEq, Eq, Hash)]
+#[repr(transparent)]
+pub struct Graphemes(str);
+
+impl Graphemes {
+    #[inline]
+    pub fn new(s: &str) -> &Self {
+        unsafe { &*(s as *const str as *const Graphemes) }
+    }
+
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    #[inline]
+    fn is_boundary(&self, index: usize) -> bool {
+        GraphemeCursor::new(index, self.0.len(), true)
+            .is_boundary(&self.0, 0)
+            .unwrap_or(false)
+    }
+}
+
+impl Hay for Graphemes {
+    type Index = usize;
+
+    #[inline]
+    fn empty<'a>() -> &'a Self {
+        Graphemes::new("")
+    }
+
+    #[inline]
+    fn start_index(&self) -> usize {
+        0
+    }
+
+    #[inline]
+    fn end_index(&self) -> usize {
+        self.0.len()
+    }
+
+    #[inline]
+    unsafe fn slice_unchecked(&self, range: Range<usize>) -> &Self {
+        Graphemes::new(self.0.get_unchecked(range))
+    }
+
+    #[inline]
+    unsafe fn next_index(&self, index: usize) -> usize {
+        let len = self.0.get_unchecked(index..).graphemes(true).next().map_or(0, str::len);
+        index + len
+    }
+
+    #[inline]
+    unsafe fn prev_index(&self, index: usize) -> usize {
+        let len = self.0.get_unchecked(..index).graphemes(true).next_back().map_or(0, str::len);
+        index - len
+    }
+}
+
+//------------------------------------------------------------------------------
+// String needles
+//------------------------------------------------------------------------------
+
+/// Searcher of string needles in a [`Graphemes`](struct.Graphemes.html) hay.
+///
+/// Occurrences of the needle which start or end inside a cluster are skipped.
+#[derive(Debug, Clone)]
+pub struct GraphemeStrSearcher<'p> {
+    needle: &'p [u8],
+    // `None` for an empty needle.
+    two_way: Option<TwoWaySearcher<'p, u8>>,
+    empty: EmptySearcher,
+}
+
+impl<'p> GraphemeStrSearcher<'p> {
+    #[inline]
+    fn new(needle: &'p str) -> Self {
+        let needle = needle.as_bytes();
+        GraphemeStrSearcher {
+            needle,
+            two_way: if needle.is_empty() { None } else { Some(TwoWaySearcher::new(needle)) },
+            empty: EmptySearcher::default(),
+        }
+    }
+}
+
+unsafe impl<'p> Searcher<Graphemes> for GraphemeStrSearcher<'p> {
+    #[inline]
+    fn search(&mut self, span: Span<&Graphemes>) -> Option<Range<usize>> {
+        let two_way = match &mut self.two_way {
+            Some(two_way) => two_way,
+            None => return self.empty.search(span),
+        };
+        let (hay, range) = span.into_parts();
+        let mut start = range.start;
+        loop {
+            let found = two_way.next(hay.0.as_bytes(), start..range.end)?;
+            if hay.is_boundary(found.start) && hay.is_boundary(found.end) {
+                return Some(found);
+            }
+            start = found.start + 1;
+        }
+    }
+
+    #[inline]
+    fn consume(&mut self, span: Span<&Graphemes>) -> Option<usize> {
+        let (hay, range) = span.into_parts();
+        let end = range.start + self.needle.len();
+        if end <= range.end
+            && &hay.0.as_bytes()[range.start..end] == self.needle
+            && hay.is_boundary(end)
+        {
+            Some(end)
+        } else {
+            None
+        }
+    }
+}
+
+unsafe impl<'p> ReverseSearcher<Graphemes> for GraphemeStrSearcher<'p> {
+    #[inline]
+    fn rsearch(&mut self, span: Span<&Graphemes>) -> Option<Range<usize>> {
+        let two_way = match &mut self.two_way {
+            Some(two_way) => two_way,
+            None => return self.empty.rsearch(span),
+        };
+        let (hay, range) = span.into_parts();
+        let mut end = range.end;
+        loop {
+            let found = two_way.next_back(hay.0.as_bytes(), range.start..end)?;
+            if hay.is_boundary(found.start) && hay.is_boundary(found.end) {
+                return Some(found);
+            }
+            end = found.end - 1;
+        }
+    }
+
+    #[inline]
+    fn rconsume(&mut self, span: Span<&Graphemes>) -> Option<usize> {
+        let (hay, range) = span.into_parts();
+        let start = range.end.checked_sub(self.needle.len())?;
+        if start >= range.start
+            && &hay.0.as_bytes()[start..range.end] == self.needle
+            && hay.is_boundary(start)
+        {
+            Some(start)
+        } else {
+            None
+        }
+    }
+}
+
+impl<'h, 'p> Pattern<&'h Graphemes> for &'p str {
+    type Searcher = GraphemeStrSearcher<'p>;
+
+    #[inline]
+    fn into_searcher(self) -> Self::Searcher {
+        GraphemeStrSearcher::new(self)
+    }
+}
+
+impl<'h, 'p> Pattern<&'h Graphemes> for &'p Graphemes {
+    type Searcher = GraphemeStrSearcher<'p>;
+
+    #[inline]
+    fn into_searcher(self) -> Self::Searcher {
+        GraphemeStrSearcher::new(&self.0)
+    }
+}
+
+//------------------------------------------------------------------------------
+// Cluster predicates
+//------------------------------------------------------------------------------
+
+/// Searcher of cluster predicates `FnMut(&str) -> bool` in a
+/// [`Graphemes`](struct.Graphemes.html) hay.
+#[derive(Debug, Clone)]
+pub struct GraphemeSearcher<F> {
+    predicate: F,
+}
+
+unsafe impl<F: FnMut(&str) -> bool> Searcher<Graphemes> for GraphemeSearcher<F> {
+    #[inline]
+    fn search(&mut self, span: Span<&Graphemes>) -> Option<Range<usize>> {
+        let (hay, range) = span.into_parts();
+        let mut start = range.start;
+        while start < range.end {
+            let end = unsafe { hay.next_index(start) };
+            if (self.predicate)(&hay.0[start..end]) {
+                return Some(start..end);
+            }
+            start = end;
+        }
+        None
+    }
+
+    #[inline]
+    fn consume(&mut self, span: Span<&Graphemes>) -> Option<usize> {
+        let (hay, range) = span.into_parts();
+        if range.start == range.end {
+            return None;
+        }
+        let end = unsafe { hay.next_index(range.start) };
+        if (self.predicate)(&hay.0[range.start..end]) { Some(end) } else { None }
+    }
+}
+
+unsafe impl<F: FnMut(&str) -> bool> ReverseSearcher<Graphemes> for GraphemeSearcher<F> {
+    #[inline]
+    fn rsearch(&mut self, span: Span<&Graphemes>) -> Option<Range<usize>> {
+        let (hay, range) = span.into_parts();
+        let mut end = range.end;
+        while end > range.start {
+            let start = unsafe { hay.prev_index(end) };
+            if (self.predicate)(&hay.0[start..end]) {
+                return Some(start..end);
+            }
+            end = start;
+        }
+        None
+    }
+
+    #[inline]
+    fn rconsume(&mut self, span: Span<&Graphemes>) -> Option<usize> {
+        let (hay, range) = span.into_parts();
+        if range.start == range.end {
+            return None;
+        }
+        let start = unsafe { hay.prev_index(range.end) };
+        if (self.predicate)(&hay.0[start..range.end]) { Some(start) } else { None }
+    }
+}
+
+unsafe impl<F: FnMut(&str) -> bool> DoubleEndedSearcher<Graphemes> for GraphemeSearcher<F> {}
+
+impl<F: FnMut(&str) -> bool> FusedSearcher<Graphemes> for GraphemeSearcher<F> {}
+
+impl<'h, F: FnMut(&str) -> bool> Pattern<&'h Graphemes> for F {
+    type Searcher = GraphemeSearcher<F>;
+
+    #[inline]
+    fn into_searcher(self) -> Self::Searcher {
+        GraphemeSearcher { predicate: self }
+    }
+}
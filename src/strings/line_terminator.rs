@@ -0,0 +1,126 @@
+use pattern::*;
+use haystack::{Haystack, Span};
+use memchr::{memchr, memrchr};
+use std::ops::Range;
+
+/// A pattern matching a line terminator: `\n`, or `\r\n` as a whole.
+///
+/// A lone `\r` is not a line terminator. Splitting with
+/// [`split_terminator`](::ext::split_terminator) reproduces `str::lines`,
+/// while [`split`](::ext::split) also yields an empty last line after a
+/// trailing terminator.
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate pattern_3;
+/// use pattern_3::LineTerminator;
+/// use pattern_3::ext::{split, split_terminator};
+///
+/// let text = "one\r\ntwo\rtwo\n\nfour\r\n";
+/// assert_eq!(
+///     split_terminator(text, LineTerminator).collect::<Vec<_>>(),
+///     text.lines().collect::<Vec<_>>(),
+/// );
+/// assert_eq!(
+///     split(&b"a\r\nb\n"[..], LineTerminator).collect::<Vec<_>>(),
+///     vec![&b"a"[..], b"b", b""],
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct LineTerminator;
+
+/// Searcher of the [`LineTerminator`] pattern.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LineTerminatorSearcher;
+
+/// Returns the start of the terminator whose `\n` is at `bytes[i]`.
+#[inline]
+fn terminator_start(bytes: &[u8], start: usize, i: usize) -> usize {
+    if i > start && bytes[i - 1] == b'\r' {
+        i - 1
+    } else {
+        i
+    }
+}
+
+#[inline]
+fn search(bytes: &[u8], range: Range<usize>) -> Option<Range<usize>> {
+    let i = memchr(b'\n', &bytes[range.clone()])? + range.start;
+    Some(terminator_start(bytes, range.start, i)..(i + 1))
+}
+
+#[inline]
+fn rsearch(bytes: &[u8], range: Range<usize>) -> Option<Range<usize>> {
+    let i = memrchr(b'\n', &bytes[range.clone()])? + range.start;
+    Some(terminator_start(bytes, range.start, i)..(i + 1))
+}
+
+#[inline]
+fn consume(bytes: &[u8], range: Range<usize>) -> Option<usize> {
+    match &bytes[range.clone()] {
+        [b'\n', ..] => Some(range.start + 1),
+        [b'\r', b'\n', ..] => Some(range.start + 2),
+        _ => None,
+    }
+}
+
+#[inline]
+fn rconsume(bytes: &[u8], range: Range<usize>) -> Option<usize> {
+    match &bytes[range.clone()] {
+        [.., b'\r', b'\n'] => Some(range.end - 2),
+        [.., b'\n'] => Some(range.end - 1),
+        _ => None,
+    }
+}
+
+macro_rules! impl_searcher {
+    ($hay:ty, |$h:ident| $bytes:expr) => {
+        unsafe impl Searcher<$hay> for LineTerminatorSearcher {
+            #[inline]
+            fn search(&mut self, span: Span<&$hay>) -> Option<Range<usize>> {
+                let ($h, range) = span.into_parts();
+                search($bytes, range)
+            }
+
+            #[inline]
+            fn consume(&mut self, span: Span<&$hay>) -> Option<usize> {
+                let ($h, range) = span.into_parts();
+                consume($bytes, range)
+            }
+        }
+
+        unsafe impl ReverseSearcher<$hay> for LineTerminatorSearcher {
+            #[inline]
+            fn rsearch(&mut self, span: Span<&$hay>) -> Option<Range<usize>> {
+                let ($h, range) = span.into_parts();
+                rsearch($bytes, range)
+            }
+
+            #[inline]
+            fn rconsume(&mut self, span: Span<&$hay>) -> Option<usize> {
+                let ($h, range) = span.into_parts();
+                rconsume($bytes, range)
+            }
+        }
+
+        unsafe impl DoubleEndedSearcher<$hay> for LineTerminatorSearcher {}
+
+        impl FusedSearcher<$hay> for LineTerminatorSearcher {}
+    }
+}
+
+impl_searcher!(str, |hay| hay.as_bytes());
+impl_searcher!([u8], |hay| hay);
+
+impl<H: Haystack> Pattern<H> for LineTerminator
+where
+    LineTerminatorSearcher: Searcher<H::Hay>,
+{
+    type Searcher = LineTerminatorSearcher;
+
+    #[inline]
+    fn into_searcher(self) -> Self::Searcher {
+        LineTerminatorSearcher
+    }
+}
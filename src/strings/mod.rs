@@ -1,5 +1,7 @@
 use haystack::{Hay, Haystack};
 use std::ops::Range;
+#[cfg(feature = "std")]
+use std::borrow::Cow;
 
 impl Hay for str {
     type Index = usize;
@@ -36,8 +38,15 @@ impl Hay for str {
 }
 
 impl<'h> Haystack for &'h mut str {
+    type Hay = str;
+
     #[inline]
-    fn empty() -> &'h mut str {
+    fn hay(&self) -> &str {
+        self
+    }
+
+    #[inline]
+    fn empty(&self) -> &'h mut str {
         Self::default()
     }
 
@@ -59,6 +68,151 @@ impl<'h> Haystack for &'h mut str {
     }
 }
 
+#[cfg(feature = "std")]
+impl Haystack for Box<str> {
+    type Hay = str;
+
+    #[inline]
+    fn hay(&self) -> &str {
+        self
+    }
+
+    #[inline]
+    fn empty(&self) -> Self {
+        Box::default()
+    }
+
+    #[inline]
+    unsafe fn slice_unchecked(self, range: Range<usize>) -> Self {
+        let mut string = self.into_string();
+        string.truncate(range.end);
+        string.drain(..range.start);
+        string.into_boxed_str()
+    }
+
+    #[inline]
+    unsafe fn split_around(self, range: Range<usize>) -> [Self; 3] {
+        let mut left = self.into_string();
+        let right = left.split_off(range.end);
+        let middle = left.split_off(range.start);
+        [left.into_boxed_str(), middle.into_boxed_str(), right.into_boxed_str()]
+    }
+
+    #[inline]
+    fn restore_range(&self, range: Range<usize>, subrange: Range<usize>) -> Range<usize> {
+        (subrange.start + range.start)..(subrange.end + range.start)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Haystack for String {
+    type Hay = str;
+
+    #[inline]
+    fn hay(&self) -> &str {
+        self
+    }
+
+    #[inline]
+    fn empty(&self) -> Self {
+        String::new()
+    }
+
+    #[inline]
+    unsafe fn slice_unchecked(mut self, range: Range<usize>) -> Self {
+        self.truncate(range.end);
+        self.drain(..range.start);
+        self
+    }
+
+    #[inline]
+    unsafe fn split_around(mut self, range: Range<usize>) -> [Self; 3] {
+        let right = self.split_off(range.end);
+        let middle = self.split_off(range.start);
+        [self, middle, right]
+    }
+
+    #[inline]
+    fn restore_range(&self, range: Range<usize>, subrange: Range<usize>) -> Range<usize> {
+        (subrange.start + range.start)..(subrange.end + range.start)
+    }
+}
+
+// Borrowed pieces stay borrowed, so zero-copy paths are kept when possible.
+#[cfg(feature = "std")]
+impl<'a> Haystack for Cow<'a, str> {
+    type Hay = str;
+
+    #[inline]
+    fn hay(&self) -> &str {
+        self
+    }
+
+    #[inline]
+    fn empty(&self) -> Self {
+        Cow::Borrowed("")
+    }
+
+    #[inline]
+    unsafe fn slice_unchecked(self, range: Range<usize>) -> Self {
+        match self {
+            Cow::Borrowed(s) => Cow::Borrowed(Haystack::slice_unchecked(s, range)),
+            Cow::Owned(s) => Cow::Owned(Haystack::slice_unchecked(s, range)),
+        }
+    }
+
+    #[inline]
+    unsafe fn split_around(self, range: Range<usize>) -> [Self; 3] {
+        match self {
+            Cow::Borrowed(s) => {
+                let [left, middle, right] = Haystack::split_around(s, range);
+                [Cow::Borrowed(left), Cow::Borrowed(middle), Cow::Borrowed(right)]
+            }
+            Cow::Owned(s) => {
+                let [left, middle, right] = Haystack::split_around(s, range);
+                [Cow::Owned(left), Cow::Owned(middle), Cow::Owned(right)]
+            }
+        }
+    }
+
+    #[inline]
+    fn restore_range(&self, range: Range<usize>, subrange: Range<usize>) -> Range<usize> {
+        (subrange.start + range.start)..(subrange.end + range.start)
+    }
+}
+
 mod char;
-mod func;
+pub(crate) mod func;
 mod str;
+mod utf8_bytes;
+mod whitespace;
+mod whitespace_runs;
+mod line_terminator;
+mod ascii_str;
+#[cfg(feature = "std")]
+pub(crate) mod normalized;
+#[cfg(feature = "std")]
+mod shared;
+#[cfg(feature = "phonetic")]
+mod phonetic;
+#[cfg(feature = "ropey")]
+mod rope;
+#[cfg(feature = "unicode-segmentation")]
+mod graphemes;
+
+pub use self::str::Utf8Needle;
+pub use self::utf8_bytes::{Utf8Bytes, Utf8BytesSearcher};
+pub use self::whitespace::{WhitespaceTolerant, WhitespaceTolerantSearcher};
+pub use self::whitespace_runs::{Whitespace, WhitespaceSearcher};
+pub use self::line_terminator::{LineTerminator, LineTerminatorSearcher};
+pub use self::ascii_str::{AsciiStr, AsciiCharSearcher, AsciiMultiCharSearcher};
+#[cfg(feature = "std")]
+pub use self::shared::{ArcStr, RcStr};
+#[cfg(feature = "std")]
+pub use self::normalized::{NormalizedView, NormalizedMatchRanges, NormalizedRMatchRanges};
+#[cfg(feature = "unicode-segmentation")]
+pub use self::graphemes::{Graphemes, GraphemeSearcher, GraphemeStrSearcher};
+#[cfg(feature = "phonetic")]
+pub use self::phonetic::{Soundex, SoundexSearcher};
+#[cfg(feature = "ropey")]
+pub use self::rope::rope_replace_with;
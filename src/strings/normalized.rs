@@ -0,0 +1,282 @@
+use pattern::{Pattern, Searcher, ReverseSearcher};
+use ext::{MatchRanges, RMatchRanges, find_range, rfind_range, match_ranges, rmatch_ranges};
+use std::iter::FusedIterator;
+use std::ops::Range;
+
+/// A string searched through a per-character mapping.
+///
+/// The mapping is applied once when the view is constructed. Each character
+/// of the original string may map to any number of characters (e.g.
+/// `char::to_lowercase` may produce several, and a filter stripping control
+/// characters produces none). Patterns are then matched against the
+/// normalized text, and every reported range is translated back into the
+/// coordinates of the original string.
+///
+/// A match covering only part of the expansion of an original character is
+/// widened to cover that whole character.
+///
+/// ```rust
+/// extern crate pattern_3;
+/// use pattern_3::NormalizedView;
+///
+/// let view = NormalizedView::new("Straße 5", |c: char| c.to_lowercase());
+/// assert_eq!(view.normalized(), "straße 5");
+///
+/// let view = NormalizedView::new("A\u{0}B\u{7}C", |c: char| Some(c).filter(|c| !c.is_control()));
+/// assert_eq!(view.find_range("ABC"), Some(0..5));
+/// ```
+#[derive(Debug, Clone)]
+pub struct NormalizedView<'h> {
+    original: &'h str,
+    normalized: String,
+    // For every byte of `normalized`, the index of the original segment it was
+    // produced from.
+    origins: Vec<usize>,
+    // Returns the length of the segment at the start of the string. A segment
+    // is normally a single character, but can be longer if the mapping needs
+    // to look at several characters at once.
+    segment_len: fn(&str) -> usize,
+}
+
+fn char_len(s: &str) -> usize {
+    s.chars().next().map_or(0, char::len_utf8)
+}
+
+impl<'h> NormalizedView<'h> {
+    pub fn new<F, I>(original: &'h str, mut map: F) -> Self
+    where
+        F: FnMut(char) -> I,
+        I: IntoIterator<Item = char>,
+    {
+        Self::from_segments(original, char_len, |segment, normalized| {
+            for c in segment.chars() {
+                normalized.extend(map(c));
+            }
+        })
+    }
+
+    fn from_segments<F>(original: &'h str, segment_len: fn(&str) -> usize, mut map: F) -> Self
+    where
+        F: FnMut(&str, &mut String),
+    {
+        let mut normalized = String::with_capacity(original.len());
+        let mut origins = Vec::with_capacity(original.len());
+        let mut index = 0;
+        while index < original.len() {
+            let end = index + segment_len(&original[index..]);
+            map(&original[index..end], &mut normalized);
+            origins.resize(normalized.len(), index);
+            index = end;
+        }
+        NormalizedView { original, normalized, origins, segment_len }
+    }
+
+    #[inline]
+    pub fn original(&self) -> &'h str {
+        self.original
+    }
+
+    #[inline]
+    pub fn normalized(&self) -> &str {
+        &self.normalized
+    }
+
+    /// Translates a range of the normalized text into the original string.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the range is out of bounds of the normalized text.
+    pub fn original_range(&self, range: Range<usize>) -> Range<usize> {
+        assert!(range.start <= range.end && range.end <= self.normalized.len());
+        let start = match self.origins.get(range.start) {
+            Some(&start) => start,
+            None => self.original.len(),
+        };
+        if range.start == range.end {
+            return start..start;
+        }
+        let last = self.origins[range.end - 1];
+        start..(last + (self.segment_len)(&self.original[last..]))
+    }
+
+    pub fn find_range<'v, P>(&'v self, pattern: P) -> Option<Range<usize>>
+    where
+        P: Pattern<&'v str>,
+    {
+        let range = find_range(&*self.normalized, pattern)?;
+        Some(self.original_range(range))
+    }
+
+    pub fn rfind_range<'v, P>(&'v self, pattern: P) -> Option<Range<usize>>
+    where
+        P: Pattern<&'v str>,
+        P::Searcher: ReverseSearcher<str>,
+    {
+        let range = rfind_range(&*self.normalized, pattern)?;
+        Some(self.original_range(range))
+    }
+
+    pub fn match_ranges<'v, P>(&'v self, pattern: P) -> NormalizedMatchRanges<'v, 'h, P::Searcher>
+    where
+        P: Pattern<&'v str>,
+    {
+        NormalizedMatchRanges {
+            view: self,
+            inner: match_ranges(&*self.normalized, pattern),
+        }
+    }
+
+    pub fn rmatch_ranges<'v, P>(&'v self, pattern: P) -> NormalizedRMatchRanges<'v, 'h, P::Searcher>
+    where
+        P: Pattern<&'v str>,
+        P::Searcher: ReverseSearcher<str>,
+    {
+        NormalizedRMatchRanges {
+            view: self,
+            inner: rmatch_ranges(&*self.normalized, pattern),
+        }
+    }
+}
+
+macro_rules! generate_normalized_iterator {
+    ($name:ident, $inner:ident, $searcher:ident) => {
+        #[derive(Debug, Clone)]
+        pub struct $name<'v, 'h: 'v, S> {
+            view: &'v NormalizedView<'h>,
+            inner: $inner<&'v str, S>,
+        }
+
+        impl<'v, 'h, S> Iterator for $name<'v, 'h, S>
+        where
+            S: $searcher<str>,
+        {
+            type Item = (Range<usize>, &'h str);
+
+            #[inline]
+            fn next(&mut self) -> Option<Self::Item> {
+                let (range, _) = self.inner.next()?;
+                let range = self.view.original_range(range);
+                Some((range.clone(), &self.view.original[range]))
+            }
+        }
+
+        impl<'v, 'h, S> FusedIterator for $name<'v, 'h, S>
+        where
+            S: $searcher<str>,
+        {}
+    }
+}
+
+generate_normalized_iterator!(NormalizedMatchRanges, MatchRanges, Searcher);
+generate_normalized_iterator!(NormalizedRMatchRanges, RMatchRanges, ReverseSearcher);
+
+//------------------------------------------------------------------------------
+// Case-insensitive view
+//------------------------------------------------------------------------------
+
+/// Appends the case folding of `c` to `out`.
+///
+/// This is the simple approximation of Unicode full case folding which std
+/// provides: the character is uppercased, and then each resulting character is
+/// lowercased. It maps e.g. `ß` to `ss` and final `ς` to `σ`.
+pub(crate) fn push_folded(c: char, out: &mut String) {
+    if c.is_ascii() {
+        out.push(c.to_ascii_lowercase());
+    } else {
+        out.extend(c.to_uppercase().flat_map(char::to_lowercase));
+    }
+}
+
+impl<'h> NormalizedView<'h> {
+    /// Creates a case-folded view of `original`, making any `str` pattern
+    /// case-insensitive.
+    ///
+    /// String needles must be folded themselves (e.g. with
+    /// [`ext::case_fold`](ext/fn.case_fold.html)) if they may contain
+    /// uppercase characters. A match covering only one `s` of a folded `ß` is
+    /// widened to cover the whole `ß`.
+    ///
+    /// ```rust
+    /// extern crate pattern_3;
+    /// use pattern_3::NormalizedView;
+    /// use pattern_3::ext::case_fold;
+    ///
+    /// let view = NormalizedView::case_folded("Die STRASSE, die Straße");
+    /// assert_eq!(view.normalized(), "die strasse, die strasse");
+    /// assert_eq!(view.find_range("die"), Some(0..3));
+    /// assert_eq!(view.rfind_range(&*case_fold("Straße")), Some(17..24));
+    /// assert_eq!(
+    ///     view.match_ranges(&*case_fold("STRASSE")).map(|(_, s)| s).collect::<Vec<_>>(),
+    ///     vec!["STRASSE", "Straße"]
+    /// );
+    /// ```
+    pub fn case_folded(original: &'h str) -> Self {
+        Self::from_segments(original, char_len, |segment, normalized| {
+            for c in segment.chars() {
+                push_folded(c, normalized);
+            }
+        })
+    }
+}
+
+//------------------------------------------------------------------------------
+// Diacritic-insensitive view
+//------------------------------------------------------------------------------
+
+#[cfg(feature = "unicode")]
+fn base_with_marks_len(s: &str) -> usize {
+    use unicode_normalization::char::is_combining_mark;
+
+    let mut chars = s.char_indices();
+    chars.next();
+    chars
+        .find(|&(_, c)| !is_combining_mark(c))
+        .map_or(s.len(), |(i, _)| i)
+}
+
+#[cfg(feature = "unicode")]
+impl<'h> NormalizedView<'h> {
+    /// Creates a view of `original` with all combining marks removed.
+    ///
+    /// Precomposed characters are canonically decomposed before their marks
+    /// are dropped, so `"é"` and `"e\u{301}"` both appear as `"e"`. Match
+    /// ranges always cover the marks following a matched base character.
+    ///
+    /// Needles should be passed through the same view if they may contain
+    /// diacritics themselves.
+    ///
+    /// ```rust
+    /// extern crate pattern_3;
+    /// use pattern_3::NormalizedView;
+    ///
+    /// let view = NormalizedView::without_diacritics("Mon résumé");
+    /// assert_eq!(view.normalized(), "Mon resume");
+    /// assert_eq!(view.find_range("resume"), Some(4..12));
+    ///
+    /// let needle = NormalizedView::without_diacritics("Résumé");
+    /// assert_eq!(view.find_range(&needle.normalized()[1..]), Some(5..12));
+    /// ```
+    pub fn without_diacritics(original: &'h str) -> Self {
+        use unicode_normalization::char::{decompose_canonical, is_combining_mark};
+
+        Self::from_segments(original, base_with_marks_len, |segment, normalized| {
+            for c in segment.chars() {
+                let start = normalized.len();
+                let mut has_mark = false;
+                decompose_canonical(c, |d| {
+                    if is_combining_mark(d) {
+                        has_mark = true;
+                    } else {
+                        normalized.push(d);
+                    }
+                });
+                // Keep characters like Hangul syllables composed if nothing
+                // was stripped from their decomposition.
+                if !has_mark {
+                    normalized.truncate(start);
+                    normalized.push(c);
+                }
+            }
+        })
+    }
+}
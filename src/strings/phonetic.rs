@@ -0,0 +1,176 @@
+use pattern::*;
+use haystack::{Haystack, Span};
+use std::ops::Range;
+
+/// Returns the Soundex digit of an uppercase ASCII letter. Vowels are `0`, and
+/// `H` and `W`, which neither count nor separate letters, are `None`.
+fn soundex_digit(c: u8) -> Option<u8> {
+    Some(match c {
+        b'B' | b'F' | b'P' | b'V' => b'1',
+        b'C' | b'G' | b'J' | b'K' | b'Q' | b'S' | b'X' | b'Z' => b'2',
+        b'D' | b'T' => b'3',
+        b'L' => b'4',
+        b'M' | b'N' => b'5',
+        b'R' => b'6',
+        b'H' | b'W' => return None,
+        _ => b'0',
+    })
+}
+
+/// Computes the American Soundex code of a word. Characters other than ASCII
+/// letters are ignored. Returns `None` if the word contains no ASCII letters.
+fn soundex(word: &str) -> Option<[u8; 4]> {
+    let mut letters = word.bytes().filter(u8::is_ascii_alphabetic).map(|b| b.to_ascii_uppercase());
+    let first = letters.next()?;
+    let mut code = [first, b'0', b'0', b'0'];
+    let mut len = 1;
+    let mut last = soundex_digit(first);
+    for c in letters {
+        let digit = soundex_digit(c);
+        match digit {
+            None => continue,
+            Some(b'0') => {}
+            Some(d) if digit != last => {
+                code[len] = d;
+                len += 1;
+                if len == 4 {
+                    break;
+                }
+            }
+            Some(_) => {}
+        }
+        last = digit;
+    }
+    Some(code)
+}
+
+#[inline]
+fn is_word_char(c: char) -> bool {
+    c.is_alphabetic()
+}
+
+/// A pattern matching whole words which sound like the needle.
+///
+/// Words are maximal runs of alphabetic characters. A word matches when its
+/// [Soundex] code equals the code of the needle, so `"Robert"` matches
+/// `"Rupert"` but not `"Rubin"`. Only ASCII letters contribute to the code,
+/// and words without any ASCII letters never match. Words cut off by the
+/// boundary of a span are not considered.
+///
+/// [Soundex]: https://en.wikipedia.org/wiki/Soundex
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate pattern_3;
+/// use pattern_3::Soundex;
+/// use pattern_3::ext::matches;
+///
+/// let names = "Robert, Rupert, Rubin and Rob";
+/// assert_eq!(matches(names, Soundex("Robert")).collect::<Vec<_>>(), vec!["Robert", "Rupert"]);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Soundex<'p>(pub &'p str);
+
+/// Searcher of a [`Soundex`] pattern.
+#[derive(Debug, Clone)]
+pub struct SoundexSearcher {
+    code: Option<[u8; 4]>,
+}
+
+impl SoundexSearcher {
+    #[inline]
+    fn is_match(&self, word: &str) -> bool {
+        self.code.is_some() && soundex(word) == self.code
+    }
+
+    /// Returns the end of the word starting at `start`, if it lies entirely
+    /// within `..end`.
+    fn word_end(hay: &str, start: usize, end: usize) -> Option<usize> {
+        let len = hay[start..].find(|c| !is_word_char(c)).unwrap_or(hay.len() - start);
+        if start + len <= end { Some(start + len) } else { None }
+    }
+
+    /// Returns the start of the word ending at `end`, if it lies entirely
+    /// within `start..`.
+    fn word_start(hay: &str, start: usize, end: usize) -> Option<usize> {
+        let word_start = hay[..end].rfind(|c| !is_word_char(c)).map_or(0, |i| i + hay[i..].chars().next().unwrap().len_utf8());
+        if start <= word_start { Some(word_start) } else { None }
+    }
+}
+
+unsafe impl Searcher<str> for SoundexSearcher {
+    fn search(&mut self, span: Span<&str>) -> Option<Range<usize>> {
+        let (hay, range) = span.into_parts();
+        let mut index = range.start;
+        // skip the remainder of a word cut by the start of the span.
+        if hay[..index].chars().next_back().is_some_and(is_word_char) {
+            index += hay[index..range.end].find(|c| !is_word_char(c))?;
+        }
+        loop {
+            let start = index + hay[index..range.end].find(is_word_char)?;
+            let end = Self::word_end(hay, start, range.end)?;
+            if self.is_match(&hay[start..end]) {
+                return Some(start..end);
+            }
+            index = end;
+        }
+    }
+
+    fn consume(&mut self, span: Span<&str>) -> Option<usize> {
+        let (hay, range) = span.into_parts();
+        if hay[..range.start].chars().next_back().is_some_and(is_word_char) {
+            return None;
+        }
+        let end = Self::word_end(hay, range.start, range.end)?;
+        if end > range.start && self.is_match(&hay[range.start..end]) {
+            Some(end)
+        } else {
+            None
+        }
+    }
+}
+
+unsafe impl ReverseSearcher<str> for SoundexSearcher {
+    fn rsearch(&mut self, span: Span<&str>) -> Option<Range<usize>> {
+        let (hay, range) = span.into_parts();
+        let mut index = range.end;
+        // skip the remainder of a word cut by the end of the span.
+        if hay[index..].chars().next().is_some_and(is_word_char) {
+            index = range.start + hay[range.start..index].rfind(|c| !is_word_char(c))?;
+        }
+        loop {
+            let last = range.start + hay[range.start..index].rfind(is_word_char)?;
+            let end = last + hay[last..].chars().next().unwrap().len_utf8();
+            let start = Self::word_start(hay, range.start, end)?;
+            if self.is_match(&hay[start..end]) {
+                return Some(start..end);
+            }
+            index = start;
+        }
+    }
+
+    fn rconsume(&mut self, span: Span<&str>) -> Option<usize> {
+        let (hay, range) = span.into_parts();
+        if hay[range.end..].chars().next().is_some_and(is_word_char) {
+            return None;
+        }
+        let start = Self::word_start(hay, range.start, range.end)?;
+        if start < range.end && self.is_match(&hay[start..range.end]) {
+            Some(start)
+        } else {
+            None
+        }
+    }
+}
+
+unsafe impl DoubleEndedSearcher<str> for SoundexSearcher {}
+
+impl<'p, H: Haystack<Hay = str>> Pattern<H> for Soundex<'p> {
+    type Searcher = SoundexSearcher;
+
+    #[inline]
+    fn into_searcher(self) -> Self::Searcher {
+        SoundexSearcher { code: soundex(self.0) }
+    }
+}
@@ -0,0 +1,61 @@
+use slices::ChunkedHay;
+use ropey::{Rope, RopeSlice};
+
+// A rope is searched through its UTF-8 bytes, so indices are byte offsets.
+// Since both the rope and a `&str` or `char` needle are valid UTF-8, every
+// match starts and ends on a char boundary.
+
+impl ChunkedHay for Rope {
+    type Item = u8;
+
+    #[inline]
+    fn for_each_chunk<'h, F: FnMut(&'h [u8])>(&'h self, f: F) {
+        self.chunks().map(str::as_bytes).for_each(f)
+    }
+}
+
+impl<'a> ChunkedHay for RopeSlice<'a> {
+    type Item = u8;
+
+    #[inline]
+    fn for_each_chunk<'h, F: FnMut(&'h [u8])>(&'h self, f: F) {
+        self.chunks().map(str::as_bytes).for_each(f)
+    }
+}
+
+/// Replaces every non-overlapping occurrence of `from` in the rope in place,
+/// without flattening it.
+///
+/// The replacer is called on each match in order, then the edits are applied
+/// from the end so that earlier offsets stay valid. Returns the number of
+/// replacements.
+///
+/// ```rust
+/// extern crate pattern_3;
+/// extern crate ropey;
+/// use pattern_3::rope_replace_with;
+/// use ropey::Rope;
+///
+/// let mut rope = Rope::from_str("a-b-c");
+/// assert_eq!(rope_replace_with(&mut rope, "-", |_| "+".to_owned()), 2);
+/// assert_eq!(rope.to_string(), "a+b+c");
+/// ```
+pub fn rope_replace_with<F>(rope: &mut Rope, from: &str, mut replacer: F) -> usize
+where
+    F: FnMut(RopeSlice) -> String,
+{
+    let edits = rope
+        .match_ranges(from)
+        .map(|range| {
+            let to = replacer(rope.byte_slice(range.clone()));
+            (range, to)
+        })
+        .collect::<Vec<_>>();
+    for (range, to) in edits.iter().rev() {
+        let start = rope.byte_to_char(range.start);
+        let end = rope.byte_to_char(range.end);
+        rope.remove(start..end);
+        rope.insert(start, to);
+    }
+    edits.len()
+}
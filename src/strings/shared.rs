@@ -0,0 +1,189 @@
+use haystack::{Haystack, SharedHaystack};
+use std::borrow::Borrow;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::{Deref, Range};
+use std::rc::Rc;
+use std::sync::Arc;
+
+macro_rules! shared_str {
+    ($(#[$attr:meta])* $name:ident, $ptr:ident) => {
+        $(#[$attr])*
+        #[derive(Clone)]
+        pub struct $name {
+            buffer: $ptr<str>,
+            range: Range<usize>,
+        }
+
+        impl $name {
+            /// Returns the whole buffer this substring was sliced from.
+            #[inline]
+            pub fn buffer(&self) -> &$ptr<str> {
+                &self.buffer
+            }
+
+            /// Returns the range of this substring within its buffer.
+            #[inline]
+            pub fn range(&self) -> Range<usize> {
+                self.range.clone()
+            }
+        }
+
+        impl From<$ptr<str>> for $name {
+            #[inline]
+            fn from(buffer: $ptr<str>) -> Self {
+                let range = 0..buffer.len();
+                $name { buffer, range }
+            }
+        }
+
+        impl<'a> From<&'a str> for $name {
+            #[inline]
+            fn from(s: &'a str) -> Self {
+                $name::from($ptr::<str>::from(s))
+            }
+        }
+
+        impl From<String> for $name {
+            #[inline]
+            fn from(s: String) -> Self {
+                $name::from($ptr::<str>::from(s))
+            }
+        }
+
+        impl Deref for $name {
+            type Target = str;
+
+            #[inline]
+            fn deref(&self) -> &str {
+                unsafe { self.buffer.get_unchecked(self.range.clone()) }
+            }
+        }
+
+        impl AsRef<str> for $name {
+            #[inline]
+            fn as_ref(&self) -> &str {
+                self
+            }
+        }
+
+        impl Borrow<str> for $name {
+            #[inline]
+            fn borrow(&self) -> &str {
+                self
+            }
+        }
+
+        impl fmt::Debug for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                fmt::Debug::fmt(&**self, f)
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                fmt::Display::fmt(&**self, f)
+            }
+        }
+
+        impl PartialEq for $name {
+            #[inline]
+            fn eq(&self, other: &Self) -> bool {
+                **self == **other
+            }
+        }
+
+        impl Eq for $name {}
+
+        impl PartialEq<str> for $name {
+            #[inline]
+            fn eq(&self, other: &str) -> bool {
+                **self == *other
+            }
+        }
+
+        impl<'a> PartialEq<&'a str> for $name {
+            #[inline]
+            fn eq(&self, other: &&'a str) -> bool {
+                **self == **other
+            }
+        }
+
+        impl Hash for $name {
+            #[inline]
+            fn hash<S: Hasher>(&self, state: &mut S) {
+                (**self).hash(state)
+            }
+        }
+
+        impl Haystack for $name {
+            type Hay = str;
+
+            #[inline]
+            fn hay(&self) -> &str {
+                self
+            }
+
+            #[inline]
+            fn empty(&self) -> Self {
+                $name::from("")
+            }
+
+            #[inline]
+            unsafe fn slice_unchecked(self, range: Range<usize>) -> Self {
+                let start = self.range.start;
+                $name {
+                    buffer: self.buffer,
+                    range: (start + range.start)..(start + range.end),
+                }
+            }
+
+            #[inline]
+            unsafe fn split_around(self, range: Range<usize>) -> [Self; 3] {
+                let start = self.range.start;
+                let end = self.range.end;
+                [
+                    $name { buffer: self.buffer.clone(), range: start..(start + range.start) },
+                    $name { buffer: self.buffer.clone(), range: (start + range.start)..(start + range.end) },
+                    $name { buffer: self.buffer, range: (start + range.end)..end },
+                ]
+            }
+
+            #[inline]
+            fn restore_range(&self, range: Range<usize>, subrange: Range<usize>) -> Range<usize> {
+                (subrange.start + range.start)..(subrange.end + range.start)
+            }
+        }
+
+        /// Cloning only bumps the reference count, so the whole substring is
+        /// retained and pieces are sliced out when they are yielded.
+        impl SharedHaystack for $name {}
+    }
+}
+
+shared_str! {
+    /// A substring of an `Arc<str>`, sharing the buffer it was sliced from.
+    ///
+    /// Splitting or matching an `ArcStr` yields more `ArcStr`s, which are
+    /// `Send` and can outlive any borrow of the original string, making them
+    /// suitable for storing matches in long-lived structures.
+    ///
+    /// ```rust
+    /// extern crate pattern_3;
+    /// use pattern_3::ArcStr;
+    /// use pattern_3::ext::split;
+    ///
+    /// let words: Vec<ArcStr> = split(ArcStr::from("to be or not"), ' ').collect();
+    /// assert_eq!(words, ["to", "be", "or", "not"]);
+    /// assert_eq!(words[2].range(), 6..8);
+    /// assert!(words.iter().all(|w| std::sync::Arc::ptr_eq(w.buffer(), words[0].buffer())));
+    /// ```
+    ArcStr, Arc
+}
+
+shared_str! {
+    /// A substring of an `Rc<str>`, sharing the buffer it was sliced from.
+    ///
+    /// This is the single-threaded counterpart of [`ArcStr`](struct.ArcStr.html).
+    RcStr, Rc
+}
@@ -1,7 +1,15 @@
 use pattern::*;
-use haystack::{Span, Haystack};
-use slices::slice::{TwoWaySearcher, NaiveSearcher, SliceSearcher};
+use haystack::{Haystack, Span};
+use slices::slice::{TwoWaySearcher, NaiveSearcher, SliceSearcher, SliceHay};
 use std::ops::Range;
+use std::str::{from_utf8, Utf8Error};
+
+impl SliceHay<u8> for str {
+    #[inline]
+    fn as_slice(&self) -> &[u8] {
+        self.as_bytes()
+    }
+}
 
 unsafe impl<'p> Searcher<str> for TwoWaySearcher<'p, u8> {
     #[inline]
@@ -61,9 +69,47 @@ unsafe impl<'p> ReverseSearcher<str> for NaiveSearcher<'p, u8> {
     }
 }
 
+/// A byte-string needle which has been checked to be valid UTF-8, so it can
+/// be searched for in `str` haystacks.
+///
+/// Since UTF-8 is self-synchronizing, every occurrence of a valid UTF-8 needle
+/// in a `str` starts and ends on character boundaries.
+///
+/// ```rust
+/// extern crate pattern_3;
+/// use pattern_3::Utf8Needle;
+/// use pattern_3::ext::find;
+///
+/// let needle = Utf8Needle::new(b"\xc3\xa9t\xc3\xa9").unwrap();
+/// assert_eq!(find("un été chaud", needle), Some(3));
+/// assert!(Utf8Needle::new(b"\xc3").is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Utf8Needle<'p>(&'p str);
+
+impl<'p> Utf8Needle<'p> {
+    /// Validates a byte-string needle, failing if it is not valid UTF-8.
+    #[inline]
+    pub fn new(bytes: &'p [u8]) -> Result<Self, Utf8Error> {
+        from_utf8(bytes).map(Utf8Needle)
+    }
+
+    /// Returns the needle as a string slice.
+    #[inline]
+    pub fn as_str(&self) -> &'p str {
+        self.0
+    }
+
+    /// Returns the needle as a byte slice.
+    #[inline]
+    pub fn as_bytes(&self) -> &'p [u8] {
+        self.0.as_bytes()
+    }
+}
+
 macro_rules! impl_pattern {
     (<[$($gen:tt)*]> for $pat:ty) => {
-        impl<$($gen)*, H: Haystack<Target = str>> Pattern<H> for $pat {
+        impl<$($gen)*, H: Haystack<Hay = str>> Pattern<H> for $pat {
             type Searcher = SliceSearcher<'p, u8>;
 
             #[inline]
@@ -83,3 +129,20 @@ impl_pattern!(<['p]> for &'p str);
 #[cfg(feature = "std")]
 impl_pattern!(<['p]> for &'p String);
 impl_pattern!(<['q, 'p]> for &'q &'p str);
+impl_pattern!(<['p]> for Utf8Needle<'p>);
+
+// A string needle also finds its UTF-8 encoding in a byte string, which makes
+// it usable on text stored as bytes, such as the chunks of a rope.
+impl<'h, 'p> Pattern<&'h [u8]> for &'p str {
+    type Searcher = SliceSearcher<'p, u8>;
+
+    #[inline]
+    fn into_searcher(self) -> Self::Searcher {
+        SliceSearcher::new_searcher(self.as_bytes())
+    }
+
+    #[inline]
+    fn into_consumer(self) -> Self::Searcher {
+        SliceSearcher::new_consumer(self.as_bytes())
+    }
+}
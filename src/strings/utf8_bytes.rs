@@ -0,0 +1,186 @@
+use pattern::*;
+use haystack::{Hay, Span};
+use std::fmt;
+use std::ops::Range;
+use std::str::{from_utf8, from_utf8_unchecked, Utf8Error};
+
+/// A byte buffer known to be valid UTF-8.
+///
+/// The buffer is validated once on construction. Its indices are the
+/// character boundaries, so `char`, `&str` and `char` predicate patterns can
+/// search it directly, and every piece produced can be reinterpreted as a
+/// `&str` with [`as_str`](#method.as_str) at no cost.
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate pattern_3;
+/// use pattern_3::Utf8Bytes;
+/// use pattern_3::ext::split;
+///
+/// let packet = &b"GET /caf\xc3\xa9 HTTP/1.1"[..];
+/// let line = Utf8Bytes::new(packet).unwrap();
+/// let words = split(line, ' ').map(Utf8Bytes::as_str).collect::<Vec<_>>();
+/// assert_eq!(words, vec!["GET", "/café", "HTTP/1.1"]);
+///
+/// assert!(Utf8Bytes::new(b"\xc3").is_err());
+/// ```
+#[repr(transparent)]
+#[derive(PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Utf8Bytes([u8]);
+
+impl Utf8Bytes {
+    /// Validates a byte buffer, failing if it is not valid UTF-8.
+    #[inline]
+    pub fn new(bytes: &[u8]) -> Result<&Utf8Bytes, Utf8Error> {
+        let s = from_utf8(bytes)?;
+        Ok(s.into())
+    }
+
+    /// Returns the buffer as a string slice.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        unsafe { from_utf8_unchecked(&self.0) }
+    }
+
+    /// Returns the underlying bytes.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Returns the length of the buffer in bytes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Checks whether the buffer is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// Views a string as a UTF-8 byte buffer.
+impl<'a> From<&'a str> for &'a Utf8Bytes {
+    #[inline]
+    fn from(s: &'a str) -> Self {
+        unsafe { &*(s.as_bytes() as *const [u8] as *const Utf8Bytes) }
+    }
+}
+
+impl fmt::Debug for Utf8Bytes {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl Hay for Utf8Bytes {
+    type Index = usize;
+
+    #[inline]
+    fn empty<'a>() -> &'a Self {
+        "".into()
+    }
+
+    #[inline]
+    fn start_index(&self) -> usize {
+        0
+    }
+
+    #[inline]
+    fn end_index(&self) -> usize {
+        self.len()
+    }
+
+    #[inline]
+    unsafe fn slice_unchecked(&self, range: Range<usize>) -> &Self {
+        self.as_str().get_unchecked(range).into()
+    }
+
+    #[inline]
+    unsafe fn next_index(&self, index: usize) -> usize {
+        self.as_str().next_index(index)
+    }
+
+    #[inline]
+    unsafe fn prev_index(&self, index: usize) -> usize {
+        self.as_str().prev_index(index)
+    }
+}
+
+impl<'h> Span<&'h Utf8Bytes> {
+    /// Reinterprets the span as a string span.
+    #[inline]
+    pub fn as_str(self) -> Span<&'h str> {
+        let (haystack, range) = self.into_parts();
+        unsafe { Span::from_parts(haystack.as_str(), range) }
+    }
+}
+
+/// Searcher of string patterns in a [`Utf8Bytes`] hay.
+///
+/// The matches are found by the searcher of the same pattern in a `str`.
+#[derive(Debug, Clone)]
+pub struct Utf8BytesSearcher<S>(S);
+
+unsafe impl<S: Searcher<str>> Searcher<Utf8Bytes> for Utf8BytesSearcher<S> {
+    #[inline]
+    fn search(&mut self, span: Span<&Utf8Bytes>) -> Option<Range<usize>> {
+        self.0.search(span.as_str())
+    }
+
+    #[inline]
+    fn consume(&mut self, span: Span<&Utf8Bytes>) -> Option<usize> {
+        self.0.consume(span.as_str())
+    }
+
+    #[inline]
+    fn trim_start(&mut self, hay: &Utf8Bytes) -> usize {
+        self.0.trim_start(hay.as_str())
+    }
+}
+
+unsafe impl<S: ReverseSearcher<str>> ReverseSearcher<Utf8Bytes> for Utf8BytesSearcher<S> {
+    #[inline]
+    fn rsearch(&mut self, span: Span<&Utf8Bytes>) -> Option<Range<usize>> {
+        self.0.rsearch(span.as_str())
+    }
+
+    #[inline]
+    fn rconsume(&mut self, span: Span<&Utf8Bytes>) -> Option<usize> {
+        self.0.rconsume(span.as_str())
+    }
+
+    #[inline]
+    fn trim_end(&mut self, hay: &Utf8Bytes) -> usize {
+        self.0.trim_end(hay.as_str())
+    }
+}
+
+unsafe impl<S: DoubleEndedSearcher<str>> DoubleEndedSearcher<Utf8Bytes> for Utf8BytesSearcher<S> {}
+
+// FIXME cannot impl `Pattern<(_: Haystack<Hay = Utf8Bytes>)>` due to RFC 1672 being postponed.
+macro_rules! impl_pattern {
+    (<[$($gen:tt)*]> for $pat:ty) => {
+        impl<'h, $($gen)*> Pattern<&'h Utf8Bytes> for $pat {
+            type Searcher = Utf8BytesSearcher<<$pat as Pattern<&'h str>>::Searcher>;
+
+            #[inline]
+            fn into_searcher(self) -> Self::Searcher {
+                Utf8BytesSearcher(Pattern::<&'h str>::into_searcher(self))
+            }
+
+            #[inline]
+            fn into_consumer(self) -> Self::Searcher {
+                Utf8BytesSearcher(Pattern::<&'h str>::into_consumer(self))
+            }
+        }
+    }
+}
+
+impl_pattern!(<[]> for char);
+impl_pattern!(<['p]> for &'p str);
+impl_pattern!(<['p]> for &'p [char]);
+impl_pattern!(<[F: FnMut(char) -> bool]> for F);
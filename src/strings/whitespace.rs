@@ -0,0 +1,154 @@
+use pattern::*;
+use haystack::{Haystack, Span};
+use std::ops::Range;
+
+/// Matches `needle` against a prefix of `hay`, returning the length of the
+/// matched prefix.
+fn match_forward(needle: &str, hay: &str) -> Option<usize> {
+    let mut needle = needle.chars().peekable();
+    let mut hay_chars = hay.char_indices().peekable();
+    while let Some(c) = needle.next() {
+        if c.is_whitespace() {
+            while needle.peek().is_some_and(|c| c.is_whitespace()) {
+                needle.next();
+            }
+            if !hay_chars.peek().is_some_and(|&(_, d)| d.is_whitespace()) {
+                return None;
+            }
+            while hay_chars.peek().is_some_and(|&(_, d)| d.is_whitespace()) {
+                hay_chars.next();
+            }
+        } else {
+            match hay_chars.next() {
+                Some((_, d)) if d == c => {}
+                _ => return None,
+            }
+        }
+    }
+    Some(hay_chars.peek().map_or(hay.len(), |&(i, _)| i))
+}
+
+/// Matches `needle` against a suffix of `hay`, returning the start of the
+/// matched suffix.
+fn match_backward(needle: &str, hay: &str) -> Option<usize> {
+    let mut needle = needle.chars().rev().peekable();
+    let mut hay_chars = hay.char_indices().rev().peekable();
+    let mut start = hay.len();
+    while let Some(c) = needle.next() {
+        if c.is_whitespace() {
+            while needle.peek().is_some_and(|c| c.is_whitespace()) {
+                needle.next();
+            }
+            if !hay_chars.peek().is_some_and(|&(_, d)| d.is_whitespace()) {
+                return None;
+            }
+            while let Some(&(i, d)) = hay_chars.peek() {
+                if !d.is_whitespace() {
+                    break;
+                }
+                start = i;
+                hay_chars.next();
+            }
+        } else {
+            match hay_chars.next() {
+                Some((i, d)) if d == c => start = i,
+                _ => return None,
+            }
+        }
+    }
+    Some(start)
+}
+
+/// A string pattern which treats every run of whitespace as equivalent.
+///
+/// Any run of whitespace in the needle matches any non-empty run of
+/// whitespace in the haystack, which is consumed entirely. Everything else
+/// must match exactly. The reported range covers the actual text in the
+/// haystack, so it may be longer or shorter than the needle.
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate pattern_3;
+/// use pattern_3::WhitespaceTolerant;
+/// use pattern_3::ext::find_range;
+///
+/// let text = "call foo(\n    bar)";
+/// assert_eq!(find_range(text, WhitespaceTolerant("foo( bar)")), Some(5..18));
+/// assert_eq!(find_range(text, WhitespaceTolerant("foo(bar)")), None);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct WhitespaceTolerant<'p>(pub &'p str);
+
+/// Searcher of a [`WhitespaceTolerant`] pattern.
+#[derive(Debug, Clone)]
+pub struct WhitespaceTolerantSearcher<'p> {
+    needle: &'p str,
+    empty: EmptySearcher,
+}
+
+unsafe impl<'p> Searcher<str> for WhitespaceTolerantSearcher<'p> {
+    fn search(&mut self, span: Span<&str>) -> Option<Range<usize>> {
+        if self.needle.is_empty() {
+            return self.empty.search(span);
+        }
+        let (hay, range) = span.into_parts();
+        let hay = &hay[..range.end];
+        let first = self.needle.chars().next().unwrap();
+        let mut index = range.start;
+        loop {
+            // The match can only start with the first character of the needle,
+            // or with any whitespace if the needle starts with whitespace.
+            index += if first.is_whitespace() {
+                hay[index..].find(char::is_whitespace)?
+            } else {
+                hay[index..].find(first)?
+            };
+            if let Some(len) = match_forward(self.needle, &hay[index..]) {
+                return Some(index..(index + len));
+            }
+            index += hay[index..].chars().next().unwrap().len_utf8();
+        }
+    }
+
+    fn consume(&mut self, span: Span<&str>) -> Option<usize> {
+        let (hay, range) = span.into_parts();
+        let len = match_forward(self.needle, &hay[range.clone()])?;
+        Some(range.start + len)
+    }
+}
+
+unsafe impl<'p> ReverseSearcher<str> for WhitespaceTolerantSearcher<'p> {
+    fn rsearch(&mut self, span: Span<&str>) -> Option<Range<usize>> {
+        if self.needle.is_empty() {
+            return self.empty.rsearch(span);
+        }
+        let (hay, range) = span.into_parts();
+        let hay = &hay[range.start..];
+        let ends = hay[..(range.end - range.start)].char_indices().map(|(i, c)| i + c.len_utf8());
+        for end in ends.rev() {
+            if let Some(start) = match_backward(self.needle, &hay[..end]) {
+                return Some((range.start + start)..(range.start + end));
+            }
+        }
+        None
+    }
+
+    fn rconsume(&mut self, span: Span<&str>) -> Option<usize> {
+        let (hay, range) = span.into_parts();
+        let start = match_backward(self.needle, &hay[range.clone()])?;
+        Some(range.start + start)
+    }
+}
+
+impl<'p, H: Haystack<Hay = str>> Pattern<H> for WhitespaceTolerant<'p> {
+    type Searcher = WhitespaceTolerantSearcher<'p>;
+
+    #[inline]
+    fn into_searcher(self) -> Self::Searcher {
+        WhitespaceTolerantSearcher {
+            needle: self.0,
+            empty: EmptySearcher::default(),
+        }
+    }
+}
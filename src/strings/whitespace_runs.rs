@@ -0,0 +1,344 @@
+use pattern::*;
+use haystack::{Haystack, Span};
+use std::ops::Range;
+
+/// A pattern matching maximal runs of whitespace.
+///
+/// On `str` haystacks whitespace is defined by `char::is_whitespace`, on
+/// `[u8]` haystacks by `u8::is_ascii_whitespace`. The haystack is scanned
+/// 16 bytes at a time for bytes which can start a whitespace character,
+/// using SSE2 on x86-64 and NEON on AArch64.
+///
+/// Unlike `str::split_whitespace`, splitting on `Whitespace` yields an empty
+/// string before leading and after trailing whitespace.
+///
+/// # Examples
+///
+/// ```rust
+/// extern crate pattern_3;
+/// use pattern_3::Whitespace;
+/// use pattern_3::ext::{split, trim};
+///
+/// let text = " Mary\u{a0}had\t\n a little lamb ";
+/// assert_eq!(
+///     split(trim(text, Whitespace), Whitespace).collect::<Vec<_>>(),
+///     text.split_whitespace().collect::<Vec<_>>(),
+/// );
+/// assert_eq!(
+///     split(&b"a\r\nb\xa0c"[..], Whitespace).collect::<Vec<_>>(),
+///     vec![&b"a"[..], b"b\xa0c"],
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct Whitespace;
+
+/// Searcher of the [`Whitespace`] pattern.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct WhitespaceSearcher;
+
+const CHUNK: usize = 16;
+
+#[cfg(all(target_arch = "x86_64", target_feature = "sse2"))]
+mod classify {
+    use std::arch::x86_64::*;
+
+    /// Number of bits each byte takes in a candidate mask.
+    pub const STRIDE: u32 = 1;
+
+    /// Returns a mask of the bytes in `chunk` which may start a whitespace
+    /// character: ASCII bytes up to `b' '` and, in Unicode mode, the lead
+    /// bytes `0xc2` and `0xe1..=0xe3`.
+    #[inline]
+    pub fn candidates(chunk: &[u8], unicode: bool) -> u64 {
+        debug_assert!(chunk.len() >= super::CHUNK);
+        unsafe {
+            let v = _mm_loadu_si128(chunk.as_ptr() as *const __m128i);
+            let low = _mm_cmpeq_epi8(_mm_min_epu8(v, _mm_set1_epi8(0x20)), v);
+            let mut mask = _mm_movemask_epi8(low) as u32;
+            if unicode {
+                let lead2 = _mm_cmpeq_epi8(v, _mm_set1_epi8(0xc2u8 as i8));
+                let offset = _mm_sub_epi8(v, _mm_set1_epi8(0xe1u8 as i8));
+                let lead3 = _mm_cmpeq_epi8(_mm_min_epu8(offset, _mm_set1_epi8(2)), offset);
+                mask |= _mm_movemask_epi8(_mm_or_si128(lead2, lead3)) as u32;
+            }
+            mask as u64
+        }
+    }
+}
+
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+mod classify {
+    use std::arch::aarch64::*;
+
+    /// Number of bits each byte takes in a candidate mask.
+    pub const STRIDE: u32 = 4;
+
+    /// Returns a mask of the bytes in `chunk` which may start a whitespace
+    /// character: ASCII bytes up to `b' '` and, in Unicode mode, the lead
+    /// bytes `0xc2` and `0xe1..=0xe3`.
+    #[inline]
+    pub fn candidates(chunk: &[u8], unicode: bool) -> u64 {
+        debug_assert!(chunk.len() >= super::CHUNK);
+        unsafe {
+            let v = vld1q_u8(chunk.as_ptr());
+            let mut m = vcleq_u8(v, vdupq_n_u8(0x20));
+            if unicode {
+                let lead2 = vceqq_u8(v, vdupq_n_u8(0xc2));
+                let lead3 = vcleq_u8(vsubq_u8(v, vdupq_n_u8(0xe1)), vdupq_n_u8(2));
+                m = vorrq_u8(m, vorrq_u8(lead2, lead3));
+            }
+            // NEON has no movemask; narrowing keeps one nibble per byte.
+            let narrowed = vshrn_n_u16(vreinterpretq_u16_u8(m), 4);
+            vget_lane_u64(vreinterpret_u64_u8(narrowed), 0)
+        }
+    }
+}
+
+#[cfg(not(any(
+    all(target_arch = "x86_64", target_feature = "sse2"),
+    all(target_arch = "aarch64", target_feature = "neon"),
+)))]
+mod classify {
+    /// Number of bits each byte takes in a candidate mask.
+    pub const STRIDE: u32 = 1;
+
+    /// Returns a mask of the bytes in `chunk` which may start a whitespace
+    /// character: ASCII bytes up to `b' '` and, in Unicode mode, the lead
+    /// bytes `0xc2` and `0xe1..=0xe3`.
+    #[inline]
+    pub fn candidates(chunk: &[u8], unicode: bool) -> u64 {
+        let mut mask = 0;
+        for (i, &b) in chunk[..super::CHUNK].iter().enumerate() {
+            if b <= 0x20 || (unicode && (b == 0xc2 || b.wrapping_sub(0xe1) <= 2)) {
+                mask |= 1 << i;
+            }
+        }
+        mask
+    }
+}
+
+const LANE: u64 = (1 << classify::STRIDE) - 1;
+
+/// Returns the length of the whitespace character starting at `bytes[i]`,
+/// if there is one and it ends before `end`.
+#[inline]
+fn whitespace_at(bytes: &[u8], i: usize, end: usize, unicode: bool) -> Option<usize> {
+    let len = match bytes[i] {
+        b'\t' | b'\n' | b'\x0c' | b'\r' | b' ' => return Some(1),
+        b'\x0b' if unicode => return Some(1),
+        0xc2 if unicode => 2,
+        0xe1..=0xe3 if unicode => 3,
+        _ => return None,
+    };
+    if i + len > end {
+        return None;
+    }
+    let is_whitespace = match (bytes[i], &bytes[(i + 1)..(i + len)]) {
+        (0xc2, &[0x85]) | (0xc2, &[0xa0]) => true,
+        (0xe1, &[0x9a, 0x80]) => true,
+        (0xe2, &[0x80, b]) => b <= 0x8a || b == 0xa8 || b == 0xa9 || b == 0xaf,
+        (0xe2, &[0x81, 0x9f]) => true,
+        (0xe3, &[0x80, 0x80]) => true,
+        _ => false,
+    };
+    if is_whitespace {
+        Some(len)
+    } else {
+        None
+    }
+}
+
+/// Finds the first whitespace character in `bytes[start..end]`.
+fn find_whitespace(bytes: &[u8], mut start: usize, end: usize, unicode: bool) -> Option<Range<usize>> {
+    while start + CHUNK <= end {
+        let mut mask = classify::candidates(&bytes[start..], unicode);
+        while mask != 0 {
+            let i = start + (mask.trailing_zeros() / classify::STRIDE) as usize;
+            if let Some(len) = whitespace_at(bytes, i, end, unicode) {
+                return Some(i..(i + len));
+            }
+            mask &= !(LANE << ((i - start) as u32 * classify::STRIDE));
+        }
+        start += CHUNK;
+    }
+    (start..end).find_map(|i| whitespace_at(bytes, i, end, unicode).map(|len| i..(i + len)))
+}
+
+/// Finds the last whitespace character in `bytes[start..end]`.
+fn rfind_whitespace(bytes: &[u8], start: usize, end: usize, unicode: bool) -> Option<Range<usize>> {
+    let mut chunk_end = end;
+    while chunk_end >= start + CHUNK {
+        let chunk_start = chunk_end - CHUNK;
+        let mut mask = classify::candidates(&bytes[chunk_start..], unicode);
+        while mask != 0 {
+            let lane = (63 - mask.leading_zeros()) / classify::STRIDE;
+            let i = chunk_start + lane as usize;
+            if let Some(len) = whitespace_at(bytes, i, end, unicode) {
+                return Some(i..(i + len));
+            }
+            mask &= !(LANE << (lane * classify::STRIDE));
+        }
+        chunk_end = chunk_start;
+    }
+    (start..chunk_end).rev().find_map(|i| whitespace_at(bytes, i, end, unicode).map(|len| i..(i + len)))
+}
+
+/// Extends a run of whitespace ending at `end` as far as possible within
+/// `..limit`.
+#[inline]
+fn extend_forward(bytes: &[u8], mut end: usize, limit: usize, unicode: bool) -> usize {
+    while end < limit {
+        match whitespace_at(bytes, end, limit, unicode) {
+            Some(len) => end += len,
+            None => break,
+        }
+    }
+    end
+}
+
+/// Extends a run of whitespace starting at `start` as far as possible within
+/// `limit..`.
+#[inline]
+fn extend_backward(bytes: &[u8], mut start: usize, limit: usize, unicode: bool) -> usize {
+    while start > limit {
+        let mut i = start - 1;
+        if unicode {
+            while i > limit && bytes[i] & 0xc0 == 0x80 && start - i < 3 {
+                i -= 1;
+            }
+        }
+        match whitespace_at(bytes, i, start, unicode) {
+            Some(len) if i + len == start => start = i,
+            _ => break,
+        }
+    }
+    start
+}
+
+#[inline]
+fn search(bytes: &[u8], range: Range<usize>, unicode: bool) -> Option<Range<usize>> {
+    let found = find_whitespace(bytes, range.start, range.end, unicode)?;
+    Some(found.start..extend_forward(bytes, found.end, range.end, unicode))
+}
+
+#[inline]
+fn rsearch(bytes: &[u8], range: Range<usize>, unicode: bool) -> Option<Range<usize>> {
+    let found = rfind_whitespace(bytes, range.start, range.end, unicode)?;
+    Some(extend_backward(bytes, found.start, range.start, unicode)..found.end)
+}
+
+#[inline]
+fn consume(bytes: &[u8], range: Range<usize>, unicode: bool) -> Option<usize> {
+    let end = extend_forward(bytes, range.start, range.end, unicode);
+    if end == range.start {
+        None
+    } else {
+        Some(end)
+    }
+}
+
+#[inline]
+fn rconsume(bytes: &[u8], range: Range<usize>, unicode: bool) -> Option<usize> {
+    let start = extend_backward(bytes, range.end, range.start, unicode);
+    if start == range.end {
+        None
+    } else {
+        Some(start)
+    }
+}
+
+unsafe impl Searcher<str> for WhitespaceSearcher {
+    #[inline]
+    fn search(&mut self, span: Span<&str>) -> Option<Range<usize>> {
+        let (hay, range) = span.into_parts();
+        search(hay.as_bytes(), range, true)
+    }
+
+    #[inline]
+    fn consume(&mut self, span: Span<&str>) -> Option<usize> {
+        let (hay, range) = span.into_parts();
+        consume(hay.as_bytes(), range, true)
+    }
+
+    #[inline]
+    fn trim_start(&mut self, hay: &str) -> usize {
+        extend_forward(hay.as_bytes(), 0, hay.len(), true)
+    }
+}
+
+unsafe impl ReverseSearcher<str> for WhitespaceSearcher {
+    #[inline]
+    fn rsearch(&mut self, span: Span<&str>) -> Option<Range<usize>> {
+        let (hay, range) = span.into_parts();
+        rsearch(hay.as_bytes(), range, true)
+    }
+
+    #[inline]
+    fn rconsume(&mut self, span: Span<&str>) -> Option<usize> {
+        let (hay, range) = span.into_parts();
+        rconsume(hay.as_bytes(), range, true)
+    }
+
+    #[inline]
+    fn trim_end(&mut self, hay: &str) -> usize {
+        extend_backward(hay.as_bytes(), hay.len(), 0, true)
+    }
+}
+
+unsafe impl DoubleEndedSearcher<str> for WhitespaceSearcher {}
+
+impl FusedSearcher<str> for WhitespaceSearcher {}
+
+unsafe impl Searcher<[u8]> for WhitespaceSearcher {
+    #[inline]
+    fn search(&mut self, span: Span<&[u8]>) -> Option<Range<usize>> {
+        let (hay, range) = span.into_parts();
+        search(hay, range, false)
+    }
+
+    #[inline]
+    fn consume(&mut self, span: Span<&[u8]>) -> Option<usize> {
+        let (hay, range) = span.into_parts();
+        consume(hay, range, false)
+    }
+
+    #[inline]
+    fn trim_start(&mut self, hay: &[u8]) -> usize {
+        extend_forward(hay, 0, hay.len(), false)
+    }
+}
+
+unsafe impl ReverseSearcher<[u8]> for WhitespaceSearcher {
+    #[inline]
+    fn rsearch(&mut self, span: Span<&[u8]>) -> Option<Range<usize>> {
+        let (hay, range) = span.into_parts();
+        rsearch(hay, range, false)
+    }
+
+    #[inline]
+    fn rconsume(&mut self, span: Span<&[u8]>) -> Option<usize> {
+        let (hay, range) = span.into_parts();
+        rconsume(hay, range, false)
+    }
+
+    #[inline]
+    fn trim_end(&mut self, hay: &[u8]) -> usize {
+        extend_backward(hay, hay.len(), 0, false)
+    }
+}
+
+unsafe impl DoubleEndedSearcher<[u8]> for WhitespaceSearcher {}
+
+impl FusedSearcher<[u8]> for WhitespaceSearcher {}
+
+impl<H: Haystack> Pattern<H> for Whitespace
+where
+    WhitespaceSearcher: Searcher<H::Hay>,
+{
+    type Searcher = WhitespaceSearcher;
+
+    #[inline]
+    fn into_searcher(self) -> Self::Searcher {
+        WhitespaceSearcher
+    }
+}
@@ -0,0 +1,62 @@
+#![cfg(feature = "aho-corasick")]
+
+extern crate aho_corasick;
+extern crate pattern_3;
+
+use aho_corasick::{AhoCorasick, MatchKind, StartKind};
+use pattern_3::ext::*;
+
+#[test]
+fn test_aho_corasick_many_needles() {
+    let needles = (0..300).map(|i| format!("w{}x", i)).collect::<Vec<_>>();
+    let ac = AhoCorasick::new(&needles).unwrap();
+    let hay = "w1x w299x w300x w42xw7x";
+    assert_eq!(matches(hay, &ac).collect::<Vec<_>>(), vec!["w1x", "w299x", "w42x", "w7x"]);
+    assert_eq!(split(hay, &ac).collect::<Vec<_>>(), vec!["", " ", " w300x ", "", ""]);
+}
+
+#[test]
+fn test_aho_corasick_consume() {
+    let anchored = AhoCorasick::builder()
+        .match_kind(MatchKind::LeftmostFirst)
+        .start_kind(StartKind::Both)
+        .build(&["ab", "a"])
+        .unwrap();
+    assert_eq!(trim_start("ababac", &anchored), "c");
+    assert!(!starts_with("cab", &anchored));
+
+    // with standard semantics an unanchored search would report "bc" first
+    let standard = AhoCorasick::builder()
+        .start_kind(StartKind::Both)
+        .build(&["abcd", "bc"])
+        .unwrap();
+    assert!(starts_with("abcd", &standard));
+    assert_eq!(trim_start("abcdabcdx", &standard), "x");
+    assert_eq!(matches("abcd", &standard).collect::<Vec<_>>(), vec!["bc"]);
+}
+
+#[test]
+#[should_panic(expected = "StartKind::Anchored or StartKind::Both")]
+fn test_aho_corasick_consume_unanchored() {
+    let ac = AhoCorasick::new(&["abcd", "bc"]).unwrap();
+    starts_with("abcd", &ac);
+}
+
+#[test]
+fn test_aho_corasick_boundaries() {
+    // a needle of bytes which never match at character boundaries of a `str`
+    let ac = AhoCorasick::new(&[&b"\xa9"[..]]).unwrap();
+    assert_eq!(find("é©", &ac), None);
+    assert_eq!(find(&"é©".as_bytes()[..], &ac), Some(1));
+
+    let empty = AhoCorasick::new(&[""]).unwrap();
+    assert_eq!(matches("aé", &empty).count(), 3);
+}
+
+#[test]
+fn test_aho_corasick_replace() {
+    let ac = AhoCorasick::new(&["cat", "dog"]).unwrap();
+    let mut out = Vec::new();
+    replace_with(&b"cat and dog"[..], &ac, |_| &b"pet"[..], |s| out.extend_from_slice(s));
+    assert_eq!(out, b"pet and pet");
+}
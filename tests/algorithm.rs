@@ -0,0 +1,202 @@
+extern crate pattern_3;
+
+use pattern_3::{Algorithm, SearcherBuilder};
+use pattern_3::ext::*;
+
+// Small deterministic generator so the haystacks are reproducible.
+fn lcg_bytes(seed: u32, len: usize, alphabet: u8) -> Vec<u8> {
+    let mut state = seed;
+    (0..len).map(|_| {
+        state = state.wrapping_mul(1103515245).wrapping_add(12345);
+        b'a' + ((state >> 16) as u8 % alphabet)
+    }).collect()
+}
+
+const ALGORITHMS: &[Algorithm] = &[
+    Algorithm::Auto, Algorithm::TwoWay, Algorithm::Kmp, Algorithm::Bmh, Algorithm::Bndm,
+    Algorithm::Simd,
+];
+
+#[test]
+fn test_algorithms_agree() {
+    for seed in 0..40 {
+        let hay = lcg_bytes(seed, 300, 2 + (seed % 3) as u8);
+        for len in 1..12 {
+            let start = (seed as usize * 7) % (hay.len() - len);
+            let needle = &hay[start..(start + len)];
+            let expected = match_indices(&*hay, needle).map(|(i, _)| i).collect::<Vec<_>>();
+            let rexpected = rmatch_indices(&*hay, needle).map(|(i, _)| i).collect::<Vec<_>>();
+            for &algorithm in ALGORITHMS {
+                let needle = SearcherBuilder::new().algorithm(algorithm).needle(needle);
+                assert_eq!(match_indices(&*hay, needle).map(|(i, _)| i).collect::<Vec<_>>(), expected);
+                assert_eq!(rmatch_indices(&*hay, needle).map(|(i, _)| i).collect::<Vec<_>>(), rexpected);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_long_needles() {
+    for seed in 0..10 {
+        let hay = lcg_bytes(seed, 3000, 3 + (seed % 20) as u8);
+        for &len in &[127, 128, 200, 600] {
+            let start = (seed as usize * 131) % (hay.len() - len);
+            let needle = &hay[start..(start + len)];
+            let expected = match_indices(&*hay, needle).map(|(i, _)| i).collect::<Vec<_>>();
+            let rexpected = rmatch_indices(&*hay, needle).map(|(i, _)| i).collect::<Vec<_>>();
+            assert!(expected.contains(&start));
+            for &algorithm in ALGORITHMS {
+                let needle = SearcherBuilder::new().algorithm(algorithm).needle(needle);
+                assert_eq!(match_indices(&*hay, needle).map(|(i, _)| i).collect::<Vec<_>>(), expected);
+                assert_eq!(rmatch_indices(&*hay, needle).map(|(i, _)| i).collect::<Vec<_>>(), rexpected);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_bmh_near_matches() {
+    let bmh = SearcherBuilder::new().algorithm(Algorithm::Bmh);
+    let mut hay = "a".repeat(5000);
+    let needle = "a".repeat(300) + "b";
+    let rneedle = "b".to_owned() + &"a".repeat(300);
+    assert_eq!(find(&*hay, bmh.needle(&*needle)), None);
+    assert_eq!(rfind(&*hay, bmh.needle(&*rneedle)), None);
+    hay.insert(4000, 'b');
+    assert_eq!(find(&*hay, bmh.needle(&*needle)), Some(3700));
+    assert_eq!(rfind(&*hay, bmh.needle(&*rneedle)), Some(4000));
+    assert_eq!(find(&*hay, &*needle), Some(3700));
+    assert_eq!(rfind(&*hay, &*rneedle), Some(4000));
+}
+
+#[test]
+fn test_bmh_non_bytes() {
+    let bmh = SearcherBuilder::new().algorithm(Algorithm::Bmh);
+    let hay = [1, 1, 1, 2, 1, 1, 2];
+    assert_eq!(find(&hay[..], bmh.needle(&[1, 1, 2][..])), Some(1));
+    assert_eq!(rfind(&hay[..], bmh.needle(&[1, 1, 2][..])), Some(4));
+    assert_eq!(find(&hay[..], bmh.needle(&[2, 2][..])), None);
+}
+
+#[test]
+fn test_periodic_needles() {
+    let hay = "aaaaabaaaaabaaaabaaaaaab";
+    for needle in &["aa", "aaaaab", "aabaa", "abaaaa", "baaaaab", "ab"] {
+        let expected = hay.match_indices(needle).collect::<Vec<_>>();
+        let rexpected = hay.rmatch_indices(needle).collect::<Vec<_>>();
+        for &algorithm in ALGORITHMS {
+            let needle = SearcherBuilder::new().algorithm(algorithm).needle(*needle);
+            assert_eq!(match_indices(hay, needle).collect::<Vec<_>>(), expected);
+            assert_eq!(rmatch_indices(hay, needle).collect::<Vec<_>>(), rexpected);
+        }
+    }
+}
+
+#[test]
+fn test_kmp_non_bytes() {
+    let kmp = SearcherBuilder::new().algorithm(Algorithm::Kmp);
+    let hay = [1.0, 1.0, 1.0, 2.0, 1.0, 1.0, 2.0];
+    assert_eq!(find(&hay[..], kmp.needle(&[1.0, 1.0, 2.0][..])), Some(1));
+    assert_eq!(rfind(&hay[..], kmp.needle(&[1.0, 1.0, 2.0][..])), Some(4));
+    assert_eq!(find(&hay[..], kmp.needle(&[2.0, 2.0][..])), None);
+}
+
+#[test]
+fn test_kmp_edge_cases() {
+    let kmp = SearcherBuilder::new().algorithm(Algorithm::Kmp);
+    assert_eq!(split("a,b,,c", kmp.needle(",")).collect::<Vec<_>>(), vec!["a", "b", "", "c"]);
+    assert_eq!(match_indices("abc", kmp.needle("")).collect::<Vec<_>>(), vec![(0, ""), (1, ""), (2, ""), (3, "")]);
+    assert_eq!(find("ab", kmp.needle("abc")), None);
+    assert_eq!(trim_start("ababc", kmp.needle("ab")), "c");
+    assert_eq!(trim_end("cabab", kmp.needle("ab")), "c");
+    assert!(starts_with("héllo", kmp.needle("hé")));
+    assert_eq!(rsplit("x--y--z", kmp.needle("--")).collect::<Vec<_>>(), vec!["z", "y", "x"]);
+}
+
+#[test]
+fn test_short_needles() {
+    for seed in 0..20 {
+        let hay = lcg_bytes(seed, 2000, 2 + (seed % 30) as u8);
+        for &len in &[8, 31, 32, 63, 64, 65] {
+            let start = (seed as usize * 97) % (hay.len() - len);
+            let needle = &hay[start..(start + len)];
+            let expected = match_indices(&*hay, needle).map(|(i, _)| i).collect::<Vec<_>>();
+            let rexpected = rmatch_indices(&*hay, needle).map(|(i, _)| i).collect::<Vec<_>>();
+            assert!(expected.contains(&start));
+            for &algorithm in ALGORITHMS {
+                let needle = SearcherBuilder::new().algorithm(algorithm).needle(needle);
+                assert_eq!(match_indices(&*hay, needle).map(|(i, _)| i).collect::<Vec<_>>(), expected);
+                assert_eq!(rmatch_indices(&*hay, needle).map(|(i, _)| i).collect::<Vec<_>>(), rexpected);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_bndm_near_matches() {
+    let bndm = SearcherBuilder::new().algorithm(Algorithm::Bndm);
+    let mut hay = "a".repeat(5000);
+    let needle = "a".repeat(60) + "b";
+    let rneedle = "b".to_owned() + &"a".repeat(60);
+    assert_eq!(find(&*hay, bndm.needle(&*needle)), None);
+    assert_eq!(rfind(&*hay, bndm.needle(&*rneedle)), None);
+    hay.insert(4000, 'b');
+    assert_eq!(find(&*hay, bndm.needle(&*needle)), Some(3940));
+    assert_eq!(rfind(&*hay, bndm.needle(&*rneedle)), Some(4000));
+    assert_eq!(find(&*hay, &*needle), Some(3940));
+    assert_eq!(rfind(&*hay, &*rneedle), Some(4000));
+}
+
+#[test]
+fn test_bndm_non_bytes() {
+    let bndm = SearcherBuilder::new().algorithm(Algorithm::Bndm);
+    let hay = ['a', 'a', 'a', 'b', 'a', 'a', 'b'];
+    assert_eq!(find(&hay[..], bndm.needle(&['a', 'a', 'b'][..])), Some(1));
+    assert_eq!(rfind(&hay[..], bndm.needle(&['a', 'a', 'b'][..])), Some(4));
+    assert_eq!(find(&hay[..], bndm.needle(&['b', 'b'][..])), None);
+}
+
+#[test]
+fn test_simd_lane_boundaries() {
+    let simd = SearcherBuilder::new().algorithm(Algorithm::Simd);
+    for hay_len in 0..100 {
+        let hay = lcg_bytes(hay_len as u32, hay_len, 3);
+        for &len in &[1, 2, 3, 15, 16, 17, 31, 32, 33] {
+            for start in 0..(hay_len.saturating_sub(len) + 1).min(5) {
+                let needle = match hay.get(start..(start + len)) {
+                    Some(needle) => needle,
+                    None => continue,
+                };
+                let expected = match_indices(&*hay, needle).map(|(i, _)| i).collect::<Vec<_>>();
+                let rexpected = rmatch_indices(&*hay, needle).map(|(i, _)| i).collect::<Vec<_>>();
+                let needle = simd.needle(needle);
+                assert_eq!(match_indices(&*hay, needle).map(|(i, _)| i).collect::<Vec<_>>(), expected);
+                assert_eq!(rmatch_indices(&*hay, needle).map(|(i, _)| i).collect::<Vec<_>>(), rexpected);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_simd_near_matches() {
+    let simd = SearcherBuilder::new().algorithm(Algorithm::Simd);
+    let mut hay = "a".repeat(5000);
+    let needle = "a".repeat(100) + "ba";
+    let rneedle = "ab".to_owned() + &"a".repeat(100);
+    assert_eq!(find(&*hay, simd.needle(&*needle)), None);
+    assert_eq!(rfind(&*hay, simd.needle(&*rneedle)), None);
+    hay.insert(4000, 'b');
+    assert_eq!(find(&*hay, simd.needle(&*needle)), Some(3900));
+    assert_eq!(rfind(&*hay, simd.needle(&*rneedle)), Some(3999));
+    assert_eq!(find(&*hay, &*needle), Some(3900));
+    assert_eq!(rfind(&*hay, &*rneedle), Some(3999));
+}
+
+#[test]
+fn test_simd_non_bytes() {
+    let simd = SearcherBuilder::new().algorithm(Algorithm::Simd);
+    let hay = [1u16, 1, 1, 2, 1, 1, 2];
+    assert_eq!(find(&hay[..], simd.needle(&[1, 1, 2][..])), Some(1));
+    assert_eq!(rfind(&hay[..], simd.needle(&[1, 1, 2][..])), Some(4));
+    assert_eq!(find(&hay[..], simd.needle(&[2, 2][..])), None);
+}
@@ -0,0 +1,78 @@
+#![feature(allocator_api)]
+
+extern crate pattern_3;
+
+use pattern_3::ext::*;
+use std::alloc::{AllocError, Allocator, Global, Layout};
+use std::cell::Cell;
+use std::ptr::NonNull;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = Cell::new(0);
+}
+
+#[derive(Clone, Copy, Default, Debug)]
+struct Arena;
+
+unsafe impl Allocator for Arena {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        ALLOCATIONS.with(|a| a.set(a.get() + 1));
+        Global.allocate(layout)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        Global.deallocate(ptr, layout)
+    }
+}
+
+fn arena_vec(elems: &[u8]) -> Vec<u8, Arena> {
+    let mut v = Vec::new_in(Arena);
+    v.extend_from_slice(elems);
+    v
+}
+
+#[test]
+fn test_split_keeps_allocator() {
+    let before = ALLOCATIONS.with(Cell::get);
+    let parts: Vec<Vec<u8, Arena>> = split(arena_vec(b"key=value=x"), &b"="[..]).collect();
+    assert_eq!(parts, vec![arena_vec(b"key"), arena_vec(b"value"), arena_vec(b"x")]);
+    assert!(ALLOCATIONS.with(Cell::get) > before);
+}
+
+#[test]
+fn test_trim_and_matches() {
+    assert_eq!(trim(arena_vec(b"  hi  "), |b: &u8| *b == b' '), arena_vec(b"hi"));
+    assert_eq!(matches(arena_vec(b"abcabc"), &b"bc"[..]).count(), 2);
+    assert_eq!(find(arena_vec(b"abcabc"), &b"ca"[..]), Some(2));
+    assert!(starts_with(arena_vec(b"abc"), &b"ab"[..]));
+}
+
+// An allocator borrowing its state, so it cannot be `Default`.
+#[derive(Clone, Copy, Debug)]
+struct Counted<'a>(&'a Cell<usize>);
+
+unsafe impl<'a> Allocator for Counted<'a> {
+    fn allocate(&self, layout: Layout) -> Result<NonNull<[u8]>, AllocError> {
+        self.0.set(self.0.get() + 1);
+        Global.allocate(layout)
+    }
+
+    unsafe fn deallocate(&self, ptr: NonNull<u8>, layout: Layout) {
+        Global.deallocate(ptr, layout)
+    }
+}
+
+#[test]
+fn test_stateful_allocator() {
+    let count = Cell::new(0);
+    let mut v = Vec::new_in(Counted(&count));
+    v.extend_from_slice(b"a,b,,c");
+    let parts = split(v.clone(), &b","[..]).collect::<Vec<_>>();
+    assert_eq!(parts.iter().map(|p| &**p).collect::<Vec<&[u8]>>(), vec![&b"a"[..], b"b", b"", b"c"]);
+    assert!(parts.iter().all(|p| p.allocator().0 as *const _ == &count as *const _));
+
+    let boxed = v.into_boxed_slice();
+    assert_eq!(&*trim_start(boxed.clone(), |b: &u8| *b != b','), b",b,,c");
+    assert_eq!(rsplit(boxed, |b: &u8| *b == b',').map(|p| p.len()).collect::<Vec<_>>(), vec![1, 0, 1, 1]);
+    assert!(count.get() > 0);
+}
@@ -0,0 +1,137 @@
+extern crate pattern_3;
+
+use pattern_3::{AnyOf, LongestOf};
+use pattern_3::ext::*;
+
+#[test]
+fn test_any_of_str() {
+    let hay = "a = b != c == d";
+    let ops = AnyOf(&["==", "!=", "="]);
+    assert_eq!(
+        match_ranges_with(hay, ops).map(|(r, i, _)| (r.start, i)).collect::<Vec<_>>(),
+        vec![(2, 2), (6, 1), (11, 0)],
+    );
+    assert_eq!(rfind(hay, ops), Some(11));
+    assert_eq!(rmatches(hay, ops).collect::<Vec<_>>(), vec!["==", "!=", "="]);
+    assert_eq!(split(hay, ops).collect::<Vec<_>>(), vec!["a ", " b ", " c ", " d"]);
+}
+
+#[test]
+fn test_any_of_priority() {
+    // the first listed needle wins at the same position
+    let hay = "<<=";
+    assert_eq!(matches(hay, AnyOf(&["<", "<<="])).collect::<Vec<_>>(), vec!["<", "<"]);
+    assert_eq!(matches(hay, AnyOf(&["<<=", "<"])).collect::<Vec<_>>(), vec!["<<="]);
+    assert_eq!(find(hay, AnyOf(&["=", "<"])), Some(0));
+}
+
+#[test]
+fn test_any_of_slice() {
+    let hay = &[1, 2, 3, 4, 2, 3, 1][..];
+    let needles: &[&[i32]] = &[&[2, 3], &[4], &[3, 1]];
+    assert_eq!(
+        match_ranges_with(hay, AnyOf(needles)).map(|(r, i, _)| (r, i)).collect::<Vec<_>>(),
+        vec![(1..3, 0), (3..4, 1), (4..6, 0)],
+    );
+    assert_eq!(rfind_range(hay, AnyOf(needles)), Some(5..7));
+    assert_eq!(trim_start(hay, AnyOf(&[&[1][..], &[2]])), &[3, 4, 2, 3, 1]);
+    assert_eq!(trim_end(hay, AnyOf(needles)), &[1, 2, 3, 4, 2]);
+}
+
+#[test]
+fn test_any_of_empty() {
+    assert_eq!(matches("aé", AnyOf(&[""])).count(), 3);
+    assert_eq!(rmatches("aé", AnyOf(&[""])).count(), 3);
+    assert_eq!(find("abc", AnyOf::<str>(&[])), None);
+    assert_eq!(
+        match_ranges("ab", AnyOf(&["x", ""])).map(|(r, _)| r).collect::<Vec<_>>(),
+        vec![0..0, 1..1, 2..2],
+    );
+}
+
+#[test]
+fn test_any_of_within() {
+    let ops = AnyOf(&["+", "-"]);
+    assert_eq!(match_ranges_within("1+2-3+4", vec![0..3, 4..7], ops).collect::<Vec<_>>(), vec![1..2, 5..6]);
+}
+
+#[test]
+fn test_longest_of() {
+    let ops = ["<", "=", "<=", "<<="];
+    assert_eq!(
+        match_ranges_with("a<=b<c<<=d", LongestOf(&ops)).map(|(r, i, _)| (r, i)).collect::<Vec<_>>(),
+        vec![(1..3, 2), (4..5, 0), (6..9, 3)],
+    );
+    assert_eq!(
+        rmatch_ranges("a<=b<<=", LongestOf(&ops)).map(|(r, _)| r).collect::<Vec<_>>(),
+        vec![4..7, 1..3],
+    );
+    assert_eq!(split("x<=y", LongestOf(&ops)).collect::<Vec<_>>(), vec!["x", "y"]);
+    assert_eq!(split("x<=y", AnyOf(&ops)).collect::<Vec<_>>(), vec!["x", "", "y"]);
+    // equal lengths are resolved by order
+    assert_eq!(
+        match_ranges_with(&[1, 2][..], LongestOf(&[&[1][..], &[1]])).map(|(_, i, _)| i).collect::<Vec<_>>(),
+        vec![0],
+    );
+    assert_eq!(trim_start("<=<x", LongestOf(&["<", "<=", ""])), "x");
+}
+
+// Compares the automaton against trying every needle at every position.
+#[test]
+fn test_any_of_matches_naive() {
+    fn naive(hay: &str, needles: &[&str], longest: bool, reverse: bool) -> Vec<(std::ops::Range<usize>, usize)> {
+        let mut found = Vec::new();
+        let (mut start, mut end) = (0, hay.len());
+        loop {
+            let candidates = (start..=end).filter_map(|pos| {
+                let mut best: Option<(usize, usize)> = None;
+                for (i, n) in needles.iter().enumerate() {
+                    let fits = if reverse { hay[start..pos].ends_with(n) } else { hay[pos..end].starts_with(n) };
+                    if fits && best.map_or(true, |(_, len)| longest && n.len() > len) {
+                        best = Some((i, n.len()));
+                    }
+                }
+                best.map(|(i, len)| if reverse { ((pos - len)..pos, i) } else { (pos..(pos + len), i) })
+            });
+            let m = if reverse { candidates.last() } else { candidates.into_iter().next() };
+            match m {
+                Some((r, i)) => {
+                    if reverse { end = r.start } else { start = r.end }
+                    found.push((r, i));
+                }
+                None => return found,
+            }
+        }
+    }
+
+    fn next(seed: &mut u32, n: u32) -> u32 {
+        *seed = seed.wrapping_mul(1103515245).wrapping_add(12345);
+        (*seed >> 16) % n
+    }
+    fn word(seed: &mut u32, max: u32) -> String {
+        (0..1 + next(seed, max)).map(|_| if next(seed, 3) == 0 { 'b' } else { 'a' }).collect()
+    }
+
+    let mut seed = 12345;
+    for _ in 0..500 {
+        let hay = word(&mut seed, 12);
+        let needles = (0..1 + next(&mut seed, 4)).map(|_| word(&mut seed, 3)).collect::<Vec<_>>();
+        let needles = needles.iter().map(|s| &**s).collect::<Vec<_>>();
+        for &longest in &[false, true] {
+            let (forward, backward) = if longest {
+                (
+                    match_ranges_with(&*hay, LongestOf(&needles)).map(|(r, i, _)| (r, i)).collect::<Vec<_>>(),
+                    rmatch_ranges(&*hay, LongestOf(&needles)).map(|(r, _)| r).collect::<Vec<_>>(),
+                )
+            } else {
+                (
+                    match_ranges_with(&*hay, AnyOf(&needles)).map(|(r, i, _)| (r, i)).collect::<Vec<_>>(),
+                    rmatch_ranges(&*hay, AnyOf(&needles)).map(|(r, _)| r).collect::<Vec<_>>(),
+                )
+            };
+            assert_eq!(forward, naive(&hay, &needles, longest, false), "{:?} in {:?}", needles, hay);
+            let expected = naive(&hay, &needles, longest, true).into_iter().map(|(r, _)| r).collect::<Vec<_>>();
+            assert_eq!(backward, expected, "{:?} in {:?}", needles, hay);
+        }
+    }
+}
@@ -0,0 +1,37 @@
+extern crate pattern_3;
+
+use pattern_3::Approx;
+use pattern_3::ext::*;
+
+#[test]
+fn test_approx_f64() {
+    let signal = vec![0.0, 0.5, 1.0, 0.5, 0.0, 0.52, 0.98, 0.49, 0.0];
+    let template = &[0.5, 1.0, 0.5][..];
+    let pattern = Approx { needle: template, epsilon: 0.05 };
+    assert_eq!(match_ranges(&*signal, pattern).map(|(r, _)| r).collect::<Vec<_>>(), vec![1..4, 5..8]);
+    assert_eq!(rmatch_ranges(&*signal, pattern).map(|(r, _)| r).collect::<Vec<_>>(), vec![5..8, 1..4]);
+    assert_eq!(find(&*signal, Approx { needle: template, epsilon: 0.0 }), Some(1));
+    assert_eq!(rfind(&*signal, Approx { needle: template, epsilon: 0.0 }), Some(1));
+    assert!(starts_with(&*signal, Approx { needle: &[0.01][..], epsilon: 0.01 }));
+    assert!(!ends_with(&*signal, Approx { needle: &[0.01][..], epsilon: 0.001 }));
+    assert_eq!(split(signal, Approx { needle: &[0.0][..], epsilon: 0.1 }).count(), 4);
+}
+
+#[test]
+fn test_approx_f32() {
+    let hay = &[1.0f32, 2.0, 3.0, 2.001, 3.001][..];
+    let pattern = Approx { needle: &[2.0f32, 3.0][..], epsilon: 0.01 };
+    assert_eq!(match_indices(hay, pattern).map(|(i, _)| i).collect::<Vec<_>>(), vec![1, 3]);
+    assert_eq!(trim_start(hay, Approx { needle: &[1.0f32][..], epsilon: 0.0 }), &[2.0, 3.0, 2.001, 3.001]);
+}
+
+#[test]
+fn test_approx_nan_and_empty() {
+    let hay = &[::std::f64::NAN, 1.0][..];
+    assert_eq!(find(hay, Approx { needle: &[::std::f64::NAN][..], epsilon: 1.0 }), None);
+    assert_eq!(find(hay, Approx { needle: &[0.0][..], epsilon: ::std::f64::INFINITY }), Some(1));
+    assert_eq!(
+        match_indices(hay, Approx { needle: &[][..], epsilon: 0.0 }).map(|(i, _)| i).collect::<Vec<_>>(),
+        vec![0, 1, 2],
+    );
+}
@@ -0,0 +1,40 @@
+extern crate pattern_3;
+
+use pattern_3::AsciiCaseInsensitive;
+use pattern_3::ext::*;
+
+#[test]
+fn test_ascii_case_insensitive_str() {
+    let hay = "Error: ERROR in module eRRoR";
+    let needle = AsciiCaseInsensitive("error");
+    assert_eq!(find(hay, needle), Some(0));
+    assert_eq!(rfind(hay, needle), Some(23));
+    assert_eq!(match_indices(hay, needle).map(|(i, _)| i).collect::<Vec<_>>(), vec![0, 7, 23]);
+    assert_eq!(rmatches(hay, needle).collect::<Vec<_>>(), vec!["eRRoR", "ERROR", "Error"]);
+    assert_eq!(find(hay, AsciiCaseInsensitive("errors")), None);
+    assert_eq!(trim_start(hay, AsciiCaseInsensitive("ERROR:")), " ERROR in module eRRoR");
+    assert!(ends_with(hay, AsciiCaseInsensitive("ERROR")));
+}
+
+#[test]
+fn test_ascii_case_insensitive_non_ascii() {
+    // only ASCII letters are folded
+    let hay = "STRASSE straße STRAẞE";
+    assert_eq!(find(hay, AsciiCaseInsensitive("straße")), Some(8));
+    assert_eq!(rfind(hay, AsciiCaseInsensitive("straße")), Some(8));
+    assert_eq!(find(hay, AsciiCaseInsensitive("Ä")), None);
+    // the empty needle still stops at character boundaries only
+    assert_eq!(matches("aé", AsciiCaseInsensitive("")).count(), 3);
+}
+
+#[test]
+fn test_ascii_case_insensitive_bytes() {
+    let hay = &b"Host: x\r\nCONTENT-TYPE: text/plain\r\ncontent-length: 3\r\n"[..];
+    assert_eq!(find(hay, AsciiCaseInsensitive(&b"content-type"[..])), Some(9));
+    assert_eq!(find(hay, AsciiCaseInsensitive("Content-Length")), Some(35));
+    assert_eq!(
+        split(hay, AsciiCaseInsensitive(&b"\r\n"[..])).count(),
+        4,
+    );
+    assert_eq!(matches(&b"abc"[..], AsciiCaseInsensitive(&b""[..])).count(), 4);
+}
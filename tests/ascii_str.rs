@@ -0,0 +1,40 @@
+extern crate pattern_3;
+
+use pattern_3::AsciiStr;
+use pattern_3::ext::*;
+
+#[test]
+fn test_ascii_str_search() {
+    let hay = AsciiStr::from_ascii("key=value; other=thing").unwrap();
+    assert_eq!(find(hay, '='), Some(3));
+    assert_eq!(rfind(hay, '='), Some(16));
+    assert_eq!(find(hay, "other"), Some(11));
+    assert_eq!(find(hay, AsciiStr::from_ascii("thing").unwrap()), Some(17));
+    assert_eq!(find(hay, |c: char| c.is_ascii_whitespace()), Some(10));
+    assert_eq!(rfind(hay, &[';', 'k'][..]), Some(9));
+    assert_eq!(
+        split(hay, &[';', '='][..]).map(AsciiStr::as_str).collect::<Vec<_>>(),
+        vec!["key", "value", " other", "thing"],
+    );
+    assert_eq!(matches(hay, "").count(), hay.len() + 1);
+}
+
+#[test]
+fn test_ascii_str_non_ascii_char() {
+    let hay = AsciiStr::from_ascii(b"caf\xc3\xa9").map(AsciiStr::as_str);
+    assert_eq!(hay, None);
+    let hay = AsciiStr::from_ascii("cafe").unwrap();
+    assert_eq!(find(hay, 'é'), None);
+    assert_eq!(find(hay, "é"), None);
+    assert!(!contains(hay, |c: char| !c.is_ascii()));
+}
+
+#[test]
+fn test_ascii_str_trim() {
+    let hay = AsciiStr::from_ascii("\t  x y \n").unwrap();
+    assert_eq!(trim(hay, char::is_whitespace).as_str(), "x y");
+    assert_eq!(trim_start(hay, '\t').as_bytes(), b"  x y \n");
+    assert_eq!(trim_end(hay, '\n').as_str(), "\t  x y ");
+    assert!(starts_with(hay, "\t "));
+    assert!(ends_with(hay, '\n'));
+}
@@ -0,0 +1,41 @@
+extern crate pattern_3;
+
+use pattern_3::ext::*;
+
+fn boxed(s: &str) -> Box<str> {
+    s.to_owned().into_boxed_str()
+}
+
+#[test]
+fn test_boxed_str_split() {
+    let haystack = boxed("a, b,c");
+    assert_eq!(split(haystack.clone(), ", ").collect::<Vec<_>>(), vec![boxed("a"), boxed("b,c")]);
+    assert_eq!(split(haystack.clone(), ',').collect::<Vec<_>>(), vec![boxed("a"), boxed(" b"), boxed("c")]);
+    assert_eq!(
+        rsplitn(haystack, 2, |c: char| c == ' ').collect::<Vec<_>>(),
+        vec![boxed("b,c"), boxed("a,")]
+    );
+}
+
+#[test]
+fn test_boxed_str_trim() {
+    assert_eq!(trim(boxed("  hello\t"), char::is_whitespace), boxed("hello"));
+    assert_eq!(trim_start(boxed("xxhixx"), "x"), boxed("hixx"));
+    assert_eq!(trim_end(boxed("xxhixx"), 'x'), boxed("xxhi"));
+}
+
+#[test]
+fn test_boxed_slice() {
+    let haystack = vec![1, 0, 2, 3, 0, 0, 4].into_boxed_slice();
+    assert_eq!(split(haystack.clone(), &[0][..]).collect::<Vec<_>>(), vec![
+        vec![1].into_boxed_slice(),
+        vec![2, 3].into_boxed_slice(),
+        vec![].into_boxed_slice(),
+        vec![4].into_boxed_slice(),
+    ]);
+    assert_eq!(
+        match_indices(haystack.clone(), |x: &i32| *x > 2).collect::<Vec<_>>(),
+        vec![(3, vec![3].into_boxed_slice()), (6, vec![4].into_boxed_slice())]
+    );
+    assert_eq!(trim(haystack, |x: &i32| *x != 0), vec![0, 2, 3, 0, 0].into_boxed_slice());
+}
@@ -0,0 +1,81 @@
+#![cfg(feature = "bstr")]
+
+extern crate bstr;
+extern crate pattern_3;
+
+use bstr::{BStr, BString, ByteSlice};
+use pattern_3::{Lit, Lossy};
+use pattern_3::ext::*;
+
+#[test]
+fn test_bstr_substring() {
+    let haystack = BStr::new(b"key=value\xff=more");
+    let needle = BStr::new("=");
+    assert_eq!(match_indices(haystack, needle).collect::<Vec<_>>(), vec![
+        (3, needle),
+        (10, needle),
+    ]);
+    assert_eq!(
+        split(haystack, &b"="[..]).collect::<Vec<_>>(),
+        vec![BStr::new("key"), BStr::new(b"value\xff"), BStr::new("more")]
+    );
+    assert_eq!(rfind(haystack, needle), Some(10));
+}
+
+#[test]
+fn test_bstr_lossy_char() {
+    let haystack = BStr::new(b"a\xffb\xe2\x98c\xe2\x98\x83");
+    assert_eq!(match_ranges(haystack, Lossy('\u{fffd}')).collect::<Vec<_>>(), vec![
+        (1..2, BStr::new(b"\xff")),
+        (3..5, BStr::new(b"\xe2\x98")),
+    ]);
+    assert_eq!(find(haystack, Lossy('☃')), Some(6));
+    assert_eq!(
+        rmatch_indices(haystack, Lossy(|c: char| c.is_ascii())).collect::<Vec<_>>(),
+        vec![(5, BStr::new("c")), (2, BStr::new("b")), (0, BStr::new("a"))]
+    );
+}
+
+#[test]
+fn test_bstr_trim() {
+    let haystack = BStr::new(b" \t\xffx \n");
+    assert_eq!(trim(haystack, Lossy(|c: char| c.is_whitespace())), BStr::new(b"\xffx"));
+    let chars: &[char] = &[' ', '\t', '\u{fffd}'];
+    assert_eq!(trim_start(haystack, Lossy(chars)), BStr::new("x \n"));
+}
+
+#[test]
+fn test_bstr_mut() {
+    let mut buffer = b"one,two,three".to_vec();
+    for piece in split(buffer.as_mut_slice().as_bstr_mut(), BStr::new(",")) {
+        piece[0].make_ascii_uppercase();
+    }
+    assert_eq!(buffer, b"One,Two,Three");
+}
+
+#[test]
+fn test_bstr_str_needles() {
+    let haystack = BStr::new(b"GET /index.html\xff HTTP/1.1");
+    assert_eq!(find(haystack, Lit(" HTTP/")), Some(16));
+    assert_eq!(split(haystack, Lit(" ")).count(), 3);
+    assert_eq!(rfind(haystack, &BString::from("/")), Some(21));
+    assert!(starts_with(haystack, Lit("GET")));
+
+    let mut buffer = b"a-b-c".to_vec();
+    assert_eq!(split(buffer.as_bstr_mut(), Lit("-")).count(), 3);
+}
+
+#[test]
+fn test_boxed_bstr() {
+    let boxed = |b: &[u8]| Box::<BStr>::from(b.to_vec().into_boxed_slice());
+    let haystack = boxed(b"one\xfftwo three");
+    assert_eq!(
+        split(haystack.clone(), Lossy('\u{fffd}')).collect::<Vec<_>>(),
+        vec![boxed(b"one"), boxed(b"two three")]
+    );
+    assert_eq!(
+        rsplitn(haystack, 2, Lit(" ")).collect::<Vec<_>>(),
+        vec![boxed(b"three"), boxed(b"one\xfftwo")]
+    );
+    assert_eq!(trim(boxed(b"  x  "), Lossy(char::is_whitespace)), boxed(b"x"));
+}
@@ -0,0 +1,50 @@
+extern crate pattern_3;
+
+use pattern_3::{AsciiWhitespaceByte, AsciiDigit, AsciiAlphanumeric};
+use pattern_3::ext::*;
+
+#[test]
+fn test_ascii_whitespace_byte() {
+    let hay = &b" \t a  b\r\n"[..];
+    assert_eq!(trim(hay, AsciiWhitespaceByte), b"a  b");
+    assert_eq!(trim_start(hay, AsciiWhitespaceByte), b"a  b\r\n");
+    assert_eq!(trim_end(hay, AsciiWhitespaceByte), b" \t a  b");
+    assert_eq!(
+        split(&b"a b\tc"[..], AsciiWhitespaceByte).collect::<Vec<_>>(),
+        vec![&b"a"[..], b"b", b"c"],
+    );
+    assert_eq!(trim(&b"\x0b"[..], AsciiWhitespaceByte), b"\x0b");
+    assert_eq!(trim(&b"  "[..], AsciiWhitespaceByte), b"");
+}
+
+#[test]
+fn test_ascii_digit() {
+    let hay = b"v1.23-rc4".to_vec();
+    assert_eq!(match_indices(&*hay, AsciiDigit).map(|(i, _)| i).collect::<Vec<_>>(), vec![1, 3, 4, 8]);
+    assert_eq!(rfind(&*hay, AsciiDigit), Some(8));
+    assert_eq!(trim_end(hay, AsciiDigit), b"v1.23-rc".to_vec());
+    assert_eq!(find(&[0xd9u8, 0xa0][..], AsciiDigit), None);
+}
+
+#[test]
+fn test_ascii_alphanumeric() {
+    let hay = &b"--foo_bar9--"[..];
+    assert_eq!(find(hay, AsciiAlphanumeric), Some(2));
+    assert_eq!(rfind(hay, AsciiAlphanumeric), Some(9));
+    assert_eq!(trim(hay, |b: &u8| !b.is_ascii_alphanumeric()), b"foo_bar9");
+    assert_eq!(matches(hay, AsciiAlphanumeric).count(), 7);
+}
+
+#[test]
+fn test_negated_byte_class() {
+    use pattern_3::pattern::not;
+
+    let hay = &b"v1.25-rc"[..];
+    assert_eq!(find(hay, not(AsciiAlphanumeric)), Some(2));
+    assert_eq!(rfind(hay, not(AsciiAlphanumeric)), Some(5));
+    assert_eq!(trim(&b"ab12cd"[..], not(AsciiDigit)), b"12");
+    assert_eq!(
+        split(&b"a b\tc"[..], not(AsciiWhitespaceByte)).collect::<Vec<_>>(),
+        vec![&b""[..], b" ", b"\t", b""],
+    );
+}
@@ -0,0 +1,38 @@
+extern crate pattern_3;
+
+use pattern_3::ext::*;
+
+#[test]
+fn test_byte_set_search() {
+    let hay = &b"GET /a?b=c&d=e HTTP/1.1\r\n"[..];
+    assert_eq!(find(hay, [b'?', b'#']), Some(6));
+    assert_eq!(rfind(hay, [b'&', b'=', b'?']), Some(12));
+    assert_eq!(
+        split(&hay[7..14], [b'&', b'=']).collect::<Vec<_>>(),
+        vec![&b"b"[..], b"c", b"d", b"e"],
+    );
+    assert_eq!(rsplit(&b"a\rb\nc"[..], [b'\r', b'\n']).collect::<Vec<_>>(), vec![&b"c"[..], b"b", b"a"]);
+    assert_eq!(matches(hay, [b'x', b'y', b'z']).count(), 0);
+    assert_eq!(matches(hay, [b'/', b'/']).count(), 2);
+}
+
+#[test]
+fn test_byte_set_trim() {
+    let hay = b"\r\n\r\nbody\r\n".to_vec();
+    assert_eq!(trim(&hay[..], [b'\r', b'\n']), b"body");
+    assert_eq!(trim_start(hay.clone(), [b'\r', b'\n', b'b']), b"ody\r\n");
+    assert!(starts_with(&hay[..], [b'\n', b'\r']));
+    assert!(!ends_with(&hay[..], [b'b', b'y']));
+}
+
+#[test]
+fn test_byte_search() {
+    let hay = vec![b'x'; 1000].into_iter().chain(b"\nabc\n".iter().cloned()).collect::<Vec<_>>();
+    assert_eq!(find(&hay[..], b'\n'), Some(1000));
+    assert_eq!(rfind(&hay[..], b'\n'), Some(1004));
+    assert_eq!(find(&hay[..], b'y'), None);
+    assert_eq!(split(&hay[995..], b'\n').collect::<Vec<_>>(), vec![&b"xxxxx"[..], b"abc", b""]);
+    assert_eq!(trim_start(&hay[..], b'x'), b"\nabc\n");
+    assert_eq!(trim_end(hay.clone().into_boxed_slice(), b'\n').len(), 1004);
+    assert_eq!(matches(&b"a\0b\0"[..], 0u8).count(), 2);
+}
@@ -0,0 +1,56 @@
+#![cfg(feature = "bytes")]
+
+extern crate bytes;
+extern crate pattern_3;
+
+use bytes::Bytes;
+use pattern_3::ext::*;
+
+#[test]
+fn test_bytes_split_shares_buffer() {
+    let frame = Bytes::from_static(b"GET /index.html HTTP/1.1\r\nHost: example.com\r\n\r\n");
+    let lines = split_terminator(frame.clone(), &b"\r\n"[..]).collect::<Vec<_>>();
+    assert_eq!(lines, vec![
+        Bytes::from_static(b"GET /index.html HTTP/1.1"),
+        Bytes::from_static(b"Host: example.com"),
+        Bytes::new(),
+    ]);
+    // the pieces point into the original buffer.
+    assert_eq!(lines[1].as_ptr(), frame[26..].as_ptr());
+}
+
+#[test]
+fn test_bytes_match_ranges() {
+    let haystack = Bytes::from(b"a,b,,c".to_vec());
+    assert_eq!(match_ranges(haystack.clone(), |b: &u8| *b == b',').collect::<Vec<_>>(), vec![
+        (1..2, Bytes::from_static(b",")),
+        (3..4, Bytes::from_static(b",")),
+        (4..5, Bytes::from_static(b",")),
+    ]);
+    assert_eq!(rsplitn(haystack, 2, &b",,"[..]).collect::<Vec<_>>(), vec![
+        Bytes::from_static(b"c"),
+        Bytes::from_static(b"a,b"),
+    ]);
+}
+
+#[test]
+fn test_bytes_trim() {
+    let haystack = Bytes::from_static(b"  payload ");
+    assert_eq!(trim(haystack, |b: &u8| *b == b' '), Bytes::from_static(b"payload"));
+}
+
+#[test]
+fn test_bytes_zero_copy_parsing() {
+    let frame = Bytes::from(b"Content-Length: 42\r\nX-Id: abc\r\n".to_vec());
+    let base = frame.as_ptr() as usize;
+    for line in split_terminator(frame.clone(), &b"\r\n"[..]) {
+        let mut fields = splitn(line.clone(), 2, &b": "[..]);
+        let name = fields.next().unwrap();
+        let value = fields.next().unwrap();
+        assert_eq!(name.as_ptr(), line.as_ptr());
+        assert_eq!(value.as_ptr() as usize - base, line.as_ptr() as usize - base + name.len() + 2);
+    }
+    for (range, piece) in match_ranges(frame.clone(), &b"\r\n"[..]) {
+        assert_eq!(piece.as_ptr() as usize - base, range.start);
+    }
+}
@@ -0,0 +1,42 @@
+extern crate pattern_3;
+
+use pattern_3::Cached;
+use pattern_3::ext::*;
+
+#[test]
+fn test_cached_str() {
+    let lines = ["foo bar", "bar foo foo", "baz"];
+    for _ in 0..3 {
+        let found = lines.iter().map(|line| find(*line, Cached("foo"))).collect::<Vec<_>>();
+        assert_eq!(found, vec![Some(0), Some(4), None]);
+        let found = lines.iter().map(|line| rfind(*line, Cached("foo"))).collect::<Vec<_>>();
+        assert_eq!(found, vec![Some(0), Some(8), None]);
+    }
+    assert_eq!(split("a, b, c", Cached(", ")).collect::<Vec<_>>(), vec!["a", "b", "c"]);
+    assert!(starts_with("foobar", Cached("foo")));
+    assert!(ends_with("foobar", Cached("bar")));
+    assert_eq!(trim_start("xxaxx", Cached("x")), "axx");
+    assert_eq!(match_indices("ab", Cached("")).map(|(i, _)| i).collect::<Vec<_>>(), vec![0, 1, 2]);
+}
+
+#[test]
+fn test_cached_bytes() {
+    let needle = b"\r\n".to_vec();
+    assert_eq!(find(&b"GET /\r\n"[..], Cached(&*needle)), Some(5));
+    assert_eq!(
+        split(b"a\r\nb".to_vec(), Cached(&*needle)).collect::<Vec<_>>(),
+        vec![b"a".to_vec(), b"b".to_vec()],
+    );
+}
+
+#[test]
+fn test_cached_many_needles() {
+    // more distinct needles than the cache holds.
+    let needles = (0..200).map(|i| format!("<{}>", i)).collect::<Vec<_>>();
+    let hay = needles.concat();
+    for _ in 0..2 {
+        for (i, needle) in needles.iter().enumerate() {
+            assert_eq!(find(&*hay, Cached(&**needle)), find(&*hay, &**needle), "needle {}", i);
+        }
+    }
+}
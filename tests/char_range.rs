@@ -0,0 +1,36 @@
+extern crate pattern_3;
+
+use pattern_3::ext::*;
+
+#[test]
+fn test_char_range_search() {
+    let hay = "Hello, Wörld 42!";
+    assert_eq!(find(hay, 'a'..='z'), Some(1));
+    assert_eq!(rfind(hay, 'a'..='z'), Some(12));
+    assert_eq!(find(hay, '0'..'9'), Some(14));
+    assert_eq!(find(hay, 'À'..='ÿ'), Some(8));
+    assert_eq!(matches(hay, 'A'..='Z').collect::<Vec<_>>(), vec!["H", "W"]);
+    assert_eq!(rmatches(hay, '0'..='9').collect::<Vec<_>>(), vec!["2", "4"]);
+    assert_eq!(split(hay, ' '..='/').collect::<Vec<_>>(), vec!["Hello", "", "Wörld", "42", ""]);
+}
+
+#[test]
+fn test_char_range_empty() {
+    assert_eq!(find("abc", 'b'..'b'), None);
+    #[allow(clippy::reversed_empty_ranges)]
+    {
+        assert_eq!(find("abc", 'c'..'a'), None);
+        assert_eq!(find("abc", 'c'..='a'), None);
+    }
+    assert_eq!(find("abc", 'c'..='c'), Some(2));
+}
+
+#[test]
+fn test_char_range_trim() {
+    let hay = String::from("123abc456");
+    assert_eq!(trim(&*hay, '0'..='9'), "abc");
+    assert_eq!(trim_start(hay.clone(), '0'..='9'), "abc456");
+    assert_eq!(trim_end(&*hay, '4'..='\u{10FFFF}'), "123");
+    assert!(starts_with(&*hay, '1'..'2'));
+    assert!(!ends_with(&*hay, '1'..'6'));
+}
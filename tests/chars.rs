@@ -0,0 +1,39 @@
+extern crate pattern_3;
+
+use pattern_3::ConstNeedle;
+use pattern_3::ext::*;
+
+fn chars(s: &str) -> Vec<char> {
+    s.chars().collect()
+}
+
+#[test]
+fn test_char_buffer_split_trim() {
+    let line = chars("  ls -la ~/ñandú  ");
+    let trimmed = trim(&line[..], |c: &char| *c == ' ');
+    assert_eq!(trimmed, &chars("ls -la ~/ñandú")[..]);
+    assert_eq!(
+        split(trimmed, ConstNeedle([' '])).collect::<Vec<_>>(),
+        vec![&chars("ls")[..], &chars("-la")[..], &chars("~/ñandú")[..]]
+    );
+    assert_eq!(find(&line[..], ConstNeedle(['ñ'])), Some(11));
+    assert_eq!(rfind(&line[..], ConstNeedle([' '])), Some(17));
+    assert_eq!(trim_end(&line[..], |c: &char| c.is_whitespace() || *c == 'ú').len(), 15);
+}
+
+#[test]
+fn test_char_buffer_subslice_and_owned() {
+    let text = chars("a→b→c");
+    let arrow = chars("→");
+    assert_eq!(match_indices(&text[..], &arrow[..]).map(|(i, _)| i).collect::<Vec<_>>(), vec![1, 3]);
+    assert_eq!(
+        split(text.clone(), ConstNeedle(['→'])).collect::<Vec<_>>(),
+        vec![chars("a"), chars("b"), chars("c")]
+    );
+
+    let mut buffer = chars("x,y");
+    for piece in split(&mut buffer[..], ConstNeedle([','])) {
+        piece[0] = piece[0].to_ascii_uppercase();
+    }
+    assert_eq!(buffer, chars("X,Y"));
+}
@@ -0,0 +1,94 @@
+extern crate pattern_3;
+
+use pattern_3::{ChunkedHay, Concat};
+
+use std::collections::VecDeque;
+
+#[test]
+fn test_chunked_across_boundaries() {
+    let chunks: Vec<&[u8]> = vec![b"ab", b"", b"a", b"bab", b"a", b"b"];
+    let hay = &chunks[..];
+    assert_eq!(ChunkedHay::len(hay), 8);
+    assert_eq!(hay.match_indices(&b"ab"[..]).collect::<Vec<_>>(), vec![0, 2, 4, 6]);
+    assert_eq!(hay.match_indices(&b"abab"[..]).collect::<Vec<_>>(), vec![0, 4]);
+    assert_eq!(hay.find(&b"babab"[..]), Some(1));
+    assert_eq!(hay.find(&b"bb"[..]), None);
+    assert_eq!(hay.match_indices(&b""[..]).count(), 9);
+}
+
+#[test]
+fn test_chunked_matches_flattened() {
+    let text = b"abracadabra, abracadabra! cadabra";
+    let chunks: Vec<Vec<u8>> = text.chunks(3).map(|c| c.to_vec()).collect();
+    for needle in [&b"abra"[..], b"cadabra", b"a, a", b"!", b"abracadabra, abracadabra! cadabra", b"zz"].iter() {
+        let expected = (0..text.len())
+            .filter(|&i| text[i..].starts_with(needle))
+            .fold(Vec::new(), |mut v: Vec<usize>, i| {
+                if v.last().map_or(true, |&j| j + needle.len() <= i) {
+                    v.push(i);
+                }
+                v
+            });
+        assert_eq!(chunks.match_indices(*needle).collect::<Vec<_>>(), expected);
+
+        let expected = (0..text.len())
+            .rev()
+            .filter(|&i| text[i..].starts_with(needle))
+            .fold(Vec::new(), |mut v: Vec<usize>, i| {
+                if v.last().map_or(true, |&j| i + needle.len() <= j) {
+                    v.push(i);
+                }
+                v
+            });
+        assert_eq!(chunks.rmatch_indices(*needle).collect::<Vec<_>>(), expected);
+    }
+}
+
+#[test]
+fn test_chunked_deque() {
+    let mut deque = VecDeque::with_capacity(8);
+    deque.extend(b"lo wo".iter().cloned());
+    for &b in b"hel".iter().rev() {
+        deque.push_front(b);
+    }
+    assert_eq!(deque.find(&b"llo w"[..]), Some(2));
+    assert!(!ChunkedHay::is_empty(&deque));
+}
+
+#[test]
+fn test_concat_positions() {
+    let pieces: Vec<&[u8]> = vec![b"ab", b"", b"a", b"bab", b"a", b"b"];
+    let concat = Concat::new(&pieces[..]);
+    assert_eq!(concat.match_positions(&b"ab"[..]).collect::<Vec<_>>(), vec![(0, 0), (2, 0), (3, 1), (4, 0)]);
+    assert_eq!(concat.find(&b"bab"[..]), Some((0, 1)));
+    assert_eq!(concat.find(&b"bb"[..]), None);
+    assert_eq!(concat.match_positions(&b""[..]).last(), Some((5, 1)));
+
+    let strings = vec!["héllo ".to_string(), "wör".to_string(), "ld".to_string()];
+    let concat = Concat::new(&strings[..]);
+    assert_eq!(concat.find("world".as_bytes()), None);
+    assert_eq!(concat.find("wörld".as_bytes()), Some((1, 0)));
+    assert_eq!(concat.find("o w".as_bytes()), Some((0, 5)));
+
+    let empty: [&str; 0] = [];
+    assert_eq!(Concat::new(&empty[..]).find(&b""[..]), Some((0, 0)));
+}
+
+#[test]
+fn test_chunked_predicate() {
+    let chunks: Vec<&[u8]> = vec![b"ab", b"", b" c", b"d e "];
+    let hay = &chunks[..];
+    assert_eq!(hay.match_indices(|b: &u8| *b == b' ').collect::<Vec<_>>(), vec![2, 5, 7]);
+    assert_eq!(hay.find(|b: &u8| b.is_ascii_digit()), None);
+}
+
+#[test]
+fn test_chunked_str_and_char() {
+    // `é` is split across the first two chunks.
+    let chunks: Vec<&[u8]> = vec![b"caf\xc3", b"\xa9 au lait, caf", b"\xc3\xa9"];
+    let hay = &chunks[..];
+    assert_eq!(hay.match_indices("café").collect::<Vec<_>>(), vec![0, 15]);
+    assert_eq!(hay.match_indices('é').collect::<Vec<_>>(), vec![3, 18]);
+    assert_eq!(hay.find(','), Some(13));
+    assert_eq!(hay.find('x'), None);
+}
@@ -0,0 +1,32 @@
+extern crate pattern_3;
+
+use pattern_3::ext::*;
+
+const REQUEST_LINE: &str = "GET /index.html HTTP/1.1\r\n";
+const PATH_START: usize = match const_find(REQUEST_LINE.as_bytes(), b" ") {
+    Some(i) => i + 1,
+    None => panic!("no space in request line"),
+};
+const _: () = assert!(const_starts_with(REQUEST_LINE.as_bytes(), b"GET "));
+const _: () = assert!(const_ends_with(REQUEST_LINE.as_bytes(), b"\r\n"));
+const _: () = assert!(!const_contains(REQUEST_LINE.as_bytes(), b"POST"));
+
+#[test]
+fn test_const_eval() {
+    assert_eq!(PATH_START, 4);
+}
+
+#[test]
+fn test_const_fns_at_runtime() {
+    assert_eq!(const_find(b"abcabc", b"ca"), Some(2));
+    assert_eq!(const_find(b"abc", b""), Some(0));
+    assert_eq!(const_find(b"", b""), Some(0));
+    assert_eq!(const_find(b"ab", b"abc"), None);
+    assert_eq!(const_find("jalapeño".as_bytes(), "ñ".as_bytes()), Some(6));
+    assert!(const_contains(b"hello", b"ll"));
+    assert!(!const_contains(b"hello", b"lo!"));
+    assert!(const_starts_with(b"hello", b""));
+    assert!(!const_starts_with(b"he", b"hello"));
+    assert!(const_ends_with(b"hello", b"llo"));
+    assert!(!const_ends_with(b"lo", b"hello"));
+}
@@ -0,0 +1,37 @@
+extern crate pattern_3;
+
+use pattern_3::ConstNeedle;
+use pattern_3::ext::*;
+
+#[test]
+fn test_const_needle_bytes() {
+    let hay = &b"<s>one</s><s>two</s>"[..];
+    assert_eq!(match_indices(hay, ConstNeedle(*b"</s>")).map(|(i, _)| i).collect::<Vec<_>>(), vec![6, 16]);
+    assert_eq!(rmatch_indices(hay, ConstNeedle(*b"</s>")).map(|(i, _)| i).collect::<Vec<_>>(), vec![16, 6]);
+    assert_eq!(find(hay, ConstNeedle(*b"<t>")), None);
+    assert!(starts_with(hay, ConstNeedle(*b"<s>")));
+    assert!(ends_with(hay, ConstNeedle(*b"</s>")));
+    assert!(!ends_with(hay, ConstNeedle(*b"<s>")));
+}
+
+#[test]
+fn test_const_needle_overlapping() {
+    assert_eq!(match_indices(&b"aaaaa"[..], ConstNeedle(*b"aa")).map(|(i, _)| i).collect::<Vec<_>>(), vec![0, 2]);
+    assert_eq!(rmatch_indices(&b"aaaaa"[..], ConstNeedle(*b"aa")).map(|(i, _)| i).collect::<Vec<_>>(), vec![3, 1]);
+    assert_eq!(find(&b"xyzxyab"[..], ConstNeedle(*b"yab")), Some(4));
+    assert_eq!(rfind(&b"yabzxyzxy"[..], ConstNeedle(*b"yab")), Some(0));
+}
+
+#[test]
+fn test_const_needle_empty() {
+    assert_eq!(match_indices(&b"ab"[..], ConstNeedle::<u8, 0>([])).map(|(i, _)| i).collect::<Vec<_>>(), vec![0, 1, 2]);
+    assert_eq!(rmatch_indices(&b"ab"[..], ConstNeedle::<u8, 0>([])).map(|(i, _)| i).collect::<Vec<_>>(), vec![2, 1, 0]);
+}
+
+#[test]
+fn test_const_needle_owned_and_generic() {
+    let hay = vec![1, 2, 3, 1, 2, 3, 4];
+    assert_eq!(split(hay, ConstNeedle([2, 3])).collect::<Vec<_>>(), vec![vec![1], vec![1], vec![4]]);
+    let hay = vec![1.0, 2.0, 3.0].into_boxed_slice();
+    assert_eq!(trim_start(hay, ConstNeedle([1.0])), vec![2.0, 3.0].into_boxed_slice());
+}
@@ -0,0 +1,66 @@
+extern crate pattern_3;
+
+use pattern_3::ext::*;
+use std::borrow::Cow;
+
+#[test]
+fn test_cow_str() {
+    let borrowed: Cow<str> = Cow::Borrowed(", ");
+    let owned: Cow<str> = Cow::Owned(format!("{}{}", ",", " "));
+    assert_eq!(split("a, b, c", borrowed.clone()).collect::<Vec<_>>(), vec!["a", "b", "c"]);
+    assert_eq!(split("a, b, c", owned.clone()).collect::<Vec<_>>(), vec!["a", "b", "c"]);
+    assert_eq!(rfind("a, b, c", owned.clone()), Some(4));
+    assert!(starts_with(", x", borrowed));
+    assert_eq!(trim_end("x, , ", owned), "x");
+    assert_eq!(
+        match_indices("ab", Cow::Borrowed("")).map(|(i, _)| i).collect::<Vec<_>>(),
+        vec![0, 1, 2],
+    );
+}
+
+#[test]
+fn test_cow_slice() {
+    let hay = vec![1, 2, 3, 1, 2, 3, 4];
+    let needle: Cow<[i32]> = Cow::Owned(vec![2, 3]);
+    assert_eq!(match_indices(&*hay, needle.clone()).map(|(i, _)| i).collect::<Vec<_>>(), vec![1, 4]);
+    assert_eq!(rmatch_indices(&*hay, needle.clone()).map(|(i, _)| i).collect::<Vec<_>>(), vec![4, 1]);
+    assert_eq!(find(&*hay, Cow::Borrowed(&[3, 4][..])), Some(5));
+    assert_eq!(split(hay.clone(), needle).collect::<Vec<_>>(), vec![vec![1], vec![1], vec![4]]);
+    assert_eq!(trim_start(&*hay, Cow::Borrowed(&[1, 2, 3][..])), &[4]);
+    assert!(ends_with(&b"key: value"[..], Cow::Borrowed(&b"value"[..])));
+}
+
+fn is_borrowed(s: &Cow<str>) -> bool {
+    match s {
+        Cow::Borrowed(_) => true,
+        Cow::Owned(_) => false,
+    }
+}
+
+#[test]
+fn test_cow_haystack() {
+    let pieces: Vec<Cow<str>> = split(Cow::Borrowed("a,b,,c"), ',').collect();
+    assert_eq!(pieces, vec!["a", "b", "", "c"]);
+    assert!(pieces.iter().all(is_borrowed));
+
+    let pieces: Vec<Cow<str>> = rsplitn(Cow::Owned("a, b, c".to_string()), 2, ", ").collect();
+    assert_eq!(pieces, vec!["c", "a, b"]);
+    assert!(!pieces.iter().any(is_borrowed));
+
+    let trimmed = trim(Cow::Borrowed("  x  "), char::is_whitespace);
+    assert_eq!(trimmed, "x");
+    assert!(is_borrowed(&trimmed));
+}
+
+#[test]
+fn test_cow_haystack_replace() {
+    let mut out: Vec<Cow<str>> = Vec::new();
+    replace_with(
+        Cow::Borrowed("one two"),
+        " ",
+        |_| Cow::Owned("__".to_string()),
+        |piece| out.push(piece),
+    );
+    assert_eq!(out.concat(), "one__two");
+    assert!(is_borrowed(&out[0]) && !is_borrowed(&out[1]) && is_borrowed(&out[2]));
+}
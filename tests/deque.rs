@@ -0,0 +1,79 @@
+extern crate pattern_3;
+
+use pattern_3::ChunkedHay;
+
+use std::collections::VecDeque;
+
+// Builds a deque whose contents wrap around the end of its buffer.
+fn wrapped(front: &[u8], back: &[u8]) -> VecDeque<u8> {
+    let mut deque = VecDeque::with_capacity(front.len() + back.len());
+    deque.extend(back.iter().cloned());
+    for &x in front.iter().rev() {
+        deque.push_front(x);
+    }
+    deque
+}
+
+#[test]
+fn test_deque_straddling() {
+    let deque = wrapped(b"xxab", b"cdxabcd");
+    let (front, back) = deque.as_slices();
+    assert!(!front.is_empty() && !back.is_empty());
+    assert_eq!(deque.iter().cloned().collect::<Vec<_>>(), b"xxabcdxabcd");
+
+    assert_eq!(deque.find(&b"abcd"[..]), Some(2));
+    assert_eq!(deque.match_indices(&b"abcd"[..]).collect::<Vec<_>>(), vec![2, 7]);
+    assert_eq!(deque.match_indices(&b"x"[..]).collect::<Vec<_>>(), vec![0, 1, 6]);
+    assert_eq!(deque.find(&b"bcdxa"[..]), Some(3));
+    assert_eq!(deque.find(&b"dd"[..]), None);
+}
+
+#[test]
+fn test_deque_edge_cases() {
+    let deque = wrapped(b"aa", b"aa");
+    assert_eq!(deque.match_indices(&b"aa"[..]).collect::<Vec<_>>(), vec![0, 2]);
+    assert_eq!(deque.match_indices(&b"aaa"[..]).collect::<Vec<_>>(), vec![0]);
+    assert_eq!(deque.find(&b"aaaaa"[..]), None);
+    assert_eq!(deque.match_indices(&b""[..]).collect::<Vec<_>>(), vec![0, 1, 2, 3, 4]);
+
+    let empty = VecDeque::<u8>::new();
+    assert_eq!(empty.match_indices(&b""[..]).collect::<Vec<_>>(), vec![0]);
+    assert_eq!(empty.find(&b"a"[..]), None);
+}
+
+#[test]
+fn test_deque_reverse() {
+    let deque = wrapped(b"xxab", b"cdxabcd");
+    assert_eq!(deque.rfind(&b"abcd"[..]), Some(7));
+    assert_eq!(deque.rmatch_indices(&b"abcd"[..]).collect::<Vec<_>>(), vec![7, 2]);
+    assert_eq!(deque.rmatch_indices(&b"x"[..]).collect::<Vec<_>>(), vec![6, 1, 0]);
+    assert_eq!(deque.rfind(&b"bcdxa"[..]), Some(3));
+    assert_eq!(deque.rfind(&b"dd"[..]), None);
+    assert_eq!(deque.rfind(|b: &u8| *b == b'b'), Some(8));
+    assert_eq!(deque.rmatch_indices(&b""[..]).count(), 12);
+
+    let deque = wrapped(b"aa", b"aa");
+    assert_eq!(deque.rmatch_indices(&b"aaa"[..]).collect::<Vec<_>>(), vec![1]);
+    assert_eq!(deque.rmatch_indices(&b""[..]).collect::<Vec<_>>(), vec![4, 3, 2, 1, 0]);
+}
+
+#[test]
+fn test_deque_split_and_trim() {
+    let deque = wrapped(b"--a-", b"-b--c-");
+    assert!(!deque.as_slices().0.is_empty() && !deque.as_slices().1.is_empty());
+    assert_eq!(deque.iter().cloned().collect::<Vec<_>>(), b"--a--b--c-");
+    assert_eq!(deque.split_ranges(&b"--"[..]).collect::<Vec<_>>(), vec![0..0, 2..3, 5..6, 8..10]);
+    assert_eq!(deque.rsplit_ranges(&b"--"[..]).collect::<Vec<_>>(), vec![8..10, 5..6, 2..3, 0..0]);
+    assert_eq!(deque.rsplit_ranges(&b"-"[..]).collect::<Vec<_>>(), vec![
+        10..10, 8..9, 7..7, 5..6, 4..4, 2..3, 1..1, 0..0,
+    ]);
+    let dash = |b: &u8| *b == b'-';
+    assert_eq!(deque.trim_start(dash), 2);
+    assert_eq!(deque.trim_end(dash), 9);
+    assert_eq!(deque.trim_end(&b"--"[..]), 10);
+    assert_eq!(deque.trim_start(&b"--"[..]), 2);
+
+    let empty = VecDeque::<u8>::new();
+    assert_eq!(empty.rsplit_ranges(dash).collect::<Vec<_>>(), vec![0..0]);
+    assert_eq!(empty.trim_end(dash), 0);
+}
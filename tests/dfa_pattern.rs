@@ -0,0 +1,122 @@
+#![cfg(feature = "regex-automata")]
+
+extern crate pattern_3;
+extern crate regex_automata;
+
+use pattern_3::DfaPattern;
+use pattern_3::ext::*;
+
+#[test]
+fn test_dfa_forward() {
+    let re = DfaPattern::new(r"\s*,\s*").unwrap();
+    assert_eq!(split("a, b ,c,, d", &re).collect::<Vec<_>>(), vec!["a", "b", "c", "", "d"]);
+    let re = DfaPattern::new(r"[0-9]*").unwrap();
+    assert_eq!(
+        match_ranges("a1bbb2", &re).map(|(r, _)| r).collect::<Vec<_>>(),
+        vec![0..0, 1..2, 3..3, 4..4, 5..6],
+    );
+    let re = DfaPattern::new(r"^a").unwrap();
+    assert_eq!(matches("aaa", &re).count(), 1);
+    assert_eq!(trim_start("aaa", &re), "aa");
+}
+
+#[test]
+fn test_dfa_reverse() {
+    let re = DfaPattern::new(r"\s*,\s*").unwrap();
+    assert_eq!(rsplit("a, b ,c", &re).collect::<Vec<_>>(), vec!["c", "b", "a"]);
+    let re = DfaPattern::new(r"[0-9]+").unwrap();
+    assert_eq!(rfind("a12b345c", &re), Some(4));
+    assert_eq!(rmatches("a12b345c", &re).collect::<Vec<_>>(), vec!["345", "12"]);
+    assert_eq!(trim_end("abc123", &re), "abc");
+    assert!(ends_with("abc123", &re));
+    assert!(!ends_with("123abc", &re));
+    let re = DfaPattern::new(r"a$").unwrap();
+    assert_eq!(rmatches("aaa", &re).count(), 1);
+}
+
+#[test]
+fn test_dfa_empty_matches() {
+    let re = DfaPattern::new(r"").unwrap();
+    assert_eq!(matches("aé", &re).count(), 3);
+    assert_eq!(rmatches("aé", &re).count(), 3);
+    assert_eq!(matches(&b"ab"[..], &re).count(), 3);
+}
+
+#[test]
+fn test_dfa_unicode() {
+    let re = DfaPattern::new(r"\p{Greek}+").unwrap();
+    let hay = "abc αβγ def δ";
+    assert_eq!(matches(hay, &re).collect::<Vec<_>>(), vec!["αβγ", "δ"]);
+    assert_eq!(rmatches(hay, &re).collect::<Vec<_>>(), vec!["δ", "αβγ"]);
+}
+
+#[test]
+fn test_dfa_bytes() {
+    let re = DfaPattern::new_bytes(r"(?-u)\xff+").unwrap();
+    let hay = &b"a\xff\xffb\xffc"[..];
+    assert_eq!(split(hay, &re).collect::<Vec<_>>(), vec![&b"a"[..], b"b", b"c"]);
+    assert_eq!(rsplit(hay, &re).collect::<Vec<_>>(), vec![&b"c"[..], b"b", b"a"]);
+}
+
+#[test]
+fn test_dfa_bytes_on_str() {
+    // matches splitting a character are never reported on strings
+    let re = DfaPattern::new_bytes(r"(?-u)\xa9").unwrap();
+    assert_eq!(find("é©", &re), None);
+    assert_eq!(rfind("é©", &re), None);
+    assert_eq!(find(&"é©".as_bytes()[..], &re), Some(1));
+}
+
+fn quit_byte_pattern() -> DfaPattern<regex_automata::dfa::dense::DFA<Vec<u32>>> {
+    use regex_automata::dfa::dense;
+    use regex_automata::dfa::regex::Regex;
+    use regex_automata::nfa::thompson;
+
+    let config = dense::Config::new().unicode_word_boundary(true);
+    let regex = Regex::builder().dense(config.clone()).build(r"\bfoo\b").unwrap();
+    let reverse_search = dense::Builder::new()
+        .configure(config)
+        .thompson(thompson::Config::new().reverse(true))
+        .build(r"\bfoo\b")
+        .unwrap();
+    DfaPattern::from_dfas(regex, reverse_search)
+}
+
+#[test]
+fn test_dfa_quit_bytes() {
+    let re = quit_byte_pattern();
+    assert_eq!(find("a foo b", &re), Some(2));
+    assert_eq!(rfind("a foo b", &re), Some(2));
+    assert_eq!(split("foo, bar, foo", &re).collect::<Vec<_>>(), vec!["", ", bar, ", ""]);
+}
+
+#[cfg(not(debug_assertions))]
+#[test]
+fn test_dfa_quit_byte_release() {
+    // searches stop at the first non-ASCII byte
+    let re = quit_byte_pattern();
+    assert_eq!(find("é foo", &re), None);
+    assert_eq!(rfind("foo é", &re), None);
+    assert!(!starts_with("éfoo", &re));
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "DFA search failed")]
+fn test_dfa_quit_byte_find() {
+    find("é foo", &quit_byte_pattern());
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "DFA search failed")]
+fn test_dfa_quit_byte_rfind() {
+    rfind("foo é", &quit_byte_pattern());
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "DFA search failed")]
+fn test_dfa_quit_byte_consume() {
+    starts_with("éfoo", &quit_byte_pattern());
+}
@@ -0,0 +1,47 @@
+extern crate pattern_3;
+
+use pattern_3::{EitherStrand, Strand};
+use pattern_3::ext::*;
+
+#[test]
+fn test_either_strand() {
+    //            0    5    10   15   20
+    let genome = b"ACCGTTTACGGTNNAACCGTacggt";
+    let found = EitherStrand(b"ACCGT").match_ranges(genome).collect::<Vec<_>>();
+    assert_eq!(found, vec![
+        (0..5, Strand::Forward),
+        (7..12, Strand::Reverse),
+        (15..20, Strand::Forward),
+    ]);
+    assert_eq!(
+        EitherStrand(b"accgt").match_ranges(genome).collect::<Vec<_>>(),
+        vec![(20..25, Strand::Reverse)],
+    );
+    assert_eq!(find(&genome[1..], EitherStrand(b"ACCGT")), Some(6));
+    assert_eq!(rfind(&genome[..], EitherStrand(b"ACCGT")), Some(15));
+    assert_eq!(
+        rmatch_indices(&genome[..], EitherStrand(b"ACCGT")).map(|(i, _)| i).collect::<Vec<_>>(),
+        vec![15, 7, 0],
+    );
+    assert!(starts_with(&b"ACGGTAA"[..], EitherStrand(b"ACCGT")));
+}
+
+#[test]
+fn test_palindromic_site() {
+    // EcoRI site is its own reverse complement.
+    let found = EitherStrand(b"GAATTC").match_ranges(b"TTGAATTCAAGAATTC").collect::<Vec<_>>();
+    assert_eq!(found, vec![(2..8, Strand::Forward), (10..16, Strand::Forward)]);
+}
+
+#[test]
+fn test_either_strand_iupac_and_empty() {
+    assert_eq!(
+        EitherStrand(b"RAN").match_ranges(b"NTY").collect::<Vec<_>>(),
+        vec![(0..3, Strand::Reverse)],
+    );
+    assert_eq!(
+        EitherStrand(b"").match_ranges(b"AC").collect::<Vec<_>>(),
+        vec![(0..0, Strand::Forward), (1..1, Strand::Forward), (2..2, Strand::Forward)],
+    );
+    assert_eq!(split(b"AAxTTxA".to_vec(), EitherStrand(b"AA")).collect::<Vec<_>>(), vec![b"".to_vec(), b"x".to_vec(), b"xA".to_vec()]);
+}
@@ -0,0 +1,48 @@
+extern crate pattern_3;
+
+use pattern_3::Filtered;
+use pattern_3::ext::{ends_with, match_ranges, rmatch_ranges, split, starts_with};
+use std::ops::Range;
+
+fn followed_by_digit(hay: &str, range: Range<usize>) -> bool {
+    hay[range.end..].starts_with(|c: char| c.is_ascii_digit())
+}
+
+#[test]
+fn test_filtered() {
+    let hay = "v v1 vv2 v";
+    assert_eq!(
+        match_ranges(hay, Filtered('v', followed_by_digit)).map(|(r, _)| r).collect::<Vec<_>>(),
+        vec![2..3, 6..7],
+    );
+    assert_eq!(
+        rmatch_ranges(hay, Filtered('v', followed_by_digit)).map(|(r, _)| r).collect::<Vec<_>>(),
+        vec![6..7, 2..3],
+    );
+    assert_eq!(
+        split("a1b2c", Filtered("", |h: &str, r: Range<usize>| r.start % 2 == 0 && r.start > 0)).collect::<Vec<_>>(),
+        vec!["a1", "b2", "c"],
+    );
+}
+
+#[test]
+fn test_filtered_overlapping() {
+    // a rejected match does not hide an overlapping one
+    let odd_start = |_: &[u8], r: Range<usize>| r.start % 2 == 1;
+    assert_eq!(
+        match_ranges(&b"aaaa"[..], Filtered(&b"aa"[..], odd_start)).map(|(r, _)| r).collect::<Vec<_>>(),
+        vec![1..3],
+    );
+    assert_eq!(
+        rmatch_ranges(&b"aaaaa"[..], Filtered(&b"aa"[..], odd_start)).map(|(r, _)| r).collect::<Vec<_>>(),
+        vec![3..5, 1..3],
+    );
+}
+
+#[test]
+fn test_filtered_consume() {
+    assert!(starts_with("v1", Filtered('v', followed_by_digit)));
+    assert!(!starts_with("vx", Filtered('v', followed_by_digit)));
+    assert!(ends_with("xv", Filtered('v', |h: &str, r: Range<usize>| r.start == 1 && h.len() == 2)));
+    assert!(!ends_with("xv", Filtered('v', |_: &str, _: Range<usize>| false)));
+}
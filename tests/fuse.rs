@@ -0,0 +1,58 @@
+extern crate pattern_3;
+
+use pattern_3::*;
+use pattern_3::ext::*;
+
+// A searcher which forgets that it is exhausted, reporting a match at the
+// start of every other span.
+struct Flaky(bool);
+
+unsafe impl Searcher<str> for Flaky {
+    fn search(&mut self, span: Span<&str>) -> Option<std::ops::Range<usize>> {
+        let (_, range) = span.into_parts();
+        self.0 = !self.0;
+        if self.0 { Some(range.start..range.start) } else { None }
+    }
+
+    fn consume(&mut self, span: Span<&str>) -> Option<usize> {
+        Some(span.into_parts().1.start)
+    }
+}
+
+fn assert_fused<S: FusedSearcher<str>>(_: &S) {}
+
+#[test]
+fn test_fuse_searcher() {
+    let span = Span::from("abc");
+    let mut flaky = Flaky(false);
+    assert_eq!(flaky.search(span.clone()), Some(0..0));
+    assert_eq!(flaky.search(span.clone()), None);
+    assert_eq!(flaky.search(span.clone()), Some(0..0));
+
+    let mut fused = Fuse::new(Flaky(true));
+    assert_fused(&fused);
+    assert_eq!(fused.search(span.clone()), None);
+    assert_eq!(fused.search(span.clone()), None);
+    assert_eq!(fused.consume(span.clone()), Some(0));
+}
+
+#[test]
+fn test_fuse_directions() {
+    let mut fused = Pattern::<&str>::into_searcher(Fuse::new(""));
+    let empty = Span::from("");
+    assert_eq!(fused.search(empty.clone()), Some(0..0));
+    assert_eq!(fused.search(empty.clone()), None);
+    // each end is fused separately
+    assert_eq!(fused.rsearch(empty.clone()), Some(0..0));
+    assert_eq!(fused.rsearch(empty.clone()), None);
+    assert_eq!(fused.search(Span::from("xy")), None);
+}
+
+#[test]
+fn test_fuse_pattern() {
+    assert_eq!(split("a,b", Fuse::new(',')).collect::<Vec<_>>(), vec!["a", "b"]);
+    assert_eq!(rsplit("a,b", Fuse::new(',')).collect::<Vec<_>>(), vec!["b", "a"]);
+    assert_eq!(trim(",a,", Fuse::new(',')), "a");
+    assert_fused(&Pattern::<&str>::into_searcher('x'));
+    assert_fused(&Pattern::<&str>::into_searcher(|c: char| c == 'x'));
+}
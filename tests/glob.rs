@@ -0,0 +1,52 @@
+extern crate pattern_3;
+
+use pattern_3::Glob;
+use pattern_3::ext::*;
+
+#[test]
+fn test_glob_forward() {
+    let hay = "log-2023.txt log-2024.txt.gz log.txt";
+    let glob = Glob::new("log-*.txt");
+    assert_eq!(matches(hay, &glob).collect::<Vec<_>>(), vec!["log-2023.txt", "log-2024.txt"]);
+    assert_eq!(find_range(hay, &Glob::new("log-????")), Some(0..8));
+    assert_eq!(find_range(hay, &Glob::new("*.")), Some(0..9));
+    assert_eq!(find_range(hay, &Glob::new("q*")), None);
+    assert_eq!(find_range(hay, &Glob::new("t*q")), None);
+}
+
+#[test]
+fn test_glob_reverse() {
+    let hay = "a1b a22b a333b";
+    let glob = Glob::new("a*b");
+    assert_eq!(rmatches(hay, &glob).collect::<Vec<_>>(), vec!["a333b", "a22b", "a1b"]);
+    assert_eq!(rfind_range(hay, &Glob::new("a??b")), Some(4..8));
+    assert_eq!(rsplit(hay, &Glob::new(" ")).collect::<Vec<_>>(), vec!["a333b", "a22b", "a1b"]);
+}
+
+#[test]
+fn test_glob_unicode() {
+    let hay = "→x← →yy←";
+    assert_eq!(matches(hay, &Glob::new("→?←")).collect::<Vec<_>>(), vec!["→x←"]);
+    assert_eq!(matches(hay, &Glob::new("?x")).collect::<Vec<_>>(), vec!["→x"]);
+    assert_eq!(rmatches(hay, &Glob::new("y?")).collect::<Vec<_>>(), vec!["y←"]);
+    // `?` is a single byte on byte slices
+    assert_eq!(find_range(&"é!".as_bytes()[..], &Glob::new("??!")), Some(0..3));
+    assert_eq!(find_range("é!", &Glob::new("??!")), None);
+}
+
+#[test]
+fn test_glob_escape_and_empty() {
+    assert_eq!(find_range("a*b ab", &Glob::new(r"a\*b")), Some(0..3));
+    assert_eq!(find_range(r"a\", &Glob::new("a\\")), Some(0..2));
+    assert_eq!(matches("aé", &Glob::new("**")).count(), 3);
+    assert_eq!(matches("ab", &Glob::new("")).count(), 3);
+}
+
+#[test]
+fn test_glob_consume() {
+    let glob = Glob::new("<*>");
+    assert_eq!(trim_start("<a><b>c<d>", &glob), "c<d>");
+    assert_eq!(trim_end("<a><b>c<d>", &glob), "<a><b>c");
+    assert!(starts_with("[x]y", &Glob::new("[?]")));
+    assert!(ends_with("y[xx]", &Glob::new("[*]")));
+}
@@ -0,0 +1,35 @@
+#![cfg(feature = "unicode-segmentation")]
+
+extern crate pattern_3;
+
+use pattern_3::Graphemes;
+use pattern_3::ext::*;
+
+#[test]
+fn test_graphemes_no_partial_cluster_matches() {
+    // "e\u{301}" is a single cluster, so "e" alone must not match it.
+    let text = Graphemes::new("e\u{301}te\u{301} ete");
+    assert_eq!(match_indices(text, "e").map(|(i, _)| i).collect::<Vec<_>>(), vec![8, 10]);
+    assert_eq!(rfind(text, "e"), Some(10));
+    assert_eq!(find(text, "e\u{301}"), Some(0));
+    assert!(!starts_with(text, "e"));
+    assert!(ends_with(text, "te"));
+    assert!(!ends_with(Graphemes::new("te\u{301}"), "te"));
+}
+
+#[test]
+fn test_graphemes_split_trim() {
+    let text = Graphemes::new("👨‍👩‍👧,🇫🇷,x");
+    let pieces: Vec<&str> = split(text, ",").map(|g| g.as_str()).collect();
+    assert_eq!(pieces, vec!["👨‍👩‍👧", "🇫🇷", "x"]);
+
+    // Splitting on a cluster predicate yields whole clusters between them.
+    let clusters: Vec<&str> = match_ranges(text, |g: &str| g != ",").map(|(_, g)| g.as_str()).collect();
+    assert_eq!(clusters, vec!["👨‍👩‍👧", "🇫🇷", "x"]);
+
+    let padded = Graphemes::new("🇫🇷🇫🇷ab🇫🇷");
+    assert_eq!(trim(padded, |g: &str| g == "🇫🇷").as_str(), "ab");
+    // A lone regional indicator never matches half of a flag.
+    assert_eq!(find(padded, "\u{1f1f7}\u{1f1eb}"), None);
+    assert_eq!(match_indices(Graphemes::new("ab"), "").map(|(i, _)| i).collect::<Vec<_>>(), vec![0, 1, 2]);
+}
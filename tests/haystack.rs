@@ -0,0 +1,68 @@
+extern crate pattern_3;
+
+use pattern_3::Haystack;
+use pattern_3::ext::*;
+
+use std::ops::Range;
+use std::rc::Rc;
+
+// A substring of a shared `String`. It does not implement `Deref`; the hay is
+// only reachable through `Haystack::hay`.
+#[derive(Clone, Debug)]
+struct Sub {
+    buffer: Rc<String>,
+    range: Range<usize>,
+}
+
+impl Sub {
+    fn new(s: &str) -> Self {
+        Sub { buffer: Rc::new(s.to_owned()), range: 0..s.len() }
+    }
+}
+
+impl PartialEq<&'static str> for Sub {
+    fn eq(&self, other: &&'static str) -> bool {
+        self.hay() == *other
+    }
+}
+
+impl Haystack for Sub {
+    type Hay = str;
+
+    fn hay(&self) -> &str {
+        &self.buffer[self.range.clone()]
+    }
+
+    fn empty(&self) -> Self {
+        Sub { buffer: self.buffer.clone(), range: self.range.start..self.range.start }
+    }
+
+    unsafe fn split_around(self, range: Range<usize>) -> [Self; 3] {
+        let start = self.range.start;
+        [
+            Sub { buffer: self.buffer.clone(), range: start..(start + range.start) },
+            Sub { buffer: self.buffer.clone(), range: (start + range.start)..(start + range.end) },
+            Sub { buffer: self.buffer, range: (start + range.end)..self.range.end },
+        ]
+    }
+
+    unsafe fn slice_unchecked(self, range: Range<usize>) -> Self {
+        let start = self.range.start;
+        Sub { buffer: self.buffer, range: (start + range.start)..(start + range.end) }
+    }
+
+    fn restore_range(&self, range: Range<usize>, subrange: Range<usize>) -> Range<usize> {
+        (subrange.start + range.start)..(subrange.end + range.start)
+    }
+}
+
+#[test]
+fn test_haystack_without_deref() {
+    let parts = split(Sub::new("ab,cd,,e"), ',').collect::<Vec<_>>();
+    assert_eq!(parts, ["ab", "cd", "", "e"]);
+    assert!(parts.iter().all(|p| Rc::ptr_eq(&p.buffer, &parts[0].buffer)));
+    assert_eq!(parts[1].range, 3..5);
+
+    assert_eq!(trim(Sub::new("  hi "), ' '), "hi");
+    assert_eq!(match_ranges(Sub::new("xaxa"), 'a').map(|(r, _)| r).collect::<Vec<_>>(), [1..2, 3..4]);
+}
@@ -0,0 +1,24 @@
+extern crate pattern_3;
+
+use pattern_3::Lazy;
+
+#[test]
+fn test_lazy_needles() {
+    let digits = Lazy((0..30u8).map(|i| b'0' + i % 10));
+    assert_eq!(digits.find(b'7'), Some(7));
+    assert_eq!(digits.match_indices(&b"90"[..]).collect::<Vec<_>>(), vec![9, 19]);
+    assert_eq!(digits.match_indices(|b: &u8| *b == b'0').collect::<Vec<_>>(), vec![0, 10, 20]);
+    assert_eq!(digits.find(&b"99"[..]), None);
+    assert!(!digits.starts_with(b'1'));
+}
+
+#[test]
+fn test_lazy_non_overlapping_and_empty() {
+    let text = Lazy("aaaa".chars());
+    assert_eq!(text.match_indices(&['a', 'a'][..]).collect::<Vec<_>>(), vec![0, 2]);
+    assert_eq!(text.match_indices(&[][..]).collect::<Vec<_>>(), vec![0, 1, 2, 3, 4]);
+
+    let empty = Lazy("".chars());
+    assert_eq!(empty.match_indices(&[][..]).collect::<Vec<_>>(), vec![0]);
+    assert_eq!(empty.find('a'), None);
+}
@@ -0,0 +1,66 @@
+extern crate pattern_3;
+
+use pattern_3::{LineAnchor, LineAnchored};
+use pattern_3::ext::{ends_with, match_indices, rmatch_indices, split, starts_with};
+
+#[test]
+fn test_line_start() {
+    let log = "error: a\nnote: no error: here\nerror: b";
+    assert_eq!(
+        match_indices(log, LineAnchored::start("error:")).collect::<Vec<_>>(),
+        vec![(0, "error:"), (30, "error:")],
+    );
+    assert_eq!(
+        rmatch_indices(log, LineAnchored::start("error:")).collect::<Vec<_>>(),
+        vec![(30, "error:"), (0, "error:")],
+    );
+    assert_eq!(
+        split(log, LineAnchored::start("error: ")).collect::<Vec<_>>(),
+        vec!["", "a\nnote: no error: here\n", "b"],
+    );
+
+    let bytes = &b"xx\nx\nax"[..];
+    assert_eq!(
+        match_indices(bytes, LineAnchored::start(&b"x"[..])).map(|(i, _)| i).collect::<Vec<_>>(),
+        vec![0, 3],
+    );
+}
+
+#[test]
+fn test_line_end() {
+    let code = "x;\nx; y\ny;";
+    assert_eq!(
+        match_indices(code, LineAnchored::end(';')).collect::<Vec<_>>(),
+        vec![(1, ";"), (9, ";")],
+    );
+    assert_eq!(
+        rmatch_indices(code, LineAnchored::end(';')).collect::<Vec<_>>(),
+        vec![(9, ";"), (1, ";")],
+    );
+    assert_eq!(
+        match_indices("aa\naaa", LineAnchored { pattern: "aa", anchor: LineAnchor::End })
+            .collect::<Vec<_>>(),
+        vec![(0, "aa"), (4, "aa")],
+    );
+}
+
+#[test]
+fn test_line_anchored_consume() {
+    assert!(starts_with("error: x", LineAnchored::start("error")));
+    assert!(!starts_with("errors", LineAnchored::end("error")));
+    assert!(starts_with("error\nx", LineAnchored::end("error")));
+    assert!(ends_with("a\nerror", LineAnchored::start("error")));
+    assert!(!ends_with("an error", LineAnchored::start("error")));
+}
+
+#[test]
+fn test_line_anchored_empty() {
+    assert_eq!(
+        match_indices("ab\n\nc", LineAnchored::start("")).map(|(i, _)| i).collect::<Vec<_>>(),
+        vec![0, 3, 4],
+    );
+    assert_eq!(
+        match_indices("ab\n\nc", LineAnchored::end("")).map(|(i, _)| i).collect::<Vec<_>>(),
+        vec![2, 3, 5],
+    );
+}
@@ -0,0 +1,40 @@
+extern crate pattern_3;
+
+use pattern_3::LineTerminator;
+use pattern_3::ext::*;
+
+#[test]
+fn test_line_terminator_lines() {
+    let cases = [
+        "", "\n", "\r\n", "a", "a\n", "a\r\n", "a\r", "\ra\r\r\nb", "a\n\nb\r\n\r\nc",
+        "a\r\nb\r\n\n", "\n\r", "ü\r\nß\n",
+    ];
+    for text in cases.iter() {
+        let expected = text.lines().collect::<Vec<_>>();
+        assert_eq!(split_terminator(*text, LineTerminator).collect::<Vec<_>>(), expected, "{:?}", text);
+        let mut rev = rsplit_terminator(*text, LineTerminator).collect::<Vec<_>>();
+        rev.reverse();
+        assert_eq!(rev, expected, "{:?}", text);
+    }
+}
+
+#[test]
+fn test_line_terminator() {
+    let text = "a\r\nb\n\r\nc\r";
+    assert_eq!(
+        match_ranges(text, LineTerminator).map(|(r, _)| r).collect::<Vec<_>>(),
+        vec![1..3, 4..5, 5..7],
+    );
+    assert_eq!(
+        rmatch_ranges(text, LineTerminator).map(|(r, _)| r).collect::<Vec<_>>(),
+        vec![5..7, 4..5, 1..3],
+    );
+    assert_eq!(trim_end("line\r\n\n", LineTerminator), "line");
+    assert_eq!(trim_end("line\r\r", LineTerminator), "line\r\r");
+    assert_eq!(trim_start("\r\n\nline", LineTerminator), "line");
+    assert!(ends_with(&b"x\r\n"[..], LineTerminator));
+    assert_eq!(
+        split(&b"a\r\nb\r"[..], LineTerminator).collect::<Vec<_>>(),
+        vec![&b"a"[..], b"b\r"],
+    );
+}
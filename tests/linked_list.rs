@@ -430,7 +430,13 @@ impl<T> Hay for Slice<T> {
 }
 
 impl<T> Haystack for DList<T> {
-    fn empty() -> Self {
+    type Hay = Slice<T>;
+
+    fn hay(&self) -> &Slice<T> {
+        self
+    }
+
+    fn empty(&self) -> Self {
         DList::new()
     }
 
@@ -0,0 +1,40 @@
+extern crate pattern_3;
+
+use pattern_3::{FollowedBy, PrecededBy};
+use pattern_3::ext::{ends_with, match_indices, rmatch_indices, split, starts_with};
+
+#[test]
+fn test_followed_by() {
+    let prices = "5 USD, 7 EUR, 9 USD";
+    let usd = FollowedBy(|c: char| c.is_ascii_digit(), " USD");
+    assert_eq!(match_indices(prices, usd).collect::<Vec<_>>(), vec![(0, "5"), (14, "9")]);
+    assert_eq!(rmatch_indices(prices, usd).collect::<Vec<_>>(), vec![(14, "9"), (0, "5")]);
+    // the assertion may overlap the next match
+    assert_eq!(match_indices("aaa", FollowedBy('a', 'a')).collect::<Vec<_>>(), vec![(0, "a"), (1, "a")]);
+    assert_eq!(match_indices("ab", FollowedBy('b', "")).collect::<Vec<_>>(), vec![(1, "b")]);
+}
+
+#[test]
+fn test_preceded_by() {
+    let src = "$1 2 $3";
+    let dollars = PrecededBy(|c: char| c.is_ascii_digit(), '$');
+    assert_eq!(match_indices(src, dollars).collect::<Vec<_>>(), vec![(1, "1"), (6, "3")]);
+    assert_eq!(rmatch_indices(src, dollars).collect::<Vec<_>>(), vec![(6, "3"), (1, "1")]);
+    assert_eq!(split(src, PrecededBy(' ', '2')).collect::<Vec<_>>(), vec!["$1 2", "$3"]);
+
+    let bytes = &[0, 1, 2, 1, 3, 1][..];
+    assert_eq!(
+        match_indices(bytes, PrecededBy(&[1][..], &[0][..])).map(|(i, _)| i).collect::<Vec<_>>(),
+        vec![1],
+    );
+}
+
+#[test]
+fn test_lookaround_consume() {
+    assert!(starts_with("ab", FollowedBy('a', 'b')));
+    assert!(!starts_with("ac", FollowedBy('a', 'b')));
+    assert!(ends_with("ab", PrecededBy('b', 'a')));
+    assert!(!ends_with("cb", PrecededBy('b', 'a')));
+    // the assertion is checked against the whole hay, not only the consumed part
+    assert!(!starts_with("a", FollowedBy('a', 'b')));
+}
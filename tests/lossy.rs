@@ -0,0 +1,51 @@
+extern crate pattern_3;
+
+use pattern_3::Lossy;
+use pattern_3::ext::*;
+
+#[test]
+fn test_lossy_char() {
+    let haystack = &b"a\xffb\xe2\x98c\xe2\x98\x83"[..];
+    assert_eq!(match_ranges(haystack, Lossy('\u{fffd}')).collect::<Vec<_>>(), vec![
+        (1..2, &b"\xff"[..]),
+        (3..5, &b"\xe2\x98"[..]),
+    ]);
+    assert_eq!(find(haystack, Lossy('☃')), Some(6));
+    assert_eq!(rfind(haystack, Lossy('\u{fffd}')), Some(3));
+    assert_eq!(
+        rmatch_indices(haystack, Lossy(|c: char| c.is_ascii())).collect::<Vec<_>>(),
+        vec![(5, &b"c"[..]), (2, &b"b"[..]), (0, &b"a"[..])]
+    );
+}
+
+#[test]
+fn test_lossy_maximal_subparts() {
+    // Each byte which cannot start a valid sequence is its own replacement.
+    let haystack = &b"\xf0\x80\x80\xe2\x98\x83\x83"[..];
+    let forward = match_ranges(haystack, Lossy(|_| true)).map(|(r, _)| r).collect::<Vec<_>>();
+    let mut backward = rmatch_ranges(haystack, Lossy(|_| true)).map(|(r, _)| r).collect::<Vec<_>>();
+    backward.reverse();
+    assert_eq!(forward, vec![0..1, 1..2, 2..3, 3..6, 6..7]);
+    assert_eq!(forward, backward);
+}
+
+#[test]
+fn test_lossy_trim_split() {
+    let haystack = &b" \t\xffx \n"[..];
+    assert_eq!(trim(haystack, Lossy(char::is_whitespace)), &b"\xffx"[..]);
+    let chars: &[char] = &[' ', '\t', '\u{fffd}'];
+    assert_eq!(trim_start(haystack, Lossy(chars)), &b"x \n"[..]);
+    assert_eq!(
+        split(&b"a\xc3\xa9b\xffc"[..], Lossy(|c: char| !c.is_ascii())).collect::<Vec<_>>(),
+        vec![&b"a"[..], &b"b"[..], &b"c"[..]]
+    );
+}
+
+#[test]
+fn test_lossy_mut() {
+    let mut buffer = b"one\xfftwo\xfethree".to_vec();
+    for piece in split(&mut buffer[..], Lossy('\u{fffd}')) {
+        piece[0].make_ascii_uppercase();
+    }
+    assert_eq!(buffer, b"One\xffTwo\xfeThree");
+}
@@ -0,0 +1,53 @@
+extern crate pattern_3;
+
+use pattern_3::ext::*;
+
+#[test]
+fn test_split_mut_slice() {
+    let mut v = vec![1, 2, 0, 3, 0, 0, 4, 5];
+    for piece in split_mut(&mut v[..], |x: &i32| *x == 0) {
+        piece.reverse();
+    }
+    assert_eq!(v, vec![2, 1, 0, 3, 0, 0, 5, 4]);
+
+    // the pieces are disjoint borrows which can be held at the same time
+    let mut pieces: Vec<&mut [i32]> = split_mut(&mut v[..], &[0, 0][..]).collect();
+    let (left, right) = pieces.split_at_mut(1);
+    std::mem::swap(&mut left[0][0], &mut right[0][1]);
+    assert_eq!(v, vec![4, 1, 0, 3, 0, 0, 5, 2]);
+}
+
+#[test]
+fn test_split_terminator_mut_slice() {
+    let mut v = vec![1, 2, 0, 3, 0];
+    let pieces: Vec<&mut [i32]> = split_terminator_mut(&mut v[..], |x: &i32| *x == 0).collect();
+    assert_eq!(pieces, vec![&mut [1, 2][..], &mut [3][..]]);
+    for piece in split_terminator_mut(&mut v[..], |x: &i32| *x == 0) {
+        piece[0] *= 10;
+    }
+    assert_eq!(v, vec![10, 2, 0, 30, 0]);
+}
+
+#[test]
+fn test_splitn_mut_slice() {
+    let mut v = b"key=value=more".to_vec();
+    {
+        let mut pieces = splitn_mut(&mut v[..], 2, &b"="[..]);
+        pieces.next().unwrap().make_ascii_uppercase();
+        assert_eq!(&*pieces.next().unwrap(), b"value=more");
+        assert_eq!(pieces.next(), None);
+    }
+    assert_eq!(v, b"KEY=value=more");
+}
+
+#[test]
+fn test_trim_mut_slice() {
+    let mut v = vec![0, 0, 1, 2, 0, 3, 0];
+    trim_mut(&mut v[..], |x: &i32| *x == 0).iter_mut().for_each(|x| *x += 1);
+    assert_eq!(v, vec![0, 0, 2, 3, 1, 4, 0]);
+    trim_start_mut(&mut v[..], |x: &i32| *x == 0)[0] = 7;
+    trim_end_mut(&mut v[..], |x: &i32| *x == 0)[4] = 9;
+    assert_eq!(v, vec![0, 0, 7, 3, 9, 4, 0]);
+    assert_eq!(trim_end_mut(&mut v[..], &[4, 0][..]), &mut [0, 0, 7, 3, 9][..]);
+    assert!(trim_mut(&mut [0, 0][..], |x: &i32| *x == 0).is_empty());
+}
@@ -0,0 +1,49 @@
+extern crate pattern_3;
+
+use pattern_3::Narrow;
+use pattern_3::ext::*;
+use std::mem::size_of;
+use std::ops::Range;
+
+#[test]
+fn test_narrow_str() {
+    let hay = Narrow::<str>::new("αβ,γ,,δ").unwrap();
+    assert_eq!(find(hay, ','), Some(4u32));
+    assert_eq!(rfind(hay, "δ"), Some(9));
+    assert_eq!(find(hay, |c: char| c == 'γ'), Some(5));
+    assert_eq!(find(hay, &['γ', 'δ'][..]), Some(5));
+    assert_eq!(
+        split(hay, ',').map(|s| s.as_inner()).collect::<Vec<_>>(),
+        vec!["αβ", "γ", "", "δ"],
+    );
+    assert_eq!(
+        match_ranges(hay, ",").map(|(r, _)| r).collect::<Vec<Range<u32>>>(),
+        vec![4..5, 7..8, 8..9],
+    );
+    assert_eq!(trim(hay, 'α').as_inner(), "β,γ,,δ");
+    // the empty pattern still stops at character boundaries only
+    assert_eq!(matches(hay, "").count(), 8);
+}
+
+#[test]
+fn test_narrow_slice() {
+    let hay = Narrow::<[i32], u16>::new(&[1, 2, 3, 1, 2, 3, 4][..]).unwrap();
+    assert_eq!(find(hay, &[2, 3][..]), Some(1u16));
+    assert_eq!(rfind(hay, &[2, 3][..]), Some(4));
+    assert_eq!(find(hay, |x: &i32| *x > 3), Some(6));
+    assert_eq!(
+        match_indices(hay, &[1][..]).map(|(i, _)| i).collect::<Vec<u16>>(),
+        vec![0, 3],
+    );
+}
+
+#[test]
+fn test_narrow_too_long() {
+    assert!(Narrow::<[u8], u8>::new(&[0; 255][..]).is_some());
+    assert!(Narrow::<[u8], u8>::new(&[0; 256][..]).is_none());
+}
+
+#[test]
+fn test_narrow_range_size() {
+    assert_eq!(size_of::<Range<u32>>() * 2, size_of::<Range<usize>>());
+}
@@ -0,0 +1,48 @@
+#![cfg(feature = "nom")]
+
+extern crate nom;
+extern crate pattern_3;
+
+use nom::IResult;
+use nom::bytes::complete::tag;
+use nom::character::complete::{digit0, digit1, space0};
+use nom::combinator::value;
+use nom::sequence::tuple;
+use pattern_3::NomPattern;
+use pattern_3::ext::*;
+
+fn comma(i: &str) -> IResult<&str, (), ()> {
+    value((), tuple((space0, tag(","), space0)))(i)
+}
+
+#[test]
+fn test_nom_starts_with() {
+    assert!(starts_with("123abc", NomPattern::new(|i| value((), digit1::<_, ()>)(i))));
+    assert!(!starts_with("abc123", NomPattern::new(|i| value((), digit1::<_, ()>)(i))));
+}
+
+#[test]
+fn test_nom_trim_start() {
+    assert_eq!(trim_start(" ,  , x, y", NomPattern::new(comma)), "x, y");
+    assert_eq!(trim_start("x, y", NomPattern::new(comma)), "x, y");
+}
+
+#[test]
+fn test_nom_split() {
+    assert_eq!(
+        split("a , b,c  ,d", NomPattern::new(comma)).collect::<Vec<_>>(),
+        vec!["a", "b", "c", "d"]
+    );
+    assert_eq!(
+        match_ranges("x1yy23z", NomPattern::new(|i| value((), digit1::<_, ()>)(i))).collect::<Vec<_>>(),
+        vec![(1..2, "1"), (4..6, "23")]
+    );
+}
+
+#[test]
+fn test_nom_empty_matches() {
+    assert_eq!(
+        match_ranges("a1b", NomPattern::new(|i| value((), digit0::<_, ()>)(i))).collect::<Vec<_>>(),
+        vec![(0..0, ""), (1..2, "1"), (3..3, "")]
+    );
+}
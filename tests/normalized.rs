@@ -0,0 +1,101 @@
+extern crate pattern_3;
+
+use pattern_3::NormalizedView;
+use pattern_3::ext::case_fold;
+
+fn lowercase<'h>(hay: &'h str) -> NormalizedView<'h> {
+    NormalizedView::new(hay, |c: char| c.to_lowercase())
+}
+
+#[test]
+fn test_case_insensitive_ranges() {
+    let view = lowercase("Hello HELLO hello");
+    assert_eq!(
+        view.match_ranges("hello").collect::<Vec<_>>(),
+        vec![(0..5, "Hello"), (6..11, "HELLO"), (12..17, "hello")]
+    );
+    assert_eq!(
+        view.rmatch_ranges('l').map(|(r, _)| r.start).collect::<Vec<_>>(),
+        vec![15, 14, 9, 8, 3, 2]
+    );
+    assert_eq!(view.find_range("lo h"), Some(3..7));
+    assert_eq!(view.rfind_range("lo h"), Some(9..13));
+}
+
+#[test]
+fn test_expanding_mapping() {
+    // U+0130 lowercases to two characters (3 bytes) from a 2-byte original.
+    let view = lowercase("x\u{130}y");
+    assert_eq!(view.normalized(), "xi\u{307}y");
+    assert_eq!(view.find_range("i"), Some(1..3));
+    assert_eq!(view.find_range("\u{307}y"), Some(1..4));
+    assert_eq!(view.find_range("y"), Some(3..4));
+}
+
+#[test]
+fn test_removing_mapping() {
+    let view = NormalizedView::new("a-b--c", |c: char| Some(c).filter(|&c| c != '-'));
+    assert_eq!(view.normalized(), "abc");
+    assert_eq!(view.find_range("bc"), Some(2..6));
+    assert_eq!(view.match_ranges("").map(|(r, _)| r).collect::<Vec<_>>(), vec![0..0, 2..2, 5..5, 6..6]);
+    assert_eq!(view.original_range(3..3), 6..6);
+}
+
+#[test]
+fn test_empty() {
+    let view = lowercase("");
+    assert_eq!(view.find_range(""), Some(0..0));
+    assert_eq!(view.find_range("a"), None);
+    assert_eq!(view.original(), "");
+}
+
+#[cfg(feature = "unicode")]
+#[test]
+fn test_without_diacritics() {
+    let view = NormalizedView::without_diacritics("Crème brûlée, cre\u{300}me bru\u{302}le\u{301}e\u{301}");
+    assert_eq!(view.normalized(), "Creme brulee, creme brulee");
+    assert_eq!(
+        view.match_ranges("creme").collect::<Vec<_>>(),
+        vec![(17..24, "cre\u{300}me")]
+    );
+    assert_eq!(
+        view.match_ranges("brulee").collect::<Vec<_>>(),
+        vec![(7..15, "brûlée"), (25..37, "bru\u{302}le\u{301}e\u{301}")]
+    );
+    assert_eq!(view.find_range("e b"), Some(5..8));
+}
+
+#[cfg(feature = "unicode")]
+#[test]
+fn test_without_diacritics_keeps_other_text() {
+    let view = NormalizedView::without_diacritics("\u{301}한국어 ß");
+    assert_eq!(view.normalized(), "한국어 ß");
+    assert_eq!(view.find_range("국"), Some(5..8));
+    // positions are attributed to the next character kept in the view.
+    assert_eq!(view.find_range(""), Some(2..2));
+}
+
+#[test]
+fn test_case_folded_patterns() {
+    let view = NormalizedView::case_folded("Hello WORLD, hello World");
+    assert_eq!(view.find_range("world"), Some(6..11));
+    assert_eq!(view.rfind_range("hello"), Some(13..18));
+    assert_eq!(view.find_range('w'), Some(6..7));
+    assert_eq!(
+        view.match_ranges(|c: char| c == 'o').map(|(r, _)| r.start).collect::<Vec<_>>(),
+        vec![4, 7, 17, 20]
+    );
+    assert_eq!(
+        view.rmatch_ranges("world").map(|(_, s)| s).collect::<Vec<_>>(),
+        vec!["World", "WORLD"]
+    );
+}
+
+#[test]
+fn test_case_folded_expansion() {
+    assert_eq!(case_fold("Maße ΣΑΣ"), "masse σασ");
+    let view = NormalizedView::case_folded("Maße");
+    // A match covering half of the folded `ß` covers all of it.
+    assert_eq!(view.find_range("mas"), Some(0..4));
+    assert_eq!(view.find_range("sse"), Some(2..5));
+}
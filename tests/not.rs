@@ -0,0 +1,23 @@
+extern crate pattern_3;
+
+use pattern_3::pattern::not;
+use pattern_3::ext::*;
+
+#[test]
+fn test_not_char_predicate() {
+    assert_eq!(trim_start("  ++abc1--", not(char::is_alphanumeric)), "abc1--");
+    assert_eq!(trim(" ,abc1--", not(char::is_alphanumeric)), "abc1");
+    assert_eq!(find("ééx", not(|c: char| c == 'é')), Some(4));
+    assert_eq!(rfind("xéé", not(|c: char| c == 'é')), Some(0));
+    assert_eq!(split("a1b22c", not(char::is_alphabetic)).collect::<Vec<_>>(), vec!["a", "b", "", "c"]);
+}
+
+#[test]
+fn test_not_elem_predicate() {
+    let v = [0, 0, 1, 2, 0];
+    assert_eq!(trim_start(&v[..], not(|x: &i32| *x != 0)), &[1, 2, 0][..]);
+    assert_eq!(
+        match_indices(&v[..], not(|x: &i32| *x == 0)).map(|(i, _)| i).collect::<Vec<_>>(),
+        vec![2, 3],
+    );
+}
@@ -0,0 +1,113 @@
+extern crate pattern_3;
+
+use pattern_3::ext::*;
+
+use std::ffi::OsStr;
+
+#[test]
+fn test_os_str_match_ranges() {
+    let haystack = OsStr::new("foo.tar.gz");
+    let needle = OsStr::new(".");
+    assert_eq!(match_ranges(haystack, needle).collect::<Vec<_>>(), vec![
+        (3..4, needle),
+        (7..8, needle),
+    ]);
+    assert_eq!(rfind(haystack, needle), Some(7));
+    assert_eq!(
+        split(haystack, needle).collect::<Vec<_>>(),
+        vec![OsStr::new("foo"), OsStr::new("tar"), OsStr::new("gz")]
+    );
+}
+
+#[test]
+fn test_os_str_empty_needle() {
+    let haystack = OsStr::new("a\u{e9}");
+    let needle = OsStr::new("");
+    assert_eq!(match_indices(haystack, needle).collect::<Vec<_>>(), vec![
+        (0, needle),
+        (1, needle),
+        (3, needle),
+    ]);
+}
+
+#[test]
+fn test_os_str_trim() {
+    let haystack = OsStr::new("--x--");
+    let needle = OsStr::new("-");
+    assert_eq!(trim_start(haystack, needle), OsStr::new("x--"));
+    assert_eq!(trim_end(haystack, needle), OsStr::new("--x"));
+    assert!(starts_with(haystack, OsStr::new("--x")));
+    assert!(ends_with(haystack, OsStr::new("x--")));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_os_str_reject_interior_match() {
+    use std::os::unix::ffi::OsStrExt;
+
+    // the `\xa9` in the middle of `é` must not be found.
+    let haystack = OsStr::from_bytes(b"\xc3\xa9 \xa9");
+    let needle = OsStr::from_bytes(b"\xa9");
+    assert_eq!(match_indices(haystack, needle).collect::<Vec<_>>(), vec![
+        (3, needle),
+    ]);
+    assert_eq!(rmatch_indices(haystack, needle).collect::<Vec<_>>(), vec![
+        (3, needle),
+    ]);
+    assert!(!starts_with(OsStr::from_bytes(b"\xc3\xa9"), OsStr::from_bytes(b"\xc3")));
+    assert!(!ends_with(OsStr::from_bytes(b"\xc3\xa9"), needle));
+}
+
+#[test]
+fn test_os_str_str_and_char() {
+    let haystack = OsStr::new("/tmp/a.tmp/b\u{e9}.tmp");
+    assert_eq!(find(haystack, ".tmp"), Some(6));
+    assert_eq!(rfind(haystack, ".tmp"), Some(14));
+    assert_eq!(
+        split(haystack, '/').collect::<Vec<_>>(),
+        vec![OsStr::new(""), OsStr::new("tmp"), OsStr::new("a.tmp"), OsStr::new("b\u{e9}.tmp")]
+    );
+    assert_eq!(rfind(haystack, '\u{e9}'), Some(12));
+    assert_eq!(trim_end(haystack, ".tmp"), OsStr::new("/tmp/a.tmp/b\u{e9}"));
+    assert_eq!(trim(OsStr::new("//x//"), '/'), OsStr::new("x"));
+    assert!(ends_with(haystack, "\u{e9}.tmp"));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_os_str_char_reject_interior_match() {
+    use std::os::unix::ffi::OsStrExt;
+
+    // `\xa9\xa9` is two stray continuation bytes, but the first one belongs to
+    // `é` and must not be taken as the start of a match.
+    let haystack = OsStr::from_bytes(b"\xc3\xa9\xa9 \xc3\xa9");
+    assert_eq!(find(haystack, '\u{e9}'), Some(0));
+    assert_eq!(rfind(haystack, '\u{e9}'), Some(4));
+    assert_eq!(matches(haystack, "\u{e9}").count(), 2);
+    assert_eq!(find(OsStr::from_bytes(b"\xe2\xc3\xa9"), "\u{e9}"), Some(1));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_os_str_slice_around_surrogates() {
+    use std::os::unix::ffi::OsStrExt;
+
+    // `\xed\xa0\x80\xed\xb0\x80` is a WTF-8 surrogate pair, which may only be
+    // split next to valid UTF-8, so it is sliced as a whole.
+    let haystack = OsStr::from_bytes(b"a/\xed\xa0\x80\xed\xb0\x80/b\xed\xa0\x80");
+    assert_eq!(split(haystack, '/').collect::<Vec<_>>(), vec![
+        OsStr::new("a"),
+        OsStr::from_bytes(b"\xed\xa0\x80\xed\xb0\x80"),
+        OsStr::from_bytes(b"b\xed\xa0\x80"),
+    ]);
+    let needle = OsStr::from_bytes(b"\xed\xa0\x80");
+    assert_eq!(match_indices(haystack, needle).collect::<Vec<_>>(), vec![
+        (10, needle),
+    ]);
+    assert_eq!(trim_end(haystack, needle), OsStr::from_bytes(b"a/\xed\xa0\x80\xed\xb0\x80/b"));
+    assert!(!starts_with(OsStr::from_bytes(b"\xed\xa0\x80\xed\xb0\x80"), needle));
+    assert_eq!(
+        match_indices(haystack, OsStr::new("")).map(|(i, _)| i).collect::<Vec<_>>(),
+        vec![0, 1, 2, 8, 9, 10, 13]
+    );
+}
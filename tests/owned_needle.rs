@@ -0,0 +1,84 @@
+extern crate pattern_3;
+
+use pattern_3::{ByteNeedle, StrNeedle};
+use pattern_3::ext::*;
+
+#[test]
+fn test_str_needle() {
+    let needle = StrNeedle::new("foo");
+    assert_eq!(needle.as_str(), "foo");
+    assert_eq!(find("bar foo foo", needle.clone()), Some(4));
+    assert_eq!(rfind("bar foo foo", needle.clone()), Some(8));
+    assert_eq!(
+        match_indices("foofoo foo", needle.clone()).map(|(i, _)| i).collect::<Vec<_>>(),
+        vec![0, 3, 7],
+    );
+    assert_eq!(split("a, b, c", StrNeedle::new(", ")).collect::<Vec<_>>(), vec!["a", "b", "c"]);
+    assert!(starts_with("foobar", needle.clone()));
+    assert!(!ends_with("foobar", needle.clone()));
+    assert_eq!(trim_start("foofoobar", needle.clone()), "bar");
+    assert_eq!(trim_end("barfoofoo", needle), "bar");
+    assert_eq!(match_indices("ab", StrNeedle::new("")).map(|(i, _)| i).collect::<Vec<_>>(), vec![0, 1, 2]);
+}
+
+#[test]
+fn test_byte_needle() {
+    let needle = ByteNeedle::new(b"\r\n");
+    assert_eq!(needle.as_bytes(), b"\r\n");
+    assert_eq!(find(&b"GET /\r\n"[..], needle.clone()), Some(5));
+    assert_eq!(
+        split(b"a\r\nb".to_vec(), needle).collect::<Vec<_>>(),
+        vec![b"a".to_vec(), b"b".to_vec()],
+    );
+}
+
+#[test]
+fn test_long_needle() {
+    // longer than the inline capacity.
+    let word = "abcdefghijklmnopqrstuvwxyz0123456789";
+    let hay = format!("{0}-{0}-{0}", word);
+    let needle = StrNeedle::new(word);
+    assert_eq!(needle.as_str(), word);
+    assert_eq!(
+        match_indices(&*hay, needle.clone()).map(|(i, _)| i).collect::<Vec<_>>(),
+        vec![0, 37, 74],
+    );
+    assert_eq!(
+        rmatch_indices(&*hay, needle).map(|(i, _)| i).collect::<Vec<_>>(),
+        vec![74, 37, 0],
+    );
+}
+
+#[test]
+fn test_needle_matches_borrowed() {
+    let hay = "aabaabaaabaabaabaaab";
+    for len in 1..8 {
+        for start in 0..(hay.len() - len) {
+            let needle = &hay[start..(start + len)];
+            assert_eq!(
+                match_indices(hay, StrNeedle::new(needle)).collect::<Vec<_>>(),
+                match_indices(hay, needle).collect::<Vec<_>>(),
+            );
+            assert_eq!(
+                rmatch_indices(hay, StrNeedle::new(needle)).collect::<Vec<_>>(),
+                rmatch_indices(hay, needle).collect::<Vec<_>>(),
+            );
+        }
+    }
+}
+
+#[test]
+fn test_lit() {
+    use pattern_3::Lit;
+    use std::borrow::Cow;
+    use std::rc::Rc;
+    use std::sync::Arc;
+
+    assert_eq!(find("a=b", Lit(String::from("="))), Some(1));
+    assert_eq!(rfind("a=b=c", Lit(Rc::<str>::from("="))), Some(3));
+    assert_eq!(split("a--b--c", Lit(Cow::Borrowed("--"))).collect::<Vec<_>>(), vec!["a", "b", "c"]);
+    assert_eq!(trim_start("xxaxx", Lit(Arc::<str>::from("x"))), "axx");
+    assert_eq!(find(&b"a\r\nb"[..], Lit(b"\r\n".to_vec())), Some(1));
+    assert_eq!(find(&b"a\r\nb"[..], Lit(String::from("\n"))), Some(2));
+    assert_eq!(split(b"a,b".to_vec(), Lit(*b",")).collect::<Vec<_>>(), vec![b"a".to_vec(), b"b".to_vec()]);
+}
@@ -61,4 +61,12 @@ fn test_negative_zero() {
     ]);
 }
 
+#[test]
+fn test_eq_magic() {
+    let magic = &[0x7f, b'E', b'L', b'F'][..];
+    assert!(eq(&b"\x7fELF"[..], magic));
+    assert!(!eq(&b"\x7fELF\x02"[..], magic));
+    assert!(!eq(&[0.0, NAN][..], &[0.0, NAN][..]));
+}
+
 fn main() {}
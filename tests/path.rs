@@ -0,0 +1,54 @@
+extern crate pattern_3;
+
+use pattern_3::ext::*;
+
+use std::path::{Component, Path};
+
+#[cfg(unix)]
+#[test]
+fn test_path_split_component() {
+    let path = Path::new("/usr//lib/./rustlib");
+    assert_eq!(
+        split(path, Component::RootDir).collect::<Vec<_>>(),
+        vec![Path::new(""), Path::new("usr//lib/./rustlib")]
+    );
+    assert_eq!(
+        match_ranges(path, Component::Normal("lib".as_ref())).map(|(r, _)| r).collect::<Vec<_>>(),
+        vec![6..10]
+    );
+    // components match whole, never a part of a name
+    assert_eq!(find(path, Component::Normal("rust".as_ref())), None);
+    assert_eq!(rfind(path, Component::Normal("rustlib".as_ref())), Some(12));
+    // a `.` in the middle is not a component
+    assert_eq!(find(path, Component::CurDir), Some(10));
+    assert_eq!(find(Path::new("./a"), Component::CurDir), Some(0));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_path_prefix_suffix() {
+    let path = Path::new("/usr//lib/./rustlib/src");
+    assert!(starts_with(path, Path::new("/usr/lib")));
+    assert!(starts_with(path, Path::new("/usr/lib/rustlib")));
+    assert!(!starts_with(path, Path::new("/usr/li")));
+    assert!(!starts_with(path, Path::new("usr")));
+    assert!(ends_with(path, Path::new("rustlib/src")));
+    assert!(!ends_with(path, Path::new("lib/src")));
+    assert_eq!(trim_start(path, Path::new("/usr")), Path::new("lib/./rustlib/src"));
+    assert_eq!(trim_end(path, Path::new("src")), Path::new("/usr//lib/./rustlib/"));
+    assert_eq!(find(path, Path::new("lib/rustlib")), Some(6));
+    assert_eq!(find(path, Path::new("")), Some(0));
+}
+
+#[cfg(unix)]
+#[test]
+fn test_path_repeated() {
+    let path = Path::new("a/a/a/b");
+    let needle = Path::new("a/a");
+    assert_eq!(match_indices(path, needle).map(|(i, _)| i).collect::<Vec<_>>(), vec![0]);
+    assert_eq!(rmatch_indices(path, needle).map(|(i, _)| i).collect::<Vec<_>>(), vec![2]);
+    assert_eq!(
+        rsplit(path, Component::Normal("a".as_ref())).collect::<Vec<_>>(),
+        vec![Path::new("b"), Path::new(""), Path::new(""), Path::new("")]
+    );
+}
@@ -0,0 +1,53 @@
+#![cfg(feature = "phonetic")]
+
+extern crate pattern_3;
+
+use pattern_3::Soundex;
+use pattern_3::ext::*;
+
+#[test]
+fn test_soundex_codes() {
+    // pairs sharing a code, from the classic examples
+    for &(a, b) in &[
+        ("Robert", "Rupert"),
+        ("Ashcraft", "Ashcroft"),
+        ("Tymczak", "Tymczuk"),
+        ("Pfister", "Pfyster"),
+        ("Lee", "Li"),
+        ("Smith", "Smyth"),
+    ] {
+        assert!(eq(a, Soundex(b)), "{} ~ {}", a, b);
+    }
+    assert!(!eq("Robert", Soundex("Rubin")));
+    assert!(!eq("Robert", Soundex("Rob")));
+    assert!(eq("Roberts", Soundex("Robert")));
+    // words without ASCII letters never match
+    assert!(!eq("日本", Soundex("日本")));
+    assert!(!eq("", Soundex("")));
+}
+
+#[test]
+fn test_soundex_search() {
+    let names = "Smith, Smyth-Jones; Schmidt and smith";
+    assert_eq!(
+        match_ranges(names, Soundex("Smith")).map(|(r, _)| r).collect::<Vec<_>>(),
+        vec![0..5, 7..12, 20..27, 32..37],
+    );
+    assert_eq!(
+        rmatch_ranges(names, Soundex("Smith")).map(|(r, _)| r).collect::<Vec<_>>(),
+        vec![32..37, 20..27, 7..12, 0..5],
+    );
+    assert_eq!(split("Jon Joan John Jane", Soundex("Jon")).count(), 5);
+    assert_eq!(find("Müller", Soundex("Mller")), Some(0));
+}
+
+#[test]
+fn test_soundex_whole_words() {
+    // only whole words are matched
+    assert_eq!(find("xRobert Robert", Soundex("Robert")), Some(8));
+    assert_eq!(rfind("Robert Rob", Soundex("Robert")), Some(0));
+    assert!(starts_with("Rupert is here", Soundex("Robert")));
+    assert!(!starts_with("Rubin is here", Soundex("Robert")));
+    assert!(ends_with("it is Rupert", Soundex("Robert")));
+    assert_eq!(trim_start("Rob Rob Bob", Soundex("Rob")), " Rob Bob");
+}
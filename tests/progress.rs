@@ -0,0 +1,90 @@
+extern crate pattern_3;
+
+use pattern_3::Progress;
+use pattern_3::ext::*;
+
+#[test]
+fn test_progress_forward() {
+    let mut reached = vec![];
+    assert_eq!(matches("abcabcab", Progress("ca", |i| reached.push(i))).count(), 2);
+    assert_eq!(reached, vec![4, 7, 8]);
+
+    let mut reached = vec![];
+    let mut res = String::new();
+    replace_with("a-b", Progress('-', |i| reached.push(i)), |_| "+", |s| res.push_str(s));
+    assert_eq!(res, "a+b");
+    assert_eq!(reached, vec![2, 3]);
+}
+
+#[test]
+fn test_progress_backward() {
+    let mut reached = vec![];
+    assert_eq!(
+        rsplit("a,b,c", Progress(',', |i| reached.push(i))).collect::<Vec<_>>(),
+        vec!["c", "b", "a"],
+    );
+    assert_eq!(reached, vec![3, 1, 0]);
+
+    let mut reached = vec![];
+    assert_eq!(trim(&[0, 0, 1, 0][..], Progress(|x: &i32| *x == 0, |i| reached.push(i))), &[1]);
+    assert_eq!(reached, vec![3, 2]);
+}
+
+#[test]
+fn test_progress_long_stretch() {
+    let hay = format!("{},{}", "a".repeat(300_000), "b".repeat(100_000));
+    let mut reached = vec![];
+    assert_eq!(find(&*hay, Progress(',', |i| reached.push(i))), Some(300_000));
+    assert_eq!(reached, vec![65_536, 196_608, 300_001]);
+
+    let mut reached = vec![];
+    assert_eq!(rfind(&*hay, Progress('a', |i| reached.push(i))), Some(299_999));
+    assert_eq!(reached, vec![334_465, 299_999]);
+
+    // a match crossing the end of a window is still found
+    let hay = format!("{}{}", "x".repeat(65_535), "needle");
+    let mut reached = vec![];
+    assert_eq!(find(&*hay, Progress("needle", |i| reached.push(i))), Some(65_535));
+    assert_eq!(reached, vec![65_536, 65_541]);
+
+    let mut reached = vec![];
+    assert_eq!(find(&*"x".repeat(200_000), Progress('y', |i| reached.push(i))), None);
+    assert_eq!(reached, vec![65_536, 196_608, 200_000]);
+}
+
+#[test]
+fn test_progress_resume_windows() {
+    use std::cell::Cell;
+
+    // each character is only looked at once, however many windows are probed
+    let hay = format!("{},", "a".repeat(300_000));
+    let calls = Cell::new(0);
+    let mut reached = vec![];
+    let comma = |c: char| { calls.set(calls.get() + 1); c == ',' };
+    assert_eq!(find(&*hay, Progress(comma, |i| reached.push(i))), Some(300_000));
+    assert_eq!(reached, vec![65_536, 196_608, 300_001]);
+    assert_eq!(calls.get(), 300_001);
+
+    let calls = Cell::new(0);
+    let mut reached = vec![];
+    let comma = |c: char| { calls.set(calls.get() + 1); c == ',' };
+    assert_eq!(rfind(&*hay, Progress(comma, |i| reached.push(i))), Some(300_000));
+    assert_eq!(reached, vec![300_000]);
+    assert_eq!(calls.get(), 1);
+}
+
+#[test]
+fn test_progress_unbounded_crossing_match() {
+    use pattern_3::AnyOf;
+
+    // a shorter needle inside the window must not win over an earlier,
+    // longer match crossing the end of the window
+    let hay = format!("{}abcdef", "x".repeat(65_533));
+    let needles: &[&[u8]] = &[b"abcdef", b"c"];
+    let mut reached = vec![];
+    assert_eq!(
+        find(hay.as_bytes(), Progress(AnyOf(needles), |i| reached.push(i))),
+        Some(65_533),
+    );
+    assert_eq!(reached, vec![65_539]);
+}
@@ -0,0 +1,47 @@
+extern crate pattern_3;
+
+use pattern_3::RangesSearcher;
+use pattern_3::ext::*;
+
+#[test]
+fn test_ranges_searcher() {
+    let hay = "one, two;three";
+    let found = vec![3..5, 8..9];
+    assert_eq!(split(hay, RangesSearcher::new(found.clone())).collect::<Vec<_>>(), vec!["one", "two", "three"]);
+    assert_eq!(rsplit(hay, RangesSearcher::new(found.clone())).collect::<Vec<_>>(), vec!["three", "two", "one"]);
+    assert_eq!(
+        match_ranges(hay, RangesSearcher::new(found.clone())).map(|(r, _)| r).collect::<Vec<_>>(),
+        found,
+    );
+
+    let mut res = String::new();
+    replace_with(hay, RangesSearcher::new(found.clone()), |_| "|", |s| res.push_str(s));
+    assert_eq!(res, "one|two|three");
+
+    // double-ended iteration shares the ranges
+    let mut it = split("a,b,c,d", RangesSearcher::new(vec![1..2, 3..4, 5..6]));
+    assert_eq!(it.next(), Some("a"));
+    assert_eq!(it.next_back(), Some("d"));
+    assert_eq!(it.next(), Some("b"));
+    assert_eq!(it.next_back(), Some("c"));
+    assert_eq!(it.next(), None);
+}
+
+#[test]
+fn test_ranges_searcher_skip_and_trim() {
+    // overlapping ranges are skipped
+    assert_eq!(
+        match_ranges("aaaa", RangesSearcher::new(vec![0..2, 1..3, 2..4])).map(|(r, _)| r).collect::<Vec<_>>(),
+        vec![0..2, 2..4],
+    );
+    assert_eq!(trim("xxabxx", RangesSearcher::new(vec![0..1, 1..2, 4..5, 5..6])), "ab");
+    assert_eq!(trim_start("xxabxx", RangesSearcher::new(vec![0..1, 3..4])), "xabxx");
+    assert!(starts_with(&[1, 2][..], RangesSearcher::new(vec![0..1])));
+    assert!(!starts_with(&[1, 2][..], RangesSearcher::new(vec![1..2])));
+}
+
+#[test]
+#[should_panic]
+fn test_ranges_searcher_not_boundary() {
+    find("é", RangesSearcher::new(vec![0..1]));
+}
@@ -0,0 +1,69 @@
+#![cfg(feature = "regex")]
+
+extern crate pattern_3;
+extern crate regex;
+
+use pattern_3::ext::*;
+use regex::Regex;
+use regex::bytes;
+
+#[test]
+fn test_regex_split() {
+    let re = Regex::new(r"\s*,\s*").unwrap();
+    assert_eq!(split("a, b ,c,, d", &re).collect::<Vec<_>>(), vec!["a", "b", "c", "", "d"]);
+    assert_eq!(splitn("a, b ,c", 2, &re).collect::<Vec<_>>(), vec!["a", "b ,c"]);
+}
+
+#[test]
+fn test_regex_matches() {
+    let re = Regex::new(r"\d*").unwrap();
+    assert_eq!(
+        match_ranges("a1bbb2", &re).map(|(r, _)| r).collect::<Vec<_>>(),
+        vec![0..0, 1..2, 3..3, 4..4, 5..6],
+    );
+    let re = Regex::new(r"\b\w").unwrap();
+    assert_eq!(matches("hello big world", &re).collect::<Vec<_>>(), vec!["h", "b", "w"]);
+}
+
+#[test]
+fn test_regex_consume() {
+    let re = Regex::new(r"[a-z]+").unwrap();
+    assert!(starts_with("abc123", &re));
+    assert!(!starts_with("123abc", &re));
+    assert_eq!(trim_start("abc123", &re), "123");
+    let anchored = Regex::new(r"^a").unwrap();
+    assert_eq!(trim_start("aaab", &anchored), "aab");
+    let folded = Regex::new(r"(?i)x|\b").unwrap();
+    assert_eq!(trim_start("XxX y", &folded), " y");
+    assert!(!starts_with("yx", &Regex::new(r"(?i)x").unwrap()));
+}
+
+#[test]
+fn test_regex_replace() {
+    let re = Regex::new(r"\d+").unwrap();
+    let mut out = String::new();
+    replace_with("a1b22c333", &re, |_| "#", |s| out.push_str(s));
+    assert_eq!(out, "a#b#c#");
+}
+
+#[test]
+fn test_bytes_regex() {
+    let re = bytes::Regex::new(r"(?-u)\xff+").unwrap();
+    let hay = &b"a\xff\xffb\xffc"[..];
+    assert_eq!(split(hay, &re).collect::<Vec<_>>(), vec![&b"a"[..], b"b", b"c"]);
+    assert_eq!(find(hay, &re), Some(1));
+    assert_eq!(trim_start(&b"\xff\xffa"[..], &re), &b"a"[..]);
+}
+
+#[test]
+fn test_regex_consume_builder_options() {
+    use regex::RegexBuilder;
+
+    let re = RegexBuilder::new(r"x+").case_insensitive(true).build().unwrap();
+    assert!(starts_with("XxX y", &re));
+    assert_eq!(trim_start("XxX y", &re), " y");
+    let re = RegexBuilder::new(r"a.b").dot_matches_new_line(true).build().unwrap();
+    assert!(starts_with("a\nb", &re));
+    let re = bytes::RegexBuilder::new(r"\w+").unicode(false).build().unwrap();
+    assert_eq!(trim_start("ab\u{e9}".as_bytes(), &re), "\u{e9}".as_bytes());
+}
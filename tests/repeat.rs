@@ -0,0 +1,69 @@
+extern crate pattern_3;
+
+use pattern_3::repeat;
+use pattern_3::ext::*;
+
+fn ranges<'a>(hay: &'a str, p: pattern_3::Repeat<&'a str>) -> Vec<std::ops::Range<usize>> {
+    match_ranges(hay, p).map(|(r, _)| r).collect()
+}
+
+#[test]
+fn test_repeat_search() {
+    assert_eq!(
+        match_ranges("a-b--c---d----", repeat('-', 3..)).map(|(r, _)| r).collect::<Vec<_>>(),
+        vec![6..9, 10..14],
+    );
+    // greedy, capped at the maximum
+    assert_eq!(
+        match_ranges("-----", repeat('-', 2..=3)).map(|(r, _)| r).collect::<Vec<_>>(),
+        vec![0..3, 3..5],
+    );
+    assert_eq!(
+        rmatch_ranges("-----", repeat('-', 2..=3)).map(|(r, _)| r).collect::<Vec<_>>(),
+        vec![2..5, 0..2],
+    );
+    assert_eq!(ranges("abababxab", repeat("ab", 2..)), vec![0..6]);
+    assert_eq!(ranges("aaaaa", repeat("aa", 1..=1)), vec![0..2, 2..4]);
+    // a short prefix of the run is skipped, the rest still matches
+    assert_eq!(ranges("ab aab", repeat("a", 2..=2)), vec![3..5]);
+    assert_eq!(
+        match_indices(&[1, 1, 2, 1, 1, 1][..], repeat(|x: &i32| *x == 1, 3..=3)).count(),
+        1,
+    );
+}
+
+#[test]
+fn test_repeat_consume() {
+    assert!(starts_with("---x", repeat('-', 3..)));
+    assert!(!starts_with("--x", repeat('-', 3..)));
+    assert_eq!(trim_start("-----x", repeat('-', 2..=2)), "-x");
+    assert_eq!(trim_end("x-----", repeat('-', 3..)), "x");
+    assert!(ends_with("x==", repeat('=', 1..3)));
+}
+
+#[test]
+fn test_repeat_zero() {
+    assert_eq!(ranges("a--b", repeat("-", ..3)), vec![0..0, 1..3, 4..4]);
+    assert_eq!(ranges("--", repeat("-", 0..=0)), vec![0..0, 1..1, 2..2]);
+    assert_eq!(
+        rmatch_ranges("a--b", repeat('-', ..=2)).map(|(r, _)| r).collect::<Vec<_>>(),
+        vec![4..4, 1..3, 0..0],
+    );
+    assert_eq!(split("a-bc", repeat('-', ..=1)).collect::<Vec<_>>(), vec!["", "a", "b", "c", ""]);
+    assert!(starts_with("x", repeat('-', ..2)));
+    assert_eq!(trim_start("---x", repeat('-', ..=2)), "x");
+    assert_eq!(trim_end("x--", repeat('-', 0..)), "x");
+
+    let mut owned = String::from("a--b");
+    assert_eq!(
+        match_ranges(&mut owned[..], repeat('-', ..)).map(|(r, _)| r).collect::<Vec<_>>(),
+        vec![0..0, 1..3, 4..4],
+    );
+}
+
+#[test]
+#[should_panic(expected = "repeat count is empty")]
+#[allow(clippy::reversed_empty_ranges)]
+fn test_repeat_empty_count() {
+    repeat('-', 3..2);
+}
@@ -0,0 +1,68 @@
+extern crate pattern_3;
+
+use pattern_3::Rle;
+use pattern_3::ext::*;
+
+// Small deterministic generator producing long runs over a tiny alphabet.
+fn lcg_runs(seed: u32, runs: usize) -> Vec<(u8, usize)> {
+    let mut state = seed;
+    (0..runs).map(|_| {
+        state = state.wrapping_mul(1103515245).wrapping_add(12345);
+        let value = (state >> 16) as u8 % 3;
+        state = state.wrapping_mul(1103515245).wrapping_add(12345);
+        let count = (state >> 16) as usize % 4;
+        (value, count)
+    }).collect()
+}
+
+fn expand(runs: &[(u8, usize)]) -> Vec<u8> {
+    runs.iter().flat_map(|&(v, c)| (0..c).map(move |_| v)).collect()
+}
+
+#[test]
+fn test_rle_construction() {
+    let rle = Rle::from_runs(vec![(1, 2), (1, 3), (2, 0), (3, 1)]);
+    assert_eq!(rle.runs(), &[(1, 5), (3, 1)]);
+    assert_eq!(rle.len(), 6);
+    assert_eq!(rle.get(4), Some(&1));
+    assert_eq!(rle.get(5), Some(&3));
+    assert_eq!(rle.get(6), None);
+    assert_eq!(Rle::encode(b"aaabccc"), Rle::from_runs(vec![(b'a', 3), (b'b', 1), (b'c', 3)]));
+    assert!(Rle::<u8>::from_runs(vec![]).is_empty());
+}
+
+#[test]
+fn test_rle_search() {
+    let rle = Rle::encode(b"aaaabaaab");
+    assert_eq!(rle.match_indices(b"aa").collect::<Vec<_>>(), vec![0, 2, 5]);
+    assert_eq!(rle.match_indices(b"aab").collect::<Vec<_>>(), vec![2, 6]);
+    assert_eq!(rle.find(b"bab"), None);
+    assert_eq!(rle.rfind(b"a"), Some(7));
+    assert_eq!(rle.rfind(b"ab"), Some(7));
+    assert_eq!(rle.match_indices(b"").collect::<Vec<_>>(), (0..10).collect::<Vec<_>>());
+    assert_eq!(rle.rfind(b""), Some(9));
+    assert_eq!(Rle::<u8>::encode(b"").find(b""), Some(0));
+}
+
+#[test]
+fn test_rle_against_expanded() {
+    for seed in 0..200 {
+        let runs = lcg_runs(seed, 30);
+        let rle = Rle::from_runs(runs.iter().cloned());
+        let hay = expand(&runs);
+        for start in 0..hay.len() {
+            for len in 1..6 {
+                if start + len > hay.len() {
+                    break;
+                }
+                let needle = &hay[start..(start + len)];
+                assert_eq!(
+                    rle.match_indices(needle).collect::<Vec<_>>(),
+                    match_indices(&*hay, needle).map(|(i, _)| i).collect::<Vec<_>>(),
+                    "seed {}, needle {:?}", seed, needle,
+                );
+                assert_eq!(rle.rfind(needle), rfind(&*hay, needle), "seed {}, needle {:?}", seed, needle);
+            }
+        }
+    }
+}
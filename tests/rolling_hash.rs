@@ -0,0 +1,51 @@
+extern crate pattern_3;
+
+use pattern_3::rolling_hash::*;
+
+#[test]
+fn test_push_pop() {
+    let text = b"the quick brown fox";
+    let mut hash = RollingHash::new();
+    for &b in &text[..] {
+        hash.push(b);
+    }
+    assert_eq!(hash, RollingHash::of(text));
+    for i in 0..text.len() {
+        hash.pop(text[i]);
+        assert_eq!(hash, RollingHash::of(&text[(i + 1)..]));
+    }
+    assert_eq!(hash, RollingHash::new());
+    assert_eq!(hash.value(), 0);
+}
+
+#[test]
+fn test_roll() {
+    let text = b"abcdefgh";
+    let mut hash = RollingHash::of(&text[..3]);
+    for i in 3..text.len() {
+        hash.roll(text[i - 3], text[i]);
+        assert_eq!(hash, RollingHash::of(&text[(i - 2)..=i]));
+    }
+}
+
+#[test]
+fn test_windows() {
+    let hay = b"mississippi";
+    let it = windows(hay, 4);
+    assert_eq!(it.len(), 8);
+    for (i, hash) in it {
+        assert_eq!(hash, RollingHash::of(&hay[i..(i + 4)]));
+    }
+    assert_eq!(windows(hay, 12).count(), 0);
+    assert_eq!(windows(hay, 0).count(), 12);
+    assert_eq!(windows(b"", 0).collect::<Vec<_>>(), vec![(0, RollingHash::new())]);
+}
+
+#[test]
+fn test_find() {
+    assert_eq!(find(b"mississippi", b"ssip"), Some(5));
+    assert_eq!(find(b"mississippi", b"issi"), Some(1));
+    assert_eq!(find(b"mississippi", b"spa"), None);
+    assert_eq!(find(b"abc", b""), Some(0));
+    assert_eq!(find(b"", b"a"), None);
+}
@@ -0,0 +1,64 @@
+#![cfg(feature = "ropey")]
+
+extern crate pattern_3;
+extern crate ropey;
+
+use pattern_3::ChunkedHay;
+use pattern_3::rope_replace_with;
+use ropey::Rope;
+
+fn long_text() -> String {
+    (0..500).map(|i| format!("line {} — naïve café\n", i)).collect()
+}
+
+#[test]
+fn test_rope_find() {
+    let text = long_text();
+    let rope = Rope::from_str(&text);
+    assert!(rope.chunks().count() > 1);
+
+    let expected = text.match_indices("café\nline 4").map(|(i, _)| i).collect::<Vec<_>>();
+    assert_eq!(rope.match_indices("café\nline 4").collect::<Vec<_>>(), expected);
+    assert_eq!(rope.find("line 499"), text.find("line 499"));
+    assert_eq!(rope.find("line 500"), None);
+
+    let slice = rope.slice(100..2000);
+    let expected = slice.to_string().match_indices("naïve").map(|(i, _)| i..(i + 6)).collect::<Vec<_>>();
+    assert!(!expected.is_empty());
+    assert_eq!(slice.match_ranges("naïve").collect::<Vec<_>>(), expected);
+}
+
+#[test]
+fn test_rope_find_char() {
+    let text = long_text();
+    let rope = Rope::from_str(&text);
+    let expected = text.match_indices('ï').map(|(i, _)| i).collect::<Vec<_>>();
+    assert_eq!(expected.len(), 500);
+    assert_eq!(rope.match_indices('ï').collect::<Vec<_>>(), expected);
+    assert_eq!(rope.find('\n'), text.find('\n'));
+    assert_eq!(rope.slice(10..).find('é'), text[text.char_indices().nth(10).unwrap().0..].find('é'));
+    assert_eq!(rope.find('ü'), None);
+}
+
+#[test]
+fn test_rope_replace_with() {
+    let text = long_text();
+    let mut rope = Rope::from_str(&text);
+    let mut seen = 0;
+    let count = rope_replace_with(&mut rope, "café", |m| {
+        assert_eq!(m, "café");
+        seen += 1;
+        format!("bar{}", seen)
+    });
+    assert_eq!(count, 500);
+    let mut n = 0;
+    let expected = text.split("café").fold(String::new(), |mut out, piece| {
+        if n > 0 {
+            out.push_str(&format!("bar{}", n));
+        }
+        n += 1;
+        out.push_str(piece);
+        out
+    });
+    assert_eq!(rope.to_string(), expected);
+}
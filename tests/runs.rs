@@ -0,0 +1,38 @@
+extern crate pattern_3;
+
+use pattern_3::Runs;
+use pattern_3::ext::*;
+
+#[test]
+fn test_runs() {
+    let text = "  ab   c d  ";
+    let ws = Runs(char::is_whitespace);
+    assert_eq!(
+        match_ranges(text, ws).map(|(r, _)| r).collect::<Vec<_>>(),
+        vec![0..2, 4..7, 8..9, 10..12],
+    );
+    assert_eq!(
+        rmatch_ranges(text, ws).map(|(r, _)| r).collect::<Vec<_>>(),
+        vec![10..12, 8..9, 4..7, 0..2],
+    );
+    assert_eq!(split("a  b c", ws).collect::<Vec<_>>(), vec!["a", "b", "c"]);
+    assert_eq!(matches("aa-aaa-a", Runs('a')).collect::<Vec<_>>(), vec!["aa", "aaa", "a"]);
+    // multi-codeword inner patterns
+    assert_eq!(matches("abababa-ab", Runs("ab")).collect::<Vec<_>>(), vec!["ababab", "ab"]);
+    assert_eq!(
+        match_ranges(&[1, 1, 0, 1][..], Runs(|x: &i32| *x == 1)).map(|(r, _)| r).collect::<Vec<_>>(),
+        vec![0..2, 3..4],
+    );
+}
+
+#[test]
+fn test_runs_consume() {
+    assert!(starts_with("   x", Runs(' ')));
+    assert_eq!(trim_start("xxxy", Runs('x')), "y");
+    assert_eq!(trim("--a--", Runs('-')), "a");
+    assert!(!ends_with("x", Runs(' ')));
+    let mut it = split("1  2  3", Runs(' '));
+    assert_eq!(it.next_back(), Some("3"));
+    assert_eq!(it.next(), Some("1"));
+    assert_eq!(it.next_back(), Some("2"));
+}
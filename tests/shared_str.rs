@@ -0,0 +1,55 @@
+extern crate pattern_3;
+
+use pattern_3::{ArcStr, RcStr};
+use pattern_3::ext::*;
+
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::thread;
+
+#[test]
+fn test_arc_str_split() {
+    let text = ArcStr::from("key=value; other=thing");
+    let pairs: Vec<ArcStr> = split(text.clone(), "; ").collect();
+    assert_eq!(pairs, ["key=value", "other=thing"]);
+    assert_eq!(pairs[1].range(), 11..22);
+    assert!(Arc::ptr_eq(pairs[1].buffer(), text.buffer()));
+
+    let mut map = HashMap::new();
+    for pair in pairs {
+        let mut kv = splitn(pair, 2, '=');
+        let key = kv.next().unwrap();
+        map.insert(key, kv.next().unwrap());
+    }
+    drop(text);
+    assert_eq!(&*map["other"], "thing");
+}
+
+#[test]
+fn test_arc_str_match_ranges() {
+    let text = ArcStr::from(String::from("abcabcab"));
+    let found: Vec<_> = match_ranges(trim_start(text, 'a'), "ab").collect();
+    assert_eq!(found.len(), 2);
+    assert_eq!(found[0].0, 2..4);
+    assert_eq!(found[0].1.range(), 3..5);
+    assert_eq!(found[1].1, "ab");
+}
+
+#[test]
+fn test_arc_str_cross_threads() {
+    let handles: Vec<_> = split(ArcStr::from("x y zz"), char::is_whitespace)
+        .map(|word| thread::spawn(move || word.len()))
+        .collect();
+    let lens: Vec<usize> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+    assert_eq!(lens, vec![1, 1, 2]);
+}
+
+#[test]
+fn test_rc_str() {
+    let buffer: Rc<str> = Rc::from("  a, b  ");
+    let trimmed = trim(RcStr::from(buffer.clone()), ' ');
+    assert_eq!(trimmed, "a, b");
+    assert_eq!(rsplit(trimmed, ", ").collect::<Vec<_>>(), ["b", "a"]);
+    assert_eq!(Rc::strong_count(&buffer), 1);
+}
@@ -0,0 +1,45 @@
+extern crate pattern_3;
+
+use pattern_3::{Sorted, SortedRun};
+use pattern_3::ext::*;
+
+#[test]
+fn test_sorted_elements() {
+    let hay = &[1, 1, 2, 3, 3, 3, 5][..];
+    assert_eq!(find(hay, Sorted(1)), Some(0));
+    assert_eq!(rfind(hay, Sorted(3)), Some(5));
+    assert_eq!(find(hay, Sorted(0)), None);
+    assert_eq!(find(hay, Sorted(4)), None);
+    assert_eq!(find(hay, Sorted(6)), None);
+    assert_eq!(match_indices(hay, Sorted(3)).map(|(i, _)| i).collect::<Vec<_>>(), vec![3, 4, 5]);
+    assert_eq!(rmatch_indices(hay, Sorted(3)).map(|(i, _)| i).collect::<Vec<_>>(), vec![5, 4, 3]);
+    assert_eq!(matches(hay, Sorted(3)).rev().count(), 3);
+    assert_eq!(trim_start(hay, Sorted(1)), &[2, 3, 3, 3, 5]);
+    assert_eq!(trim_end(hay, Sorted(5)), &[1, 1, 2, 3, 3, 3]);
+    assert_eq!(trim(&[4, 4][..], Sorted(4)), &[]);
+    assert!(starts_with(hay, Sorted(1)));
+    assert!(!ends_with(hay, Sorted(3)));
+    assert_eq!(find(&[][..], Sorted(1)), None);
+}
+
+#[test]
+fn test_sorted_runs() {
+    let hay = vec![1, 1, 2, 3, 3, 3, 5];
+    assert_eq!(find_range(&*hay, SortedRun(3)), Some(3..6));
+    assert_eq!(rfind_range(&*hay, SortedRun(1)), Some(0..2));
+    assert_eq!(find_range(&*hay, SortedRun(4)), None);
+    assert_eq!(split(&*hay, SortedRun(3)).collect::<Vec<_>>(), vec![&[1, 1, 2][..], &[5]]);
+    assert_eq!(rsplit(&*hay, SortedRun(1)).collect::<Vec<_>>(), vec![&[2, 3, 3, 3, 5][..], &[]]);
+    assert_eq!(trim_start(hay.clone(), SortedRun(1)), vec![2, 3, 3, 3, 5]);
+    assert_eq!(trim_end(&*hay, SortedRun(5)), &[1, 1, 2, 3, 3, 3]);
+}
+
+#[test]
+fn test_sorted_large() {
+    let hay = (0..10000).map(|i| i / 7).collect::<Vec<_>>();
+    for v in (0..1500).step_by(37) {
+        let expected = hay.iter().position(|&x| x == v).map(|i| i..(i + 7));
+        assert_eq!(find_range(&*hay, SortedRun(v)), expected);
+        assert_eq!(matches(&*hay, Sorted(v)).count(), if v < 1428 { 7 } else if v == 1428 { 4 } else { 0 });
+    }
+}
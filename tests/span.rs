@@ -0,0 +1,65 @@
+extern crate pattern_3;
+
+use pattern_3::Span;
+use pattern_3::ext::*;
+
+#[test]
+fn test_span_chunks() {
+    let chunks = Span::from(&[1, 2, 3, 4, 5, 6, 7][..]).chunks(3, 0).map(|s| s.into()).collect::<Vec<_>>();
+    assert_eq!(chunks, vec![&[1, 2][..], &[3, 4], &[5, 6, 7]]);
+
+    let ranges = Span::from("héllo wörld").chunks(4, 0).map(|s| s.original_range()).collect::<Vec<_>>();
+    assert_eq!(ranges, vec![0..3, 3..6, 6..10, 10..13]);
+
+    let chunks = Span::from("ab").chunks(4, 0).map(|s| s.into()).collect::<Vec<&str>>();
+    assert_eq!(chunks, vec!["", "", "a", "b"]);
+
+    let mut it = Span::from("abc").chunks(1, 5);
+    assert_eq!(it.len(), 1);
+    assert_eq!(it.next().map(|s| s.into()), Some("abc"));
+    assert!(it.next().is_none());
+}
+
+#[test]
+#[should_panic]
+fn test_span_chunks_zero() {
+    Span::from("abc").chunks(0, 0);
+}
+
+#[test]
+fn test_span_chunks_merge_matches() {
+    let hay = "needle-haystack-needle-needle-haystack-needle".repeat(5);
+    let needle = "needle";
+    let expected = match_indices(&*hay, needle).map(|(i, _)| i).collect::<Vec<_>>();
+    for n in 1..20 {
+        let chunks = Span::from(&*hay).chunks(n, needle.len() - 1).collect::<Vec<_>>();
+        let starts = chunks.iter().skip(1).map(|s| s.original_range().start).chain(Some(hay.len()));
+        let mut found = Vec::new();
+        for (chunk, next_start) in chunks.iter().zip(starts) {
+            let offset = chunk.original_range().start;
+            let text: &str = chunk.clone().into();
+            found.extend(
+                match_indices(text, needle)
+                    .map(|(i, _)| i + offset)
+                    .filter(|&i| i < next_start),
+            );
+        }
+        assert_eq!(found, expected, "{} chunks", n);
+    }
+}
+
+#[test]
+fn test_span_as_slice() {
+    let span = unsafe { Span::from_parts("héllo wörld", 7..13) };
+    assert_eq!(span.as_slice(), "wörld");
+    assert!(span.starts_with('w'));
+    assert_eq!(span.find('r'), Some(3));
+
+    let span = unsafe { Span::from_parts(&[1, 2, 3, 4][..], 1..3) };
+    assert_eq!(span.as_slice(), &[2, 3]);
+    assert_eq!(span[0], 2);
+
+    let empty = unsafe { Span::from_parts("abc", 3..3) };
+    assert_eq!(empty.as_slice(), "");
+    assert!(empty.is_empty());
+}
@@ -0,0 +1,44 @@
+extern crate pattern_3;
+
+use pattern_3::ext::*;
+
+use std::thread;
+
+fn owned(s: &[&str]) -> Vec<String> {
+    s.iter().map(|s| s.to_string()).collect()
+}
+
+#[test]
+fn test_string_split() {
+    let haystack = "a, b,c".to_string();
+    assert_eq!(split(haystack.clone(), ", ").collect::<Vec<_>>(), owned(&["a", "b,c"]));
+    assert_eq!(split(haystack.clone(), ',').collect::<Vec<_>>(), owned(&["a", " b", "c"]));
+    assert_eq!(splitn(haystack.clone(), 2, ',').collect::<Vec<_>>(), owned(&["a", " b,c"]));
+    assert_eq!(
+        rsplitn(haystack, 2, |c: char| c == ' ').collect::<Vec<_>>(),
+        owned(&["b,c", "a,"])
+    );
+    assert_eq!(trim("  héllo\t".to_string(), char::is_whitespace), "héllo");
+}
+
+#[test]
+fn test_string_replace() {
+    let mut result = String::new();
+    replace_with(
+        "one two  three".to_string(),
+        ' ',
+        |_| "_".to_string(),
+        |piece: String| result.push_str(&piece),
+    );
+    assert_eq!(result, "one_two__three");
+}
+
+#[test]
+fn test_string_pieces_cross_threads() {
+    let text = "alpha\nbeta\ngamma".to_string();
+    let handles: Vec<_> = split(text, "\n")
+        .map(|line| thread::spawn(move || line.to_uppercase()))
+        .collect();
+    let lines: Vec<String> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+    assert_eq!(lines, owned(&["ALPHA", "BETA", "GAMMA"]));
+}
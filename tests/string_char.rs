@@ -307,4 +307,25 @@ fn test_str() {
     ]);
 }
 
+#[test]
+fn test_shared_lead_byte() {
+    // '的' and '猫' both start with the byte 0xE7.
+    search_asserts! ("的猫的猫", '猫', "forward iteration sharing a lead byte", [
+        next = Some(3..6),
+        next = Some(9..12),
+        next = None,
+    ]);
+
+    search_asserts! ("的猫的猫", '的', "reverse iteration sharing a lead byte", [
+        next_back = Some(6..9),
+        next_back = Some(0..3),
+        next_back = None,
+    ]);
+
+    search_asserts! ("💩💨💩", '💨', "double-ended iteration sharing a lead byte", [
+        next = Some(4..8),
+        next_back = None,
+    ]);
+}
+
 fn main() {}
\ No newline at end of file
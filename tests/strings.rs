@@ -13,6 +13,18 @@ fn test_find() {
     assert_eq!(find("ประเทศไทย中华Việt Nam", |c: char| c == '华'), Some(30));
 }
 
+#[test]
+fn test_utf8_needle() {
+    let needle = Utf8Needle::new("中华".as_bytes()).unwrap();
+    assert_eq!(needle.as_str(), "中华");
+    assert_eq!(find("ประเทศไทย中华Việt Nam", needle), Some(27));
+    assert_eq!(split("a中华b中华", needle).collect::<Vec<_>>(), vec!["a", "b", ""]);
+    assert!(starts_with("中华", needle));
+    assert_eq!(find("ab", Utf8Needle::new(b"").unwrap()), Some(0));
+    assert!(Utf8Needle::new(b"\xe4\xb8").is_err());
+    assert!(Utf8Needle::new(b"\xff").is_err());
+}
+
 #[test]
 fn test_rfind() {
     assert_eq!(rfind("hello", 'l'), Some(3));
@@ -68,6 +80,56 @@ fn test_trim_matches() {
     assert_eq!(trim("123foo1bar123", |c: char| c.is_numeric()), "foo1bar");
 }
 
+#[test]
+fn test_eq() {
+    assert!(eq("hello", "hello"));
+    assert!(!eq("hello", "hell"));
+    assert!(!eq("hell", "hello"));
+    assert!(eq("", ""));
+    assert!(eq("é", 'é'));
+    assert!(!eq("éé", 'é'));
+    assert!(eq("7", |c: char| c.is_numeric()));
+    assert!(!eq("", |c: char| c.is_numeric()));
+}
+
+#[test]
+fn test_prefix_match_len() {
+    assert_eq!(prefix_match_len("hello world", "help"), 3);
+    assert_eq!(prefix_match_len("hello", "hello world"), 5);
+    assert_eq!(prefix_match_len("hello", "hello"), 5);
+    assert_eq!(prefix_match_len("hello", ""), 0);
+    assert_eq!(prefix_match_len("", "hello"), 0);
+    assert_eq!(prefix_match_len("jalapeño", "jalapeña"), 8);
+    assert_eq!(prefix_match_len("\u{10000}", "\u{10001}"), 0);
+    assert_eq!(prefix_match_len(&[1, 2, 3, 4][..], &[1, 2, 4][..]), 2);
+}
+
+#[test]
+fn test_suffix_prefix_len() {
+    assert_eq!(suffix_prefix_len("chunk ends with nee", "needle"), 3);
+    assert_eq!(suffix_prefix_len("aabaab", "aabaabaab"), 6);
+    assert_eq!(suffix_prefix_len("xaaaa", "aaab"), 3);
+    assert_eq!(suffix_prefix_len("needle", "needle"), 6);
+    assert_eq!(suffix_prefix_len("needles", "needle"), 0);
+    assert_eq!(suffix_prefix_len("abc", ""), 0);
+    assert_eq!(suffix_prefix_len("", "abc"), 0);
+    assert_eq!(suffix_prefix_len("ab", "abcdef"), 2);
+    assert_eq!(suffix_prefix_len("jalapeñ", "ñandú"), 2);
+    assert_eq!(suffix_prefix_len(&[1, 2, 1, 2, 1][..], &[1, 2, 1, 3][..]), 3);
+
+    // compare against a naive implementation.
+    let hay = "abaababaabaababaababa";
+    for end in 0..=hay.len() {
+        for needle in &["a", "ab", "aba", "abaab", "babaab", "ababa", "aabaababa"] {
+            let expected = (0..=needle.len().min(end))
+                .rev()
+                .find(|&k| hay[..end].ends_with(&needle[..k]))
+                .unwrap();
+            assert_eq!(suffix_prefix_len(&hay[..end], *needle), expected, "{:?} {:?}", &hay[..end], needle);
+        }
+    }
+}
+
 #[test]
 fn test_contains_char() {
     assert!(contains("abc", 'b'));
@@ -393,6 +455,28 @@ fn test_ends_with() {
     assert!(ends_with("ddö", "dö"));
 }
 
+#[test]
+fn test_contains_any() {
+    assert!(contains_any("buy cheap pills now", &["pills", "casino"]));
+    assert!(contains_any("visit the casino", &["pills", "casino"]));
+    assert!(!contains_any("hello world", &["pills", "casino"]));
+    assert!(!contains_any::<_, str>("hello world", &[]));
+    assert!(contains_any("", &[""]));
+    assert!(contains_any(&b"abc"[..], &[&b"x"[..], b"c"]));
+}
+
+#[test]
+fn test_match_frequencies() {
+    let hay = "the cat sat on the mat with the hat";
+    assert_eq!(match_frequencies(hay, vec!["the", "at", "dog"]), vec![3, 4, 0]);
+    assert_eq!(match_frequencies("aaaaa", vec!["aa", "a"]), vec![2, 5]);
+    assert_eq!(match_frequencies("abc", vec!["ab", "bc"]), vec![1, 1]);
+    assert_eq!(match_frequencies("abc", vec![""]), vec![4]);
+    assert_eq!(match_frequencies("abc", Vec::<&str>::new()), Vec::<usize>::new());
+    assert_eq!(match_frequencies("a,b;c,d", vec![',', ';']), vec![2, 1]);
+    assert_eq!(match_frequencies(&[1, 2, 1, 3][..], vec![&[1][..], &[3]]), vec![2, 1]);
+}
+
 #[test]
 fn test_contains() {
     assert!(contains("abcde", "bcd"));
@@ -558,6 +642,365 @@ fn test_trim_strings() {
     assert_eq!(trim_end("ababababa", "ba"), "a");
 }
 
+#[test]
+fn test_match_ranges_within() {
+    // skip the string literal and the comment
+    let src = r#"foo("foo"); foo(1); // foo"#;
+    let code = vec![0..4, 9..21];
+    assert_eq!(match_ranges_within(src, code.clone(), "foo").collect::<Vec<_>>(), vec![0..3, 12..15]);
+    assert_eq!(match_ranges_within(src, code.clone(), "o(").collect::<Vec<_>>(), vec![2..4, 14..16]);
+    // matches crossing the gaps are not reported
+    assert_eq!(match_ranges_within(src, vec![0..6, 6..11], "(\"foo\")").count(), 0);
+    assert_eq!(match_ranges_within(src, Vec::new(), 'f').count(), 0);
+    assert_eq!(match_ranges_within("abc", vec![0..1, 2..3], "").collect::<Vec<_>>(), vec![0..0, 1..1, 2..2, 3..3]);
+    assert_eq!(
+        match_ranges_within(&[1, 2, 1, 2, 1][..], vec![1..5], &[1][..]).collect::<Vec<_>>(),
+        vec![2..3, 4..5],
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_match_ranges_within_unsorted() {
+    match_ranges_within("abcabc", vec![3..6, 0..3], 'a').count();
+}
+
+#[test]
+#[should_panic]
+fn test_match_ranges_within_not_boundary() {
+    match_ranges_within("é", vec![0..1], 'a').count();
+}
+
+#[test]
+fn test_verify_matches() {
+    let src = "abcabcab";
+    assert_eq!(verify_matches(src, vec![6, 0, 1, 3], "abc").collect::<Vec<_>>(), vec![0..3, 3..6]);
+    assert_eq!(verify_matches(src, vec![0, 3, 3], "abca").collect::<Vec<_>>(), vec![0..4, 3..7, 3..7]);
+    assert_eq!(verify_matches(src, vec![8, 2], "").collect::<Vec<_>>(), vec![8..8, 2..2]);
+    assert_eq!(verify_matches(src, 0..9, 'c').collect::<Vec<_>>(), vec![2..3, 5..6]);
+    assert_eq!(
+        verify_matches(&[1, 2, 1, 2][..], vec![0, 2, 3], &[1, 2][..]).collect::<Vec<_>>(),
+        vec![0..2, 2..4],
+    );
+}
+
+#[test]
+#[should_panic]
+fn test_verify_matches_not_boundary() {
+    verify_matches("é", vec![1], 'a').count();
+}
+
+#[test]
+fn test_nearest_match() {
+    let line = "fn f(a, b) { g(c, d) }";
+    assert_eq!(nearest_match(line, 8, ',', TieBreak::Before), Some(6..7));
+    assert_eq!(nearest_match(line, 11, ',', TieBreak::Before), Some(6..7));
+    assert_eq!(nearest_match(line, 14, ',', TieBreak::Before), Some(16..17));
+    assert_eq!(nearest_match(line, 0, ',', TieBreak::Before), Some(6..7));
+    assert_eq!(nearest_match(line, line.len(), ',', TieBreak::Before), Some(16..17));
+    assert_eq!(nearest_match(line, 5, ';', TieBreak::Before), None);
+
+    // equidistant from both parentheses
+    assert_eq!(nearest_match("(ab)", 2, |c: char| c == '(' || c == ')', TieBreak::Before), Some(0..1));
+    assert_eq!(nearest_match("(ab)", 2, |c: char| c == '(' || c == ')', TieBreak::After), Some(3..4));
+
+    // an occurrence straddling the index is ignored
+    assert_eq!(nearest_match("a--b--c", 2, "--", TieBreak::After), Some(4..6));
+    assert_eq!(nearest_match("é,é", 2, ',', TieBreak::After), Some(2..3));
+    assert_eq!(nearest_match(&[1, 0, 2, 0, 3][..], 4, &[0][..], TieBreak::After), Some(3..4));
+}
+
+#[test]
+#[should_panic]
+fn test_nearest_match_not_boundary() {
+    nearest_match("é,é", 1, ',', TieBreak::After);
+}
+
+#[test]
+fn test_split_ranges() {
+    let hay = "a,bc,,déf";
+    let ranges = split_ranges(hay, ',').collect::<Vec<_>>();
+    assert_eq!(ranges, vec![0..1, 2..4, 5..5, 6..10]);
+    assert_eq!(
+        ranges.iter().map(|r| &hay[r.clone()]).collect::<Vec<_>>(),
+        split(hay, ',').collect::<Vec<_>>(),
+    );
+    assert_eq!(rsplit_ranges(hay, ',').collect::<Vec<_>>(), vec![6..10, 5..5, 2..4, 0..1]);
+    assert_eq!(split_ranges(hay, ',').rev().collect::<Vec<_>>(), vec![6..10, 5..5, 2..4, 0..1]);
+    assert_eq!(split_ranges("", ',').collect::<Vec<_>>(), vec![0..0]);
+    assert_eq!(split_ranges(",", ',').collect::<Vec<_>>(), vec![0..0, 1..1]);
+    assert_eq!(split_ranges("ab", "").collect::<Vec<_>>(), vec![0..0, 0..1, 1..2, 2..2]);
+
+    let mut both = split_ranges("1 2 3 4", ' ');
+    assert_eq!(both.next(), Some(0..1));
+    assert_eq!(both.next_back(), Some(6..7));
+    assert_eq!(both.next(), Some(2..3));
+    assert_eq!(both.next_back(), Some(4..5));
+    assert_eq!(both.next(), None);
+
+    let hay = vec![1, 0, 2, 3, 0];
+    assert_eq!(split_ranges(hay, &[0][..]).collect::<Vec<_>>(), vec![0..1, 2..4, 5..5]);
+}
+
+#[test]
+fn test_split2() {
+    fn collect<'a>(hay: &'a str) -> Vec<Vec<&'a str>> {
+        split2(hay, '\n', '\t').map(|fields| fields.collect()).collect()
+    }
+    assert_eq!(collect("a\tb\nc\td\n"), vec![vec!["a", "b"], vec!["c", "d"]]);
+    assert_eq!(collect("a\tb\nc"), vec![vec!["a", "b"], vec!["c"]]);
+    assert_eq!(collect("a\t\n\n\tb"), vec![vec!["a", ""], vec![""], vec!["", "b"]]);
+    assert_eq!(collect(""), Vec::<Vec<&str>>::new());
+
+    let mut records = split2("name=é;x=1", ';', '=');
+    assert_eq!(records.next().unwrap().rev().collect::<Vec<_>>(), vec!["é", "name"]);
+    assert_eq!(records.next().unwrap().collect::<Vec<_>>(), vec!["x", "1"]);
+    assert!(records.next().is_none());
+}
+
+#[test]
+fn test_rreplacen() {
+    assert_eq!(rreplacen("archive.tar.gz", ".", "_", 1), "archive.tar_gz");
+    assert_eq!(rreplacen("archive.tar.gz", ".", "_", 2), "archive_tar_gz");
+    assert_eq!(rreplacen("archive.tar.gz", ".", "_", 5), "archive_tar_gz");
+    assert_eq!(rreplacen("archive.tar.gz", ".", "_", 0), "archive.tar.gz");
+    assert_eq!(rreplacen("aaaaa", "aa", "b", 1), "aaab");
+    assert_eq!(rreplacen("aaaaa", "aa", "b", 2), "abb");
+    assert_eq!(rreplacen("abc", "", "-", 2), "ab-c-");
+    assert_eq!(rreplacen("αβγ", |c: char| c != 'β', "x", 1), "αβx");
+
+    let mut res = Vec::new();
+    rreplacen_with(&[1, 0, 2, 0, 3][..], &[0][..], |_| &[9, 9][..], 1, |h| res.extend_from_slice(h));
+    assert_eq!(res, vec![1, 0, 2, 9, 9, 3]);
+}
+
+#[test]
+fn test_replace_into() {
+    let mut buf = String::with_capacity(64);
+    let capacity = buf.capacity();
+    for (line, expected) in [("a,b,c", "a;b;c"), ("", ""), (",", ";"), ("abc", "abc")].iter() {
+        replace_into(*line, ",", ";", &mut buf);
+        assert_eq!(buf, *expected);
+        assert_eq!(buf.capacity(), capacity);
+    }
+    replace_into("αβγ", 'β', "", &mut buf);
+    assert_eq!(buf, "αγ");
+
+    let mut buf = vec![7; 8];
+    replace_into(&[1, 0, 2, 0][..], &[0][..], &[9, 9][..], &mut buf);
+    assert_eq!(buf, vec![1, 9, 9, 2, 9, 9]);
+}
+
+#[test]
+fn test_coalesce() {
+    let merged = coalesce(vec![0..2, 1..3, 3..4, 6..7, 6..6, 8..10, 8..9]).collect::<Vec<_>>();
+    assert_eq!(merged, vec![0..4, 6..7, 8..10]);
+    assert_eq!(coalesce(Vec::<std::ops::Range<usize>>::new()).next(), None);
+
+    // overlapping matches of several patterns are reported as clean blocks
+    let text = "password: hunter2, pass: hunter3";
+    let blocks = coalesced_match_ranges(text, vec!["hunter", "password", "ter2", "ter3", "pass"]);
+    assert_eq!(blocks, vec![0..8, 10..17, 19..23, 25..32]);
+    let redacted = blocks.iter().rev().fold(text.to_owned(), |mut s, r| {
+        s.replace_range(r.clone(), "***");
+        s
+    });
+    assert_eq!(redacted, "***: ***, ***: ***");
+}
+
+#[test]
+fn test_split_front_back() {
+    let mut cursor = split_front_back("GET /a b c HTTP/1.1 200 17", ' ');
+    assert_eq!(cursor.next_front(), Some("GET"));
+    assert_eq!(cursor.next_back(), Some("17"));
+    assert_eq!(cursor.next_back(), Some("200"));
+    assert_eq!(cursor.next_back(), Some("HTTP/1.1"));
+    assert_eq!(cursor.remainder(), Some("/a b c"));
+    assert_eq!(cursor.remainder_range(), Some(4..10));
+    assert_eq!(cursor.into_remainder(), Some("/a b c"));
+
+    // the ends meet in the middle without overlapping
+    let mut cursor = split_front_back("a,b,c", ',');
+    assert_eq!(cursor.next_back(), Some("c"));
+    assert_eq!(cursor.next_front(), Some("a"));
+    assert_eq!(cursor.remainder(), Some("b"));
+    assert_eq!(cursor.next_back(), Some("b"));
+    assert_eq!(cursor.remainder(), None);
+    assert_eq!(cursor.next_front(), None);
+    assert_eq!(cursor.next_back(), None);
+
+    // separators found from opposite ends may differ, but never overlap
+    let mut cursor = split_front_back("xxxxx", "xx");
+    assert_eq!(cursor.next_front(), Some(""));
+    assert_eq!(cursor.next_back(), Some(""));
+    assert_eq!(cursor.remainder(), Some("x"));
+    assert_eq!(cursor.next_front(), Some("x"));
+    assert_eq!(cursor.next_back(), None);
+
+    let mut v = vec![1, 0, 2, 0, 3];
+    {
+        let mut cursor = split_front_back(&mut v[..], |x: &i32| *x == 0);
+        cursor.next_back().unwrap()[0] = 30;
+        cursor.next_front().unwrap()[0] = 10;
+        assert_eq!(cursor.remainder_range(), Some(2..3));
+        cursor.into_remainder().unwrap()[0] = 20;
+    }
+    assert_eq!(v, vec![10, 0, 20, 0, 30]);
+}
+
+#[test]
+fn test_splitter() {
+    let split = |splitter: Splitter<char, _>, s| splitter.split(s).collect::<Vec<&str>>();
+    let rsplit = |splitter: Splitter<char, _>, s| splitter.split(s).collect::<Vec<&str>>();
+
+    // the plain builder agrees with the existing functions
+    assert_eq!(split(Splitter::new(','), "a,,b,"), vec!["a", "", "b", ""]);
+    assert_eq!(rsplit(Splitter::new(',').from_end(), "a,,b,"), vec!["", "b", "", "a"]);
+    assert_eq!(split(Splitter::new(',').max(2), "a,b,c"), vec!["a", "b,c"]);
+    assert_eq!(rsplit(Splitter::new(',').max(2).from_end(), "a,b,c"), vec!["c", "a,b"]);
+    assert_eq!(split(Splitter::new(',').terminator(), "a,b,"), vec!["a", "b"]);
+    assert_eq!(rsplit(Splitter::new(',').terminator().from_end(), "a,b,"), vec!["b", "a"]);
+    assert_eq!(split(Splitter::new(',').max(0), "a,b"), Vec::<&str>::new());
+
+    // combinations
+    assert_eq!(split(Splitter::new(',').terminator().max(2), "a,b,"), vec!["a", "b,"]);
+    assert_eq!(split(Splitter::new(',').terminator().max(3), "a,b,"), vec!["a", "b"]);
+    assert_eq!(split(Splitter::new(',').skip_empty(), ",a,,b,"), vec!["a", "b"]);
+    assert_eq!(split(Splitter::new(',').skip_empty().max(2), ",a,,b,c"), vec!["a", ",b,c"]);
+    assert_eq!(rsplit(Splitter::new(',').skip_empty().from_end(), ",a,,b,"), vec!["b", "a"]);
+    assert_eq!(split(Splitter::new('\n').keep_terminator(), "a\nb\n\nc"), vec!["a\n", "b\n", "\n", "c"]);
+    assert_eq!(split(Splitter::new('\n').keep_terminator(), "a\nb\n"), vec!["a\n", "b\n"]);
+    assert_eq!(
+        rsplit(Splitter::new('\n').keep_terminator().from_end(), "a\nb\n\nc"),
+        vec!["c", "\n", "b\n", "a\n"],
+    );
+    assert_eq!(rsplit(Splitter::new('\n').keep_terminator().from_end(), "a\nb\n"), vec!["b\n", "a\n"]);
+    assert_eq!(
+        rsplit(Splitter::new('\n').keep_terminator().max(2).from_end(), "a\nb\nc\n"),
+        vec!["c\n", "a\nb\n"],
+    );
+    assert_eq!(
+        split(Splitter::new('\n').keep_terminator().skip_empty(), ""),
+        Vec::<&str>::new(),
+    );
+
+    let mut v = vec![1, 0, 2, 0, 0, 3];
+    let pieces = Splitter::new(&[0][..]).skip_empty().split(&mut v[..]).collect::<Vec<_>>();
+    assert_eq!(pieces, vec![&mut [1][..], &mut [2][..], &mut [3][..]]);
+    let mut v = vec![1, 0, 2, 0, 3];
+    let pieces = Splitter::new(&[0][..]).keep_terminator().from_end().split(&mut v[..]).collect::<Vec<_>>();
+    assert_eq!(pieces, vec![&mut [3][..], &mut [2, 0][..], &mut [1, 0][..]]);
+}
+
+#[test]
+fn test_replace_all_pairs() {
+    // swapping does not re-replace the output of another rule
+    assert_eq!(replace_all_pairs("a cat and a dog", &[("cat", "dog"), ("dog", "cat")]), "a dog and a cat");
+    // leftmost, then longest, then first listed
+    assert_eq!(replace_all_pairs("abcd", &[("bc", "1"), ("abc", "2"), ("cd", "3")]), "2d");
+    assert_eq!(replace_all_pairs("abcd", &[("ab", "1"), ("abc", "2")]), "2d");
+    assert_eq!(replace_all_pairs("abab", &[("ab", "1"), ("ab", "2")]), "11");
+    assert_eq!(replace_all_pairs("&<>", &[("&", "&amp;"), ("<", "&lt;"), (">", "&gt;")]), "&amp;&lt;&gt;");
+    assert_eq!(replace_all_pairs("abc", &[("x", "y")]), "abc");
+    assert_eq!(replace_all_pairs::<&str>("abc", &[]), "abc");
+    assert_eq!(replace_all_pairs("ab", &[("", "-")]), "-a-b-");
+    assert_eq!(replace_all_pairs("ab", &[("", "-"), ("a", "x")]), "x-b-");
+
+    let mut res = Vec::new();
+    replace_all_pairs_with(&[1, 2, 3, 1][..], &[(&[1][..], &[0, 0][..]), (&[2, 3][..], &[][..])], |h| res.extend_from_slice(h));
+    assert_eq!(res, vec![0, 0, 0, 0]);
+}
+
+#[test]
+fn test_extract() {
+    let digits: String = extract("tel: +1 (555) 010-9999", |c: char| c.is_ascii_digit());
+    assert_eq!(digits, "15550109999");
+    let none: String = extract("abc", 'x');
+    assert_eq!(none, "");
+    let bytes: Vec<u8> = extract(&[1, 0, 2, 0, 0, 3][..], |b: &u8| *b != 0);
+    assert_eq!(bytes, vec![1, 2, 3]);
+
+    let mut found = Vec::new();
+    extract_with("x = 12 + 3", |c: char| c.is_ascii_digit(), |s| found.push(s));
+    assert_eq!(found, vec!["1", "2", "3"]);
+    let mut found = Vec::new();
+    extract_with("a--b---c", "--", |s| found.push(s));
+    assert_eq!(found, vec!["--", "--"]);
+}
+
+#[test]
+fn test_trim_line_prefix() {
+    let doc: String = trim_line_prefix("    |fn main() {\n    |    body\n    |}\n", "    |");
+    assert_eq!(doc, "fn main() {\n    body\n}\n");
+    // only one occurrence is removed, and unprefixed lines are kept
+    let quoted: String = trim_line_prefix("> > a\nb\n>\n>c", '>');
+    assert_eq!(quoted, " > a\nb\n\nc");
+    // the prefix never spans a line break
+    let spaced: String = trim_line_prefix("a\n  b", |c: char| c.is_whitespace());
+    assert_eq!(spaced, "a\n b");
+    let bytes: Vec<u8> = trim_line_prefix(&b"# x\r\n# y"[..], &b"# "[..]);
+    assert_eq!(bytes, b"x\r\ny");
+
+    let mut lines = Vec::new();
+    trim_line_prefix_with("//a\n//b\n", "//", |s| lines.push(s));
+    assert_eq!(lines, vec!["a\n", "b\n"]);
+    let mut lines = Vec::new();
+    trim_line_prefix_with("", "//", |s| lines.push(s));
+    assert_eq!(lines, vec![""]);
+
+    // unique haystacks are rebased after every split
+    let mut lines = Vec::new();
+    trim_line_prefix_with(String::from("//a\nb\n// c"), "//", |s| lines.push(s));
+    assert_eq!(lines, vec!["a\n", "b\n", " c"]);
+    let mut text = String::from("> x\n>y\nz");
+    let mut lines = Vec::new();
+    trim_line_prefix_with(&mut *text, "> ", |s: &mut str| lines.push(s.to_owned()));
+    assert_eq!(lines, vec!["x\n", ">y\n", "z"]);
+}
+
+#[test]
+fn test_replace_edit_log() {
+    let (res, log) = replace_edit_log("a-b--c", '-', "<->");
+    assert_eq!(res, "a<->b<-><->c");
+    assert_eq!(log, vec![(1..2, 3), (3..4, 3), (4..5, 3)]);
+    let (res, log) = replace_edit_log("héllo", "é", "");
+    assert_eq!(res, "hllo");
+    assert_eq!(log, vec![(1..3, 0)]);
+    assert_eq!(replace_edit_log("abc", 'x', "y"), ("abc".to_owned(), vec![]));
+
+    // unique haystacks report ranges in the original coordinates too
+    let mut v = vec![1, 0, 2, 0, 0, 3];
+    let mut out = Vec::new();
+    let mut log = Vec::new();
+    replace_with_edits(
+        &mut v[..],
+        |x: &i32| *x == 0,
+        |_| &mut [][..],
+        |h: &mut [i32]| out.extend_from_slice(h),
+        |range, len| log.push((range, len)),
+    );
+    assert_eq!(out, vec![1, 2, 3]);
+    assert_eq!(log, vec![(1..2, 0), (3..4, 0), (4..5, 0)]);
+}
+
+#[test]
+fn test_replace_preserving_case() {
+    assert_eq!(replace_preserving_case("Foo", "Foo", "bar"), "Bar");
+    assert_eq!(replace_preserving_case("FOO", "FOO", "bar"), "BAR");
+    assert_eq!(replace_preserving_case("foo", "foo", "Bar"), "bar");
+    assert_eq!(replace_preserving_case("fOo", "fOo", "bar"), "bar");
+    assert_eq!(replace_preserving_case("F", "F", "bar"), "Bar");
+    assert_eq!(replace_preserving_case("123", "123", "bar"), "bar");
+    assert_eq!(
+        replace_preserving_case("# Colour and COLOUR and colour", |c: char| c.eq_ignore_ascii_case(&'o'), "x"),
+        "# Cxlxur and CXLXUR and cxlxur",
+    );
+    assert_eq!(
+        replace_preserving_case("Straße", "Straße", "gasse"),
+        "Gasse",
+    );
+}
+
 // fn str_replacen<'a>(src: &'a str, from: impl pattern_3::Pattern<&'a str>, to: &'a str, n: usize) -> String {
 fn str_replacen<'a, P>(src: &'a str, from: P, to: &'a str, n: usize) -> String
 where
@@ -653,6 +1096,38 @@ fn test_replace_pattern() {
 }
 
 
+#[test]
+fn test_mut_str_pieces() {
+    let mut s = String::from("über,straße,öl");
+    for piece in split_mut(s.as_mut_str(), ',') {
+        piece.make_ascii_uppercase();
+    }
+    assert_eq!(s, "üBER,STRAßE,öL");
+
+    {
+        let mut pieces = splitn_mut(s.as_mut_str(), 2, 'ß');
+        let first: &mut str = pieces.next().unwrap();
+        let rest: &mut str = pieces.next().unwrap();
+        assert_eq!(pieces.next(), None);
+        first.make_ascii_lowercase();
+        assert_eq!(&*rest, "E,öL");
+    }
+    assert_eq!(s, "über,straßE,öL");
+
+    let mut t = String::from("  ¡hola!\u{3000}");
+    {
+        let inner = trim_mut(t.as_mut_str(), char::is_whitespace);
+        assert_eq!(&*inner, "¡hola!");
+        inner.make_ascii_uppercase();
+    }
+    assert_eq!(t, "  ¡HOLA!\u{3000}");
+
+    // multi-byte patterns split on character boundaries only
+    let mut u = String::from("aé€é€b");
+    let pieces: Vec<&mut str> = split_mut(u.as_mut_str(), "é€").collect();
+    assert_eq!(pieces, vec!["a", "", "b"]);
+}
+
 #[test]
 fn test_mut_str() {
     use std::ops::Range;
@@ -688,3 +1163,26 @@ fn test_mut_str() {
     }
 }
 
+
+#[test]
+fn test_longest_common_substring() {
+    assert_eq!(longest_common_substring("xabcdey", "zzbcdzabcz"), (2..5, 2..5));
+    assert_eq!(longest_common_substring("abc", "def"), (0..0, 0..0));
+    assert_eq!(longest_common_substring("", "abc"), (0..0, 0..0));
+    assert_eq!(longest_common_substring("banana", "ananas"), (1..6, 0..5));
+    assert_eq!(longest_common_substring("naïve café", "cafés naïves"), (0..6, 7..13));
+    assert_eq!(longest_common_substring(&[1, 2, 3, 4, 5][..], &[9, 3, 4, 5, 1][..]), (2..5, 1..4));
+
+    // compare against a naive implementation.
+    let a = "abaababaabaababaababaabb";
+    let b = "bbaababbabaaabaababaaa";
+    let (ra, rb) = longest_common_substring(a, b);
+    let naive = (0..=b.len())
+        .flat_map(|i| (i..=b.len()).map(move |j| (i, j)))
+        .filter(|&(i, j)| a.contains(&b[i..j]))
+        .map(|(i, j)| j - i)
+        .max()
+        .unwrap();
+    assert_eq!(ra.len(), naive);
+    assert_eq!(&a[ra], &b[rb]);
+}
@@ -0,0 +1,51 @@
+extern crate pattern_3;
+
+use pattern_3::{Pattern, Runs, Searcher, ReverseSearcher, Span, Then};
+use pattern_3::ext::*;
+
+#[test]
+fn test_then() {
+    let digits = Runs(|c: char| c.is_ascii_digit());
+    let text = "a--1 --x ---23";
+    assert_eq!(
+        match_ranges(text, Then("--", digits)).map(|(r, _)| r).collect::<Vec<_>>(),
+        vec![1..4, 10..14],
+    );
+    assert_eq!(
+        rmatch_ranges(text, Then("--", digits)).map(|(r, _)| r).collect::<Vec<_>>(),
+        vec![10..14, 1..4],
+    );
+    assert_eq!(split(text, Then(' ', '-')).collect::<Vec<_>>(), vec!["a--1", "-x", "--23"]);
+    assert_eq!(find("abab", Then('a', "")), Some(0));
+    assert_eq!(find("abab", Then("", 'b')), Some(1));
+    assert_eq!(find(&[1, 2, 1, 3][..], Then(&[1][..], |x: &i32| *x > 2)), Some(2));
+}
+
+#[test]
+fn test_then_consume() {
+    assert!(starts_with("--12x", Then("--", '1')));
+    assert!(!starts_with("-12x", Then("--", '1')));
+    assert_eq!(trim_start("ababac", Then('a', 'b')), "ac");
+    assert!(ends_with("x--3", Then("--", '3')));
+    assert_eq!(trim_end("cabab", Then('a', 'b')), "c");
+}
+
+#[test]
+fn test_then_captures() {
+    let pair = Then(Runs(char::is_alphanumeric), Then('=', Runs(char::is_alphanumeric)));
+    let hay = "a b=1 key=value";
+    let mut searcher = Pattern::<&str>::into_searcher(pair);
+    assert_eq!(searcher.captures(), None);
+    assert_eq!(searcher.search(Span::from(hay)), Some(2..5));
+    assert_eq!(searcher.captures(), Some([2..3, 3..5]));
+    assert_eq!(searcher.rsearch(Span::from(hay)), Some(6..15));
+    assert_eq!(searcher.captures(), Some([6..9, 9..15]));
+
+    let mut consumer = Pattern::<&str>::into_consumer(Then("--", Runs(|c: char| c.is_ascii_digit())));
+    assert_eq!(consumer.consume(Span::from("--12x")), Some(4));
+    assert_eq!(consumer.captures(), Some([0..2, 2..4]));
+    assert_eq!(consumer.rconsume(Span::from("x--12")), Some(1));
+    assert_eq!(consumer.captures(), Some([1..3, 3..5]));
+    assert_eq!(consumer.consume(Span::from("-12")), None);
+    assert_eq!(consumer.captures(), Some([1..3, 3..5]));
+}
@@ -0,0 +1,81 @@
+extern crate pattern_3;
+
+use pattern_3::ext::*;
+
+// Small deterministic generator so the haystacks are reproducible.
+fn lcg_bytes(seed: u32, len: usize, alphabet: u8) -> Vec<u8> {
+    let mut state = seed;
+    (0..len).map(|_| {
+        state = state.wrapping_mul(1103515245).wrapping_add(12345);
+        b'a' + ((state >> 16) as u8 % alphabet)
+    }).collect()
+}
+
+fn naive_match_indices(hay: &[u8], needle: &[u8]) -> Vec<usize> {
+    let mut res = Vec::new();
+    let mut i = 0;
+    while i + needle.len() <= hay.len() {
+        if &hay[i..(i + needle.len())] == needle {
+            res.push(i);
+            i += needle.len();
+        } else {
+            i += 1;
+        }
+    }
+    res
+}
+
+fn naive_rmatch_indices(hay: &[u8], needle: &[u8]) -> Vec<usize> {
+    let mut res = Vec::new();
+    let mut end = hay.len();
+    while end >= needle.len() {
+        if &hay[(end - needle.len())..end] == needle {
+            res.push(end - needle.len());
+            end -= needle.len();
+        } else {
+            end -= 1;
+        }
+    }
+    res
+}
+
+#[test]
+fn test_long_needles_against_naive() {
+    for seed in 0..20 {
+        // A long needle whose critical factorization falls in the long period
+        // case, repeated (with mutations) throughout the haystack.
+        let block = lcg_bytes(seed, 300, 2);
+        let mut needle = block.clone();
+        needle.extend_from_slice(&block[..200]);
+
+        let mut hay = Vec::new();
+        for round in 0..6 {
+            hay.extend_from_slice(&needle);
+            hay.extend_from_slice(&block[..(round * 37 % 300)]);
+            hay.extend(lcg_bytes(seed + round as u32, 50, 2));
+        }
+
+        let found = match_indices(&*hay, &*needle).map(|(i, _)| i).collect::<Vec<_>>();
+        assert_eq!(found, naive_match_indices(&hay, &needle), "seed {}", seed);
+
+        let found = rmatch_indices(&*hay, &*needle).map(|(i, _)| i).collect::<Vec<_>>();
+        assert_eq!(found, naive_rmatch_indices(&hay, &needle), "seed {}", seed);
+    }
+}
+
+#[test]
+fn test_long_aperiodic_needle() {
+    let needle = lcg_bytes(7, 1000, 4);
+    let mut hay = lcg_bytes(8, 5000, 4);
+    hay.extend_from_slice(&needle);
+    hay.extend_from_slice(&needle[..999]);
+    hay.extend_from_slice(&needle);
+    assert_eq!(
+        match_indices(&*hay, &*needle).map(|(i, _)| i).collect::<Vec<_>>(),
+        naive_match_indices(&hay, &needle),
+    );
+    assert_eq!(
+        rmatch_indices(&*hay, &*needle).map(|(i, _)| i).collect::<Vec<_>>(),
+        naive_rmatch_indices(&hay, &needle),
+    );
+}
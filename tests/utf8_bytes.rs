@@ -0,0 +1,37 @@
+extern crate pattern_3;
+
+use pattern_3::Utf8Bytes;
+use pattern_3::ext::*;
+
+#[test]
+fn test_utf8_bytes_search() {
+    let hay = Utf8Bytes::new("ประเทศไทย中华Việt Nam".as_bytes()).unwrap();
+    assert_eq!(find(hay, '华'), Some(30));
+    assert_eq!(find(hay, "Việt"), Some(33));
+    assert_eq!(rfind(hay, |c: char| c.is_ascii_uppercase()), Some(40));
+    assert_eq!(find(hay, &['中', 'N'][..]), Some(27));
+    assert_eq!(
+        split(hay, ' ').map(Utf8Bytes::as_str).collect::<Vec<_>>(),
+        vec!["ประเทศไทย中华Việt", "Nam"],
+    );
+    // the empty pattern stops at character boundaries only
+    assert_eq!(matches(<&Utf8Bytes>::from("aé"), "").count(), 3);
+}
+
+#[test]
+fn test_utf8_bytes_trim() {
+    let hay = <&Utf8Bytes>::from("  é x  ");
+    assert_eq!(trim(hay, char::is_whitespace).as_str(), "é x");
+    assert_eq!(trim_start(hay, ' ').as_bytes(), "é x  ".as_bytes());
+    assert!(starts_with(hay, "  é"));
+    assert!(ends_with(hay, ' '));
+}
+
+#[test]
+fn test_utf8_bytes_new() {
+    assert!(Utf8Bytes::new(b"\xff").is_err());
+    assert!(Utf8Bytes::new(b"\xe4\xb8").is_err());
+    let hay = Utf8Bytes::new(b"ok").unwrap();
+    assert_eq!(hay.as_str(), "ok");
+    assert_eq!(format!("{:?}", hay), "\"ok\"");
+}
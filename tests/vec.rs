@@ -0,0 +1,37 @@
+extern crate pattern_3;
+
+use pattern_3::ext::*;
+
+use std::thread;
+
+fn strings(s: &[&str]) -> Vec<String> {
+    s.iter().map(|s| s.to_string()).collect()
+}
+
+#[test]
+fn test_vec_split_owned() {
+    let haystack = strings(&["a", "b", "", "c", "", "", "d"]);
+    let parts: Vec<Vec<String>> = split(haystack, |s: &String| s.is_empty()).collect();
+    assert_eq!(parts, vec![strings(&["a", "b"]), strings(&["c"]), strings(&[]), strings(&["d"])]);
+
+    let haystack = vec![1, 0, 2, 3, 0, 4];
+    let parts: Vec<Vec<i32>> = rsplitn(haystack, 2, &[0][..]).collect();
+    assert_eq!(parts, vec![vec![4], vec![1, 0, 2, 3]]);
+}
+
+#[test]
+fn test_vec_trim_and_match() {
+    assert_eq!(trim(vec![0, 0, 1, 2, 0], |x: &i32| *x == 0), vec![1, 2]);
+    let found: Vec<(usize, Vec<u8>)> = match_indices(b"ab-cd--e".to_vec(), &b"-"[..]).collect();
+    assert_eq!(found, vec![(2, b"-".to_vec()), (5, b"-".to_vec()), (6, b"-".to_vec())]);
+}
+
+#[test]
+fn test_vec_pieces_cross_threads() {
+    let lines = b"one\ntwo\nthree".to_vec();
+    let handles: Vec<_> = split(lines, &b"\n"[..])
+        .map(|line| thread::spawn(move || line.len()))
+        .collect();
+    let lens: Vec<usize> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+    assert_eq!(lens, vec![3, 3, 5]);
+}
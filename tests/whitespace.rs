@@ -0,0 +1,60 @@
+extern crate pattern_3;
+
+use pattern_3::Whitespace;
+use pattern_3::ext::*;
+
+#[test]
+fn test_whitespace_matches_char_is_whitespace() {
+    for c in (0..0x11_0000).filter_map(std::char::from_u32) {
+        let s = c.to_string();
+        assert_eq!(starts_with(&*s, Whitespace), c.is_whitespace(), "{:?}", c);
+        assert_eq!(ends_with(&*s, Whitespace), c.is_whitespace(), "{:?}", c);
+    }
+    for b in 0..=255u8 {
+        assert_eq!(contains(&[b][..], Whitespace), b.is_ascii_whitespace(), "{:?}", b);
+    }
+}
+
+#[test]
+fn test_whitespace_split() {
+    // long enough to go through the vectorized scan, with runs crossing
+    // chunk boundaries
+    let text = "Lorem ipsum  dolor\u{3000}sit\t\tamet,\u{2028}\u{a0}consectetur \
+                adipiscing\r\nelit.\u{85}Sed  \u{1680}do eiusmod tempor ß incididunt   ";
+    for len in 0..=text.len() {
+        if !text.is_char_boundary(len) {
+            continue;
+        }
+        let text = &text[..len];
+        let expected = text.split_whitespace().collect::<Vec<_>>();
+        let trimmed = trim(text, Whitespace);
+        let fwd = split(trimmed, Whitespace).filter(|s| !s.is_empty()).collect::<Vec<_>>();
+        let mut rev = rsplit(trimmed, Whitespace).filter(|s| !s.is_empty()).collect::<Vec<_>>();
+        rev.reverse();
+        assert_eq!(fwd, expected);
+        assert_eq!(rev, expected);
+        assert_eq!(trimmed, text.trim());
+    }
+}
+
+#[test]
+fn test_whitespace_runs() {
+    let text = " a  \u{a0}b\n";
+    assert_eq!(
+        match_ranges(text, Whitespace).map(|(r, _)| r).collect::<Vec<_>>(),
+        vec![0..1, 2..6, 7..8],
+    );
+    assert_eq!(
+        rmatch_ranges(text, Whitespace).map(|(r, _)| r).collect::<Vec<_>>(),
+        vec![7..8, 2..6, 0..1],
+    );
+    let bytes = &b"key =\x0bvalue\t\t# \xc2\xa0comment \n"[..];
+    assert_eq!(
+        split(bytes, Whitespace).collect::<Vec<_>>(),
+        vec![&b"key"[..], b"=\x0bvalue", b"#", b"\xc2\xa0comment", b""],
+    );
+    assert_eq!(
+        rsplit(bytes, Whitespace).collect::<Vec<_>>(),
+        vec![&b""[..], b"\xc2\xa0comment", b"#", b"=\x0bvalue", b"key"],
+    );
+}
@@ -0,0 +1,41 @@
+extern crate pattern_3;
+
+use pattern_3::WhitespaceTolerant;
+use pattern_3::ext::*;
+
+#[test]
+fn test_whitespace_tolerant() {
+    let needle = WhitespaceTolerant("foo  bar");
+    assert_eq!(find_range("x foo\n   bar y", needle), Some(2..12));
+    assert_eq!(find_range("x foo\tbar y", needle), Some(2..9));
+    assert_eq!(find_range("x foobar y", needle), None);
+    assert_eq!(rfind_range("foo bar, foo\u{3000}bar", needle), Some(9..18));
+    assert_eq!(
+        match_ranges("foo bar foo  bar", needle).map(|(r, _)| r).collect::<Vec<_>>(),
+        vec![0..7, 8..16],
+    );
+    assert_eq!(
+        rmatch_ranges("foo bar foo  bar", needle).map(|(r, _)| r).collect::<Vec<_>>(),
+        vec![8..16, 0..7],
+    );
+}
+
+#[test]
+fn test_whitespace_tolerant_edges() {
+    // leading and trailing whitespace in the needle consume whole runs
+    assert_eq!(find_range("a  b", WhitespaceTolerant(" b")), Some(1..4));
+    assert_eq!(find_range("a  b", WhitespaceTolerant("a ")), Some(0..3));
+    assert_eq!(rfind_range("a  b", WhitespaceTolerant(" b")), Some(1..4));
+    assert_eq!(rfind_range("a  b", WhitespaceTolerant("a ")), Some(0..3));
+    assert_eq!(split("a \n b\tc", WhitespaceTolerant(" ")).collect::<Vec<_>>(), vec!["a", "b", "c"]);
+    assert_eq!(split("ab", WhitespaceTolerant("")).collect::<Vec<_>>(), vec!["", "a", "b", ""]);
+}
+
+#[test]
+fn test_whitespace_tolerant_consume() {
+    assert!(starts_with("fn  main()", WhitespaceTolerant("fn main")));
+    assert!(!starts_with("fnmain()", WhitespaceTolerant("fn main")));
+    assert!(ends_with("x }\n}", WhitespaceTolerant("} }")));
+    assert_eq!(trim_start("  \n x", WhitespaceTolerant(" ")), "x");
+    assert_eq!(trim_end("x \t\n", WhitespaceTolerant(" ")), "x");
+}
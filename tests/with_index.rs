@@ -0,0 +1,50 @@
+extern crate pattern_3;
+
+use pattern_3::{with_index, RcStr};
+use pattern_3::ext::*;
+
+#[test]
+fn test_with_index_str() {
+    let line = "x,y,z,w";
+    let late_comma = with_index(|i: usize, c: char| i > 2 && c == ',');
+    assert_eq!(split(line, late_comma).collect::<Vec<_>>(), vec!["x,y", "z", "w"]);
+    assert_eq!(rsplit(line, late_comma).collect::<Vec<_>>(), vec!["w", "z", "x,y"]);
+
+    // byte offsets are reported relative to the whole haystack
+    let mut seen = Vec::new();
+    assert_eq!(find("aé€b", with_index(|i: usize, c: char| { seen.push(i); c == 'b' })), Some(6));
+    assert_eq!(seen, vec![0, 1, 3, 6]);
+    assert_eq!(rfind("é€é", with_index(|i: usize, c: char| c == 'é' && i > 0)), Some(5));
+
+    assert!(starts_with("abc", with_index(|i: usize, _: char| i == 0)));
+    assert!(!ends_with("abc", with_index(|i: usize, _: char| i == 0)));
+    assert_eq!(trim_end("aaaa", with_index(|i: usize, _: char| i >= 2)), "aa");
+}
+
+#[test]
+fn test_with_index_slice() {
+    let v = [1, 0, 1, 0, 1, 0];
+    let even_zero = with_index(|i: usize, x: &i32| i % 4 == 1 && *x == 0);
+    assert_eq!(
+        match_indices(&v[..], even_zero).map(|(i, _)| i).collect::<Vec<_>>(),
+        vec![1, 5],
+    );
+    assert_eq!(
+        rmatch_indices(&v[..], even_zero).map(|(i, _)| i).collect::<Vec<_>>(),
+        vec![5, 1],
+    );
+    assert_eq!(trim_start(&v[..], with_index(|i: usize, _: &i32| i < 3)), &[0, 1, 0][..]);
+    assert_eq!(split(&v[..], with_index(|i: usize, _: &i32| i == 2)).count(), 2);
+}
+
+#[test]
+fn test_with_index_owned() {
+    // shared owned haystacks keep positions relative to the whole haystack
+    let line = RcStr::from("a,b,c,d,e");
+    let late_comma = with_index(|i: usize, c: char| c == ',' && i >= 4);
+    assert_eq!(
+        split(line.clone(), late_comma).collect::<Vec<_>>(),
+        split(&*line, late_comma).collect::<Vec<_>>(),
+    );
+    assert_eq!(split(line, late_comma).collect::<Vec<_>>(), vec!["a,b,c", "d", "e"]);
+}
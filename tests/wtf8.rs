@@ -1,3 +1,5 @@
+#![cfg(feature = "wtf8")]
+
 extern crate pattern_3;
 
 use pattern_3::Wtf8;
@@ -166,3 +168,33 @@ fn test_match_string_with_surrogates() {
         ]);
     }
 }
+
+#[test]
+fn test_wtf8_char() {
+    unsafe {
+        // 'a', U+1F4A9, unpaired high surrogate, 'b'
+        let hay = Wtf8::from_bytes_unchecked(b"a\xf0\x9f\x92\xa9\xed\xa0\xbdb");
+        assert_eq!(find(hay, '\u{1f4a9}'), Some(1));
+        assert_eq!(rfind(hay, '\u{1f4a9}'), Some(1));
+        assert_eq!(
+            match_ranges(hay, '\u{fffd}').map(|(r, _)| r).collect::<Vec<_>>(),
+            vec![5..8]
+        );
+        assert_eq!(
+            rmatch_indices(hay, |c: char| c.is_ascii()).collect::<Vec<_>>(),
+            vec![(8, Wtf8::from_str("b")), (0, Wtf8::from_str("a"))]
+        );
+        assert_eq!(
+            split(hay, '\u{fffd}').collect::<Vec<_>>(),
+            vec![Wtf8::from_bytes_unchecked(b"a\xf0\x9f\x92\xa9"), Wtf8::from_str("b")]
+        );
+        let chars: &[char] = &['a', 'b', '\u{fffd}'];
+        assert_eq!(trim(hay, chars), Wtf8::from_str("\u{1f4a9}"));
+
+        // the halves of a split supplementary code point are replacements
+        let half = &hay[3..8];
+        assert_eq!(find(half, '\u{fffd}'), Some(0));
+        assert_eq!(trim_start(half, '\u{fffd}'), Wtf8::from_str(""));
+        assert_eq!(trim_end(&hay[0..3], '\u{fffd}'), Wtf8::from_str("a"));
+    }
+}